@@ -1,75 +1,699 @@
-//! # Falcon-AIR Main Binary
+//! # Falcon-AIR Command Line
 //!
-//! This is the main entry point for the Falcon-AIR STARK proof system.
-//! It demonstrates how to generate a complete STARK proof for all modular
-//! arithmetic operations in the field Z_q where q = 12289.
+//! Proves and verifies Falcon signature verifications with the Falcon-AIR STARK proof
+//! system.
 //!
 //! # Usage
 //!
-//! The binary generates a STARK proof for the Falcon signature scheme operations
-//! and saves it to a compressed file for later verification.
+//! ```bash
+//! # Prove with 128 bits of conjectured security that sig.bin is a valid signature of
+//! # msg.bin under pk.bin
+//! falcon-air prove --pk pk.bin --sig sig.bin --msg msg.bin -o proof.bin
+//! # Prove with 96 bits of conjectured security and Poseidon252 Merkle trees
+//! falcon-air prove --pk pk.bin --sig sig.bin --msg msg.bin --security 96 --hash poseidon252
+//! # Verify the proof against the expected public inputs, with at least 128 bits of
+//! # conjectured security
+//! falcon-air verify proof.bin --pk pk.bin --sig sig.bin --msg msg.bin
+//! # Accept proofs with at least 96 bits of conjectured security
+//! falcon-air verify proof.bin --pk pk.bin --sig sig.bin --msg msg.bin --min-security 96
+//! # Print the parameters, public inputs, claims and sizes of a proof
+//! falcon-air inspect proof.bin
+//! # Time proving and verifying the test vectors
+//! falcon-air bench --degree 512 --batch 16
+//! ```
+//!
+//! # Inputs
+//!
+//! The public key and the signature are read in the standard Falcon encodings: the
+//! 14-bit packed public key and the compressed signature (header byte, 40-byte nonce and
+//! compressed s2). The input files hold raw bytes, or their hexadecimal encoding with
+//! `--format hex`, see [`InputFormat`]: the format is never guessed, as a message may
+//! well be made of hexadecimal digits.
+//!
+//! # Security
+//!
+//! `prove` and `bench` default to 128 bits of conjectured security, and `verify` rejects
+//! proofs below `--min-security`, 128 bits by default. The `default` preset of stwo is
+//! only meant for tests.
 //!
 //! # Output
 //!
-//! - Generates a STARK proof for all arithmetic operations
-//! - Saves the proof to `proof.bin` in compressed format
-//! - Uses bzip2 compression for efficient storage
+//! Proofs are encoded with `FalconProof::to_bytes` and compressed with bzip2. The
+//! encoding records the Merkle hash of the proof, which `verify` and `inspect` read back.
 //!
-//! # Example
+//! # Exit Codes
 //!
-//! ```bash
-//! cargo run --release
-//! # This will generate proof.bin containing the compressed STARK proof
-//! ```
+//! - `0`: success
+//! - `1`: the proof does not verify
+//! - `2`: invalid command line arguments
+//! - `3`: an input or proof file cannot be read or decoded
+//! - `4`: proof generation failed
 
-use std::io::Write;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 use bzip2::Compression;
+use bzip2::read::BzDecoder;
 use bzip2::write::BzEncoder;
-use falcon::big_air::prove_falcon;
-use falcon::input::{MSG_POINT, PK, TEST_S1};
-use tracing_subscriber::fmt::format::FmtSpan;
-
-/// Main function that generates a complete STARK proof for Falcon signature operations.
-///
-/// This function demonstrates the complete workflow:
-/// 1. Generates a STARK proof for all modular arithmetic operations
-/// 2. Serializes the proof using bincode for efficient binary representation
-/// 3. Compresses the proof using bzip2 for storage efficiency
-/// 4. Saves the compressed proof to `proof.bin`
-///
-/// # Returns
-///
-/// Returns `()` on success, or panics with an error message on failure.
-///
-/// # Panics
-///
-/// This function will panic if:
-/// - Proof generation fails
-/// - File creation fails
-/// - Serialization fails
-/// - Compression fails
-fn main() {
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use falcon::big_air::config::ProverConfig;
+use falcon::big_air::proof::{
+    FalconMerkleChannel, FalconProof, MAX_PROOF_BYTES, MerkleHash, ProofDecodeError, proof_hash,
+};
+use falcon::big_air::prover::FalconProver;
+use falcon::big_air::public_inputs::PublicInputs;
+use falcon::big_air::{
+    FalconInstance, FalconProveError, prove_falcon_batch_with_config, verify_falcon_batch,
+    verify_falcon_batch_with_circuit_id,
+};
+use falcon::encoding::{self, EncodingError};
+use falcon::input::{self, falcon_512};
+use falcon::{CircuitParams, FALCON_512_LOG_SIZE, FALCON_1024_LOG_SIZE};
+use stwo::core::vcs::blake2_merkle::Blake2sMerkleChannel;
+use stwo::core::vcs::poseidon252_merkle::Poseidon252MerkleChannel;
+use stwo::core::verifier::VerificationError;
+use stwo::prover::backend::BackendForChannel;
+use stwo::prover::backend::simd::SimdBackend;
+use thiserror::Error;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Prove and verify Falcon signatures with STARKs")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Prove that a signature of a message verifies under a public key
+    Prove {
+        #[command(flatten)]
+        inputs: InputArgs,
+        /// Where to write the compressed proof
+        #[arg(short, long, default_value = "proof.bin")]
+        output: PathBuf,
+        /// The security level of the proof
+        #[arg(long, value_enum, default_value_t = Security::Bits128)]
+        security: Security,
+        /// The Merkle hash of the proof
+        #[arg(long, value_enum, default_value_t = Hash::Blake2s)]
+        hash: Hash,
+    },
+    /// Verify a proof against the expected public inputs
+    Verify {
+        /// The compressed proof
+        proof: PathBuf,
+        #[command(flatten)]
+        inputs: InputArgs,
+        /// The lowest security level of the proofs to accept
+        #[arg(long, value_enum, default_value_t = Security::Bits128)]
+        min_security: Security,
+    },
+    /// Print the parameters, public inputs, claims and sizes of a proof
+    Inspect {
+        /// The compressed proof
+        proof: PathBuf,
+    },
+    /// Time proving and verifying the built-in test vectors
+    Bench {
+        /// The Falcon degree
+        #[arg(long, value_enum, default_value_t = Degree::Falcon1024)]
+        degree: Degree,
+        /// The number of signatures proven in each proof
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        batch: u32,
+        /// The number of proofs to generate and verify
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+        /// The security level of the proofs
+        #[arg(long, value_enum, default_value_t = Security::Bits128)]
+        security: Security,
+        /// The Merkle hash of the proofs
+        #[arg(long, value_enum, default_value_t = Hash::Blake2s)]
+        hash: Hash,
+    },
+}
+
+/// A supported Falcon degree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Degree {
+    #[value(name = "512")]
+    Falcon512,
+    #[value(name = "1024")]
+    Falcon1024,
+}
+
+impl Degree {
+    fn log_size(self) -> u32 {
+        match self {
+            Self::Falcon512 => FALCON_512_LOG_SIZE,
+            Self::Falcon1024 => FALCON_1024_LOG_SIZE,
+        }
+    }
+}
+
+/// A preset of security parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Security {
+    /// The default stwo parameters, fast but only meant for testing
+    Default,
+    /// 96 bits of conjectured security
+    #[value(name = "96")]
+    Bits96,
+    /// 128 bits of conjectured security
+    #[value(name = "128")]
+    Bits128,
+}
+
+impl Security {
+    fn config(self) -> ProverConfig {
+        match self {
+            Self::Default => ProverConfig::default(),
+            Self::Bits96 => ProverConfig::conjectured_96_bits(),
+            Self::Bits128 => ProverConfig::conjectured_128_bits(),
+        }
+    }
+}
+
+/// A supported Merkle hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Hash {
+    /// Blake2s, the fastest to prove and verify natively
+    Blake2s,
+    /// Poseidon over the Stark field, cheap to verify in Cairo
+    Poseidon252,
+}
+
+/// How the input files are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    /// Raw bytes
+    Binary,
+    /// Hexadecimal digits, whitespace is ignored
+    Hex,
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// The encoded public key
+    #[arg(long)]
+    pk: PathBuf,
+    /// The compressed signature
+    #[arg(long)]
+    sig: PathBuf,
+    /// The signed message
+    #[arg(long)]
+    msg: PathBuf,
+    /// How the input files are encoded
+    #[arg(long, value_enum, default_value_t = InputFormat::Binary)]
+    format: InputFormat,
+}
+
+/// Errors reported by the command line.
+#[derive(Debug, Error)]
+enum CliError {
+    #[error("{}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{}: invalid hexadecimal encoding", path.display())]
+    InvalidHex { path: PathBuf },
+    #[error("{}: {source}", path.display())]
+    Encoding {
+        path: PathBuf,
+        source: EncodingError,
+    },
+    #[error("the signature is for 2^{signature} coefficients but the public key has 2^{pk}")]
+    DegreeMismatch { pk: u32, signature: u32 },
+    #[error("{}: {source}", path.display())]
+    ProofDecode {
+        path: PathBuf,
+        source: ProofDecodeError,
+    },
+    #[error("{}: proof decompresses to {MAX_PROOF_BYTES} bytes or more", path.display())]
+    ProofTooLarge { path: PathBuf },
+    #[error("proof generation failed: {0}")]
+    Proving(#[from] FalconProveError),
+    #[error("proof rejected: {0}")]
+    Verification(#[from] VerificationError),
+}
+
+impl CliError {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Verification(_) => ExitCode::from(1),
+            Self::Io { .. }
+            | Self::InvalidHex { .. }
+            | Self::Encoding { .. }
+            | Self::DegreeMismatch { .. }
+            | Self::ProofDecode { .. }
+            | Self::ProofTooLarge { .. } => ExitCode::from(3),
+            Self::Proving(_) => ExitCode::from(4),
+        }
+    }
+}
+
+fn main() -> ExitCode {
     tracing_subscriber::fmt()
-        .with_span_events(FmtSpan::ENTER | FmtSpan::CLOSE)
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
         .init();
-    // Generate a complete STARK proof for all arithmetic operations
-    // This includes modular addition, multiplication, subtraction, and range checking
-    let proof = prove_falcon(TEST_S1, PK, MSG_POINT).unwrap();
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Prove {
+            inputs,
+            output,
+            security,
+            hash: Hash::Blake2s,
+        } => prove::<Blake2sMerkleChannel>(&inputs, &output, security),
+        Command::Prove {
+            inputs,
+            output,
+            security,
+            hash: Hash::Poseidon252,
+        } => prove::<Poseidon252MerkleChannel>(&inputs, &output, security),
+        Command::Verify {
+            proof,
+            inputs,
+            min_security,
+        } => read_proof_hash(&proof).and_then(|(hash, bytes)| match hash {
+            MerkleHash::Blake2s => {
+                verify::<Blake2sMerkleChannel>(&proof, &bytes, &inputs, min_security)
+            }
+            MerkleHash::Poseidon252 => {
+                verify::<Poseidon252MerkleChannel>(&proof, &bytes, &inputs, min_security)
+            }
+        }),
+        Command::Inspect { proof } => {
+            read_proof_hash(&proof).and_then(|(hash, bytes)| match hash {
+                MerkleHash::Blake2s => inspect::<Blake2sMerkleChannel>(&proof, &bytes),
+                MerkleHash::Poseidon252 => inspect::<Poseidon252MerkleChannel>(&proof, &bytes),
+            })
+        }
+        Command::Bench {
+            degree,
+            batch,
+            iterations,
+            security,
+            hash: Hash::Blake2s,
+        } => bench::<Blake2sMerkleChannel>(degree, batch, iterations, security),
+        Command::Bench {
+            degree,
+            batch,
+            iterations,
+            security,
+            hash: Hash::Poseidon252,
+        } => bench::<Poseidon252MerkleChannel>(degree, batch, iterations, security),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            error.exit_code()
+        }
+    }
+}
+
+/// Proves the signature verification of the inputs and writes the compressed proof.
+fn prove<MC: FalconMerkleChannel>(
+    inputs: &InputArgs,
+    output: &Path,
+    security: Security,
+) -> Result<(), CliError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    let public_inputs = read_public_inputs(&inputs.pk, &inputs.sig, &inputs.msg, inputs.format)?;
+    let instance = FalconInstance::new(
+        &public_inputs.signature,
+        &public_inputs.pk,
+        &public_inputs.message,
+    );
+    let proof = prove_falcon_batch_with_config::<MC>(&[instance], security.config())?;
+    let size = write_proof(&proof, output)?;
+    println!(
+        "Proved Falcon-{} signature verification with {} bits of conjectured security, wrote {} ({size} bytes)",
+        public_inputs.pk.len(),
+        proof.config.security_bits(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Verifies a decompressed proof against the given public inputs, with at least the
+/// security level `min_security`.
+fn verify<MC: FalconMerkleChannel>(
+    path: &Path,
+    bytes: &[u8],
+    inputs: &InputArgs,
+    min_security: Security,
+) -> Result<(), CliError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    let proof = decode_proof::<MC>(bytes, path)?;
+    let public_inputs = [read_public_inputs(
+        &inputs.pk,
+        &inputs.sig,
+        &inputs.msg,
+        inputs.format,
+    )?];
+    let n_instances = public_inputs.len();
+    let security_bits = proof.config.security_bits();
+    verify_falcon_batch(proof, &public_inputs, &min_security.config())?;
+    println!(
+        "Proof verified ({n_instances} signature(s), {security_bits} bits of conjectured security, {:?} Merkle hash)",
+        MC::HASH
+    );
+    Ok(())
+}
+
+/// Prints the parameters, public inputs, claims and sizes of a decompressed proof.
+fn inspect<MC: FalconMerkleChannel>(path: &Path, bytes: &[u8]) -> Result<(), CliError> {
+    let proof = decode_proof::<MC>(bytes, path)?;
+
+    println!("Parameters: {:?}", proof.params);
+    println!("Merkle hash: {:?}", MC::HASH);
+    println!("Prover configuration: {:?}", proof.config);
+    println!(
+        "Conjectured security: {} bits",
+        proof.config.security_bits()
+    );
+    println!("Signatures: {}", proof.public_inputs.len());
+    for (i, instance) in proof.public_inputs.iter().enumerate() {
+        println!(
+            "  #{i}: {} public key coefficients, {}-byte signature, {}-byte message",
+            instance.pk.len(),
+            instance.signature.len(),
+            instance.message.len()
+        );
+    }
+    println!("Batch log size: {}", proof.claim.log_n_instances());
+    println!("Largest trace log size: {}", proof.claim.max_log_size());
+    println!("Interaction proof of work: {}", proof.interaction_pow);
+    println!("Claim: {:#?}", proof.claim);
+    println!("Interaction claim: {:#?}", proof.interaction_claim);
+    println!("Encoded size: {} bytes", bytes.len());
+    println!("Compressed size: {} bytes", read_file(path)?.len());
+    Ok(())
+}
+
+/// Proves and verifies batches of the test vectors of the given degree and prints the
+/// average timings.
+fn bench<MC: FalconMerkleChannel>(
+    degree: Degree,
+    batch: u32,
+    iterations: u32,
+    security: Security,
+) -> Result<(), CliError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    let (params, instance) = match degree {
+        Degree::Falcon512 => (
+            CircuitParams::falcon_512(),
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE),
+        ),
+        Degree::Falcon1024 => (
+            CircuitParams::falcon_1024(),
+            FalconInstance::new(input::SIGNATURE, input::PK, input::MESSAGE),
+        ),
+    };
+    let instances = vec![instance; batch as usize];
+    let public_inputs = vec![instance.public_inputs(); instances.len()];
+
+    // The preprocessed trace is built once and reused by every iteration, and its root is
+    // pinned by the verifier
+    let start = Instant::now();
+    let config = security.config();
+    let prover = FalconProver::<SimdBackend, MC>::new(params, config)?;
+    let circuit_id = prover.circuit_id(&instances)?;
+    let setup = start.elapsed();
+
+    let mut proving = Duration::ZERO;
+    let mut verifying = Duration::ZERO;
+    let mut size = 0;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let proof = prover.prove_batch(&instances)?;
+        proving += start.elapsed();
+        size = proof.to_bytes().len();
+
+        let start = Instant::now();
+        verify_falcon_batch_with_circuit_id(proof, &public_inputs, circuit_id, &config)?;
+        verifying += start.elapsed();
+    }
+    println!(
+        "Falcon-{}, {batch} signature(s) per proof, {iterations} iteration(s), {} bits of conjectured security, {:?} Merkle hash",
+        1 << degree.log_size(),
+        security.config().security_bits(),
+        MC::HASH
+    );
+    println!("Circuit setup: {setup:?}");
+    println!("Proving: {:?} per proof", proving / iterations);
+    println!("Verifying: {:?} per proof", verifying / iterations);
+    println!("Proof size: {size} bytes");
+    Ok(())
+}
+
+/// Reads and decodes a public key, a signature and a message.
+fn read_public_inputs(
+    pk: &Path,
+    sig: &Path,
+    msg: &Path,
+    format: InputFormat,
+) -> Result<PublicInputs, CliError> {
+    let public_key = encoding::decode_public_key(&read_input(pk, format)?).map_err(|source| {
+        CliError::Encoding {
+            path: pk.to_path_buf(),
+            source,
+        }
+    })?;
+    let signature_bytes = read_input(sig, format)?;
+    let signature =
+        encoding::decode_signature(&signature_bytes).map_err(|source| CliError::Encoding {
+            path: sig.to_path_buf(),
+            source,
+        })?;
+    if signature.log_n != public_key.log_n {
+        return Err(CliError::DegreeMismatch {
+            pk: public_key.log_n,
+            signature: signature.log_n,
+        });
+    }
+    let message = read_input(msg, format)?;
+    Ok(PublicInputs::new(&public_key.h, &signature_bytes, &message))
+}
+
+/// Reads an input file in the given format.
+fn read_input(path: &Path, format: InputFormat) -> Result<Vec<u8>, CliError> {
+    let bytes = read_file(path)?;
+    match format {
+        InputFormat::Binary => Ok(bytes),
+        InputFormat::Hex => decode_hex(&bytes).ok_or_else(|| CliError::InvalidHex {
+            path: path.to_path_buf(),
+        }),
+    }
+}
+
+/// Decodes hexadecimal digits, ignoring whitespace, or returns `None` if `text` is not
+/// an even number of hexadecimal digits.
+fn decode_hex(text: &[u8]) -> Option<Vec<u8>> {
+    let digits = text
+        .iter()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|&c| (c as char).to_digit(16))
+        .collect::<Option<Vec<_>>>()?;
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    Some(
+        digits
+            .chunks_exact(2)
+            .map(|pair| (pair[0] * 16 + pair[1]) as u8)
+            .collect(),
+    )
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, CliError> {
+    std::fs::read(path).map_err(|source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Decompresses a proof, stopping at [`MAX_PROOF_BYTES`] so that a small file cannot
+/// expand into an unbounded allocation.
+fn decompress(compressed: &[u8], path: &Path) -> Result<Vec<u8>, CliError> {
+    let mut bytes = Vec::new();
+    BzDecoder::new(compressed)
+        .take(MAX_PROOF_BYTES as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|source| CliError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    // The input was cut at the cap, so the proof is at least that large
+    if bytes.len() == MAX_PROOF_BYTES {
+        return Err(CliError::ProofTooLarge {
+            path: path.to_path_buf(),
+        });
+    }
+    Ok(bytes)
+}
+
+/// Reads and decompresses a proof, and returns the Merkle hash recorded in its encoding
+/// with the decompressed bytes.
+fn read_proof_hash(path: &Path) -> Result<(MerkleHash, Vec<u8>), CliError> {
+    let bytes = decompress(&read_file(path)?, path)?;
+    let hash = proof_hash(&bytes).map_err(|source| CliError::ProofDecode {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok((hash, bytes))
+}
+
+/// Decodes a decompressed proof.
+fn decode_proof<MC: FalconMerkleChannel>(
+    bytes: &[u8],
+    path: &Path,
+) -> Result<FalconProof<MC>, CliError> {
+    FalconProof::from_bytes(bytes).map_err(|source| CliError::ProofDecode {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Encodes and compresses a proof to a file, and returns the compressed size.
+fn write_proof<MC: FalconMerkleChannel>(
+    proof: &FalconProof<MC>,
+    path: &Path,
+) -> Result<usize, CliError> {
+    let io_error = |source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let file = File::create(path).map_err(io_error)?;
+    let mut encoder = BzEncoder::new(file, Compression::best());
+    encoder.write_all(&proof.to_bytes()).map_err(io_error)?;
+    let file = encoder.finish().map_err(io_error)?;
+    Ok(file.metadata().map_err(io_error)?.len() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex(b"00ff3A\n"), Some(vec![0x00, 0xff, 0x3a]));
+        assert_eq!(decode_hex(b"0 0\r\nff"), Some(vec![0x00, 0xff]));
+        assert_eq!(decode_hex(b"0ff"), None);
+        assert_eq!(decode_hex(b"0g"), None);
+    }
+
+    #[test]
+    fn test_decompress_rejects_proofs_at_the_cap() {
+        let compress = |len| {
+            let mut encoder = BzEncoder::new(Vec::new(), Compression::fast());
+            encoder.write_all(&vec![0; len]).unwrap();
+            encoder.finish().unwrap()
+        };
+        let path = Path::new("proof.bin");
 
-    // Create a file to store the compressed proof
-    let proof_file = std::fs::File::create("proof.bin").unwrap();
+        assert_eq!(decompress(&compress(1000), path).unwrap(), vec![0; 1000]);
+        assert!(matches!(
+            decompress(&compress(MAX_PROOF_BYTES), path),
+            Err(CliError::ProofTooLarge { .. })
+        ));
+    }
 
-    // Serialize the proof to binary format using bincode
-    // This provides efficient binary representation of the proof data
-    let serialized_bytes = bincode::serialize(&proof).unwrap();
+    #[test]
+    fn test_cli_parses_subcommands() {
+        Cli::try_parse_from([
+            "falcon-air",
+            "prove",
+            "--pk",
+            "a",
+            "--sig",
+            "b",
+            "--msg",
+            "c",
+        ])
+        .unwrap();
+        Cli::try_parse_from(["falcon-air", "bench", "--degree", "512"]).unwrap();
+        Cli::try_parse_from(["falcon-air", "bench", "--hash", "poseidon252"]).unwrap();
+        // Expected public inputs must all be given
+        assert!(Cli::try_parse_from(["falcon-air", "verify", "proof.bin"]).is_err());
+        assert!(Cli::try_parse_from(["falcon-air", "verify", "proof.bin", "--pk", "a"]).is_err());
+        assert!(Cli::try_parse_from(["falcon-air", "bench", "--degree", "256"]).is_err());
+        assert!(Cli::try_parse_from(["falcon-air", "bench", "--batch", "0"]).is_err());
+        assert!(Cli::try_parse_from(["falcon-air", "bench", "--hash", "sha256"]).is_err());
+    }
 
-    // Compress the proof using bzip2 with best compression level
-    // This significantly reduces the file size for storage and transmission
-    let mut bz_encoder = BzEncoder::new(proof_file, Compression::best());
-    bz_encoder.write_all(&serialized_bytes).unwrap();
-    bz_encoder.finish().unwrap();
+    #[test]
+    fn test_cli_defaults() {
+        let verify = |args: &[&str]| {
+            let cli = Cli::try_parse_from(
+                [
+                    "falcon-air",
+                    "verify",
+                    "proof.bin",
+                    "--pk",
+                    "a",
+                    "--sig",
+                    "b",
+                    "--msg",
+                    "c",
+                ]
+                .iter()
+                .chain(args),
+            )
+            .unwrap();
+            match cli.command {
+                Command::Verify {
+                    inputs,
+                    min_security,
+                    ..
+                } => (inputs.format, min_security),
+                command => panic!("unexpected command {command:?}"),
+            }
+        };
+        // The inputs are raw bytes unless hexadecimal is asked for, and proofs need 128
+        // bits of conjectured security unless another level is asked for
+        assert_eq!(verify(&[]), (InputFormat::Binary, Security::Bits128));
+        assert_eq!(
+            verify(&["--format", "hex", "--min-security", "96"]),
+            (InputFormat::Hex, Security::Bits96)
+        );
+        assert!(
+            Cli::try_parse_from([
+                "falcon-air",
+                "verify",
+                "proof.bin",
+                "--pk",
+                "a",
+                "--sig",
+                "b",
+                "--msg",
+                "c",
+                "--format",
+                "auto",
+            ])
+            .is_err()
+        );
 
-    // The compressed proof is now saved to proof.bin
-    // This file can be used for later verification or transmission
+        match Cli::try_parse_from(["falcon-air", "bench"])
+            .unwrap()
+            .command
+        {
+            Command::Bench { security, .. } => assert_eq!(security, Security::Bits128),
+            command => panic!("unexpected command {command:?}"),
+        }
+    }
 }
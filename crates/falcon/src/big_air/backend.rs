@@ -0,0 +1,93 @@
+//! # Proving Backends
+//!
+//! The traces of every component are generated with the SIMD backend, whose packed
+//! columns the trace generators are written against. Proving itself (twiddles,
+//! commitments, proof of work, constraint evaluation and FRI) runs on any backend
+//! implementing [`FalconBackend`], which moves the generated columns to the backend and
+//! exposes the components as its provers.
+//!
+//! - [`SimdBackend`]: the default, vectorized with AVX-512, AVX2 or NEON when available
+//! - [`CpuBackend`]: a second, scalar prover backend, much slower but producing the same
+//!   proof
+//!
+//! [`CpuBackend`] is not a fallback for targets without SIMD: the traces still need the
+//! SIMD backend, which runs on every target with or without vector instructions.
+
+use stwo::{
+    core::fields::m31::BaseField,
+    prover::{
+        ComponentProver,
+        backend::{Backend, Column, cpu::CpuBackend, simd::SimdBackend},
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{FrameworkComponent, FrameworkEval};
+
+/// A backend the Big AIR can be proven on.
+pub trait FalconBackend: Backend {
+    /// Moves columns generated with the SIMD backend to this backend.
+    fn from_simd_columns(
+        columns: Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+    ) -> Vec<CircleEvaluation<Self, BaseField, BitReversedOrder>>;
+
+    /// Returns a framework component as a prover on this backend.
+    fn as_prover<E: FrameworkEval + Sync>(
+        component: &FrameworkComponent<E>,
+    ) -> &dyn ComponentProver<Self>;
+}
+
+impl FalconBackend for SimdBackend {
+    fn from_simd_columns(
+        columns: Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+    ) -> Vec<CircleEvaluation<Self, BaseField, BitReversedOrder>> {
+        columns
+    }
+
+    fn as_prover<E: FrameworkEval + Sync>(
+        component: &FrameworkComponent<E>,
+    ) -> &dyn ComponentProver<Self> {
+        component
+    }
+}
+
+impl FalconBackend for CpuBackend {
+    fn from_simd_columns(
+        columns: Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+    ) -> Vec<CircleEvaluation<Self, BaseField, BitReversedOrder>> {
+        columns
+            .into_iter()
+            .map(|column| CircleEvaluation::new(column.domain, column.values.to_cpu()))
+            .collect()
+    }
+
+    fn as_prover<E: FrameworkEval + Sync>(
+        component: &FrameworkComponent<E>,
+    ) -> &dyn ComponentProver<Self> {
+        component
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stwo::{
+        core::{fields::m31::M31, poly::circle::CanonicCoset},
+        prover::backend::simd::column::BaseColumn,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_from_simd_columns_keeps_values_and_domain() {
+        let domain = CanonicCoset::new(5).circle_domain();
+        let values = (0..32).map(M31).collect::<Vec<_>>();
+        let column = CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
+            domain,
+            BaseColumn::from_iter(values.clone()),
+        );
+
+        let cpu_column = CpuBackend::from_simd_columns(vec![column]).remove(0);
+
+        assert_eq!(cpu_column.domain, domain);
+        assert_eq!(cpu_column.values, values);
+    }
+}
@@ -14,14 +14,25 @@
 //! in the Falcon signature scheme implementation.
 
 use crate::{
-    HIGH_SIG_BOUND, LOW_SIG_BOUND, POLY_LOG_SIZE, POLY_SIZE,
-    big_air::relation::InputLookupElements,
+    CircuitParams,
+    big_air::{
+        FalconInstance,
+        public_inputs::{Indices, PkForm, PublicInputs, log_n_instances, pad_batch},
+        relation::{F_POLY_ID, G_POLY_ID, InputLookupElements},
+    },
+    encoding::decompression,
+    hash_to_point::{
+        keccak, padded_blocks, sampler,
+        sampler::{BYTE_BOUND, REJECTION_RANGE_BOUND},
+        sponge, sponge_steps,
+    },
     impl_mix_into,
-    ntts::{intt, ntt, roots},
+    ntts::{intt, layout::ButterflyLayout, ntt, roots},
     polys::{euclidean_norm, mul, sub},
-    zq::{Q, range_check},
+    zq::range_check,
 };
 use itertools::{Itertools, chain};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::fields::m31::M31,
     prover::{
@@ -32,11 +43,14 @@ use stwo::{
 use stwo_constraint_framework::preprocessed_columns::PreProcessedColumnId;
 
 impl_mix_into!(
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
     pub struct BigClaim {
+        pub params: CircuitParams,
         pub f_ntt_butterfly: ntt::butterfly::Claim,
         pub f_ntt_merges: Vec<ntt::Claim>,
-        pub g_ntt_butterfly: ntt::butterfly::Claim,
+        /// The G-NTT butterfly, if the public key is given by its coefficients
+        pub g_ntt_butterfly: Option<ntt::butterfly::Claim>,
+        /// The G-NTT merges, empty if the public key is given in NTT form
         pub g_ntt_merges: Vec<ntt::Claim>,
         pub mul: mul::Claim,
         pub intt_merges: Vec<intt::Claim>,
@@ -49,6 +63,12 @@ impl_mix_into!(
         pub range_check: range_check::Claim,
         pub roots: Vec<roots::preprocessed::Claim>,
         pub inv_roots: Vec<roots::inv_preprocessed::Claim>,
+        pub keccak: keccak::Claim,
+        pub sponge: sponge::Claim,
+        pub sampler: sampler::Claim,
+        pub byte_range_check: range_check::Claim,
+        pub rejection_range_check: range_check::Claim,
+        pub decompression: decompression::Claim,
     }
 );
 #[derive(Debug, Clone)]
@@ -57,8 +77,9 @@ pub struct AllTraces {
     pub f_ntt_butterfly: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
     /// Trace columns from NTT operations
     pub f_ntt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
-    /// Trace columns from butterfly operations
-    pub g_ntt_butterfly: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    /// Trace columns from butterfly operations, if the public key is given by its
+    /// coefficients
+    pub g_ntt_butterfly: Option<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
     /// Trace columns from NTT operations
     pub g_ntt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
     /// Trace columns from multiplication operations
@@ -82,6 +103,18 @@ pub struct AllTraces {
     /// Trace columns from roots operations
     pub roots: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
     pub inv_roots: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    /// Trace columns from the Keccak-f[1600] rounds
+    pub keccak: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    /// Trace columns from the SHAKE256 sponge steps
+    pub sponge: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    /// Trace columns from the HashToPoint rejection sampling
+    pub sampler: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    /// Trace column from byte range checking: multiplicities
+    pub byte_range_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
+    /// Trace column from rejected chunks range checking: multiplicities
+    pub rejection_range_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
+    /// Trace columns from the signature decompression
+    pub decompression: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
 }
 
 impl AllTraces {
@@ -90,7 +123,7 @@ impl AllTraces {
     pub fn new(
         f_ntt_butterfly: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         f_ntt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
-        g_ntt_butterfly: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        g_ntt_butterfly: Option<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
         g_ntt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
         mul: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         intt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
@@ -103,6 +136,12 @@ impl AllTraces {
         range_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
         roots: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         inv_roots: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        keccak: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        sponge: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        sampler: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        byte_range_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
+        rejection_range_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
+        decompression: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
     ) -> Self {
         Self {
             f_ntt_butterfly,
@@ -120,110 +159,206 @@ impl AllTraces {
             range_check,
             roots,
             inv_roots,
+            keccak,
+            sponge,
+            sampler,
+            byte_range_check,
+            rejection_range_check,
+            decompression,
         }
     }
+
+    /// Returns all the trace columns, in commitment order.
+    pub fn columns(&self) -> Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>> {
+        self.components().into_iter().flatten().collect_vec()
+    }
+
+    /// Returns the trace columns of every component, in commitment order.
+    pub fn components(&self) -> Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>> {
+        chain!(
+            [self.f_ntt_butterfly.clone()],
+            self.f_ntt_merges.iter().cloned(),
+            self.g_ntt_butterfly.iter().cloned(),
+            self.g_ntt_merges.iter().cloned(),
+            [self.mul.clone()],
+            self.intt_merges.iter().cloned(),
+            [self.ibutterfly.clone()],
+            [self.sub.clone()],
+            [self.euclidean_norm.clone()],
+            [vec![self.half_range_check.clone()]],
+            [vec![self.low_sig_bound_check.clone()]],
+            [vec![self.high_sig_bound_check.clone()]],
+            [vec![self.range_check.clone()]],
+            self.roots.iter().map(|root| vec![root.clone()]),
+            self.inv_roots.iter().map(|inv_root| vec![inv_root.clone()]),
+            [self.keccak.clone()],
+            [self.sponge.clone()],
+            [self.sampler.clone()],
+            [vec![self.byte_range_check.clone()]],
+            [vec![self.rejection_range_check.clone()]],
+            [self.decompression.clone()],
+        )
+        .collect_vec()
+    }
 }
 
 impl BigClaim {
-    /// Creates a standard BigClaim with all the default component claims.
+    /// Creates a BigClaim with all the component claims for the given parameters.
     ///
     /// This function centralizes the creation of the BigClaim structure to avoid
     /// duplication between debug and main modules.
     ///
+    /// # Parameters
+    ///
+    /// - `params`: The circuit parameters, which fix the degree of the polynomials
+    ///   and therefore the number of NTT merges and the signature bound
+    /// - `public_inputs`: The public inputs of the batch, whose number fixes the number of
+    ///   signatures stacked in the traces, whose messages fix the number of absorbed
+    ///   SHAKE256 blocks and whose first public key fixes whether there is a G-NTT
+    /// - `squeeze_blocks`: The number of SHAKE256 blocks squeezed by every instance
+    ///
     /// # Returns
     ///
-    /// Returns a `BigClaim` with all the standard component claims configured.
-    pub fn new_standard() -> Self {
-        use crate::{HIGH_SIG_BOUND, LOW_SIG_BOUND, POLY_LOG_SIZE};
+    /// Returns a `BigClaim` with all the component claims configured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the batch is empty.
+    pub fn new(params: CircuitParams, public_inputs: &[PublicInputs], squeeze_blocks: u32) -> Self {
         use stwo::prover::backend::simd::m31::LOG_N_LANES;
 
-        let range_check_log_size = crate::zq::Q.ilog2() + 1;
-
-        // The log size is constant because always pass all the coefficients to the NTT component
-        let f_ntt_merges = (1..POLY_LOG_SIZE)
-            .map(|_| ntt::Claim {
-                log_size: POLY_LOG_SIZE - 1,
+        let log_n_instances = log_n_instances(public_inputs.len());
+        // Every instance absorbs its padded blocks, squeezes `squeeze_blocks` blocks and
+        // permutes between two consecutive steps
+        let n_steps = pad_batch(public_inputs)
+            .map(|instance| {
+                padded_blocks(instance.nonce(), &instance.message).len() + squeeze_blocks as usize
             })
             .collect_vec();
+        let n_permutations = n_steps.iter().map(|n_steps| n_steps - 1).sum();
+
+        // The instances are stacked, so every per-signature trace grows with the batch
+        // while the range check and roots tables are shared
+        let poly_log_size = params.poly_log_size;
+        let trace_log_size = poly_log_size + log_n_instances;
+
         // The log size is constant because always pass all the coefficients to the NTT component
-        let g_ntt_merges = (1..POLY_LOG_SIZE)
+        let f_ntt_merges = (1..poly_log_size)
             .map(|_| ntt::Claim {
-                log_size: POLY_LOG_SIZE - 1,
+                log_size: trace_log_size - 1,
             })
             .collect_vec();
+        // A public key in NTT form is read as is by the multiplication, without G-NTT
+        let has_g_ntt = public_inputs[0].pk_form == PkForm::Coefficients;
+        let g_ntt_merges = if has_g_ntt {
+            f_ntt_merges.clone()
+        } else {
+            vec![]
+        };
         // The log size is constant because always pass all the coefficients to the INTT component
-        let intt_merges = (1..POLY_LOG_SIZE)
+        let intt_merges = (1..poly_log_size)
             .map(|_| intt::Claim {
-                log_size: POLY_LOG_SIZE - 1,
+                log_size: trace_log_size - 1,
             })
             .collect_vec();
 
         // For each stage n of the NTT merges, the roots needed size are 2 ^ (n + 2)
-        let roots = (2..=POLY_LOG_SIZE)
+        let roots = (2..=poly_log_size)
             .map(|i| roots::preprocessed::Claim {
                 log_size: std::cmp::max(LOG_N_LANES, i),
             })
             .collect_vec();
 
-        // For each stage n of the NTT merges, the roots needed size are 2 ^ (POLY_LOG_SIZE - n)
-        let inv_roots = (2..=POLY_LOG_SIZE)
+        // For each stage n of the INTT merges, the roots needed size are 2 ^ (poly_log_size - n)
+        let inv_roots = (2..=poly_log_size)
             .rev()
             .map(|i| roots::inv_preprocessed::Claim {
                 log_size: std::cmp::max(LOG_N_LANES, i),
             })
             .collect_vec();
         Self {
-            // In the butterfly component we have 2 coefficients per row so we only need POLY_LOG_SIZE - 1 rows
+            params,
+            // In the butterfly component we have 2 coefficients per row so we only need trace_log_size - 1 rows
             f_ntt_butterfly: ntt::butterfly::Claim {
-                log_size: POLY_LOG_SIZE - 1,
+                log_size: trace_log_size - 1,
             },
             f_ntt_merges,
-            // In the butterfly component we have 2 coefficients per row so we only need POLY_LOG_SIZE - 1 rows
-            g_ntt_butterfly: ntt::butterfly::Claim {
-                log_size: POLY_LOG_SIZE - 1,
-            },
+            // In the butterfly component we have 2 coefficients per row so we only need trace_log_size - 1 rows
+            g_ntt_butterfly: has_g_ntt.then_some(ntt::butterfly::Claim {
+                log_size: trace_log_size - 1,
+            }),
             g_ntt_merges,
-            // In the multiplication component we have 1 coefficient per row so we need POLY_LOG_SIZE rows
+            // In the multiplication component we have 1 coefficient per row so we need trace_log_size rows
             mul: mul::Claim {
-                log_size: POLY_LOG_SIZE,
+                log_size: trace_log_size,
             },
             intt_merges,
-            // In the INTT butterfly component we have 2 coefficients per row so we only need POLY_LOG_SIZE - 1 rows
+            // In the INTT butterfly component we have 2 coefficients per row so we only need trace_log_size - 1 rows
             ibutterfly: intt::ibutterfly::Claim {
-                log_size: POLY_LOG_SIZE - 1,
+                log_size: trace_log_size - 1,
             },
-            // In the subtraction component we have 1 coefficient per row so we need POLY_LOG_SIZE rows
+            // In the subtraction component we have 1 coefficient per row so we need trace_log_size rows
             sub: sub::Claim {
-                log_size: POLY_LOG_SIZE,
+                log_size: trace_log_size,
             },
-            // In the euclidean norm component we have 2 coefficients per row so we need POLY_LOG_SIZE
+            // In the euclidean norm component we have 2 coefficients per row so we need trace_log_size
             // rows to compute the euclidean norm of 2 polynomials (which is what we're doing here)
             euclidean_norm: euclidean_norm::Claim {
-                log_size: POLY_LOG_SIZE,
-            },
-            // Range check Q/2 so only need range_check_log_size - 1 rows
-            half_range_check: range_check::Claim {
-                log_size: range_check_log_size - 1,
-            },
-            // Range check LOW_SIG_BOUND so only need LOW_SIG_BOUND.next_power_of_two().ilog2() rows
-            low_sig_bound_check: range_check::Claim {
-                log_size: LOW_SIG_BOUND.next_power_of_two().ilog2(),
-            },
-            // Range check HIGH_SIG_BOUND so only need HIGH_SIG_BOUND.next_power_of_two().ilog2() rows
-            high_sig_bound_check: range_check::Claim {
-                log_size: HIGH_SIG_BOUND.next_power_of_two().ilog2(),
-            },
-            // Range check Q so only need range_check_log_size rows
-            range_check: range_check::Claim {
-                log_size: range_check_log_size,
+                log_size: trace_log_size,
+                bound: params.signature_bound,
+                limb_bits: params.sig_bound_limb_bits(),
             },
+            // Range check Q/2 so only need (Q/2).next_power_of_two().ilog2() rows
+            half_range_check: range_check::Claim::new(params.q / 2),
+            // Range check the low limbs of the norms and of their slacks to the signature bound
+            low_sig_bound_check: range_check::Claim::new(params.low_sig_bound()),
+            // Range check the high limbs of the norms and of their slacks to the signature bound
+            high_sig_bound_check: range_check::Claim::new(params.high_sig_bound()),
+            // Range check Q so only need Q.next_power_of_two().ilog2() rows
+            range_check: range_check::Claim::new(params.q),
             roots,
             inv_roots,
+            // One row per round of every permutation
+            keccak: keccak::Claim::new(n_permutations),
+            // One row per absorbed or squeezed block
+            sponge: sponge::Claim::new(n_steps.iter().sum()),
+            // One row per squeezed 16-bit chunk
+            sampler: sampler::Claim::new(1 << log_n_instances, squeeze_blocks),
+            // Range check the bytes of the squeezed chunks and the high parts of the
+            // decompressed coefficients
+            byte_range_check: range_check::Claim::new(BYTE_BOUND),
+            // Range check the rejected chunks minus 5q
+            rejection_range_check: range_check::Claim::new(REJECTION_RANGE_BOUND),
+            // One row per bit of the compressed signatures
+            decompression: decompression::Claim::new(poly_log_size, log_n_instances),
         }
     }
 
+    /// Returns the log size of the largest trace or table.
+    pub fn max_log_size(&self) -> u32 {
+        [
+            self.range_check.log_size,
+            self.sub.log_size,
+            self.keccak.log_size,
+            self.sponge.log_size,
+            self.sampler.log_size,
+            self.decompression.log_size,
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+
+    /// Returns the log of the number of signatures stacked in the traces.
+    pub fn log_n_instances(&self) -> u32 {
+        self.sub.log_size - self.params.poly_log_size
+    }
+
     /// Generates traces for all arithmetic operations.
     ///
+    /// The instances are stacked one after the other in every per-signature trace, and
+    /// their remainders all feed the same range check and roots tables.
+    ///
     /// # Returns
     ///
     /// Returns a tuple containing:
@@ -236,18 +371,58 @@ impl BigClaim {
     /// 2. Collects all remainder values for range checking
     /// 3. Generates the range check trace using all remainders
     /// 4. Concatenates all traces in the correct order
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not `2^log_n_instances` instances, if their public keys do not
+    /// have `params.poly_size()` coefficients or are not in the form of the claim, or if
+    /// their signatures do not decompress to `params.poly_size()` coefficients.
     pub fn gen_trace(
         &self,
-        s1: &[u32; POLY_SIZE],
-        pk: &[u32; POLY_SIZE],
-        msg_point: &[u32; POLY_SIZE],
+        instances: &[FalconInstance],
     ) -> (
         Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         AllTraces,
     ) {
-        let mut range_check_input = vec![];
+        let poly_size = self.params.poly_size();
+        assert_eq!(instances.len(), 1 << self.log_n_instances());
+        for instance in instances {
+            assert_eq!(
+                instance.pk.len(),
+                poly_size,
+                "pk must have {poly_size} coefficients"
+            );
+            assert_eq!(
+                instance.pk_form == PkForm::Coefficients,
+                self.g_ntt_butterfly.is_some(),
+                "pk must be in the form of the claim"
+            );
+        }
+        let pk = instances.iter().flat_map(|i| i.pk).copied().collect_vec();
+
+        // The signatures are decompressed into the s1 polynomials of all the instances
+        let compressed = instances.iter().map(|i| i.compressed()).collect_vec();
+        let (decompression_trace, s1, decompressed_values, decompressed_highs) =
+            self.decompression.gen_trace(&compressed, poly_size);
+
+        // HashToPoint: the sponge feeds the permutations and the sampler, which outputs
+        // the message points of all the instances
+        let steps = instances
+            .iter()
+            .map(|i| sponge_steps(i.nonce(), i.message, self.sampler.squeeze_blocks))
+            .collect_vec();
+        let (sponge_trace, permutations, chunks) = self.sponge.gen_trace(&steps);
+        let keccak_trace = self.keccak.gen_trace(&permutations);
+        let (sampler_trace, msg_point, bytes, sampled_values, rejections) =
+            self.sampler.gen_trace(&chunks, poly_size);
+        let byte_range_check_trace = self
+            .byte_range_check
+            .gen_trace(&[bytes, decompressed_highs]);
+        let rejection_range_check_trace = self.rejection_range_check.gen_trace(&[rejections]);
+
+        let mut range_check_input = vec![sampled_values, decompressed_values];
         let (f_ntt_butterfly_trace, f_ntt_butterfly_remainders, f_ntt_butterfly_output) =
-            self.f_ntt_butterfly.gen_trace(s1);
+            self.f_ntt_butterfly.gen_trace(&s1, poly_size);
         range_check_input.extend(f_ntt_butterfly_remainders);
 
         let mut f_ntt_outputs = vec![f_ntt_butterfly_output];
@@ -262,29 +437,43 @@ impl BigClaim {
             f_ntt_js.push(js);
         }
 
-        let (g_ntt_butterfly_trace, g_ntt_butterfly_remainders, g_ntt_butterfly_output) =
-            self.g_ntt_butterfly.gen_trace(pk);
-        range_check_input.extend(g_ntt_butterfly_remainders);
-
-        let mut g_ntt_outputs = vec![g_ntt_butterfly_output];
-        let mut g_ntt_traces = vec![];
-        let mut g_ntt_js = vec![];
-        for (i, merge) in self.g_ntt_merges.iter().enumerate() {
-            let (g_ntt_trace, g_ntt_remainders, g_ntt_output, js) =
-                merge.gen_trace(&g_ntt_outputs[i], i + 1);
-            range_check_input.extend(g_ntt_remainders);
-            g_ntt_outputs.push(g_ntt_output);
-            g_ntt_traces.push(g_ntt_trace);
-            g_ntt_js.push(js);
-        }
+        // A public key in NTT form skips the G-NTT, whose merges look up no roots
+        let (g_ntt_butterfly_trace, g_ntt_traces, g_ntt_js, pk_ntt) = match &self.g_ntt_butterfly {
+            Some(g_ntt_butterfly) => {
+                let (g_ntt_butterfly_trace, g_ntt_butterfly_remainders, g_ntt_butterfly_output) =
+                    g_ntt_butterfly.gen_trace(&pk, poly_size);
+                range_check_input.extend(g_ntt_butterfly_remainders);
 
-        let (mul_trace, mul_remainders) = self.mul.gen_trace(
-            &f_ntt_outputs.last().unwrap()[0],
-            &g_ntt_outputs.last().unwrap()[0],
-        );
+                let mut g_ntt_outputs = vec![g_ntt_butterfly_output];
+                let mut g_ntt_traces = vec![];
+                let mut g_ntt_js = vec![];
+                for (i, merge) in self.g_ntt_merges.iter().enumerate() {
+                    let (g_ntt_trace, g_ntt_remainders, g_ntt_output, js) =
+                        merge.gen_trace(&g_ntt_outputs[i], i + 1);
+                    range_check_input.extend(g_ntt_remainders);
+                    g_ntt_outputs.push(g_ntt_output);
+                    g_ntt_traces.push(g_ntt_trace);
+                    g_ntt_js.push(js);
+                }
+                let pk_ntt = g_ntt_outputs.last().unwrap().concat();
+                (Some(g_ntt_butterfly_trace), g_ntt_traces, g_ntt_js, pk_ntt)
+            }
+            None => (None, vec![], vec![vec![]; self.f_ntt_merges.len()], pk),
+        };
+
+        // The last merges output one polynomial per instance
+        let (mul_trace, mul_remainders) = self
+            .mul
+            .gen_trace(&f_ntt_outputs.last().unwrap().concat(), &pk_ntt);
         range_check_input.push(mul_remainders.clone());
 
-        let mut intt_outputs = vec![vec![mul_remainders.into_iter().map(|r| r.0).collect_vec()]];
+        let mul_output = mul_remainders.into_iter().map(|r| r.0).collect_vec();
+        let mut intt_outputs = vec![
+            mul_output
+                .chunks(poly_size)
+                .map(|chunk| chunk.to_vec())
+                .collect_vec(),
+        ];
         let mut intt_traces = vec![];
         let mut intt_js = vec![];
 
@@ -299,36 +488,27 @@ impl BigClaim {
         let ibutterfly_input = intt_outputs.last().unwrap().clone();
 
         let (ibutterfly_trace, ibutterfly_remainders, ibutterflied_poly) =
-            self.ibutterfly.gen_trace(&ibutterfly_input);
+            self.ibutterfly.gen_trace(&ibutterfly_input, poly_size);
 
         range_check_input.extend(ibutterfly_remainders);
 
-        let (sub_trace, sub_remainders) = self.sub.gen_trace(msg_point, &ibutterflied_poly);
+        let (sub_trace, sub_remainders) = self.sub.gen_trace(&msg_point, &ibutterflied_poly);
         range_check_input.push(sub_remainders.clone());
 
-        let (
-            euclidean_norm_trace,
-            euclidean_norm_remainders,
-            (euclidean_norm_output_low, euclidean_norm_output_high),
-        ) = self.euclidean_norm.gen_trace(
-            &sub_remainders
-                .iter()
-                .map(|r| r.0)
-                .collect_vec()
-                .try_into()
-                .unwrap(),
-            s1,
-        );
+        let (euclidean_norm_trace, euclidean_norm_remainders, low_limbs, high_limbs) =
+            self.euclidean_norm.gen_trace(
+                &sub_remainders.iter().map(|r| r.0).collect_vec(),
+                &s1,
+                poly_size,
+            );
         let half_range_check_trace = self
             .half_range_check
             .gen_trace(&[euclidean_norm_remainders]);
 
-        let low_sig_bound_check_trace = self
-            .low_sig_bound_check
-            .gen_trace(&[vec![M31(euclidean_norm_output_low)]]);
-        let high_sig_bound_check_trace = self
-            .high_sig_bound_check
-            .gen_trace(&[vec![M31(euclidean_norm_output_high)]]);
+        // The limbs of every cumulative sum of the norms and of their slacks to the
+        // signature bound are range checked
+        let low_sig_bound_check_trace = self.low_sig_bound_check.gen_trace(&[low_limbs]);
+        let high_sig_bound_check_trace = self.high_sig_bound_check.gen_trace(&[high_limbs]);
         let range_check_trace = self.range_check.gen_trace(&range_check_input);
 
         let mut roots = vec![];
@@ -351,79 +531,74 @@ impl BigClaim {
             inv_roots.push(roots_trace);
         }
 
-        (
-            chain!(
-                f_ntt_butterfly_trace.clone(),
-                f_ntt_traces.iter().flatten().cloned().collect_vec(),
-                g_ntt_butterfly_trace.clone(),
-                g_ntt_traces.iter().flatten().cloned().collect_vec(),
-                mul_trace.clone(),
-                intt_traces.iter().flatten().cloned().collect_vec(),
-                ibutterfly_trace.clone(),
-                sub_trace.clone(),
-                euclidean_norm_trace.clone(),
-                [half_range_check_trace.clone()],
-                [low_sig_bound_check_trace.clone()],
-                [high_sig_bound_check_trace.clone()],
-                [range_check_trace.clone()],
-                roots.clone(),
-                inv_roots.clone(),
-            )
-            .collect_vec(),
-            AllTraces::new(
-                f_ntt_butterfly_trace,
-                f_ntt_traces,
-                g_ntt_butterfly_trace,
-                g_ntt_traces,
-                mul_trace,
-                intt_traces,
-                ibutterfly_trace,
-                sub_trace,
-                euclidean_norm_trace,
-                half_range_check_trace,
-                low_sig_bound_check_trace,
-                high_sig_bound_check_trace,
-                range_check_trace,
-                roots,
-                inv_roots,
-            ),
-        )
+        let traces = AllTraces::new(
+            f_ntt_butterfly_trace,
+            f_ntt_traces,
+            g_ntt_butterfly_trace,
+            g_ntt_traces,
+            mul_trace,
+            intt_traces,
+            ibutterfly_trace,
+            sub_trace,
+            euclidean_norm_trace,
+            half_range_check_trace,
+            low_sig_bound_check_trace,
+            high_sig_bound_check_trace,
+            range_check_trace,
+            roots,
+            inv_roots,
+            keccak_trace,
+            sponge_trace,
+            sampler_trace,
+            byte_range_check_trace,
+            rejection_range_check_trace,
+            decompression_trace,
+        );
+        (traces.columns(), traces)
     }
 
-    /// Creates the standard preprocessed columns for range checking.
+    /// Creates the preprocessed columns of the claim.
     ///
-    /// This function creates and returns the standard preprocessed columns used
-    /// for range checking across the Big AIR system.
+    /// This function creates and returns the preprocessed columns used across the
+    /// Big AIR system: the range check tables, the roots of unity of every NTT stage
+    /// up to the polynomial size, the coefficient indices of the public inputs of the
+    /// stacked instances, the round and chunk layouts of HashToPoint and the bit layout
+    /// of the decompression.
     ///
     /// # Returns
     ///
-    /// Returns a tuple containing all the preprocessed range check columns.
-    pub fn create_preprocessed_columns() -> (
+    /// Returns a tuple containing all the preprocessed columns and their ids.
+    pub fn create_preprocessed_columns(
+        &self,
+    ) -> (
         Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         Vec<PreProcessedColumnId>,
     ) {
         let mut columns = vec![];
         let mut ids = vec![];
 
-        let range_check_preprocessed = range_check::RangeCheck::<Q>::gen_column_simd();
-        columns.push(range_check_preprocessed);
-        ids.push(range_check::RangeCheck::<Q>::id());
-
-        let half_range_check_preprocessed = range_check::RangeCheck::<{ Q / 2 }>::gen_column_simd();
-        columns.push(half_range_check_preprocessed);
-        ids.push(range_check::RangeCheck::<{ Q / 2 }>::id());
-
-        let low_sig_bound_check_preprocessed =
-            range_check::RangeCheck::<LOW_SIG_BOUND>::gen_column_simd();
-        columns.push(low_sig_bound_check_preprocessed);
-        ids.push(range_check::RangeCheck::<LOW_SIG_BOUND>::id());
-
-        let high_sig_bound_check_preprocessed =
-            range_check::RangeCheck::<HIGH_SIG_BOUND>::gen_column_simd();
-        columns.push(high_sig_bound_check_preprocessed);
-        ids.push(range_check::RangeCheck::<HIGH_SIG_BOUND>::id());
+        let params = &self.params;
+        let log_n_instances = self.log_n_instances();
+        let poly_log_size = params.poly_log_size;
+        // A limb of a caller-supplied signature bound may be one of the other bounds,
+        // whose table is then shared
+        for bound in [
+            params.q,
+            params.q / 2,
+            params.low_sig_bound(),
+            params.high_sig_bound(),
+            BYTE_BOUND,
+            REJECTION_RANGE_BOUND,
+        ]
+        .into_iter()
+        .unique()
+        {
+            let range_check = range_check::RangeCheck::new(bound);
+            columns.push(range_check.gen_column_simd());
+            ids.push(range_check.id());
+        }
 
-        for i in 2..=POLY_LOG_SIZE {
+        for i in 2..=poly_log_size {
             let roots_preprocessed = roots::preprocessed::Roots::new(i as usize).gen_column_simd();
             columns.extend(roots_preprocessed);
             ids.push(roots::preprocessed::Roots::new(i as usize).id());
@@ -431,7 +606,7 @@ impl BigClaim {
             root_id.id.push_str("_root");
             ids.push(root_id);
         }
-        for i in (2..=POLY_LOG_SIZE).rev() {
+        for i in (2..=poly_log_size).rev() {
             let inv_roots_preprocessed =
                 roots::inv_preprocessed::InvRoots::new(i as usize).gen_column_simd();
             columns.extend(inv_roots_preprocessed);
@@ -440,6 +615,54 @@ impl BigClaim {
             inv_root_id.id.push_str("_inv_root");
             ids.push(inv_root_id);
         }
+
+        // Coefficient indices of the public inputs and of s1: the public key and s1 are
+        // read by the butterflies (two coefficients per row, bit-reversed), the message
+        // point by the subtraction and s1 by the Euclidean norm (one coefficient per row).
+        // The row indices of the components with two coefficients per row key the lookups
+        // between the NTT, multiplication, INTT and subtraction stages
+        let trace_log_size = poly_log_size + log_n_instances;
+        for indices in [
+            Indices::bit_reversed(trace_log_size - 1, poly_log_size),
+            Indices::natural(trace_log_size),
+            Indices::natural_circle_domain_order(trace_log_size),
+            Indices::natural(trace_log_size - 1),
+        ] {
+            columns.push(indices.gen_column_simd());
+            ids.push(indices.id());
+        }
+
+        // Row layouts of the merges and splits, whose chunks of 2 to poly_size / 2 rows are
+        // shared by the f and g merges and the splits of the same size, and of the Euclidean
+        // norm
+        for log_chunk_size in 1..poly_log_size {
+            for layout in ButterflyLayout::all(trace_log_size - 1, 1 << log_chunk_size) {
+                columns.push(layout.gen_column_simd());
+                ids.push(layout.id());
+            }
+        }
+        // The Euclidean norm range checks every row, so only the first row of each
+        // signature is flagged
+        let norm_flags = euclidean_norm::NormFlags::new(
+            trace_log_size,
+            params.poly_size(),
+            euclidean_norm::NormColumn::IsNotFirst,
+        );
+        columns.push(norm_flags.gen_column_simd());
+        ids.push(norm_flags.id());
+
+        for round_flags in keccak::RoundFlags::all(self.keccak.log_size) {
+            columns.push(round_flags.gen_column_simd());
+            ids.push(round_flags.id());
+        }
+        for chunk_flags in sampler::ChunkFlags::all(&self.sampler, 1 << log_n_instances) {
+            columns.push(chunk_flags.gen_column_simd());
+            ids.push(chunk_flags.id());
+        }
+        for bit_flags in decompression::BitFlags::all(&self.decompression, params.poly_size()) {
+            columns.push(bit_flags.gen_column_simd());
+            ids.push(bit_flags.id());
+        }
         (columns, ids)
     }
 
@@ -455,6 +678,8 @@ impl BigClaim {
     /// # Returns
     ///
     /// Returns tuples containing the butterfly and merge components for both f and g NTTs.
+    /// There is no g butterfly nor g merge if the public key is given in NTT form.
+    #[allow(clippy::type_complexity)]
     pub fn create_ntt_components(
         claim: &BigClaim,
         lookup_elements: &crate::big_air::relation::LookupElements,
@@ -463,15 +688,19 @@ impl BigClaim {
     ) -> (
         ntt::butterfly::Component,
         Vec<ntt::Component>,
-        ntt::butterfly::Component,
+        Option<ntt::butterfly::Component>,
         Vec<ntt::Component>,
     ) {
         let f_ntt_butterfly_component = ntt::butterfly::Component::new(
             tree_span_provider,
             ntt::butterfly::Eval {
                 claim: claim.f_ntt_butterfly.clone(),
+                poly_size: claim.params.poly_size(),
                 rc_lookup_elements: lookup_elements.rc.clone(),
+                poly_id: F_POLY_ID,
                 butterfly_output_lookup_elements: lookup_elements.f_ntt_butterfly.clone(),
+                public_input_lookup_elements: None,
+                s1_lookup_elements: Some(lookup_elements.s1.clone()),
             },
             interaction_claim.f_ntt_butterfly.claimed_sum,
         );
@@ -501,15 +730,26 @@ impl BigClaim {
             })
             .collect_vec();
 
-        let g_ntt_butterfly_component = ntt::butterfly::Component::new(
-            tree_span_provider,
-            ntt::butterfly::Eval {
-                claim: claim.g_ntt_butterfly.clone(),
-                rc_lookup_elements: lookup_elements.rc.clone(),
-                butterfly_output_lookup_elements: lookup_elements.g_ntt_butterfly.clone(),
-            },
-            interaction_claim.g_ntt_butterfly.claimed_sum,
-        );
+        let g_ntt_butterfly_component = claim
+            .g_ntt_butterfly
+            .iter()
+            .zip_eq(interaction_claim.g_ntt_butterfly.iter())
+            .map(|(g_ntt_butterfly, interaction_claim)| {
+                ntt::butterfly::Component::new(
+                    tree_span_provider,
+                    ntt::butterfly::Eval {
+                        claim: g_ntt_butterfly.clone(),
+                        poly_size: claim.params.poly_size(),
+                        rc_lookup_elements: lookup_elements.rc.clone(),
+                        poly_id: G_POLY_ID,
+                        butterfly_output_lookup_elements: lookup_elements.g_ntt_butterfly.clone(),
+                        public_input_lookup_elements: Some(lookup_elements.public_input.clone()),
+                        s1_lookup_elements: None,
+                    },
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .next();
 
         let g_ntt_merges_components = claim
             .g_ntt_merges
@@ -568,10 +808,10 @@ impl BigClaim {
         intt::ibutterfly::Component,
         sub::Component,
         euclidean_norm::Component,
-        range_check::Component<{ Q / 2 }>,
-        range_check::Component<LOW_SIG_BOUND>,
-        range_check::Component<HIGH_SIG_BOUND>,
-        range_check::Component<Q>,
+        range_check::Component,
+        range_check::Component,
+        range_check::Component,
+        range_check::Component,
         Vec<roots::preprocessed::Component>,
         Vec<roots::inv_preprocessed::Component>,
     ) {
@@ -582,7 +822,13 @@ impl BigClaim {
                 rc_lookup_elements: lookup_elements.rc.clone(),
                 f_ntt_lookup_elements: lookup_elements.f_ntt.clone(),
                 g_ntt_lookup_elements: lookup_elements.g_ntt.clone(),
+                // Without G-NTT, the public key is read in NTT form
+                pk_ntt_lookup_elements: claim
+                    .g_ntt_butterfly
+                    .is_none()
+                    .then(|| lookup_elements.public_input.clone()),
                 mul_lookup_elements: lookup_elements.mul.clone(),
+                poly_log_size: claim.params.poly_log_size,
             },
             interaction_claim.mul.claimed_sum,
         );
@@ -608,7 +854,7 @@ impl BigClaim {
                             )
                         },
                         intt_lookup_elements: lookup_elements.intt.clone(),
-                        poly_size: 1 << (crate::POLY_LOG_SIZE as usize - i),
+                        poly_size: 1 << (claim.params.poly_log_size as usize - i),
                         inv_roots_lookup_elements: lookup_elements.inv_roots.clone(),
                     },
                     interaction_claim.claimed_sum,
@@ -623,6 +869,7 @@ impl BigClaim {
                 rc_lookup_elements: lookup_elements.rc.clone(),
                 intt_output_lookup_elements: lookup_elements.intt.clone(),
                 ibutterfly_output_lookup_elements: lookup_elements.ibutterfly.clone(),
+                poly_size: claim.params.poly_size(),
             },
             interaction_claim.ibutterfly.claimed_sum,
         );
@@ -634,6 +881,7 @@ impl BigClaim {
                 rc_lookup_elements: lookup_elements.rc.clone(),
                 ibutterfly_lookup_elements: lookup_elements.ibutterfly.clone(),
                 sub_lookup_elements: lookup_elements.sub.clone(),
+                msg_point_lookup_elements: lookup_elements.msg_point.clone(),
             },
             interaction_claim.sub.claimed_sum,
         );
@@ -642,17 +890,19 @@ impl BigClaim {
             tree_span_provider,
             euclidean_norm::Eval {
                 claim: claim.euclidean_norm.clone(),
+                poly_size: claim.params.poly_size(),
                 half_rc_lookup_elements: lookup_elements.half_range_check.clone(),
                 s0_lookup_elements: lookup_elements.sub.clone(),
                 low_sig_bound_check_lookup_elements: lookup_elements.low_sig_bound_check.clone(),
                 high_sig_bound_check_lookup_elements: lookup_elements.high_sig_bound_check.clone(),
+                s1_lookup_elements: lookup_elements.s1.clone(),
             },
             interaction_claim.euclidean_norm.claimed_sum,
         );
 
         let half_range_check_component = range_check::Component::new(
             tree_span_provider,
-            range_check::Eval::<{ Q / 2 }> {
+            range_check::Eval {
                 claim: claim.half_range_check.clone(),
                 lookup_elements: lookup_elements.half_range_check.clone(),
             },
@@ -661,7 +911,7 @@ impl BigClaim {
 
         let low_sig_bound_check_component = range_check::Component::new(
             tree_span_provider,
-            range_check::Eval::<LOW_SIG_BOUND> {
+            range_check::Eval {
                 claim: claim.low_sig_bound_check.clone(),
                 lookup_elements: lookup_elements.low_sig_bound_check.clone(),
             },
@@ -670,7 +920,7 @@ impl BigClaim {
 
         let high_sig_bound_check_component = range_check::Component::new(
             tree_span_provider,
-            range_check::Eval::<HIGH_SIG_BOUND> {
+            range_check::Eval {
                 claim: claim.high_sig_bound_check.clone(),
                 lookup_elements: lookup_elements.high_sig_bound_check.clone(),
             },
@@ -679,7 +929,7 @@ impl BigClaim {
 
         let range_check_component = range_check::Component::new(
             tree_span_provider,
-            range_check::Eval::<Q> {
+            range_check::Eval {
                 claim: claim.range_check.clone(),
                 lookup_elements: lookup_elements.rc.clone(),
             },
@@ -713,7 +963,7 @@ impl BigClaim {
                     roots::inv_preprocessed::Eval {
                         claim: inv_roots_claim.clone(),
                         lookup_elements: lookup_elements.inv_roots.clone(),
-                        poly_log_size: POLY_LOG_SIZE as usize - i,
+                        poly_log_size: claim.params.poly_log_size as usize - i,
                     },
                     interaction_claim.claimed_sum,
                 )
@@ -734,4 +984,120 @@ impl BigClaim {
             inv_roots_components,
         )
     }
+
+    /// Creates the HashToPoint components (keccak, sponge, sampler and their range checks).
+    ///
+    /// # Arguments
+    ///
+    /// * `claim` - The BigClaim containing all component claims
+    /// * `lookup_elements` - The lookup elements for all operations
+    /// * `interaction_claim` - The interaction claim containing claimed sums
+    /// * `tree_span_provider` - The trace location allocator
+    ///
+    /// # Returns
+    ///
+    /// Returns all the HashToPoint components as individual variables.
+    pub fn create_hash_to_point_components(
+        claim: &BigClaim,
+        lookup_elements: &crate::big_air::relation::LookupElements,
+        interaction_claim: &crate::big_air::interaction_claim::BigInteractionClaim,
+        tree_span_provider: &mut stwo_constraint_framework::TraceLocationAllocator,
+    ) -> (
+        keccak::Component,
+        sponge::Component,
+        sampler::Component,
+        range_check::Component,
+        range_check::Component,
+    ) {
+        let keccak_component = keccak::Component::new(
+            tree_span_provider,
+            keccak::Eval {
+                claim: claim.keccak.clone(),
+                keccak_input_lookup_elements: lookup_elements.keccak_input.clone(),
+                keccak_output_lookup_elements: lookup_elements.keccak_output.clone(),
+            },
+            interaction_claim.keccak.claimed_sum,
+        );
+
+        let sponge_component = sponge::Component::new(
+            tree_span_provider,
+            sponge::Eval {
+                claim: claim.sponge.clone(),
+                message_block_lookup_elements: lookup_elements.message_block.clone(),
+                keccak_input_lookup_elements: lookup_elements.keccak_input.clone(),
+                keccak_output_lookup_elements: lookup_elements.keccak_output.clone(),
+                chunk_lookup_elements: lookup_elements.chunk.clone(),
+            },
+            interaction_claim.sponge.claimed_sum,
+        );
+
+        let sampler_component = sampler::Component::new(
+            tree_span_provider,
+            sampler::Eval {
+                claim: claim.sampler.clone(),
+                poly_size: claim.params.poly_size(),
+                n_instances: 1 << claim.log_n_instances(),
+                chunk_lookup_elements: lookup_elements.chunk.clone(),
+                byte_rc_lookup_elements: lookup_elements.byte_range_check.clone(),
+                rc_lookup_elements: lookup_elements.rc.clone(),
+                rejection_rc_lookup_elements: lookup_elements.rejection_range_check.clone(),
+                msg_point_lookup_elements: lookup_elements.msg_point.clone(),
+            },
+            interaction_claim.sampler.claimed_sum,
+        );
+
+        let byte_range_check_component = range_check::Component::new(
+            tree_span_provider,
+            range_check::Eval {
+                claim: claim.byte_range_check.clone(),
+                lookup_elements: lookup_elements.byte_range_check.clone(),
+            },
+            interaction_claim.byte_range_check.claimed_sum,
+        );
+
+        let rejection_range_check_component = range_check::Component::new(
+            tree_span_provider,
+            range_check::Eval {
+                claim: claim.rejection_range_check.clone(),
+                lookup_elements: lookup_elements.rejection_range_check.clone(),
+            },
+            interaction_claim.rejection_range_check.claimed_sum,
+        );
+
+        (
+            keccak_component,
+            sponge_component,
+            sampler_component,
+            byte_range_check_component,
+            rejection_range_check_component,
+        )
+    }
+
+    /// Creates the signature decompression component.
+    ///
+    /// # Arguments
+    ///
+    /// * `claim` - The BigClaim containing all component claims
+    /// * `lookup_elements` - The lookup elements for all operations
+    /// * `interaction_claim` - The interaction claim containing claimed sums
+    /// * `tree_span_provider` - The trace location allocator
+    pub fn create_decompression_component(
+        claim: &BigClaim,
+        lookup_elements: &crate::big_air::relation::LookupElements,
+        interaction_claim: &crate::big_air::interaction_claim::BigInteractionClaim,
+        tree_span_provider: &mut stwo_constraint_framework::TraceLocationAllocator,
+    ) -> decompression::Component {
+        decompression::Component::new(
+            tree_span_provider,
+            decompression::Eval {
+                claim: claim.decompression.clone(),
+                poly_size: claim.params.poly_size(),
+                public_input_lookup_elements: lookup_elements.public_input.clone(),
+                s1_lookup_elements: lookup_elements.s1.clone(),
+                rc_lookup_elements: lookup_elements.rc.clone(),
+                byte_rc_lookup_elements: lookup_elements.byte_range_check.clone(),
+            },
+            interaction_claim.decompression.claimed_sum,
+        )
+    }
 }
@@ -0,0 +1,275 @@
+//! # Big AIR Components
+//!
+//! This module groups every framework component of the Big AIR STARK proof system
+//! in a single structure so that the prover and the verifier build them the same way.
+//!
+//! # Components
+//!
+//! - **BigComponents**: All the components, stored in commitment order
+//! - **Prover View**: The components as `ComponentProver`s for `prove`
+//! - **Verifier View**: The components as `Component`s for `verify`
+//!
+//! The order in which the components are listed must match the order in which
+//! their traces are committed by `BigClaim::gen_trace` and
+//! `BigInteractionClaim::gen_interaction_trace`.
+
+use stwo::{
+    core::{air::Component, pcs::TreeVec},
+    prover::ComponentProver,
+};
+use stwo_constraint_framework::{
+    PREPROCESSED_TRACE_IDX, TraceLocationAllocator, preprocessed_columns::PreProcessedColumnId,
+};
+
+use crate::{
+    big_air::{
+        backend::FalconBackend, claim::BigClaim, interaction_claim::BigInteractionClaim,
+        relation::LookupElements,
+    },
+    debug::relation_tracker::BigAirComponents,
+    encoding::decompression,
+    hash_to_point::{keccak, sampler, sponge},
+    ntts::{intt, ntt, roots},
+    polys::{euclidean_norm, mul, sub},
+    zq::range_check,
+};
+
+/// All the components of the Big AIR, in commitment order.
+pub struct BigComponents {
+    pub f_ntt_butterfly: ntt::butterfly::Component,
+    pub f_ntt_merges: Vec<ntt::Component>,
+    /// The G-NTT butterfly, if the public key is given by its coefficients
+    pub g_ntt_butterfly: Option<ntt::butterfly::Component>,
+    pub g_ntt_merges: Vec<ntt::Component>,
+    pub mul: mul::Component,
+    pub intt_merges: Vec<intt::Component>,
+    pub ibutterfly: intt::ibutterfly::Component,
+    pub sub: sub::Component,
+    pub euclidean_norm: euclidean_norm::Component,
+    pub half_range_check: range_check::Component,
+    pub low_sig_bound_check: range_check::Component,
+    pub high_sig_bound_check: range_check::Component,
+    pub range_check: range_check::Component,
+    pub roots: Vec<roots::preprocessed::Component>,
+    pub inv_roots: Vec<roots::inv_preprocessed::Component>,
+    pub keccak: keccak::Component,
+    pub sponge: sponge::Component,
+    pub sampler: sampler::Component,
+    pub byte_range_check: range_check::Component,
+    pub rejection_range_check: range_check::Component,
+    pub decompression: decompression::Component,
+}
+
+impl BigComponents {
+    /// Builds all the components from the claims and the drawn lookup elements.
+    ///
+    /// # Parameters
+    ///
+    /// - `claim`: The BigClaim containing all component claims
+    /// - `lookup_elements`: The lookup elements for all operations
+    /// - `interaction_claim`: The interaction claim containing claimed sums
+    /// - `preprocessed_columns_ids`: The ids of the committed preprocessed columns
+    pub fn new(
+        claim: &BigClaim,
+        lookup_elements: &LookupElements,
+        interaction_claim: &BigInteractionClaim,
+        preprocessed_columns_ids: &[PreProcessedColumnId],
+    ) -> Self {
+        let mut tree_span_provider =
+            TraceLocationAllocator::new_with_preproccessed_columns(preprocessed_columns_ids);
+
+        let (f_ntt_butterfly, f_ntt_merges, g_ntt_butterfly, g_ntt_merges) =
+            BigClaim::create_ntt_components(
+                claim,
+                lookup_elements,
+                interaction_claim,
+                &mut tree_span_provider,
+            );
+        let (
+            mul,
+            intt_merges,
+            ibutterfly,
+            sub,
+            euclidean_norm,
+            half_range_check,
+            low_sig_bound_check,
+            high_sig_bound_check,
+            range_check,
+            roots,
+            inv_roots,
+        ) = BigClaim::create_remaining_components(
+            claim,
+            lookup_elements,
+            interaction_claim,
+            &mut tree_span_provider,
+        );
+        let (keccak, sponge, sampler, byte_range_check, rejection_range_check) =
+            BigClaim::create_hash_to_point_components(
+                claim,
+                lookup_elements,
+                interaction_claim,
+                &mut tree_span_provider,
+            );
+        let decompression = BigClaim::create_decompression_component(
+            claim,
+            lookup_elements,
+            interaction_claim,
+            &mut tree_span_provider,
+        );
+
+        Self {
+            f_ntt_butterfly,
+            f_ntt_merges,
+            g_ntt_butterfly,
+            g_ntt_merges,
+            mul,
+            intt_merges,
+            ibutterfly,
+            sub,
+            euclidean_norm,
+            half_range_check,
+            low_sig_bound_check,
+            high_sig_bound_check,
+            range_check,
+            roots,
+            inv_roots,
+            keccak,
+            sponge,
+            sampler,
+            byte_range_check,
+            rejection_range_check,
+            decompression,
+        }
+    }
+
+    /// Returns the components as provers on the backend `B`, in commitment order.
+    pub fn provers<B: FalconBackend>(&self) -> Vec<&dyn ComponentProver<B>> {
+        let mut components: Vec<&dyn ComponentProver<B>> = vec![];
+        components.push(B::as_prover(&self.f_ntt_butterfly));
+        for merge in self.f_ntt_merges.iter() {
+            components.push(B::as_prover(merge));
+        }
+        for butterfly in self.g_ntt_butterfly.iter() {
+            components.push(B::as_prover(butterfly));
+        }
+        for merge in self.g_ntt_merges.iter() {
+            components.push(B::as_prover(merge));
+        }
+        components.push(B::as_prover(&self.mul));
+        for merge in self.intt_merges.iter() {
+            components.push(B::as_prover(merge));
+        }
+        components.push(B::as_prover(&self.ibutterfly));
+        components.push(B::as_prover(&self.sub));
+        components.push(B::as_prover(&self.euclidean_norm));
+        components.push(B::as_prover(&self.half_range_check));
+        components.push(B::as_prover(&self.low_sig_bound_check));
+        components.push(B::as_prover(&self.high_sig_bound_check));
+        components.push(B::as_prover(&self.range_check));
+        for root in self.roots.iter() {
+            components.push(B::as_prover(root));
+        }
+        for inv_root in self.inv_roots.iter() {
+            components.push(B::as_prover(inv_root));
+        }
+        components.push(B::as_prover(&self.keccak));
+        components.push(B::as_prover(&self.sponge));
+        components.push(B::as_prover(&self.sampler));
+        components.push(B::as_prover(&self.byte_range_check));
+        components.push(B::as_prover(&self.rejection_range_check));
+        components.push(B::as_prover(&self.decompression));
+        components
+    }
+
+    /// Returns the components as verifier components, in commitment order.
+    pub fn components(&self) -> Vec<&dyn Component> {
+        let mut components: Vec<&dyn Component> = vec![];
+        components.push(&self.f_ntt_butterfly);
+        for merge in self.f_ntt_merges.iter() {
+            components.push(merge);
+        }
+        for butterfly in self.g_ntt_butterfly.iter() {
+            components.push(butterfly);
+        }
+        for merge in self.g_ntt_merges.iter() {
+            components.push(merge);
+        }
+        components.push(&self.mul);
+        for merge in self.intt_merges.iter() {
+            components.push(merge);
+        }
+        components.push(&self.ibutterfly);
+        components.push(&self.sub);
+        components.push(&self.euclidean_norm);
+        components.push(&self.half_range_check);
+        components.push(&self.low_sig_bound_check);
+        components.push(&self.high_sig_bound_check);
+        components.push(&self.range_check);
+        for root in self.roots.iter() {
+            components.push(root);
+        }
+        for inv_root in self.inv_roots.iter() {
+            components.push(inv_root);
+        }
+        components.push(&self.keccak);
+        components.push(&self.sponge);
+        components.push(&self.sampler);
+        components.push(&self.byte_range_check);
+        components.push(&self.rejection_range_check);
+        components.push(&self.decompression);
+        components
+    }
+
+    /// Returns references to all the components, for the relation tracker.
+    pub fn tracked(&self) -> BigAirComponents<'_> {
+        BigAirComponents {
+            f_ntt_butterfly: &self.f_ntt_butterfly,
+            f_ntt_merges: &self.f_ntt_merges,
+            g_ntt_butterfly: self.g_ntt_butterfly.as_ref(),
+            g_ntt_merges: &self.g_ntt_merges,
+            mul: &self.mul,
+            intt_merges: &self.intt_merges,
+            ibutterfly: &self.ibutterfly,
+            sub: &self.sub,
+            euclidean_norm: &self.euclidean_norm,
+            half_range_check: &self.half_range_check,
+            low_sig_bound_check: &self.low_sig_bound_check,
+            high_sig_bound_check: &self.high_sig_bound_check,
+            range_check: &self.range_check,
+            roots: &self.roots,
+            inv_roots: &self.inv_roots,
+            keccak: &self.keccak,
+            sponge: &self.sponge,
+            sampler: &self.sampler,
+            byte_range_check: &self.byte_range_check,
+            rejection_range_check: &self.rejection_range_check,
+            decompression: &self.decompression,
+        }
+    }
+
+    /// Returns the column log sizes of the three committed trees.
+    ///
+    /// The main and interaction trees are the concatenation of the components' own
+    /// columns. The preprocessed tree is shared between components, so its sizes are
+    /// taken from the preprocessed columns themselves.
+    pub fn column_log_sizes(&self, preprocessed_log_sizes: Vec<u32>) -> TreeVec<Vec<u32>> {
+        let mut log_sizes = TreeVec::new(vec![vec![]; 3]);
+        for component in self.components() {
+            for (tree, tree_log_sizes) in component.trace_log_degree_bounds().iter().enumerate() {
+                log_sizes[tree].extend(tree_log_sizes.iter().copied());
+            }
+        }
+        log_sizes[PREPROCESSED_TRACE_IDX] = preprocessed_log_sizes;
+        log_sizes
+    }
+}
+
+/// Returns the log sizes of the preprocessed columns of the claim, in commitment order.
+pub fn preprocessed_log_sizes(claim: &BigClaim) -> Vec<u32> {
+    claim
+        .create_preprocessed_columns()
+        .0
+        .iter()
+        .map(|column| column.domain.log_size())
+        .collect()
+}
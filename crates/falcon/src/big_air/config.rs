@@ -0,0 +1,141 @@
+//! # Prover Configuration
+//!
+//! This module defines the security parameters of a proof: the FRI blowup factor, the
+//! number of FRI queries and the proof of work bits ground before the FRI queries and
+//! before the lookup elements are drawn.
+//!
+//! The configuration is recorded in the `FalconProof`, so the verifier replays the
+//! transcript with the parameters the prover used, after checking that they are at least
+//! as strong as the minimum configuration it requires.
+//!
+//! # Security Level
+//!
+//! The reported security level is the conjectured one of FRI with proof of work:
+//! `log_blowup_factor * n_queries + pow_bits` bits.
+
+use serde::{Deserialize, Serialize};
+use stwo::core::{channel::Channel, fri::FriConfig, pcs::PcsConfig};
+
+/// Smallest supported log base 2 of the FRI blowup factor.
+///
+/// The constraints of every component have degree 2, so their evaluation needs a
+/// blowup of at least 2.
+pub const MIN_LOG_BLOWUP_FACTOR: u32 = 1;
+
+/// The security parameters used to generate a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProverConfig {
+    /// The log base 2 of the FRI blowup factor
+    pub log_blowup_factor: u32,
+    /// The number of FRI queries
+    pub n_queries: usize,
+    /// The proof of work bits ground before drawing the FRI queries
+    pub pow_bits: u32,
+    /// The proof of work bits ground before drawing the lookup elements
+    pub interaction_pow_bits: u32,
+}
+
+impl ProverConfig {
+    /// Returns a configuration with 96 bits of conjectured security.
+    pub fn conjectured_96_bits() -> Self {
+        Self {
+            log_blowup_factor: 2,
+            n_queries: 35,
+            pow_bits: 26,
+            interaction_pow_bits: 16,
+        }
+    }
+
+    /// Returns a configuration with 128 bits of conjectured security.
+    pub fn conjectured_128_bits() -> Self {
+        Self {
+            log_blowup_factor: 2,
+            n_queries: 51,
+            pow_bits: 26,
+            interaction_pow_bits: 16,
+        }
+    }
+
+    /// Returns the PCS configuration of the commitment scheme.
+    pub fn pcs_config(&self) -> PcsConfig {
+        PcsConfig {
+            pow_bits: self.pow_bits,
+            fri_config: FriConfig::new(
+                PcsConfig::default().fri_config.log_last_layer_degree_bound,
+                self.log_blowup_factor,
+                self.n_queries,
+            ),
+        }
+    }
+
+    /// Mixes the configuration into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        self.pcs_config().mix_into(channel);
+        channel.mix_u64(self.interaction_pow_bits as u64);
+    }
+
+    /// Returns the conjectured security level of the configuration, in bits.
+    pub fn security_bits(&self) -> u32 {
+        self.pcs_config().security_bits()
+    }
+
+    /// Returns whether the configuration is at least as strong as `minimum`: it has as
+    /// many conjectured security bits and interaction proof of work bits.
+    pub fn is_at_least(&self, minimum: &ProverConfig) -> bool {
+        self.security_bits() >= minimum.security_bits()
+            && self.interaction_pow_bits >= minimum.interaction_pow_bits
+    }
+
+    /// Returns whether proofs can be generated with this configuration.
+    pub fn is_supported(&self) -> bool {
+        self.log_blowup_factor >= MIN_LOG_BLOWUP_FACTOR && self.n_queries > 0
+    }
+}
+
+impl Default for ProverConfig {
+    /// Returns the default PCS configuration of stwo, with 2 bits of interaction proof of
+    /// work. It is meant for tests and benchmarks, not for deployments.
+    fn default() -> Self {
+        let pcs_config = PcsConfig::default();
+        Self {
+            log_blowup_factor: pcs_config.fri_config.log_blowup_factor,
+            n_queries: pcs_config.fri_config.n_queries,
+            pow_bits: pcs_config.pow_bits,
+            interaction_pow_bits: 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_security_bits() {
+        assert_eq!(ProverConfig::conjectured_96_bits().security_bits(), 96);
+        assert_eq!(ProverConfig::conjectured_128_bits().security_bits(), 128);
+    }
+
+    #[test]
+    fn test_is_at_least() {
+        let minimum = ProverConfig::conjectured_128_bits();
+        assert!(minimum.is_at_least(&minimum));
+        assert!(minimum.is_at_least(&ProverConfig::conjectured_96_bits()));
+        assert!(!ProverConfig::conjectured_96_bits().is_at_least(&minimum));
+        assert!(!ProverConfig::default().is_at_least(&minimum));
+        assert!(
+            !ProverConfig {
+                interaction_pow_bits: 0,
+                ..minimum
+            }
+            .is_at_least(&minimum)
+        );
+    }
+
+    #[test]
+    fn test_default_matches_pcs_config() {
+        let config = ProverConfig::default();
+        assert_eq!(config.security_bits(), PcsConfig::default().security_bits());
+        assert!(config.is_supported());
+    }
+}
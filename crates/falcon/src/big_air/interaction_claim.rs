@@ -29,17 +29,21 @@
 //! overall proof system maintains cryptographic soundness.
 
 use crate::{
-    HIGH_SIG_BOUND, LOW_SIG_BOUND, POLY_LOG_SIZE,
     big_air::{
-        claim::AllTraces,
-        relation::{INTTInputLookupElements, InputLookupElements, LookupElements},
+        claim::{AllTraces, BigClaim},
+        relation::{
+            F_POLY_ID, G_POLY_ID, INTTInputLookupElements, InputLookupElements, LookupElements,
+        },
     },
+    encoding::decompression,
+    hash_to_point::{keccak, sampler, sponge},
     impl_big_ic,
     ntts::{intt, ntt, roots},
     polys::{euclidean_norm, mul, sub},
-    zq::{Q, range_check},
+    zq::range_check,
 };
 use itertools::{Itertools, chain};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::fields::{m31::M31, qm31::QM31},
     prover::{
@@ -49,11 +53,11 @@ use stwo::{
 };
 
 impl_big_ic!(
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct BigInteractionClaim {
         pub f_ntt_butterfly: ntt::butterfly::InteractionClaim,
         pub f_ntt_merges: Vec<ntt::InteractionClaim>,
-        pub g_ntt_butterfly: ntt::butterfly::InteractionClaim,
+        pub g_ntt_butterfly: Option<ntt::butterfly::InteractionClaim>,
         pub g_ntt_merges: Vec<ntt::InteractionClaim>,
         pub mul: mul::InteractionClaim,
         pub intt_merges: Vec<intt::InteractionClaim>,
@@ -66,6 +70,12 @@ impl_big_ic!(
         pub range_check: range_check::InteractionClaim,
         pub roots: Vec<roots::preprocessed::InteractionClaim>,
         pub inv_roots: Vec<roots::inv_preprocessed::InteractionClaim>,
+        pub keccak: keccak::InteractionClaim,
+        pub sponge: sponge::InteractionClaim,
+        pub sampler: sampler::InteractionClaim,
+        pub byte_range_check: range_check::InteractionClaim,
+        pub rejection_range_check: range_check::InteractionClaim,
+        pub decompression: decompression::InteractionClaim,
     }
 );
 
@@ -79,6 +89,8 @@ impl BigInteractionClaim {
     ///
     /// # Parameters
     ///
+    /// - `claim`: The claim the traces were generated from, fixing the range check
+    ///   bounds and the number of INTT stages
     /// - `lookup_elements`: The lookup elements for range checking and validation
     /// - `traces`: All execution traces from the main proof components
     ///
@@ -98,7 +110,10 @@ impl BigInteractionClaim {
     /// 6. Generates arithmetic operation interaction traces and claims
     /// 7. Generates range checking interaction traces and claims
     /// 8. Generates root of unity validation interaction traces and claims
+    /// 9. Generates HashToPoint interaction traces and claims
+    /// 10. Generates the signature decompression interaction trace and claim
     pub fn gen_interaction_trace(
+        claim: &BigClaim,
         lookup_elements: &LookupElements,
         traces: &AllTraces,
     ) -> (
@@ -112,6 +127,10 @@ impl BigInteractionClaim {
                 &traces.f_ntt_butterfly,
                 &lookup_elements.rc,
                 &lookup_elements.f_ntt_butterfly,
+                F_POLY_ID,
+                None,
+                Some(&lookup_elements.s1),
+                claim.params.poly_size(),
             );
         // Initialize vectors to collect interaction traces and claims for NTT merge stages
         let mut f_ntt_interaction_traces = vec![];
@@ -131,19 +150,30 @@ impl BigInteractionClaim {
                         InputLookupElements::NTT(lookup_elements.f_ntt.clone())
                     },
                     &lookup_elements.roots,
+                    1 << (i + 1),
                 );
             f_ntt_interaction_traces.push(f_ntt_interaction_trace);
             f_ntt_interaction_claims.push(f_ntt_interaction_claim);
         }
 
         // Generate interaction traces and claims for the G polynomial NTT butterfly operation
-        // This is similar to the F polynomial but operates on the G polynomial trace
-        let (g_ntt_butterfly_interaction_trace, g_ntt_butterfly_interaction_claim) =
-            ntt::butterfly::InteractionClaim::gen_interaction_trace(
-                &traces.g_ntt_butterfly,
-                &lookup_elements.rc,
-                &lookup_elements.g_ntt_butterfly,
-            );
+        // This is similar to the F polynomial but operates on the G polynomial trace, which
+        // is missing if the public key is given in NTT form
+        let (g_ntt_butterfly_interaction_trace, g_ntt_butterfly_interaction_claim) = traces
+            .g_ntt_butterfly
+            .as_ref()
+            .map(|g_ntt_butterfly| {
+                ntt::butterfly::InteractionClaim::gen_interaction_trace(
+                    g_ntt_butterfly,
+                    &lookup_elements.rc,
+                    &lookup_elements.g_ntt_butterfly,
+                    G_POLY_ID,
+                    Some(&lookup_elements.public_input),
+                    None,
+                    claim.params.poly_size(),
+                )
+            })
+            .unzip();
         let mut g_ntt_interaction_traces = vec![];
         let mut g_ntt_interaction_claims = vec![];
         for (i, merge) in traces.g_ntt_merges.iter().enumerate() {
@@ -158,6 +188,7 @@ impl BigInteractionClaim {
                         InputLookupElements::NTT(lookup_elements.g_ntt.clone())
                     },
                     &lookup_elements.roots,
+                    1 << (i + 1),
                 );
             g_ntt_interaction_traces.push(g_ntt_interaction_trace);
             g_ntt_interaction_claims.push(g_ntt_interaction_claim);
@@ -165,7 +196,15 @@ impl BigInteractionClaim {
         // Generate interaction traces and claims for modular multiplication operations
         // This establishes lookup relations for the multiplication component
         let (mul_interaction_trace, mul_interaction_claim) =
-            mul::InteractionClaim::gen_interaction_trace(&traces.mul, lookup_elements);
+            mul::InteractionClaim::gen_interaction_trace(
+                &traces.mul,
+                lookup_elements,
+                claim.params.poly_log_size,
+                claim
+                    .g_ntt_butterfly
+                    .is_none()
+                    .then_some(&lookup_elements.public_input),
+            );
         // Initialize vectors to collect interaction traces and claims for INTT merge stages
         // Each stage processes different input lookup elements based on the stage index
         let mut intt_interaction_traces = vec![];
@@ -185,6 +224,7 @@ impl BigInteractionClaim {
                     },
                     &lookup_elements.intt,
                     &lookup_elements.inv_roots,
+                    1 << (claim.params.poly_log_size as usize - i),
                 );
             intt_interaction_traces.push(intt_interaction_trace);
             intt_interaction_claims.push(intt_interaction_claim);
@@ -195,6 +235,7 @@ impl BigInteractionClaim {
             intt::ibutterfly::InteractionClaim::gen_interaction_trace(
                 &traces.ibutterfly,
                 lookup_elements,
+                claim.params.poly_size(),
             );
         // Generate interaction traces and claims for modular subtraction operations
         // This establishes lookup relations for the subtraction component
@@ -211,29 +252,33 @@ impl BigInteractionClaim {
         // Generate interaction traces and claims for half-range checking (0 to Q/2)
         // This validates that values are within the lower half of the field range
         let (half_range_check_interaction_trace, half_range_check_interaction_claim) =
-            range_check::InteractionClaim::gen_interaction_trace::<{ Q / 2 }>(
+            range_check::InteractionClaim::gen_interaction_trace(
                 &traces.half_range_check,
                 &lookup_elements.half_range_check,
+                claim.half_range_check.bound,
             );
         // Generate interaction traces and claims for low signature bound checking
         // This validates the lower 14 bits of signature polynomial norms
         let (low_sig_bound_check_interaction_trace, low_sig_bound_check_interaction_claim) =
-            range_check::InteractionClaim::gen_interaction_trace::<LOW_SIG_BOUND>(
+            range_check::InteractionClaim::gen_interaction_trace(
                 &traces.low_sig_bound_check,
                 &lookup_elements.low_sig_bound_check,
+                claim.low_sig_bound_check.bound,
             );
 
         // Generate interaction traces and claims for high signature bound checking
         // This validates the upper bits of signature polynomial norms
         let (high_sig_bound_check_interaction_trace, high_sig_bound_check_interaction_claim) =
-            range_check::InteractionClaim::gen_interaction_trace::<HIGH_SIG_BOUND>(
+            range_check::InteractionClaim::gen_interaction_trace(
                 &traces.high_sig_bound_check,
                 &lookup_elements.high_sig_bound_check,
+                claim.high_sig_bound_check.bound,
             );
         let (range_check_interaction_trace, range_check_interaction_claim) =
-            range_check::InteractionClaim::gen_interaction_trace::<Q>(
+            range_check::InteractionClaim::gen_interaction_trace(
                 &traces.range_check,
                 &lookup_elements.rc,
+                claim.range_check.bound,
             );
         let mut roots_interaction_traces = vec![];
         let mut roots_interaction_claims = vec![];
@@ -254,11 +299,59 @@ impl BigInteractionClaim {
                 roots::inv_preprocessed::InteractionClaim::gen_interaction_trace(
                     stage_root_trace,
                     &lookup_elements.inv_roots,
-                    POLY_LOG_SIZE as usize - stage,
+                    claim.params.poly_log_size as usize - stage,
                 );
             inv_roots_interaction_traces.push(inv_roots_interaction_trace);
             inv_roots_interaction_claims.push(inv_roots_interaction_claim);
         }
+
+        // Generate interaction traces and claims for HashToPoint
+        // The permutations and the sponge exchange states, the sponge sends its squeezed
+        // chunks to the sampler, which sends the message point to the subtraction
+        let (keccak_interaction_trace, keccak_interaction_claim) =
+            keccak::InteractionClaim::gen_interaction_trace(
+                &traces.keccak,
+                &lookup_elements.keccak_input,
+                &lookup_elements.keccak_output,
+            );
+        let (sponge_interaction_trace, sponge_interaction_claim) =
+            sponge::InteractionClaim::gen_interaction_trace(
+                &traces.sponge,
+                &lookup_elements.message_block,
+                &lookup_elements.keccak_input,
+                &lookup_elements.keccak_output,
+                &lookup_elements.chunk,
+            );
+        let (sampler_interaction_trace, sampler_interaction_claim) =
+            sampler::InteractionClaim::gen_interaction_trace(
+                &traces.sampler,
+                &claim.sampler,
+                claim.params.poly_size(),
+                1 << claim.log_n_instances(),
+                lookup_elements,
+            );
+        let (byte_range_check_interaction_trace, byte_range_check_interaction_claim) =
+            range_check::InteractionClaim::gen_interaction_trace(
+                &traces.byte_range_check,
+                &lookup_elements.byte_range_check,
+                claim.byte_range_check.bound,
+            );
+        let (rejection_range_check_interaction_trace, rejection_range_check_interaction_claim) =
+            range_check::InteractionClaim::gen_interaction_trace(
+                &traces.rejection_range_check,
+                &lookup_elements.rejection_range_check,
+                claim.rejection_range_check.bound,
+            );
+
+        // Generate the interaction trace and claim for the signature decompression, which
+        // reads the signature bytes and sends s1 to the forward NTT and the Euclidean norm
+        let (decompression_interaction_trace, decompression_interaction_claim) =
+            decompression::InteractionClaim::gen_interaction_trace(
+                &traces.decompression,
+                &claim.decompression,
+                claim.params.poly_size(),
+                lookup_elements,
+            );
         (
             chain!(
                 f_ntt_butterfly_interaction_trace,
@@ -267,7 +360,7 @@ impl BigInteractionClaim {
                     .flatten()
                     .cloned()
                     .collect_vec(),
-                g_ntt_butterfly_interaction_trace,
+                g_ntt_butterfly_interaction_trace.into_iter().flatten(),
                 g_ntt_interaction_traces
                     .iter()
                     .flatten()
@@ -296,6 +389,12 @@ impl BigInteractionClaim {
                     .flatten()
                     .cloned()
                     .collect_vec(),
+                keccak_interaction_trace,
+                sponge_interaction_trace,
+                sampler_interaction_trace,
+                byte_range_check_interaction_trace,
+                rejection_range_check_interaction_trace,
+                decompression_interaction_trace,
             )
             .collect_vec(),
             Self {
@@ -314,6 +413,12 @@ impl BigInteractionClaim {
                 range_check: range_check_interaction_claim,
                 roots: roots_interaction_claims,
                 inv_roots: inv_roots_interaction_claims,
+                keccak: keccak_interaction_claim,
+                sponge: sponge_interaction_claim,
+                sampler: sampler_interaction_claim,
+                byte_range_check: byte_range_check_interaction_claim,
+                rejection_range_check: rejection_range_check_interaction_claim,
+                decompression: decompression_interaction_claim,
             },
         )
     }
@@ -63,6 +63,12 @@ macro_rules! impl_big_ic {
         for ic in $self.$field.iter() { ic.mix_into($ch); }
         $crate::impl_big_ic!(@mix_fields $self $ch; $($rest)*);
     };
+    // Option field + trailing comma: its presence is mixed before it
+    (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : Option<$inner:ty>, $($rest:tt)*) => {
+        $ch.mix_u64($self.$field.is_some() as u64);
+        if let Some(ic) = &$self.$field { ic.mix_into($ch); }
+        $crate::impl_big_ic!(@mix_fields $self $ch; $($rest)*);
+    };
     // Non-Vec field + trailing comma
     (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : $ty:ty, $($rest:tt)*) => {
         $self.$field.mix_into($ch);
@@ -72,6 +78,11 @@ macro_rules! impl_big_ic {
     (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : Vec<$inner:ty>) => {
         for ic in $self.$field.iter() { ic.mix_into($ch); }
     };
+    // Option last field (no trailing comma)
+    (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : Option<$inner:ty>) => {
+        $ch.mix_u64($self.$field.is_some() as u64);
+        if let Some(ic) = &$self.$field { ic.mix_into($ch); }
+    };
     // Non-Vec last field (no trailing comma)
     (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : $ty:ty) => {
         $self.$field.mix_into($ch);
@@ -85,6 +96,11 @@ macro_rules! impl_big_ic {
         $self.$field.iter().map(|x| x.claimed_sum).sum::<QM31>()
             + $crate::impl_big_ic!(@sum_fields $self; $($rest)*)
     };
+    // Option field + trailing comma
+    (@sum_fields $self:ident; $fvis:vis $field:ident : Option<$inner:ty>, $($rest:tt)*) => {
+        $self.$field.as_ref().map_or(<QM31 as num_traits::Zero>::zero(), |x| x.claimed_sum)
+            + $crate::impl_big_ic!(@sum_fields $self; $($rest)*)
+    };
     // Non-Vec field + trailing comma
     (@sum_fields $self:ident; $fvis:vis $field:ident : $ty:ty, $($rest:tt)*) => {
         $self.$field.claimed_sum
@@ -94,6 +110,10 @@ macro_rules! impl_big_ic {
     (@sum_fields $self:ident; $fvis:vis $field:ident : Vec<$inner:ty>) => {
         $self.$field.iter().map(|x| x.claimed_sum).sum::<QM31>()
     };
+    // Option last field (no trailing comma)
+    (@sum_fields $self:ident; $fvis:vis $field:ident : Option<$inner:ty>) => {
+        $self.$field.as_ref().map_or(<QM31 as num_traits::Zero>::zero(), |x| x.claimed_sum)
+    };
     // Non-Vec last field (no trailing comma)
     (@sum_fields $self:ident; $fvis:vis $field:ident : $ty:ty) => {
         $self.$field.claimed_sum
@@ -127,6 +147,12 @@ macro_rules! impl_mix_into {
         for ic in $self.$field.iter() { ic.mix_into($ch); }
         $crate::impl_mix_into!(@mix_fields $self $ch; $($rest)*);
     };
+    // Option field + trailing comma: its presence is mixed before it
+    (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : Option<$inner:ty>, $($rest:tt)*) => {
+        $ch.mix_u64($self.$field.is_some() as u64);
+        if let Some(ic) = &$self.$field { ic.mix_into($ch); }
+        $crate::impl_mix_into!(@mix_fields $self $ch; $($rest)*);
+    };
     // Non-Vec field + trailing comma
     (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : $ty:ty, $($rest:tt)*) => {
         $self.$field.mix_into($ch);
@@ -136,6 +162,11 @@ macro_rules! impl_mix_into {
     (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : Vec<$inner:ty>) => {
         for ic in $self.$field.iter() { ic.mix_into($ch); }
     };
+    // Option last field (no trailing comma)
+    (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : Option<$inner:ty>) => {
+        $ch.mix_u64($self.$field.is_some() as u64);
+        if let Some(ic) = &$self.$field { ic.mix_into($ch); }
+    };
     // Non-Vec last field (no trailing comma)
     (@mix_fields $self:ident $ch:ident; $fvis:vis $field:ident : $ty:ty) => {
         $self.$field.mix_into($ch);
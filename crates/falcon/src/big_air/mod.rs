@@ -12,40 +12,293 @@
 //! All operations are performed in the field Z_q where q = 12289, which is compatible
 //! with the Falcon signature scheme requirements.
 //!
+pub mod backend;
 pub mod claim;
+pub mod components;
+pub mod config;
 pub mod interaction_claim;
 pub mod macros;
+pub mod proof;
+pub mod prover;
+pub mod public_inputs;
 pub mod relation;
 
 use crate::{
-    POLY_SIZE,
-    big_air::{claim::BigClaim, interaction_claim::BigInteractionClaim, relation::LookupElements},
+    CircuitParams,
+    big_air::{
+        backend::FalconBackend,
+        claim::BigClaim,
+        components::{BigComponents, preprocessed_log_sizes},
+        config::ProverConfig,
+        proof::{FalconMerkleChannel, FalconProof},
+        prover::{self, CircuitId, FalconProver},
+        public_inputs::{PkForm, PublicInputs, batch_logup_sum, mix_batch_into},
+        relation::LookupElements,
+    },
+    encoding::{self, EncodingError, NONCE_LEN},
+    hash_to_point::{self, MAX_SQUEEZE_BLOCKS},
+    native, ntts,
     zq::Q,
 };
 
+use itertools::Itertools;
+
 use num_traits::Zero;
-use stwo::{core::fields::qm31::QM31, prover::ComponentProver};
+use stwo::core::fields::qm31::QM31;
 
 use stwo::{
     core::{
-        channel::{Blake2sChannel, Channel},
-        pcs::PcsConfig,
-        poly::circle::CanonicCoset,
-        proof::StarkProof,
-        proof_of_work::GrindOps,
-        vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher},
+        channel::Channel,
+        fields::m31::BaseField,
+        pcs::CommitmentSchemeVerifier,
+        verifier::{VerificationError, verify},
     },
     prover::{
-        CommitmentSchemeProver, ProvingError, backend::simd::SimdBackend, poly::circle::PolyOps,
-        prove,
+        ProvingError,
+        backend::{BackendForChannel, simd::SimdBackend},
+        poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
-use stwo_constraint_framework::TraceLocationAllocator;
+use stwo_constraint_framework::{INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX};
+use thiserror::Error;
+
+/// Errors that can occur when proving a batch of Falcon signature verifications.
+///
+/// Everything but [`FalconProveError::UnbalancedLookup`] and [`FalconProveError::Stwo`]
+/// is detected from the inputs before any trace is built.
+#[derive(Debug, Error)]
+pub enum FalconProveError {
+    #[error("a batch must contain at least one instance")]
+    EmptyBatch,
+    #[error("unsupported polynomial size {0}")]
+    UnsupportedDegree(usize),
+    #[error("instance {instance} has {found} coefficients, expected {expected}")]
+    DegreeMismatch {
+        instance: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error(
+        "coefficient {index} of {poly} in instance {instance} is {value}, expected a value in [0, q)"
+    )]
+    CoefficientOutOfRange {
+        instance: usize,
+        poly: &'static str,
+        index: usize,
+        value: u32,
+    },
+    #[error("invalid signature encoding in instance {instance}: {source}")]
+    InvalidSignature {
+        instance: usize,
+        source: EncodingError,
+    },
+    #[error("instance {instance} squeezes {blocks} blocks, expected at most {MAX_SQUEEZE_BLOCKS}")]
+    TooManySqueezedBlocks { instance: usize, blocks: u32 },
+    #[error("instance {instance} gives its public key in {found:?} form, expected {expected:?}")]
+    PkFormMismatch {
+        instance: usize,
+        expected: PkForm,
+        found: PkForm,
+    },
+    #[error("squared norm {norm} of instance {instance} exceeds the bound {bound}")]
+    NormExceedsBound {
+        instance: usize,
+        norm: u64,
+        bound: u32,
+    },
+    #[error("unsupported circuit parameters {0:?}")]
+    UnsupportedParams(CircuitParams),
+    #[error("unsupported prover configuration {0:?}")]
+    UnsupportedConfig(ProverConfig),
+    #[error("lookup relation {relation} does not balance out")]
+    UnbalancedLookup { relation: String },
+    #[error(transparent)]
+    Stwo(#[from] ProvingError),
+}
+
+/// A Falcon signature verification to prove.
+#[derive(Debug, Clone, Copy)]
+pub struct FalconInstance<'a> {
+    /// The signature in its standard compressed encoding: a header byte, the nonce r and
+    /// the compressed s1
+    pub signature: &'a [u8],
+    /// The public key polynomial h, with coefficients in [0, Q), in the form of `pk_form`
+    pub pk: &'a [u32],
+    /// The form of `pk`
+    pub pk_form: PkForm,
+    /// The signed message, hashed with the nonce into the message point
+    pub message: &'a [u8],
+}
+
+impl<'a> FalconInstance<'a> {
+    pub fn new(signature: &'a [u8], pk: &'a [u32], message: &'a [u8]) -> Self {
+        Self {
+            signature,
+            pk,
+            pk_form: PkForm::Coefficients,
+            message,
+        }
+    }
+
+    /// Creates a signature verification against the public key in NTT form `pk_ntt`, as
+    /// output by [`ntts::ntt`](crate::ntts::ntt()).
+    ///
+    /// The multiplication reads `pk_ntt` as a public input, so the proof has no G-NTT
+    /// sub-circuit: its trace and the proof are smaller than for the coefficients of the
+    /// public key.
+    pub fn with_pk_ntt(signature: &'a [u8], pk_ntt: &'a [u32], message: &'a [u8]) -> Self {
+        Self {
+            pk_form: PkForm::Ntt,
+            ..Self::new(signature, pk_ntt, message)
+        }
+    }
+
+    /// Returns the nonce r of the signature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signature is shorter than its header and nonce.
+    pub fn nonce(&self) -> &'a [u8] {
+        &self.signature[1..1 + NONCE_LEN]
+    }
+
+    /// Returns the compressed s1 of the signature, after its header and nonce.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signature is shorter than its header and nonce.
+    pub fn compressed(&self) -> &'a [u8] {
+        &self.signature[1 + NONCE_LEN..]
+    }
+
+    /// Returns the public inputs of the instance.
+    pub fn public_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            pk_form: self.pk_form,
+            ..PublicInputs::new(self.pk, self.signature, self.message)
+        }
+    }
+
+    /// Returns the number of SHAKE256 blocks to squeeze to sample the message point.
+    pub fn squeeze_blocks(&self) -> u32 {
+        hash_to_point::squeeze_blocks(self.nonce(), self.message, self.pk.len())
+    }
+
+    /// Checks that the instance, at position `instance` in its batch, is a valid
+    /// signature verification of the degree of `params`, and returns the number of
+    /// SHAKE256 blocks it squeezes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FalconProveError` if the public key does not have `params.poly_size()`
+    /// coefficients in [0, q), in either form, the signature is not a valid encoding of a signature of
+    /// that degree, the message point needs more than [`MAX_SQUEEZE_BLOCKS`] blocks or
+    /// the squared norm of the signature exceeds the bound.
+    pub fn validate(
+        &self,
+        instance: usize,
+        params: CircuitParams,
+    ) -> Result<u32, FalconProveError> {
+        let poly_size = params.poly_size();
+        if self.pk.len() != poly_size {
+            return Err(FalconProveError::DegreeMismatch {
+                instance,
+                expected: poly_size,
+                found: self.pk.len(),
+            });
+        }
+        if let Some((index, &value)) = self.pk.iter().find_position(|&&value| value >= Q) {
+            return Err(FalconProveError::CoefficientOutOfRange {
+                instance,
+                poly: match self.pk_form {
+                    PkForm::Coefficients => "pk",
+                    PkForm::Ntt => "pk_ntt",
+                },
+                index,
+                value,
+            });
+        }
+        let signature = encoding::decode_signature(self.signature)
+            .map_err(|source| FalconProveError::InvalidSignature { instance, source })?;
+        if signature.log_n != params.poly_log_size {
+            return Err(FalconProveError::DegreeMismatch {
+                instance,
+                expected: poly_size,
+                found: 1 << signature.log_n,
+            });
+        }
+        let blocks = self.squeeze_blocks();
+        if blocks > MAX_SQUEEZE_BLOCKS {
+            return Err(FalconProveError::TooManySqueezedBlocks { instance, blocks });
+        }
+        let msg_point = hash_to_point::hash_to_point(self.nonce(), self.message, poly_size);
+        let pk = match self.pk_form {
+            PkForm::Coefficients => self.pk.to_vec(),
+            PkForm::Ntt => ntts::intt(self.pk),
+        };
+        let s0 = native::poly_sub_mod(&msg_point, &native::poly_mul_mod(&signature.s1, &pk));
+        let norm = native::squared_norm(&s0, &signature.s1);
+        if norm > params.signature_bound as u64 {
+            return Err(FalconProveError::NormExceedsBound {
+                instance,
+                norm,
+                bound: params.signature_bound,
+            });
+        }
+        Ok(blocks)
+    }
+}
 
 /// Generates a complete STARK proof for all arithmetic operations.
 ///
+/// This is [`prove_falcon_batch`] for a single signature.
+///
+/// The degree of the polynomials is inferred from the number of coefficients of `pk`:
+/// 512 for Falcon-512 and 1024 for Falcon-1024. The signature is given in its standard
+/// compressed encoding and decompressed in the proof, and the message point is computed
+/// in the proof as `HashToPoint(nonce || message)`.
+///
+/// # Errors
+///
+/// Returns `FalconProveError` if the inputs are not a valid signature verification of a
+/// supported degree, see [`FalconInstance::validate`], or if any step in the proof
+/// generation fails.
+pub fn prove_falcon<MC: FalconMerkleChannel>(
+    signature: &[u8],
+    pk: &[u32],
+    message: &[u8],
+) -> Result<FalconProof<MC>, FalconProveError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    prove_falcon_batch(&[FalconInstance::new(signature, pk, message)])
+}
+
+/// Generates a complete STARK proof for a signature verification against the public key
+/// in NTT form `pk_ntt`, as output by [`ntts::ntt`](crate::ntts::ntt()).
+///
+/// This is [`prove_falcon`] without the G-NTT sub-circuit: the multiplication reads
+/// `pk_ntt` as a public input, so the trace and the proof are smaller. The proof is
+/// verified against [`PublicInputs::with_pk_ntt`].
+///
+/// # Errors
+///
+/// Returns the errors of [`prove_falcon`].
+pub fn prove_falcon_with_pk_ntt<MC: FalconMerkleChannel>(
+    signature: &[u8],
+    pk_ntt: &[u32],
+    message: &[u8],
+) -> Result<FalconProof<MC>, FalconProveError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    prove_falcon_batch(&[FalconInstance::with_pk_ntt(signature, pk_ntt, message)])
+}
+
+/// Generates a single STARK proof for a batch of Falcon signature verifications.
+///
 /// This function orchestrates the entire proof generation process:
-/// 1. Sets up the commitment scheme and Fiat-Shamir channel
+/// 1. Sets up the commitment scheme and Fiat-Shamir channel, and mixes the public inputs
 /// 2. Commits to preprocessed columns (range check table)
 /// 3. Generates traces for all arithmetic operations
 /// 4. Commits to the main trace
@@ -53,165 +306,412 @@ use stwo_constraint_framework::TraceLocationAllocator;
 /// 6. Commits to interaction traces
 /// 7. Generates the final STARK proof
 ///
+/// The instances are stacked in taller traces for every per-signature component, while
+/// the range check, roots and inverse roots tables are shared by the whole batch. The
+/// batch is padded to the next power of two by repeating its last instance. Every
+/// instance squeezes as many SHAKE256 blocks as the instance needing the most.
+///
 /// # Returns
 ///
-/// Returns a `StarkProof` that proves the correctness of all arithmetic
+/// Returns a `FalconProof` that proves the correctness of all arithmetic
 /// operations and their range checking constraints.
 ///
 /// # Errors
 ///
-/// Returns `ProvingError` if any step in the proof generation fails,
-/// such as constraint violations or commitment failures.
-pub fn prove_falcon(
-    s1: &[u32; POLY_SIZE],
-    pk: &[u32; POLY_SIZE],
-    msg_point: &[u32; POLY_SIZE],
-) -> Result<StarkProof<Blake2sMerkleHasher>, ProvingError> {
-    // Use consistent trace size across all components
-    let range_check_log_size = Q.ilog2() + 1;
-
-    // Initialize Fiat-Shamir channel and commitment scheme
-    let channel = &mut Blake2sChannel::default();
-    let pcs_config = PcsConfig::default();
-    pcs_config.mix_into(channel);
-    let twiddles = SimdBackend::precompute_twiddles(
-        CanonicCoset::new(range_check_log_size + pcs_config.fri_config.log_blowup_factor + 1)
-            .circle_domain()
-            .half_coset,
-    );
+/// Returns `FalconProveError` if the batch is empty, if the public keys are not of a
+/// supported degree, if an instance is not a valid signature verification of the degree
+/// of the first one (see [`FalconInstance::validate`]), or if any step in the proof
+/// generation fails. The inputs are checked before any trace is built.
+pub fn prove_falcon_batch<MC: FalconMerkleChannel>(
+    instances: &[FalconInstance],
+) -> Result<FalconProof<MC>, FalconProveError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    prove_falcon_batch_with_config(instances, ProverConfig::default())
+}
 
-    // Commit to preprocessed columns (range check table)
-    let mut commitment_scheme =
-        CommitmentSchemeProver::<SimdBackend, Blake2sMerkleChannel>::new(pcs_config, &twiddles);
-    let mut tree_builder = commitment_scheme.tree_builder();
-    let (preprocessed_columns, preprocessed_columns_ids) = BigClaim::create_preprocessed_columns();
+/// Generates a single STARK proof for a batch of Falcon signature verifications with the
+/// given security parameters.
+///
+/// This is [`prove_falcon_batch`] with a [`ProverConfig`], e.g.
+/// [`ProverConfig::conjectured_128_bits`]. The configuration is recorded in the proof.
+///
+/// # Errors
+///
+/// Returns `FalconProveError::UnsupportedConfig` if proofs cannot be generated with
+/// `config`, and the errors of [`prove_falcon_batch`] otherwise.
+pub fn prove_falcon_batch_with_config<MC: FalconMerkleChannel>(
+    instances: &[FalconInstance],
+    config: ProverConfig,
+) -> Result<FalconProof<MC>, FalconProveError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    prove_falcon_batch_with_backend::<SimdBackend, MC>(instances, config)
+}
 
-    tree_builder.extend_evals(preprocessed_columns);
-    tree_builder.commit(channel);
+/// Generates a single STARK proof for a batch of Falcon signature verifications on the
+/// backend `B`.
+///
+/// This is [`prove_falcon_batch_with_config`] on another backend than the SIMD one, e.g.
+/// the scalar `CpuBackend`. The traces are still generated with the SIMD backend and
+/// moved to `B` before being committed, see [`backend`]. Every backend produces the same
+/// proof.
+///
+/// The twiddles and the preprocessed trace are built for every call: a [`FalconProver`]
+/// reuses them across proofs of the same degree.
+///
+/// # Errors
+///
+/// Returns the errors of [`prove_falcon_batch_with_config`].
+pub fn prove_falcon_batch_with_backend<B, MC>(
+    instances: &[FalconInstance],
+    config: ProverConfig,
+) -> Result<FalconProof<MC>, FalconProveError>
+where
+    B: FalconBackend + BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    let first = instances.first().ok_or(FalconProveError::EmptyBatch)?;
+    let params = CircuitParams::from_poly_size(first.pk.len())
+        .ok_or(FalconProveError::UnsupportedDegree(first.pk.len()))?;
+    FalconProver::<B, MC>::new(params, config)?.prove_batch(instances)
+}
 
-    // Generate and commit to main traces
-    let claim = BigClaim::new_standard();
-    let (trace, traces) = claim.gen_trace(s1, pk, msg_point);
-    claim.mix_into(channel);
+/// Verifies a STARK proof generated by [`prove_falcon`].
+///
+/// This is [`verify_falcon_batch`] for a single signature.
+///
+/// # Errors
+///
+/// Returns `VerificationError` if the proof does not verify against `public_inputs` or
+/// its configuration is weaker than `minimum`.
+pub fn verify_falcon<MC: FalconMerkleChannel>(
+    proof: FalconProof<MC>,
+    public_inputs: &PublicInputs,
+    minimum: &ProverConfig,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    verify_falcon_batch(proof, std::slice::from_ref(public_inputs), minimum)
+}
 
-    let mut tree_builder = commitment_scheme.tree_builder();
-    tree_builder.extend_evals(trace);
-    tree_builder.commit(channel);
+/// Verifies a STARK proof generated by [`prove_falcon_batch`].
+///
+/// The verifier replays the prover's Fiat-Shamir transcript:
+/// 1. Mixes the prover configuration and the public inputs, and commits to the preprocessed
+///    columns, after checking that their root is the one of the circuit of the claim
+/// 2. Mixes the claim and commits to the main trace
+/// 3. Checks the interaction proof of work and draws the lookup elements
+/// 4. Mixes the interaction claim and commits to the interaction trace
+/// 5. Checks that the logup sums cancel out with the public inputs contribution
+/// 6. Verifies the STARK proof against all components
+///
+/// The proof may use any supported configuration that is at least as strong as
+/// `minimum`, e.g. [`ProverConfig::conjectured_128_bits`]: a verifier has no default, as
+/// the one of [`ProverConfig`] is only meant for tests.
+///
+/// # Errors
+///
+/// Returns `VerificationError` if the batch is empty, the proof was generated for another
+/// degree than the one of the public inputs or for other public inputs, a coefficient of
+/// a public key is not in [0, q), the public keys are not all in the same form, a
+/// signature is not laid out like a signature of that degree, its claim does not match
+/// the circuit of that degree and batch size or squeezes more than [`MAX_SQUEEZE_BLOCKS`]
+/// blocks, its configuration is unsupported or weaker than `minimum`, it commits to
+/// another preprocessed trace than the one of its claim, the proof of work is invalid,
+/// the logup sums do not cancel out or the STARK proof does not verify.
+pub fn verify_falcon_batch<MC: FalconMerkleChannel>(
+    proof: FalconProof<MC>,
+    public_inputs: &[PublicInputs],
+    minimum: &ProverConfig,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    verify_batch(
+        proof,
+        public_inputs,
+        degree_params(public_inputs)?,
+        minimum,
+        None,
+    )
+}
 
-    // Generate proof of work and draw lookup relations
-    let interaction_pow = SimdBackend::grind(channel, 2);
-    channel.mix_u64(interaction_pow);
+/// Returns the circuit parameters of the degree of the public keys of a batch.
+fn degree_params(public_inputs: &[PublicInputs]) -> Result<CircuitParams, VerificationError> {
+    let first = public_inputs
+        .first()
+        .ok_or_else(|| VerificationError::InvalidStructure("empty batch".to_string()))?;
+    CircuitParams::from_poly_size(first.pk.len()).ok_or_else(|| {
+        VerificationError::InvalidStructure("unsupported polynomial degree".to_string())
+    })
+}
 
-    let lookup_elements = LookupElements::draw(channel);
+/// Verifies a STARK proof generated by a [`FalconProver`] with the parameters `params`.
+///
+/// This is [`verify_falcon_batch`] for a circuit whose signature bound is not the one
+/// of the degree, e.g. a bound of another Falcon variant or a bound chosen by the
+/// caller: the proof shows that the squared norm of every signature is at most
+/// `params.signature_bound`.
+///
+/// # Errors
+///
+/// Returns `VerificationError` if the parameters are unsupported, the public keys do not
+/// have `params.poly_size()` coefficients or the proof was generated with other
+/// parameters, and the errors of [`verify_falcon_batch`] otherwise.
+pub fn verify_falcon_batch_with_params<MC: FalconMerkleChannel>(
+    proof: FalconProof<MC>,
+    public_inputs: &[PublicInputs],
+    params: CircuitParams,
+    minimum: &ProverConfig,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    verify_batch(proof, public_inputs, params, minimum, None)
+}
 
-    // Generate and commit to interaction traces
-    let (interaction_trace, interaction_claim) =
-        BigInteractionClaim::gen_interaction_trace(&lookup_elements, &traces);
+/// Verifies a STARK proof of the circuit of `params`, whose preprocessed trace has the
+/// root `circuit_id` if it is given, or the root of the preprocessed trace of the claim
+/// otherwise.
+fn verify_batch<MC: FalconMerkleChannel>(
+    proof: FalconProof<MC>,
+    public_inputs: &[PublicInputs],
+    params: CircuitParams,
+    minimum: &ProverConfig,
+    circuit_id: Option<CircuitId<MC>>,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    let FalconProof {
+        params: proof_params,
+        config,
+        public_inputs: proof_public_inputs,
+        claim,
+        interaction_pow,
+        interaction_claim,
+        stark_proof,
+    } = proof;
 
-    interaction_claim.mix_into(channel);
+    // The proof must be about the circuit of the expected parameters and these public inputs
+    let first = public_inputs
+        .first()
+        .ok_or_else(|| VerificationError::InvalidStructure("empty batch".to_string()))?;
+    if !params.is_supported() {
+        return Err(VerificationError::InvalidStructure(
+            "unsupported circuit parameters".to_string(),
+        ));
+    }
+    if public_inputs
+        .iter()
+        .any(|instance| instance.pk.len() != params.poly_size())
+    {
+        return Err(VerificationError::InvalidStructure(
+            "unsupported polynomial degree".to_string(),
+        ));
+    }
+    // The coefficients of the public keys are added back to the logup sum as M31
+    // elements, where h and h + p are the same entry: only canonical ones are accepted
+    if public_inputs
+        .iter()
+        .any(|instance| instance.pk.iter().any(|&value| value >= params.q))
+    {
+        return Err(VerificationError::InvalidStructure(
+            "public key coefficient out of range".to_string(),
+        ));
+    }
+    if proof_params != params {
+        return Err(VerificationError::InvalidStructure(
+            "circuit parameters mismatch".to_string(),
+        ));
+    }
+    // The G-NTT sub-circuit is in the proof for all the instances or for none
+    if public_inputs
+        .iter()
+        .any(|instance| instance.pk_form != first.pk_form)
+    {
+        return Err(VerificationError::InvalidStructure(
+            "public key form mismatch".to_string(),
+        ));
+    }
+    // The proof decompresses the signatures, the verifier checks what it does not prove
+    for instance in public_inputs {
+        encoding::check_signature_layout(&instance.signature, params.poly_log_size)
+            .map_err(|error| VerificationError::InvalidStructure(error.to_string()))?;
+    }
+    // The claim only holds trace sizes, range check bounds and the number of squeezed
+    // blocks, which are all fixed by the parameters, the public inputs and that number:
+    // a loosened bound must not be accepted
+    let squeeze_blocks = claim.sampler.squeeze_blocks;
+    if !(1..=MAX_SQUEEZE_BLOCKS).contains(&squeeze_blocks) {
+        return Err(VerificationError::InvalidStructure(
+            "invalid number of squeezed blocks".to_string(),
+        ));
+    }
+    if claim != BigClaim::new(params, public_inputs, squeeze_blocks) {
+        return Err(VerificationError::InvalidStructure(
+            "claim does not match the circuit parameters".to_string(),
+        ));
+    }
+    if proof_public_inputs != public_inputs {
+        return Err(VerificationError::InvalidStructure(
+            "public inputs mismatch".to_string(),
+        ));
+    }
+    check_config(&config, minimum)?;
+    let pcs_config = config.pcs_config();
 
-    let mut tree_builder = commitment_scheme.tree_builder();
-    tree_builder.extend_evals(interaction_trace);
-    tree_builder.commit(channel);
+    // The preprocessed trace is fixed by the claim and the configuration
+    let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
+    check_preprocessed_root::<MC>(
+        stark_proof.commitments[0],
+        preprocessed_columns,
+        &config,
+        circuit_id,
+    )?;
 
-    // Set up trace location allocator with preprocessed columns
-    let mut tree_span_provider =
-        TraceLocationAllocator::new_with_preproccessed_columns(&preprocessed_columns_ids);
+    // Initialize Fiat-Shamir channel and commitment scheme
+    let channel = &mut MC::C::default();
+    config.mix_into(channel);
+    mix_batch_into(public_inputs, channel);
+    let commitment_scheme = &mut CommitmentSchemeVerifier::<MC>::new(pcs_config);
 
-    let (
-        f_ntt_butterfly_component,
-        f_ntt_merges_components,
-        g_ntt_butterfly_component,
-        g_ntt_merges_components,
-    ) = BigClaim::create_ntt_components(
+    // The column log sizes only depend on the claim, so the components are built
+    // with dummy lookup elements to retrieve them.
+    let dummy_lookup_elements = LookupElements::draw(&mut MC::C::default());
+    let log_sizes = BigComponents::new(
         &claim,
-        &lookup_elements,
+        &dummy_lookup_elements,
         &interaction_claim,
-        &mut tree_span_provider,
-    );
-    let (
-        mul_component,
-        intt_merges_components,
-        ibutterfly_component,
-        sub_component,
-        euclidean_norm_component,
-        half_range_check_component,
-        low_sig_bound_check_component,
-        high_sig_bound_check_component,
-        range_check_component,
-        roots_components,
-        inv_roots_components,
-    ) = BigClaim::create_remaining_components(
-        &claim,
-        &lookup_elements,
-        &interaction_claim,
-        &mut tree_span_provider,
-    );
+        &preprocessed_columns_ids,
+    )
+    .column_log_sizes(preprocessed_log_sizes(&claim));
 
-    #[cfg(test)]
-    {
-        use crate::debug::relation_tracker::{
-            BigAirComponents, track_and_summarize_big_air_relations,
-        };
+    // Preprocessed columns
+    commitment_scheme.commit(stark_proof.commitments[0], &log_sizes[0], channel);
 
-        let components = &BigAirComponents {
-            f_ntt_butterfly: &f_ntt_butterfly_component,
-            f_ntt_merges: &f_ntt_merges_components,
-            g_ntt_butterfly: &g_ntt_butterfly_component,
-            g_ntt_merges: &g_ntt_merges_components,
-            mul: &mul_component,
-            intt_merges: &intt_merges_components,
-            ibutterfly: &ibutterfly_component,
-            sub: &sub_component,
-            euclidean_norm: &euclidean_norm_component,
-            half_range_check: &half_range_check_component,
-            low_sig_bound_check: &low_sig_bound_check_component,
-            high_sig_bound_check: &high_sig_bound_check_component,
-            range_check: &range_check_component,
-            roots: &roots_components,
-            inv_roots: &inv_roots_components,
-        };
-        let summary = track_and_summarize_big_air_relations(&commitment_scheme, components);
-        std::fs::write("summary.txt", format!("{:?}", summary)).unwrap();
+    // Main trace
+    claim.mix_into(channel);
+    commitment_scheme.commit(
+        stark_proof.commitments[ORIGINAL_TRACE_IDX],
+        &log_sizes[ORIGINAL_TRACE_IDX],
+        channel,
+    );
 
-        // println!("summary: {:?}", summary);
+    // Proof of work and lookup relations
+    channel.mix_u64(interaction_pow);
+    if channel.trailing_zeros() < config.interaction_pow_bits {
+        return Err(VerificationError::ProofOfWork);
     }
-    assert_eq!(
-        interaction_claim.claimed_sum(),
-        QM31::zero(),
-        "invalid logup sum"
+    let lookup_elements = LookupElements::draw(channel);
+
+    // Interaction trace
+    interaction_claim.mix_into(channel);
+    commitment_scheme.commit(
+        stark_proof.commitments[INTERACTION_TRACE_IDX],
+        &log_sizes[INTERACTION_TRACE_IDX],
+        channel,
     );
 
-    let mut components: Vec<&dyn ComponentProver<SimdBackend>> = vec![];
-    components.push(&f_ntt_butterfly_component);
-    for merge in f_ntt_merges_components.iter() {
-        components.push(merge);
+    if interaction_claim.claimed_sum()
+        + batch_logup_sum(public_inputs, &lookup_elements, squeeze_blocks)
+        != QM31::zero()
+    {
+        return Err(VerificationError::InvalidStructure(
+            "invalid logup sum".to_string(),
+        ));
     }
-    components.push(&g_ntt_butterfly_component);
-    for merge in g_ntt_merges_components.iter() {
-        components.push(merge);
+
+    let components = BigComponents::new(
+        &claim,
+        &lookup_elements,
+        &interaction_claim,
+        &preprocessed_columns_ids,
+    );
+
+    verify(
+        &components.components(),
+        channel,
+        commitment_scheme,
+        stark_proof,
+    )
+}
+
+/// Checks that the configuration of a proof is supported and at least as strong as
+/// `minimum`: the proof may use another configuration than the verifier, as long as it is
+/// not weaker.
+pub(crate) fn check_config(
+    config: &ProverConfig,
+    minimum: &ProverConfig,
+) -> Result<(), VerificationError> {
+    if !config.is_supported() {
+        return Err(VerificationError::InvalidStructure(
+            "unsupported prover configuration".to_string(),
+        ));
     }
-    components.push(&mul_component);
-    for merge in intt_merges_components.iter() {
-        components.push(merge);
+    if !config.is_at_least(minimum) {
+        return Err(VerificationError::InvalidStructure(
+            "insufficient security bits".to_string(),
+        ));
     }
-    components.push(&ibutterfly_component);
-    components.push(&sub_component);
-    components.push(&euclidean_norm_component);
-    components.push(&half_range_check_component);
-    components.push(&low_sig_bound_check_component);
-    components.push(&high_sig_bound_check_component);
-    components.push(&range_check_component);
+    Ok(())
+}
 
-    for root in roots_components.iter() {
-        components.push(root);
-    }
-    for inv_root in inv_roots_components.iter() {
-        components.push(inv_root);
+/// Checks that a proof commits to `preprocessed_columns`, whose root is `circuit_id` if
+/// the verifier pinned it, or is computed by committing to them with `config` otherwise.
+///
+/// A proof committing to another preprocessed trace could choose the tables, selectors
+/// and row flags that the constraints read.
+pub(crate) fn check_preprocessed_root<MC>(
+    root: CircuitId<MC>,
+    preprocessed_columns: Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+    config: &ProverConfig,
+    circuit_id: Option<CircuitId<MC>>,
+) -> Result<(), VerificationError>
+where
+    MC: FalconMerkleChannel,
+    SimdBackend: BackendForChannel<MC>,
+{
+    let circuit_id =
+        circuit_id.unwrap_or_else(|| prover::preprocessed_root::<MC>(preprocessed_columns, config));
+    if root != circuit_id {
+        return Err(VerificationError::InvalidStructure(
+            "preprocessed trace mismatch".to_string(),
+        ));
     }
+    Ok(())
+}
 
-    // Generate the final STARK proof
-    prove::<SimdBackend, _>(&components, channel, commitment_scheme)
+/// Verifies a STARK proof generated by [`prove_falcon_batch`] for the circuit `circuit_id`.
+///
+/// This is [`verify_falcon_batch`] for a verifier that pinned the id of the circuit, e.g.
+/// from [`FalconProver::circuit_id`], instead of committing to the preprocessed trace of
+/// the claim to compute it.
+///
+/// # Errors
+///
+/// Returns `VerificationError` if the proof commits to another preprocessed trace, and
+/// the errors of [`verify_falcon_batch`] otherwise.
+pub fn verify_falcon_batch_with_circuit_id<MC: FalconMerkleChannel>(
+    proof: FalconProof<MC>,
+    public_inputs: &[PublicInputs],
+    circuit_id: CircuitId<MC>,
+    minimum: &ProverConfig,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    verify_batch(
+        proof,
+        public_inputs,
+        degree_params(public_inputs)?,
+        minimum,
+        Some(circuit_id),
+    )
 }
 
 #[cfg(test)]
@@ -219,9 +719,45 @@ mod tests {
     use super::*;
     use crate::{
         debug,
-        input::{MSG_POINT, PK, TEST_S1},
+        input::{MESSAGE, PK, SIGNATURE},
+        zq::Q,
+    };
+    use num_traits::One;
+    use stwo::core::vcs::blake2_merkle::Blake2sMerkleChannel;
+    use stwo::{
+        core::fields::m31::{M31, P},
+        prover::backend::{Column, simd::column::BaseColumn},
     };
 
+    /// Returns the root of the preprocessed trace of `proof` with the value at `row` of the
+    /// first column whose id starts with `prefix` replaced by `tamper` of it.
+    fn tampered_circuit_id(
+        proof: &FalconProof<Blake2sMerkleChannel>,
+        prefix: &str,
+        row: usize,
+        tamper: impl Fn(M31) -> M31,
+    ) -> CircuitId<Blake2sMerkleChannel> {
+        let (mut columns, ids) = proof.claim.create_preprocessed_columns();
+        let index = ids.iter().position(|id| id.id.starts_with(prefix)).unwrap();
+        let mut values = columns[index].values.to_cpu();
+        values[row] = tamper(values[row]);
+        columns[index] =
+            CircleEvaluation::new(columns[index].domain, BaseColumn::from_iter(values));
+        prover::preprocessed_root::<Blake2sMerkleChannel>(columns, &proof.config)
+    }
+
+    /// Asserts that the verifier rejects `proof` for committing to another preprocessed
+    /// trace than the one of its claim.
+    fn assert_preprocessed_trace_mismatch(
+        proof: FalconProof<Blake2sMerkleChannel>,
+        public_inputs: &[PublicInputs],
+    ) {
+        assert!(matches!(
+            verify_falcon_batch(proof, public_inputs, &ProverConfig::default()),
+            Err(VerificationError::InvalidStructure(reason)) if reason == "preprocessed trace mismatch"
+        ));
+    }
+
     /// Tests the complete STARK proof generation for all arithmetic operations.
     ///
     /// This test verifies that:
@@ -230,7 +766,7 @@ mod tests {
     /// - The proof can be generated without errors
     #[test]
     fn test_prove_falcon() {
-        match prove_falcon(TEST_S1, PK, MSG_POINT) {
+        match prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE) {
             Ok(_) => println!("Proof generation successful!"),
             Err(e) => {
                 eprintln!("Proof generation failed: {:?}", e);
@@ -239,8 +775,793 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verify_falcon() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        verify_falcon(
+            proof,
+            &PublicInputs::new(PK, SIGNATURE, MESSAGE),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_falcon_rejects_other_public_inputs() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+
+        // Other public key
+        let mut public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+        public_inputs.pk[0] = (public_inputs.pk[0] + 1) % Q;
+        assert!(verify_falcon(proof.clone(), &public_inputs, &ProverConfig::default()).is_err());
+
+        // Other public key, also swapped in the proof so that only the transcript and
+        // the logup sum can catch it
+        let mut tampered = proof.clone();
+        tampered.public_inputs = vec![public_inputs.clone()];
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        // Other message, also swapped in the proof
+        let public_inputs = PublicInputs::new(PK, SIGNATURE, b"another message");
+        let mut tampered = proof.clone();
+        tampered.public_inputs = vec![public_inputs.clone()];
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        // Other nonce, also swapped in the proof
+        let mut public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+        public_inputs.signature[1] ^= 1;
+        let mut tampered = proof.clone();
+        tampered.public_inputs = vec![public_inputs.clone()];
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        // Other compressed s1, also swapped in the proof
+        let mut public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+        public_inputs.signature[100] ^= 1;
+        let mut tampered = proof.clone();
+        tampered.public_inputs = vec![public_inputs.clone()];
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        // Zero padding appended to the signature, also swapped in the proof
+        let mut public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+        public_inputs.signature.push(0);
+        let mut tampered = proof.clone();
+        tampered.public_inputs = vec![public_inputs.clone()];
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        // A public key coefficient shifted by the M31 modulus, which adds back the same
+        // logup entry, also swapped in the proof
+        let mut public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+        public_inputs.pk[0] += P;
+        let mut tampered = proof;
+        tampered.public_inputs = vec![public_inputs.clone()];
+        assert!(matches!(
+            verify_falcon(tampered, &public_inputs, &ProverConfig::default()),
+            Err(VerificationError::InvalidStructure(reason)) if reason == "public key coefficient out of range"
+        ));
+    }
+
+    #[test]
+    fn test_verify_falcon_rejects_tampered_proof() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        let public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+
+        // Tampered out of domain sample
+        let mut tampered = proof.clone();
+        tampered.stark_proof.0.sampled_values[ORIGINAL_TRACE_IDX][0][0] += QM31::one();
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        // Tampered logup sum
+        let mut tampered = proof.clone();
+        tampered.interaction_claim.sub.claimed_sum += QM31::one();
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        // Tampered interaction proof of work
+        let mut tampered = proof;
+        tampered.interaction_pow += 1;
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_verify_falcon_rejects_tampered_squeeze_blocks() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        let public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+
+        let mut tampered = proof.clone();
+        tampered.claim.sampler.squeeze_blocks += 1;
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+
+        let mut tampered = proof;
+        tampered.claim.sampler.squeeze_blocks = MAX_SQUEEZE_BLOCKS + 1;
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_verify_falcon_rejects_other_preprocessed_trace() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        let public_inputs = [PublicInputs::new(PK, SIGNATURE, MESSAGE)];
+        assert_eq!(
+            proof.circuit_id(),
+            prover::circuit_id::<Blake2sMerkleChannel>(&proof.claim, &proof.config)
+        );
+
+        // A range check table holding a value out of the range
+        let mut tampered = proof.clone();
+        tampered.stark_proof.0.commitments[0] =
+            tampered_circuit_id(&proof, "range_check_", 0, |value| value + M31(Q));
+        assert_preprocessed_trace_mismatch(tampered, &public_inputs);
+
+        // A root of unity table with another root
+        let mut tampered = proof.clone();
+        tampered.stark_proof.0.commitments[0] =
+            tampered_circuit_id(&proof, "roots_of_unity_", 0, |value| value + M31(1));
+        assert_preprocessed_trace_mismatch(tampered, &public_inputs);
+
+        // A verifier pinning the id of another preprocessed trace rejects the honest proof
+        let circuit_id = tampered_circuit_id(&proof, "range_check_", 0, |value| value + M31(Q));
+        assert!(
+            verify_falcon_batch_with_circuit_id(
+                proof,
+                &public_inputs,
+                circuit_id,
+                &ProverConfig::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_falcon_rejects_tampered_selectors() {
+        use crate::input::falcon_512;
+
+        let proof = prove_falcon::<Blake2sMerkleChannel>(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )
+        .unwrap();
+        let public_inputs = [PublicInputs::new(
+            falcon_512::PK,
+            falcon_512::SIGNATURE,
+            falcon_512::MESSAGE,
+        )];
+
+        // A butterfly switched off or on, a root index sequence restarting elsewhere, and
+        // a signature starting on another row of the Euclidean norm
+        for prefix in [
+            "butterfly_layout_is_filled_",
+            "butterfly_layout_root_index_",
+            "euclidean_norm_is_not_first_",
+        ] {
+            let mut tampered = proof.clone();
+            tampered.stark_proof.0.commitments[0] =
+                tampered_circuit_id(&proof, prefix, 1, |value| M31(1) - value);
+            assert_preprocessed_trace_mismatch(tampered, &public_inputs);
+        }
+        verify_falcon_batch(proof, &public_inputs, &ProverConfig::default()).unwrap();
+    }
+
     #[test]
     fn test_debug_constraints() {
-        debug::assert_constraints(TEST_S1, PK, MSG_POINT);
+        debug::assert_constraints(SIGNATURE, PK, MESSAGE);
+    }
+
+    #[test]
+    fn test_prove_and_verify_falcon_512() {
+        use crate::input::falcon_512;
+
+        let proof = prove_falcon::<Blake2sMerkleChannel>(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )
+        .unwrap();
+        assert_eq!(proof.params, CircuitParams::falcon_512());
+        assert_eq!(proof.claim.f_ntt_merges.len(), 8);
+        verify_falcon(
+            proof,
+            &PublicInputs::new(falcon_512::PK, falcon_512::SIGNATURE, falcon_512::MESSAGE),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_prove_and_verify_with_config() {
+        use crate::input::falcon_512;
+
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+        let config = ProverConfig::conjectured_96_bits();
+        let proof =
+            prove_falcon_batch_with_config::<Blake2sMerkleChannel>(&[instance], config).unwrap();
+        assert_eq!(proof.config, config);
+        let public_inputs = instance.public_inputs();
+        verify_falcon(proof.clone(), &public_inputs, &config).unwrap();
+        verify_falcon(proof.clone(), &public_inputs, &ProverConfig::default()).unwrap();
+
+        // The verifier replays the transcript with the recorded configuration
+        let mut tampered = proof.clone();
+        tampered.config.n_queries += 1;
+        assert!(verify_falcon(tampered, &public_inputs, &config).is_err());
+
+        // A configuration weaker than the minimum of the verifier is rejected
+        assert!(matches!(
+            verify_falcon(
+                proof.clone(),
+                &public_inputs,
+                &ProverConfig::conjectured_128_bits()
+            ),
+            Err(VerificationError::InvalidStructure(reason)) if reason == "insufficient security bits"
+        ));
+        let mut tampered = proof;
+        tampered.config.pow_bits = 0;
+        tampered.config.n_queries = 1;
+        assert!(verify_falcon(tampered, &public_inputs, &ProverConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_prove_falcon_rejects_unsupported_config() {
+        let config = ProverConfig {
+            log_blowup_factor: 0,
+            ..ProverConfig::default()
+        };
+        assert!(matches!(
+            prove_falcon_batch_with_config::<Blake2sMerkleChannel>(
+                &[FalconInstance::new(SIGNATURE, PK, MESSAGE)],
+                config
+            ),
+            Err(FalconProveError::UnsupportedConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_cpu_and_simd_backends_produce_the_same_proof() {
+        use crate::input::falcon_512;
+        use stwo::prover::backend::cpu::CpuBackend;
+
+        let instances = [FalconInstance::new(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )];
+        let config = ProverConfig::default();
+        let simd_proof = prove_falcon_batch_with_backend::<SimdBackend, Blake2sMerkleChannel>(
+            &instances, config,
+        )
+        .unwrap();
+        let cpu_proof =
+            prove_falcon_batch_with_backend::<CpuBackend, Blake2sMerkleChannel>(&instances, config)
+                .unwrap();
+
+        assert_eq!(
+            *simd_proof.stark_proof.commitments,
+            *cpu_proof.stark_proof.commitments
+        );
+        assert_eq!(simd_proof.to_bytes(), cpu_proof.to_bytes());
+        verify_falcon(
+            cpu_proof,
+            &instances[0].public_inputs(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_falcon_rejects_other_degree() {
+        use crate::input::falcon_512;
+
+        // A Falcon-512 proof does not verify against Falcon-1024 public inputs
+        let proof = prove_falcon::<Blake2sMerkleChannel>(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )
+        .unwrap();
+        assert!(
+            verify_falcon(
+                proof.clone(),
+                &PublicInputs::new(PK, SIGNATURE, MESSAGE),
+                &ProverConfig::default()
+            )
+            .is_err()
+        );
+
+        // Nor once relabelled as a Falcon-1024 proof
+        let mut tampered = proof;
+        tampered.params = CircuitParams::falcon_1024();
+        assert!(
+            verify_falcon(
+                tampered,
+                &PublicInputs::new(falcon_512::PK, falcon_512::SIGNATURE, falcon_512::MESSAGE),
+                &ProverConfig::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_falcon_rejects_loosened_bound() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        let mut tampered = proof;
+        tampered.claim.high_sig_bound_check.bound += 1;
+        assert!(
+            verify_falcon(
+                tampered,
+                &PublicInputs::new(PK, SIGNATURE, MESSAGE),
+                &ProverConfig::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_prove_and_verify_with_signature_bound() {
+        // The squared norm of the test signature is 3277089, split in 11-bit limbs for a
+        // 22-bit bound
+        let params = CircuitParams::falcon_1024().with_signature_bound(3_500_000);
+        assert_eq!(params.sig_bound_limb_bits(), 11);
+        let prover =
+            FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(params, ProverConfig::default())
+                .unwrap();
+        let instance = FalconInstance::new(SIGNATURE, PK, MESSAGE);
+        let proof = prover.prove(&instance).unwrap();
+        assert_eq!(proof.claim.euclidean_norm.limb_bits, 11);
+
+        let decoded = FalconProof::<Blake2sMerkleChannel>::from_bytes(&proof.to_bytes()).unwrap();
+        let public_inputs = [instance.public_inputs()];
+        verify_falcon_batch_with_params(decoded, &public_inputs, params, &ProverConfig::default())
+            .unwrap();
+
+        // The proof is not about the Falcon-1024 bound, nor about a looser one
+        assert!(
+            verify_falcon_batch(proof.clone(), &public_inputs, &ProverConfig::default()).is_err()
+        );
+        assert!(
+            verify_falcon_batch_with_params(
+                proof,
+                &public_inputs,
+                params.with_signature_bound(4_000_000),
+                &ProverConfig::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_prove_falcon_rejects_unsupported_signature_bound() {
+        let prover = FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(
+            CircuitParams::falcon_1024().with_signature_bound(3_000_000),
+            ProverConfig::default(),
+        )
+        .unwrap();
+        assert!(matches!(
+            prover.prove(&FalconInstance::new(SIGNATURE, PK, MESSAGE)),
+            Err(FalconProveError::NormExceedsBound {
+                norm: 3277089,
+                bound: 3_000_000,
+                ..
+            })
+        ));
+
+        // The bound must be below 2^30 with limbs large enough to be range checked
+        for bound in [1 << 30, 100] {
+            let params = CircuitParams::falcon_1024().with_signature_bound(bound);
+            assert!(!params.is_supported());
+            assert!(matches!(
+                FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(
+                    params,
+                    ProverConfig::default()
+                ),
+                Err(FalconProveError::UnsupportedParams(_))
+            ));
+        }
+    }
+
+    /// The squared norm of the test signature.
+    const TEST_NORM: u32 = 3277089;
+
+    #[test]
+    fn test_debug_constraints_at_the_signature_bound() {
+        let instance = FalconInstance::new(SIGNATURE, PK, MESSAGE);
+        // With 11-bit limbs, the low limb 289 of the norm is above the low limb of
+        // 4000000: the norm is compared as a whole, not limb by limb
+        for bound in [TEST_NORM, TEST_NORM + 1, 4_000_000] {
+            let params = CircuitParams::falcon_1024().with_signature_bound(bound);
+            debug::assert_constraints_batch_for(params, &[instance], |_| {});
+        }
+
+        let params = CircuitParams::falcon_1024().with_signature_bound(TEST_NORM);
+        let proof =
+            FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(params, ProverConfig::default())
+                .unwrap()
+                .prove(&instance)
+                .unwrap();
+        verify_falcon_batch_with_params(
+            proof,
+            &[instance.public_inputs()],
+            params,
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_prove_falcon_rejects_norm_just_above_the_bound() {
+        let params = CircuitParams::falcon_1024().with_signature_bound(TEST_NORM - 1);
+        let prover =
+            FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(params, ProverConfig::default())
+                .unwrap();
+        assert!(matches!(
+            prover.prove(&FalconInstance::new(SIGNATURE, PK, MESSAGE)),
+            Err(FalconProveError::NormExceedsBound {
+                norm: TEST_NORM,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "squared norm 3277089 exceeds 3277088")]
+    fn test_debug_constraints_reject_norm_just_above_the_bound() {
+        let params = CircuitParams::falcon_1024().with_signature_bound(TEST_NORM - 1);
+        debug::assert_constraints_batch_for(
+            params,
+            &[FalconInstance::new(SIGNATURE, PK, MESSAGE)],
+            |_| {},
+        );
+    }
+
+    /// Returns the position of the last row of the only signature of a Euclidean norm
+    /// trace, the row of its largest cumulative sum.
+    fn last_norm_row(
+        euclidean_norm: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+    ) -> usize {
+        (0..euclidean_norm[6].values.len())
+            .max_by_key(|&row| euclidean_norm[6].values.at(row).0)
+            .unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_debug_constraints_reject_limbs_of_another_norm() {
+        // The limbs claim a norm one below the actual one, with a slack one above: their
+        // sum is still the bound and all of them are in range, but they do not recompose
+        // the cumulative sum
+        let params = CircuitParams::falcon_1024().with_signature_bound(TEST_NORM + 1);
+        debug::assert_constraints_batch_for(
+            params,
+            &[FalconInstance::new(SIGNATURE, PK, MESSAGE)],
+            |traces| {
+                let row = last_norm_row(&traces.euclidean_norm);
+                let [low_norm, low_slack] =
+                    [7, 9].map(|column| traces.euclidean_norm[column].values.at(row));
+                traces.euclidean_norm[7].values.set(row, low_norm - M31(1));
+                traces.euclidean_norm[9].values.set(row, low_slack + M31(1));
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid logup sum")]
+    fn test_debug_constraints_reject_limbs_out_of_range() {
+        // The limbs of the norm recompose it with a low limb of 2^11 + 289, which is out
+        // of the range of the low limbs
+        let params = CircuitParams::falcon_1024().with_signature_bound(TEST_NORM);
+        debug::assert_constraints_batch_for(
+            params,
+            &[FalconInstance::new(SIGNATURE, PK, MESSAGE)],
+            |traces| {
+                let row = last_norm_row(&traces.euclidean_norm);
+                let [low_norm, high_norm] =
+                    [7, 8].map(|column| traces.euclidean_norm[column].values.at(row));
+                let limb_shift = M31(params.low_sig_bound());
+                traces.euclidean_norm[7]
+                    .values
+                    .set(row, low_norm + limb_shift);
+                traces.euclidean_norm[8].values.set(row, high_norm - M31(1));
+            },
+        );
+    }
+
+    #[test]
+    fn test_debug_constraints_falcon_512() {
+        use crate::input::falcon_512;
+
+        debug::assert_constraints(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+    }
+
+    /// Swaps the rows `a` and `b` of all the columns of a component.
+    ///
+    /// The constraints of the components checked below only relate the values of a row,
+    /// and the preprocessed root indices of both swapped rows are equal, so that the swap
+    /// can only be caught by the lookups between the stages.
+    fn swap_rows(
+        columns: &mut [CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+        a: usize,
+        b: usize,
+    ) {
+        for column in columns.iter_mut() {
+            let (value_a, value_b) = (column.values.at(a), column.values.at(b));
+            column.values.set(a, value_b);
+            column.values.set(b, value_a);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid logup sum")]
+    fn test_debug_constraints_reject_permuted_ntt_output() {
+        use crate::input::falcon_512;
+
+        // The multiplication reads the first two coefficients of the NTTs in swapped order
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+        debug::assert_constraints_batch_with(&[instance], |traces| {
+            swap_rows(&mut traces.mul, 0, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid logup sum")]
+    fn test_debug_constraints_reject_permuted_intt_input() {
+        use crate::input::falcon_512;
+
+        // The last split reads its first two polynomials of 4 coefficients in swapped order
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+        debug::assert_constraints_batch_with(&[instance], |traces| {
+            let last_split = traces.intt_merges.last_mut().unwrap();
+            swap_rows(last_split, 0, 2);
+        });
+    }
+
+    #[test]
+    fn test_prove_and_verify_with_pk_ntt() {
+        use crate::input::falcon_512;
+
+        let pk_ntt = ntts::ntt(falcon_512::PK);
+        let proof = prove_falcon_with_pk_ntt::<Blake2sMerkleChannel>(
+            falcon_512::SIGNATURE,
+            &pk_ntt,
+            falcon_512::MESSAGE,
+        )
+        .unwrap();
+        assert_eq!(proof.claim.g_ntt_butterfly, None);
+        assert!(proof.claim.g_ntt_merges.is_empty());
+        let coefficients_proof = prove_falcon::<Blake2sMerkleChannel>(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )
+        .unwrap();
+        assert!(proof.to_bytes().len() < coefficients_proof.to_bytes().len());
+
+        // The public key in NTT form is bound to the proof under its own identifier
+        let public_inputs =
+            PublicInputs::with_pk_ntt(&pk_ntt, falcon_512::SIGNATURE, falcon_512::MESSAGE);
+        verify_falcon(proof.clone(), &public_inputs, &ProverConfig::default()).unwrap();
+        let mut other = public_inputs.clone();
+        other.pk_form = PkForm::Coefficients;
+        let mut tampered = proof.clone();
+        tampered.public_inputs = vec![other.clone()];
+        assert!(verify_falcon(tampered, &other, &ProverConfig::default()).is_err());
+        let mut other = public_inputs.clone();
+        other.pk[0] = (other.pk[0] + 1) % Q;
+        let mut tampered = proof;
+        tampered.public_inputs = vec![other.clone()];
+        assert!(verify_falcon(tampered, &other, &ProverConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_pk_ntt_matches_coefficients_path() {
+        use crate::input::falcon_512;
+
+        // Both paths multiply s1 by the same NTT of the public key, into the same product
+        // and norm, and the NTT form drops the G-NTT columns
+        let pk_ntt = ntts::ntt(falcon_512::PK);
+        let instances = [
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE),
+            FalconInstance::with_pk_ntt(falcon_512::SIGNATURE, &pk_ntt, falcon_512::MESSAGE),
+        ];
+        let squeeze_blocks = instances[0].squeeze_blocks();
+        let [coefficients, ntt] = instances.map(|instance| {
+            BigClaim::new(
+                CircuitParams::falcon_512(),
+                &[instance.public_inputs()],
+                squeeze_blocks,
+            )
+            .gen_trace(&[instance])
+            .1
+        });
+        let values = |columns: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>]| {
+            columns
+                .iter()
+                .map(|column| column.values.to_cpu())
+                .collect_vec()
+        };
+        assert_eq!(values(&coefficients.mul), values(&ntt.mul));
+        assert_eq!(
+            values(&coefficients.euclidean_norm),
+            values(&ntt.euclidean_norm)
+        );
+        assert!(ntt.columns().len() < coefficients.columns().len());
+
+        debug::assert_constraints_batch(&instances[1..]);
+    }
+
+    #[test]
+    fn test_prove_falcon_batch_rejects_mixed_pk_forms() {
+        let pk_ntt = ntts::ntt(PK);
+        let result = prove_falcon_batch::<Blake2sMerkleChannel>(&[
+            FalconInstance::new(SIGNATURE, PK, MESSAGE),
+            FalconInstance::with_pk_ntt(SIGNATURE, &pk_ntt, MESSAGE),
+        ]);
+        assert!(matches!(
+            result,
+            Err(FalconProveError::PkFormMismatch {
+                instance: 1,
+                expected: PkForm::Coefficients,
+                found: PkForm::Ntt,
+            })
+        ));
+    }
+
+    /// Proves and verifies a batch of `n_instances` copies of the Falcon-512 test vector.
+    fn prove_and_verify_falcon_512_batch(n_instances: usize) {
+        use crate::input::falcon_512;
+
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+        let instances = vec![instance; n_instances];
+        let proof = prove_falcon_batch::<Blake2sMerkleChannel>(&instances).unwrap();
+        assert_eq!(proof.public_inputs.len(), n_instances);
+        assert_eq!(
+            proof.claim.log_n_instances(),
+            n_instances.next_power_of_two().ilog2()
+        );
+        verify_falcon_batch(
+            proof,
+            &vec![instance.public_inputs(); n_instances],
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_prove_falcon_batch_of_1() {
+        prove_and_verify_falcon_512_batch(1);
+    }
+
+    #[test]
+    fn test_prove_falcon_batch_of_16() {
+        prove_and_verify_falcon_512_batch(16);
+    }
+
+    #[test]
+    fn test_prove_falcon_batch_of_256() {
+        prove_and_verify_falcon_512_batch(256);
+    }
+
+    #[test]
+    fn test_prove_falcon_batch_pads_to_power_of_two() {
+        prove_and_verify_falcon_512_batch(3);
+    }
+
+    #[test]
+    fn test_verify_falcon_batch_rejects_other_public_inputs() {
+        let instances = [FalconInstance::new(SIGNATURE, PK, MESSAGE); 2];
+        let proof = prove_falcon_batch::<Blake2sMerkleChannel>(&instances).unwrap();
+        let public_inputs = vec![PublicInputs::new(PK, SIGNATURE, MESSAGE); 2];
+
+        // Other public key for the second instance, also swapped in the proof
+        let mut other = public_inputs.clone();
+        other[1].pk[0] = (other[1].pk[0] + 1) % Q;
+        let mut tampered = proof.clone();
+        tampered.public_inputs = other.clone();
+        assert!(verify_falcon_batch(tampered, &other, &ProverConfig::default()).is_err());
+
+        // A single instance of the batch
+        let mut tampered = proof.clone();
+        tampered.public_inputs.truncate(1);
+        assert!(
+            verify_falcon_batch(tampered, &public_inputs[..1], &ProverConfig::default()).is_err()
+        );
+
+        // An empty batch
+        assert!(verify_falcon_batch(proof.clone(), &[], &ProverConfig::default()).is_err());
+
+        verify_falcon_batch(proof, &public_inputs, &ProverConfig::default()).unwrap();
+    }
+
+    #[test]
+    fn test_prove_falcon_batch_rejects_mixed_degrees() {
+        use crate::input::falcon_512;
+
+        let result = prove_falcon_batch::<Blake2sMerkleChannel>(&[
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE),
+            FalconInstance::new(SIGNATURE, PK, MESSAGE),
+        ]);
+        assert!(matches!(
+            result,
+            Err(FalconProveError::DegreeMismatch {
+                instance: 1,
+                expected: 512,
+                found: 1024
+            })
+        ));
+
+        // A Falcon-512 signature with a Falcon-1024 public key
+        let result = prove_falcon::<Blake2sMerkleChannel>(falcon_512::SIGNATURE, PK, MESSAGE);
+        assert!(matches!(
+            result,
+            Err(FalconProveError::DegreeMismatch {
+                instance: 0,
+                expected: 1024,
+                found: 512
+            })
+        ));
+    }
+
+    #[test]
+    fn test_prove_falcon_rejects_invalid_inputs() {
+        assert!(matches!(
+            prove_falcon_batch::<Blake2sMerkleChannel>(&[]),
+            Err(FalconProveError::EmptyBatch)
+        ));
+        assert!(matches!(
+            prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, &PK[..1000], MESSAGE),
+            Err(FalconProveError::UnsupportedDegree(1000))
+        ));
+
+        let mut signature = SIGNATURE.to_vec();
+        signature[0] ^= 1;
+        assert!(matches!(
+            prove_falcon::<Blake2sMerkleChannel>(&signature, PK, MESSAGE),
+            Err(FalconProveError::InvalidSignature { instance: 0, .. })
+        ));
+
+        let mut pk = PK.to_vec();
+        pk[7] = Q;
+        assert!(matches!(
+            prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, &pk, MESSAGE),
+            Err(FalconProveError::CoefficientOutOfRange {
+                instance: 0,
+                poly: "pk",
+                index: 7,
+                value: Q
+            })
+        ));
+    }
+
+    #[test]
+    fn test_prove_falcon_rejects_norm_above_bound() {
+        // The signature of another message is not short for this one
+        let result = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, b"another message");
+        match result {
+            Err(FalconProveError::NormExceedsBound {
+                instance: 0,
+                norm,
+                bound,
+            }) => assert!(norm > bound as u64),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_test_vectors() {
+        use crate::input::falcon_512;
+
+        let instance = FalconInstance::new(SIGNATURE, PK, MESSAGE);
+        assert_eq!(
+            instance.validate(0, CircuitParams::falcon_1024()).unwrap(),
+            17
+        );
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+        assert!(instance.validate(0, CircuitParams::falcon_512()).is_ok());
+    }
+
+    #[test]
+    fn test_debug_constraints_batch() {
+        debug::assert_constraints_batch(&[FalconInstance::new(SIGNATURE, PK, MESSAGE); 4]);
     }
 }
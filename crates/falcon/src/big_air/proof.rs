@@ -0,0 +1,402 @@
+//! # Big AIR Proof Artifact
+//!
+//! This module defines the self-contained proof artifact of the Big AIR STARK proof system.
+//!
+//! A `FalconProof` carries everything a verifier needs to rebuild the statement:
+//! - The circuit parameters (polynomial degree, Q and the signature bound)
+//! - The prover configuration (FRI and proof of work parameters)
+//! - The public inputs (public key and message point) of every signature of the batch
+//! - The claims, the interaction proof of work and the interaction claims
+//! - The STARK proof itself, committed with the Merkle channel `MC`
+//!
+//! # Encoding
+//!
+//! `to_bytes` produces `MAGIC || VERSION (u32, little endian) || HASH (u8) || bincode(FalconProof)`,
+//! where `HASH` identifies the Merkle hash of the proof, see [`MerkleHash`].
+//! `from_bytes` rejects anything with a wrong magic header, an unknown version, another
+//! hash than the one of `MC` or circuit parameters that are not one of the supported
+//! Falcon parameter sets. The payload is decoded with the fixed-size integer encoding it
+//! was written with, rejecting trailing bytes and payloads over [`MAX_PROOF_BYTES`].
+
+use std::fmt;
+
+use bincode::Options;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use stwo::core::{
+    channel::MerkleChannel,
+    proof::StarkProof,
+    vcs::{blake2_merkle::Blake2sMerkleChannel, poseidon252_merkle::Poseidon252MerkleChannel},
+};
+use thiserror::Error;
+
+use crate::{
+    CircuitParams,
+    big_air::{
+        claim::BigClaim, config::ProverConfig, interaction_claim::BigInteractionClaim,
+        prover::CircuitId, public_inputs::PublicInputs,
+    },
+};
+
+/// Magic header of an encoded proof.
+pub const PROOF_MAGIC: [u8; 4] = *b"FLCN";
+
+/// Current version of the proof encoding.
+///
+/// The version changes whenever proofs of the previous one no longer decode or verify,
+/// e.g. when the circuit changes:
+/// - 13: `ROOTS_8[7]` is 10984 = q - 1305 instead of the pasted 1098, which is not a root
+///   of unity. This changes the NTTs, the roots preprocessed columns and so the id of
+///   every circuit
+/// - 14: the Euclidean norm range checks its prefix sums on every row
+/// - 15: the prover configuration has no hiding flag
+pub const PROOF_VERSION: u32 = 15;
+
+/// Largest encoded proof payload, which bounds what a malformed proof can make the
+/// decoder allocate.
+pub const MAX_PROOF_BYTES: usize = 64 << 20;
+
+/// Length of the header of an encoded proof: magic, version and hash.
+const HEADER_LEN: usize = 9;
+
+/// The Merkle hash of a proof, recorded in its encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleHash {
+    /// Blake2s, the fastest to prove and verify natively
+    Blake2s,
+    /// Poseidon over the Stark field, cheap to verify in Cairo
+    Poseidon252,
+}
+
+impl MerkleHash {
+    /// Returns the byte identifying the hash in the proof encoding.
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Self::Blake2s => 0,
+            Self::Poseidon252 => 1,
+        }
+    }
+
+    /// Returns the hash identified by `byte`, if any.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Blake2s),
+            1 => Some(Self::Poseidon252),
+            _ => None,
+        }
+    }
+}
+
+/// A Merkle channel Falcon proofs can be generated with.
+///
+/// Its hasher is serializable, so that the proofs are.
+pub trait FalconMerkleChannel: MerkleChannel<H: Serialize + DeserializeOwned> {
+    /// The hash recorded in the encoding of the proofs
+    const HASH: MerkleHash;
+}
+
+impl FalconMerkleChannel for Blake2sMerkleChannel {
+    const HASH: MerkleHash = MerkleHash::Blake2s;
+}
+
+impl FalconMerkleChannel for Poseidon252MerkleChannel {
+    const HASH: MerkleHash = MerkleHash::Poseidon252;
+}
+
+/// A proof of a Falcon signature verification.
+///
+/// Besides the STARK proof itself, the verifier needs the claims (trace sizes and
+/// logup sums) and the interaction proof of work nonce to replay the Fiat-Shamir
+/// transcript of the prover.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "StarkProof<MC::H>: Serialize",
+    deserialize = "StarkProof<MC::H>: DeserializeOwned"
+))]
+pub struct FalconProof<MC: MerkleChannel = Blake2sMerkleChannel> {
+    pub params: CircuitParams,
+    pub config: ProverConfig,
+    pub public_inputs: Vec<PublicInputs>,
+    pub claim: BigClaim,
+    pub interaction_pow: u64,
+    pub interaction_claim: BigInteractionClaim,
+    pub stark_proof: StarkProof<MC::H>,
+}
+
+// Implemented by hand since the Merkle channels are neither `Clone` nor `Debug`
+impl<MC: MerkleChannel> Clone for FalconProof<MC>
+where
+    StarkProof<MC::H>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            params: self.params,
+            config: self.config,
+            public_inputs: self.public_inputs.clone(),
+            claim: self.claim.clone(),
+            interaction_pow: self.interaction_pow,
+            interaction_claim: self.interaction_claim.clone(),
+            stark_proof: self.stark_proof.clone(),
+        }
+    }
+}
+
+impl<MC: MerkleChannel> fmt::Debug for FalconProof<MC>
+where
+    StarkProof<MC::H>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FalconProof")
+            .field("params", &self.params)
+            .field("config", &self.config)
+            .field("public_inputs", &self.public_inputs)
+            .field("claim", &self.claim)
+            .field("interaction_pow", &self.interaction_pow)
+            .field("interaction_claim", &self.interaction_claim)
+            .field("stark_proof", &self.stark_proof)
+            .finish()
+    }
+}
+
+/// Errors that can occur when decoding a proof.
+#[derive(Debug, Error)]
+pub enum ProofDecodeError {
+    #[error("proof is too short to contain a header ({0} bytes)")]
+    TooShort(usize),
+    #[error("invalid magic header {0:?}, expected {PROOF_MAGIC:?}")]
+    InvalidMagic([u8; 4]),
+    #[error("unsupported proof version {0}, expected {PROOF_VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("unknown Merkle hash {0}")]
+    UnknownHash(u8),
+    #[error("Merkle hash mismatch: proof uses {found:?}, expected {expected:?}")]
+    HashMismatch {
+        found: MerkleHash,
+        expected: MerkleHash,
+    },
+    #[error("unsupported polynomial size 2^{0}")]
+    UnsupportedDegree(u32),
+    #[error("unsupported signature bound {0}")]
+    UnsupportedSignatureBound(u32),
+    #[error("circuit parameters mismatch: proof has {found:?}, expected {expected:?}")]
+    ParamsMismatch {
+        found: CircuitParams,
+        expected: CircuitParams,
+    },
+    #[error("failed to deserialize proof: {0}")]
+    Deserialize(#[from] bincode::Error),
+}
+
+/// Returns the Merkle hash of an encoded proof, after checking its magic header and
+/// version.
+///
+/// This tells which `FalconProof<MC>` to decode the proof as.
+///
+/// # Errors
+///
+/// Returns `ProofDecodeError` if the header is invalid, the version is unknown or the
+/// hash is not a supported one.
+pub fn proof_hash(bytes: &[u8]) -> Result<MerkleHash, ProofDecodeError> {
+    if bytes.len() < HEADER_LEN {
+        return Err(ProofDecodeError::TooShort(bytes.len()));
+    }
+    let magic: [u8; 4] = bytes[0..4].try_into().unwrap();
+    if magic != PROOF_MAGIC {
+        return Err(ProofDecodeError::InvalidMagic(magic));
+    }
+    let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+    if version != PROOF_VERSION {
+        return Err(ProofDecodeError::UnsupportedVersion(version));
+    }
+    MerkleHash::from_byte(bytes[8]).ok_or(ProofDecodeError::UnknownHash(bytes[8]))
+}
+
+impl<MC: FalconMerkleChannel> FalconProof<MC> {
+    /// Returns the id of the circuit of the proof, the root of the preprocessed trace it
+    /// commits to.
+    pub fn circuit_id(&self) -> CircuitId<MC> {
+        self.stark_proof.commitments[0]
+    }
+
+    /// Encodes the proof with its magic header, format version and Merkle hash.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = PROOF_MAGIC.to_vec();
+        bytes.extend_from_slice(&PROOF_VERSION.to_le_bytes());
+        bytes.push(MC::HASH.to_byte());
+        bytes.extend(
+            bincode_options()
+                .serialize(self)
+                .expect("proof serialization cannot fail"),
+        );
+        bytes
+    }
+
+    /// Decodes a proof produced by [`FalconProof::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProofDecodeError` if the header is invalid, the version is unknown,
+    /// the proof uses another Merkle hash than `MC`, the payload cannot be deserialized,
+    /// has trailing bytes or exceeds [`MAX_PROOF_BYTES`], or the circuit parameters are not the Falcon-512 or Falcon-1024 ones, with a
+    /// supported signature bound.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodeError> {
+        let hash = proof_hash(bytes)?;
+        if hash != MC::HASH {
+            return Err(ProofDecodeError::HashMismatch {
+                found: hash,
+                expected: MC::HASH,
+            });
+        }
+
+        let proof: Self = bincode_options().deserialize(&bytes[HEADER_LEN..])?;
+        // The signature bound may be any supported bound, the other parameters follow
+        // from the degree
+        let expected = CircuitParams::from_poly_log_size(proof.params.poly_log_size)
+            .ok_or(ProofDecodeError::UnsupportedDegree(
+                proof.params.poly_log_size,
+            ))?
+            .with_signature_bound(proof.params.signature_bound);
+        if !expected.is_supported() {
+            return Err(ProofDecodeError::UnsupportedSignatureBound(
+                expected.signature_bound,
+            ));
+        }
+        // The claim carries its own copy of the parameters, which is the one mixed
+        // into the transcript, so both must match
+        for found in [proof.params, proof.claim.params] {
+            if found != expected {
+                return Err(ProofDecodeError::ParamsMismatch { found, expected });
+            }
+        }
+        Ok(proof)
+    }
+}
+
+/// Returns the bincode options of the proof payload: the fixed-size little endian
+/// integers of `bincode::serialize`, without trailing bytes and up to
+/// [`MAX_PROOF_BYTES`].
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .with_limit(MAX_PROOF_BYTES as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        big_air::{prove_falcon, verify_falcon},
+        input::{MESSAGE, PK, SIGNATURE},
+    };
+
+    #[test]
+    fn test_proof_bytes_round_trip() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        let decoded = FalconProof::<Blake2sMerkleChannel>::from_bytes(&proof.to_bytes()).unwrap();
+        verify_falcon(
+            decoded,
+            &PublicInputs::new(PK, SIGNATURE, MESSAGE),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_proof_bytes_round_trip_poseidon252() {
+        use crate::input::falcon_512;
+
+        let proof = prove_falcon::<Poseidon252MerkleChannel>(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )
+        .unwrap();
+        let bytes = proof.to_bytes();
+        assert_eq!(proof_hash(&bytes).unwrap(), MerkleHash::Poseidon252);
+
+        // The hash of the envelope selects the channel the proof is verified with
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&bytes),
+            Err(ProofDecodeError::HashMismatch {
+                found: MerkleHash::Poseidon252,
+                expected: MerkleHash::Blake2s,
+            })
+        ));
+        let decoded = FalconProof::<Poseidon252MerkleChannel>::from_bytes(&bytes).unwrap();
+        verify_falcon(
+            decoded,
+            &PublicInputs::new(falcon_512::PK, falcon_512::SIGNATURE, falcon_512::MESSAGE),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_proof_bytes_rejects_invalid_header() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        let bytes = proof.to_bytes();
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] ^= 1;
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&wrong_magic),
+            Err(ProofDecodeError::InvalidMagic(_))
+        ));
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4..8].copy_from_slice(&(PROOF_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&wrong_version),
+            Err(ProofDecodeError::UnsupportedVersion(v)) if v == PROOF_VERSION + 1
+        ));
+
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&bytes[..4]),
+            Err(ProofDecodeError::TooShort(4))
+        ));
+
+        let mut unknown_hash = bytes.clone();
+        unknown_hash[8] = 2;
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&unknown_hash),
+            Err(ProofDecodeError::UnknownHash(2))
+        ));
+    }
+
+    #[test]
+    fn test_proof_bytes_rejects_trailing_bytes() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+        let mut bytes = proof.to_bytes();
+        bytes.push(0);
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&bytes),
+            Err(ProofDecodeError::Deserialize(_))
+        ));
+    }
+
+    #[test]
+    fn test_proof_bytes_rejects_mismatched_params() {
+        let proof = prove_falcon::<Blake2sMerkleChannel>(SIGNATURE, PK, MESSAGE).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.params.q += 1;
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&tampered.to_bytes()),
+            Err(ProofDecodeError::ParamsMismatch { .. })
+        ));
+
+        let mut tampered = proof.clone();
+        tampered.claim.params = CircuitParams::falcon_512();
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&tampered.to_bytes()),
+            Err(ProofDecodeError::ParamsMismatch { .. })
+        ));
+
+        let mut tampered = proof;
+        tampered.params.poly_log_size = 11;
+        assert!(matches!(
+            FalconProof::<Blake2sMerkleChannel>::from_bytes(&tampered.to_bytes()),
+            Err(ProofDecodeError::UnsupportedDegree(11))
+        ));
+    }
+}
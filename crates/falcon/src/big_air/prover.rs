@@ -0,0 +1,702 @@
+//! # Reusable Prover
+//!
+//! [`FalconProver`] proves signature verifications of one parameter set and keeps what
+//! does not depend on the signatures across proofs: the twiddles, the preprocessed
+//! columns (range check, roots and inverse roots tables, indices and row layouts) and
+//! the Merkle root they commit to.
+//!
+//! # Circuits
+//!
+//! The preprocessed trace is fixed by a [`CircuitKey`]: by the parameters, the
+//! configuration, the size of the batch and the sizes of the HashToPoint traces, which
+//! only grow when the messages cross a power of two number of Keccak rounds or need more
+//! squeezed blocks. Claims of messages of different lengths share their circuit as long
+//! as their keys are equal. The prover builds and caches one circuit per key it meets, up
+//! to [`MAX_CACHED_CIRCUITS`] of them by default, evicting the least recently used one,
+//! so proving many signatures of similar messages pays for the preprocessed trace once.
+//!
+//! The root of the preprocessed trace is the [`CircuitId`] of the circuit. The verifier
+//! rejects proofs committing to any other preprocessed trace than the one of their claim:
+//! it builds the preprocessed trace to compute the id with [`circuit_id`], or takes an id
+//! it pinned, see
+//! [`verify_falcon_batch_with_circuit_id`](super::verify_falcon_batch_with_circuit_id).
+
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+use itertools::Itertools;
+use num_traits::Zero;
+use stwo::{
+    core::{
+        channel::{Channel, MerkleChannel},
+        fields::{m31::BaseField, qm31::QM31},
+        poly::circle::CanonicCoset,
+        proof_of_work::GrindOps,
+        vcs::{blake2_merkle::Blake2sMerkleChannel, ops::MerkleHasher},
+    },
+    prover::{
+        CommitmentSchemeProver,
+        backend::{BackendForChannel, simd::SimdBackend},
+        poly::{
+            BitReversedOrder, circle::CircleEvaluation, circle::PolyOps, twiddles::TwiddleTree,
+        },
+        prove,
+    },
+};
+use stwo_constraint_framework::preprocessed_columns::PreProcessedColumnId;
+
+use crate::{
+    CircuitParams,
+    big_air::{
+        FalconInstance, FalconProveError,
+        backend::FalconBackend,
+        claim::BigClaim,
+        components::BigComponents,
+        config::ProverConfig,
+        interaction_claim::BigInteractionClaim,
+        proof::{FalconMerkleChannel, FalconProof},
+        public_inputs::{PublicInputs, batch_logup_sum, mix_batch_into, pad_batch},
+        relation::LookupElements,
+    },
+    debug::relation_tracker::{track_big_air_relations, unbalanced_relations},
+};
+
+/// Relations whose entries are partly added back by the verifier from the public inputs,
+/// so that they never balance out within the trace alone.
+const PUBLIC_INPUT_RELATIONS: [&str; 2] =
+    ["PublicInputLookupElements", "MessageBlockLookupElements"];
+
+/// The number of circuits a [`FalconProver`] caches by default.
+pub const MAX_CACHED_CIRCUITS: usize = 8;
+
+/// The identifier of a circuit: the Merkle root of its preprocessed trace.
+pub type CircuitId<MC> = <<MC as MerkleChannel>::H as MerkleHasher>::Hash;
+
+/// What the preprocessed trace of a claim depends on: claims with the same key share
+/// their circuit and its id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CircuitKey {
+    /// The circuit parameters
+    pub params: CircuitParams,
+    /// The security parameters, whose blowup factor sizes the committed domains
+    pub config: ProverConfig,
+    /// The log base 2 of the number of signatures stacked in the traces
+    pub log_n_instances: u32,
+    /// The log base 2 of the size of the Keccak trace, which sizes its round flags
+    pub keccak_log_size: u32,
+    /// The number of blocks squeezed by every instance, which lays out the sampler chunks
+    pub squeeze_blocks: u32,
+    /// The log base 2 of the largest trace, which sizes the twiddles
+    pub max_log_size: u32,
+}
+
+impl CircuitKey {
+    /// Returns the key of the circuit proving `claim` with `config`.
+    pub fn new(claim: &BigClaim, config: &ProverConfig) -> Self {
+        Self {
+            params: claim.params,
+            config: *config,
+            log_n_instances: claim.log_n_instances(),
+            keccak_log_size: claim.keccak.log_size,
+            squeeze_blocks: claim.sampler.squeeze_blocks,
+            max_log_size: claim.max_log_size(),
+        }
+    }
+}
+
+/// The part of a proof shared by every proof of the same circuit: of the same circuit key
+/// for the Big AIR, or of the same parameters for the [key AIR](crate::key_air).
+pub(crate) struct Circuit<B: FalconBackend> {
+    /// The twiddles of the largest committed domain of the claim
+    pub(crate) twiddles: TwiddleTree<B>,
+    /// The preprocessed columns
+    pub(crate) preprocessed_columns: Vec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    /// The ids of the preprocessed columns, in commitment order
+    pub(crate) preprocessed_columns_ids: Vec<PreProcessedColumnId>,
+}
+
+impl<B: FalconBackend> Circuit<B> {
+    /// Builds the twiddles and the preprocessed columns of `claim`, which only depend on
+    /// its [`CircuitKey`].
+    fn new(claim: &BigClaim, config: &ProverConfig) -> Self {
+        let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
+        Self::from_preprocessed_columns(
+            preprocessed_columns,
+            preprocessed_columns_ids,
+            claim.max_log_size(),
+            config,
+        )
+    }
+
+    /// Builds the twiddles of traces of up to 2^`max_log_size` rows and moves the
+    /// preprocessed columns to the backend.
+    pub(crate) fn from_preprocessed_columns(
+        preprocessed_columns: Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        preprocessed_columns_ids: Vec<PreProcessedColumnId>,
+        max_log_size: u32,
+        config: &ProverConfig,
+    ) -> Self {
+        let twiddles = B::precompute_twiddles(
+            CanonicCoset::new(max_log_size + config.log_blowup_factor + 1)
+                .circle_domain()
+                .half_coset,
+        );
+        Self {
+            twiddles,
+            preprocessed_columns: B::from_simd_columns(preprocessed_columns),
+            preprocessed_columns_ids,
+        }
+    }
+
+    /// Returns the id of the circuit, the root of its preprocessed columns committed with
+    /// `config`.
+    pub(crate) fn id<MC>(&self, config: &ProverConfig) -> CircuitId<MC>
+    where
+        B: BackendForChannel<MC>,
+        MC: FalconMerkleChannel,
+    {
+        commit_preprocessed_columns::<B, MC>(
+            self.preprocessed_columns.clone(),
+            config,
+            &self.twiddles,
+        )
+    }
+
+    /// Returns a commitment scheme with `config` committed to the preprocessed columns,
+    /// the first step of the transcript of every proof of the circuit.
+    pub(crate) fn commitment_scheme<MC>(
+        &self,
+        config: &ProverConfig,
+        channel: &mut MC::C,
+    ) -> CommitmentSchemeProver<'_, B, MC>
+    where
+        B: BackendForChannel<MC>,
+        MC: FalconMerkleChannel,
+    {
+        let mut commitment_scheme =
+            CommitmentSchemeProver::<B, MC>::new(config.pcs_config(), &self.twiddles);
+        let mut tree_builder = commitment_scheme.tree_builder();
+        tree_builder.extend_evals(self.preprocessed_columns.clone());
+        tree_builder.commit(channel);
+        commitment_scheme
+    }
+}
+
+/// The circuits built by a prover, with their ids, keyed by [`CircuitKey`].
+///
+/// The cache holds at most `capacity` circuits and evicts the least recently used one.
+struct CircuitCache<B: FalconBackend, MC: MerkleChannel> {
+    capacity: usize,
+    /// The circuits and their ids, with the tick of their last use
+    circuits: HashMap<CircuitKey, (Arc<Circuit<B>>, CircuitId<MC>, u64)>,
+    tick: u64,
+}
+
+impl<B: FalconBackend, MC: MerkleChannel> CircuitCache<B, MC> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            circuits: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the circuit of `key` and its id, building it with `build` if it is not
+    /// cached yet.
+    fn get_or_insert_with(
+        &mut self,
+        key: CircuitKey,
+        build: impl FnOnce() -> (Circuit<B>, CircuitId<MC>),
+    ) -> (Arc<Circuit<B>>, CircuitId<MC>) {
+        self.tick += 1;
+        if let Some((circuit, id, last_use)) = self.circuits.get_mut(&key) {
+            *last_use = self.tick;
+            return (circuit.clone(), *id);
+        }
+        if self.circuits.len() >= self.capacity {
+            let oldest = self
+                .circuits
+                .iter()
+                .min_by_key(|(_, (_, _, last_use))| *last_use)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                self.circuits.remove(&oldest);
+            }
+        }
+        let (circuit, id) = build();
+        let circuit = Arc::new(circuit);
+        self.circuits.insert(key, (circuit.clone(), id, self.tick));
+        (circuit, id)
+    }
+}
+
+/// A prover of Falcon signature verifications of one parameter set, reusing its
+/// preprocessed traces across proofs.
+///
+/// The cache of circuits is behind a lock, so the prover can be shared by threads proving
+/// concurrently. It holds [`MAX_CACHED_CIRCUITS`] circuits unless set otherwise with
+/// [`FalconProver::with_max_cached_circuits`].
+pub struct FalconProver<B = SimdBackend, MC = Blake2sMerkleChannel>
+where
+    B: FalconBackend + BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    params: CircuitParams,
+    config: ProverConfig,
+    /// The circuits built so far, with their ids
+    circuits: Mutex<CircuitCache<B, MC>>,
+    _channel: PhantomData<MC>,
+}
+
+impl<B, MC> FalconProver<B, MC>
+where
+    B: FalconBackend + BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    /// Creates a prover of the degree of `params` with the security parameters of
+    /// `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FalconProveError::UnsupportedParams` or
+    /// `FalconProveError::UnsupportedConfig` if proofs cannot be generated with `params`
+    /// or `config`.
+    pub fn new(params: CircuitParams, config: ProverConfig) -> Result<Self, FalconProveError> {
+        if !params.is_supported() {
+            return Err(FalconProveError::UnsupportedParams(params));
+        }
+        if !config.is_supported() {
+            return Err(FalconProveError::UnsupportedConfig(config));
+        }
+        Ok(Self {
+            params,
+            config,
+            circuits: Mutex::new(CircuitCache::new(MAX_CACHED_CIRCUITS)),
+            _channel: PhantomData,
+        })
+    }
+
+    /// Returns the prover caching at most `max_cached_circuits` circuits, at least one.
+    pub fn with_max_cached_circuits(self, max_cached_circuits: usize) -> Self {
+        Self {
+            circuits: Mutex::new(CircuitCache::new(max_cached_circuits.max(1))),
+            ..self
+        }
+    }
+
+    /// Returns the circuit parameters of the prover.
+    pub fn params(&self) -> CircuitParams {
+        self.params
+    }
+
+    /// Returns the security parameters of the prover.
+    pub fn config(&self) -> ProverConfig {
+        self.config
+    }
+
+    /// Proves a single signature verification, see [`FalconProver::prove_batch`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`FalconProver::prove_batch`].
+    pub fn prove(&self, instance: &FalconInstance) -> Result<FalconProof<MC>, FalconProveError> {
+        self.prove_batch(std::slice::from_ref(instance))
+    }
+
+    /// Generates a single STARK proof for a batch of Falcon signature verifications.
+    ///
+    /// This is [`prove_falcon_batch_with_backend`](super::prove_falcon_batch_with_backend)
+    /// with the twiddles and the preprocessed trace of the circuit of the batch taken from
+    /// the cache, or built and cached if no previous batch had the same [`CircuitKey`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `FalconProveError` if the batch is empty, an instance is not a valid
+    /// signature verification of the degree of the prover (see
+    /// [`FalconInstance::validate`]) or gives its public key in another form than the
+    /// first one, or if any step in the proof generation fails.
+    pub fn prove_batch(
+        &self,
+        instances: &[FalconInstance],
+    ) -> Result<FalconProof<MC>, FalconProveError> {
+        let (claim, public_inputs) = self.claim(instances)?;
+        let (circuit, circuit_id) = self.circuit(&claim);
+        let config = self.config;
+        let params = self.params;
+        let squeeze_blocks = claim.sampler.squeeze_blocks;
+
+        // Initialize Fiat-Shamir channel and commitment scheme
+        let channel = &mut MC::C::default();
+        config.mix_into(channel);
+        mix_batch_into(&public_inputs, channel);
+
+        // Commit to the cached preprocessed columns
+        let mut commitment_scheme = circuit.commitment_scheme::<MC>(&config, channel);
+        debug_assert_eq!(commitment_scheme.roots()[0], circuit_id);
+
+        // Generate and commit to main traces
+        let (trace, traces) = claim.gen_trace(&pad_batch(instances).copied().collect_vec());
+        claim.mix_into(channel);
+
+        let mut tree_builder = commitment_scheme.tree_builder();
+        tree_builder.extend_evals(B::from_simd_columns(trace));
+        tree_builder.commit(channel);
+
+        // Generate proof of work and draw lookup relations
+        let interaction_pow = B::grind(channel, config.interaction_pow_bits);
+        channel.mix_u64(interaction_pow);
+
+        let lookup_elements = LookupElements::draw(channel);
+
+        // Generate and commit to interaction traces
+        let (interaction_trace, interaction_claim) =
+            BigInteractionClaim::gen_interaction_trace(&claim, &lookup_elements, &traces);
+
+        interaction_claim.mix_into(channel);
+
+        let mut tree_builder = commitment_scheme.tree_builder();
+        tree_builder.extend_evals(B::from_simd_columns(interaction_trace));
+        tree_builder.commit(channel);
+
+        let components = BigComponents::new(
+            &claim,
+            &lookup_elements,
+            &interaction_claim,
+            &circuit.preprocessed_columns_ids,
+        );
+
+        #[cfg(test)]
+        {
+            use crate::debug::relation_tracker::track_and_summarize_big_air_relations;
+
+            let summary =
+                track_and_summarize_big_air_relations(&commitment_scheme, &components.tracked());
+            std::fs::write("summary.txt", format!("{:?}", summary)).unwrap();
+
+            // println!("summary: {:?}", summary);
+        }
+        if interaction_claim.claimed_sum()
+            + batch_logup_sum(&public_inputs, &lookup_elements, squeeze_blocks)
+            != QM31::zero()
+        {
+            return Err(unbalanced_lookup(&commitment_scheme, &components));
+        }
+
+        // Generate the final STARK proof
+        let stark_proof = prove::<B, MC>(&components.provers(), channel, commitment_scheme)?;
+
+        Ok(FalconProof {
+            params,
+            config,
+            public_inputs,
+            claim,
+            interaction_pow,
+            interaction_claim,
+            stark_proof,
+        })
+    }
+
+    /// Returns the id of the circuit proving `instances`, the root of its preprocessed
+    /// trace, for a verifier to pin.
+    ///
+    /// # Errors
+    ///
+    /// Returns the input errors of [`FalconProver::prove_batch`].
+    pub fn circuit_id(
+        &self,
+        instances: &[FalconInstance],
+    ) -> Result<CircuitId<MC>, FalconProveError> {
+        let (claim, _) = self.claim(instances)?;
+        Ok(self.circuit(&claim).1)
+    }
+
+    /// Returns the number of circuits in the cache.
+    pub fn n_circuits(&self) -> usize {
+        self.circuits.lock().unwrap().circuits.len()
+    }
+
+    /// Checks the instances and returns the claim and the public inputs of the batch.
+    ///
+    /// The inputs are checked before any trace is built.
+    fn claim(
+        &self,
+        instances: &[FalconInstance],
+    ) -> Result<(BigClaim, Vec<PublicInputs>), FalconProveError> {
+        if instances.is_empty() {
+            return Err(FalconProveError::EmptyBatch);
+        }
+        // The G-NTT sub-circuit is in the proof for all the instances or for none
+        let expected = instances[0].pk_form;
+        if let Some((instance, found)) = instances
+            .iter()
+            .map(|instance| instance.pk_form)
+            .find_position(|pk_form| *pk_form != expected)
+        {
+            return Err(FalconProveError::PkFormMismatch {
+                instance,
+                expected,
+                found,
+            });
+        }
+        let squeeze_blocks = instances
+            .iter()
+            .enumerate()
+            .map(|(i, instance)| instance.validate(i, self.params))
+            .process_results(|blocks| blocks.max())?
+            .unwrap();
+        let public_inputs = instances
+            .iter()
+            .map(FalconInstance::public_inputs)
+            .collect_vec();
+        let claim = BigClaim::new(self.params, &public_inputs, squeeze_blocks);
+        Ok((claim, public_inputs))
+    }
+
+    /// Returns the circuit of `claim` and its id, building and committing to its
+    /// preprocessed trace if the circuit of its key is not cached yet.
+    fn circuit(&self, claim: &BigClaim) -> (Arc<Circuit<B>>, CircuitId<MC>) {
+        let key = CircuitKey::new(claim, &self.config);
+        self.circuits.lock().unwrap().get_or_insert_with(key, || {
+            let circuit = Circuit::new(claim, &self.config);
+            let id = circuit.id(&self.config);
+            (circuit, id)
+        })
+    }
+}
+
+/// Returns the id of the circuit proving `claim` with `config`, the root of its
+/// preprocessed trace, which only depends on their [`CircuitKey`].
+///
+/// This builds and commits to the preprocessed trace, like a verifier that did not pin
+/// the id of the circuit does to check the preprocessed root of a proof.
+pub fn circuit_id<MC>(claim: &BigClaim, config: &ProverConfig) -> CircuitId<MC>
+where
+    MC: FalconMerkleChannel,
+    SimdBackend: BackendForChannel<MC>,
+{
+    let (preprocessed_columns, _) = claim.create_preprocessed_columns();
+    preprocessed_root::<MC>(preprocessed_columns, config)
+}
+
+/// Returns the root of the preprocessed columns committed with `config`.
+pub(crate) fn preprocessed_root<MC>(
+    columns: Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+    config: &ProverConfig,
+) -> CircuitId<MC>
+where
+    MC: FalconMerkleChannel,
+    SimdBackend: BackendForChannel<MC>,
+{
+    let max_log_size = columns
+        .iter()
+        .map(|column| column.domain.log_size())
+        .max()
+        .unwrap();
+    let twiddles = SimdBackend::precompute_twiddles(
+        CanonicCoset::new(max_log_size + config.log_blowup_factor + 1)
+            .circle_domain()
+            .half_coset,
+    );
+    commit_preprocessed_columns::<SimdBackend, MC>(columns, config, &twiddles)
+}
+
+/// Commits to preprocessed columns with `config` and returns their root.
+///
+/// The root does not depend on the channel nor on the size of the twiddles, as long as
+/// they cover the evaluation domains of the columns.
+fn commit_preprocessed_columns<B, MC>(
+    columns: Vec<CircleEvaluation<B, BaseField, BitReversedOrder>>,
+    config: &ProverConfig,
+    twiddles: &TwiddleTree<B>,
+) -> CircuitId<MC>
+where
+    B: FalconBackend + BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    let mut commitment_scheme = CommitmentSchemeProver::<B, MC>::new(config.pcs_config(), twiddles);
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(columns);
+    tree_builder.commit(&mut MC::C::default());
+    commitment_scheme.roots()[0]
+}
+
+/// Finds a relation whose lookups do not balance out, to explain an invalid logup sum.
+///
+/// The relations fed by the public inputs never balance out within the trace, so they
+/// are only reported if no other relation is unbalanced. This evaluates the whole
+/// committed trace and is only meant to run once proving has failed.
+fn unbalanced_lookup<B, MC>(
+    commitment_scheme: &CommitmentSchemeProver<'_, B, MC>,
+    components: &BigComponents,
+) -> FalconProveError
+where
+    B: BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    let entries = track_big_air_relations(commitment_scheme, &components.tracked());
+    let relations = unbalanced_relations(&entries);
+    let relation = relations
+        .iter()
+        .find(|relation| !PUBLIC_INPUT_RELATIONS.contains(&relation.as_str()))
+        .or(relations.first())
+        .cloned()
+        .unwrap_or_else(|| "public inputs".to_string());
+    FalconProveError::UnbalancedLookup { relation }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        big_air::{prove_falcon, public_inputs::PkForm, verify_falcon_batch_with_circuit_id},
+        input::{MESSAGE, PK, SIGNATURE, falcon_512},
+    };
+
+    #[test]
+    fn test_prover_reuses_its_circuit() {
+        let prover = FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(
+            CircuitParams::falcon_512(),
+            ProverConfig::default(),
+        )
+        .unwrap();
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+        let circuit_id = prover.circuit_id(&[instance]).unwrap();
+
+        let first = prover.prove(&instance).unwrap();
+        let second = prover.prove(&instance).unwrap();
+        assert_eq!(prover.n_circuits(), 1);
+        assert_eq!(first.circuit_id(), circuit_id);
+        assert_eq!(first.to_bytes(), second.to_bytes());
+
+        // The cached circuit produces the proof of a fresh prover
+        let fresh = prove_falcon::<Blake2sMerkleChannel>(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )
+        .unwrap();
+        assert_eq!(first.to_bytes(), fresh.to_bytes());
+
+        verify_falcon_batch_with_circuit_id(
+            first,
+            &[instance.public_inputs()],
+            circuit_id,
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_with_circuit_id_rejects_other_circuit() {
+        let prover = FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(
+            CircuitParams::falcon_1024(),
+            ProverConfig::default(),
+        )
+        .unwrap();
+        let instance = FalconInstance::new(SIGNATURE, PK, MESSAGE);
+        let proof = prover.prove(&instance).unwrap();
+
+        let other_circuit_id = FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(
+            CircuitParams::falcon_512(),
+            ProverConfig::default(),
+        )
+        .unwrap()
+        .circuit_id(&[FalconInstance::new(
+            falcon_512::SIGNATURE,
+            falcon_512::PK,
+            falcon_512::MESSAGE,
+        )])
+        .unwrap();
+        assert!(
+            verify_falcon_batch_with_circuit_id(
+                proof,
+                &[instance.public_inputs()],
+                other_circuit_id,
+                &ProverConfig::default(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_claims_of_the_same_key_share_their_circuit() {
+        let config = ProverConfig::default();
+        let public_inputs = PublicInputs::new(PK, SIGNATURE, MESSAGE);
+        let claim = BigClaim::new(CircuitParams::falcon_1024(), &[public_inputs.clone()], 1);
+
+        // The G-NTT sub-circuit has no preprocessed column
+        let ntt_public_inputs = PublicInputs {
+            pk_form: PkForm::Ntt,
+            ..public_inputs.clone()
+        };
+        let ntt_claim = BigClaim::new(CircuitParams::falcon_1024(), &[ntt_public_inputs], 1);
+        assert_ne!(claim, ntt_claim);
+        assert_eq!(
+            CircuitKey::new(&claim, &config),
+            CircuitKey::new(&ntt_claim, &config)
+        );
+        assert_eq!(
+            circuit_id::<Blake2sMerkleChannel>(&claim, &config),
+            circuit_id::<Blake2sMerkleChannel>(&ntt_claim, &config)
+        );
+
+        // A larger batch stacks the instances in taller traces
+        let batch_claim = BigClaim::new(
+            CircuitParams::falcon_1024(),
+            &[public_inputs.clone(), public_inputs],
+            1,
+        );
+        assert_ne!(
+            CircuitKey::new(&claim, &config),
+            CircuitKey::new(&batch_claim, &config)
+        );
+    }
+
+    #[test]
+    fn test_prover_evicts_least_recently_used_circuit() {
+        let prover = FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(
+            CircuitParams::falcon_512(),
+            ProverConfig::default(),
+        )
+        .unwrap()
+        .with_max_cached_circuits(2);
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+
+        let single = prover.circuit_id(&[instance]).unwrap();
+        let pair = prover.circuit_id(&[instance; 2]).unwrap();
+        assert_ne!(single, pair);
+        assert_eq!(prover.circuit_id(&[instance]).unwrap(), single);
+        assert_eq!(prover.n_circuits(), 2);
+
+        // The pair is the least recently used circuit
+        prover.circuit_id(&[instance; 4]).unwrap();
+        assert_eq!(prover.n_circuits(), 2);
+        let cache = prover.circuits.lock().unwrap();
+        let keys = cache
+            .circuits
+            .keys()
+            .map(|key| key.log_n_instances)
+            .sorted()
+            .collect_vec();
+        assert_eq!(keys, [0, 2]);
+    }
+
+    #[test]
+    fn test_prover_rejects_other_degree() {
+        let prover = FalconProver::<SimdBackend, Blake2sMerkleChannel>::new(
+            CircuitParams::falcon_512(),
+            ProverConfig::default(),
+        )
+        .unwrap();
+        assert!(matches!(
+            prover.prove(&FalconInstance::new(SIGNATURE, PK, MESSAGE)),
+            Err(FalconProveError::DegreeMismatch { .. })
+        ));
+        assert_eq!(prover.n_circuits(), 0);
+    }
+}
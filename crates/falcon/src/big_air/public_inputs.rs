@@ -0,0 +1,352 @@
+//! # Big AIR Public Inputs
+//!
+//! This module defines the public inputs of the Big AIR STARK proof system: the public key
+//! polynomial `pk`, the encoded `signature` whose nonce and compressed s1 are read by the
+//! proof, and the `message` the message point is hashed from.
+//!
+//! # Binding
+//!
+//! The public inputs are bound to the proof in two ways:
+//! - They are mixed into the Fiat-Shamir channel before anything else is committed
+//! - The components reading them consume entries from the public input relations, and
+//!   the verifier adds back the matching entries itself: `(poly id, index, value)` for the
+//!   coefficients of `pk` and the bytes of the compressed s1, and the padded SHAKE256
+//!   blocks of `nonce || message` for the sponge of HashToPoint
+//!
+//! The public key is given either by its coefficients, which go through the G-NTT
+//! sub-circuit, or directly in NTT form, see [`PkForm`]: the multiplication then reads it
+//! under its own identifier and the proof has no G-NTT at all.
+//!
+//! The logup sum of the proof therefore only cancels out for the exact `pk`, `signature`
+//! and `message` the trace was generated with.
+//!
+//! The coefficient indices are read from preprocessed columns so that the prover cannot
+//! permute the coefficients of a public polynomial.
+//!
+//! # Batches
+//!
+//! When several signatures are proven at once, the instances are stacked in the traces
+//! and coefficient `i` of instance `k` is indexed `k * poly_size + i`, byte `j` of its
+//! compressed s1 `k * bytes_per_instance + j`. The verifier pads
+//! the batch exactly like the prover before adding back the public input entries.
+
+use itertools::Itertools;
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{
+        channel::Channel,
+        fields::{
+            FieldExpOps,
+            m31::{BaseField, M31},
+            qm31::SecureField,
+        },
+        poly::circle::CanonicCoset,
+        utils::{bit_reverse, bit_reverse_coset_to_circle_domain_order},
+    },
+    prover::{
+        backend::simd::{SimdBackend, column::BaseColumn},
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{Relation, preprocessed_columns::PreProcessedColumnId};
+
+use crate::{
+    big_air::relation::LookupElements,
+    encoding::{NONCE_LEN, decompression::instance_log_size},
+    hash_to_point::{sponge::message_block_entry, sponge_steps},
+};
+
+/// Identifier of the public key polynomial in the public input relation.
+pub const PK_ID: u32 = 0;
+/// Identifier of the compressed signature bytes in the public input relation.
+pub const SIGNATURE_ID: u32 = 1;
+/// Identifier of the public key in NTT form in the public input relation.
+pub const PK_NTT_ID: u32 = 2;
+
+/// The form in which the public key is given to the proof.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PkForm {
+    /// The coefficients of h, transformed in the proof by the G-NTT sub-circuit
+    #[default]
+    Coefficients,
+    /// The NTT of h, as output by [`ntt`](crate::ntts::ntt()), read as is by the
+    /// multiplication
+    Ntt,
+}
+
+impl PkForm {
+    /// Returns the identifier of the public key in the public input relation.
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Coefficients => PK_ID,
+            Self::Ntt => PK_NTT_ID,
+        }
+    }
+}
+
+/// The public inputs of a Falcon signature verification proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicInputs {
+    /// The public key polynomial h, with coefficients in [0, Q), in the form of `pk_form`
+    pub pk: Vec<u32>,
+    /// The form of `pk`
+    pub pk_form: PkForm,
+    /// The signature in its standard compressed encoding: a header byte, the nonce r and
+    /// the compressed s1
+    pub signature: Vec<u8>,
+    /// The signed message, hashed with the nonce into the message point
+    pub message: Vec<u8>,
+}
+
+impl PublicInputs {
+    pub fn new(pk: &[u32], signature: &[u8], message: &[u8]) -> Self {
+        Self {
+            pk: pk.to_vec(),
+            pk_form: PkForm::Coefficients,
+            signature: signature.to_vec(),
+            message: message.to_vec(),
+        }
+    }
+
+    /// Returns the public inputs of a signature verification against the public key in
+    /// NTT form `pk_ntt`.
+    pub fn with_pk_ntt(pk_ntt: &[u32], signature: &[u8], message: &[u8]) -> Self {
+        Self {
+            pk_form: PkForm::Ntt,
+            ..Self::new(pk_ntt, signature, message)
+        }
+    }
+
+    /// Returns the nonce r of the signature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signature is shorter than its header and nonce.
+    pub fn nonce(&self) -> &[u8] {
+        &self.signature[1..1 + NONCE_LEN]
+    }
+
+    /// Returns the compressed s1 of the signature, after its header and nonce.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signature is shorter than its header and nonce.
+    pub fn compressed(&self) -> &[u8] {
+        &self.signature[1 + NONCE_LEN..]
+    }
+
+    /// Mixes the public inputs into the Fiat-Shamir channel.
+    ///
+    /// The signature and the message are preceded by their lengths, so that bytes cannot
+    /// be moved from one to the other.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.pk_form.id() as u64);
+        channel.mix_u32s(&self.pk);
+        for bytes in [&self.signature, &self.message] {
+            channel.mix_u64(bytes.len() as u64);
+            channel.mix_u32s(&bytes.iter().map(|byte| *byte as u32).collect_vec());
+        }
+    }
+
+    /// Returns the contribution of the public inputs to the logup sum.
+    ///
+    /// Every entry consumed by the components is added back with multiplicity 1, so that
+    /// the sum of all claimed sums plus this contribution is zero:
+    /// - the `(poly id, index, value)` entries of `pk`, whose indices are offset by
+    ///   `instance * poly_size`, the position of these public inputs in the batch, and
+    ///   whose id depends on the form of `pk`. The values are reduced modulo the M31
+    ///   modulus, so the verifier rejects coefficients of `pk` out of [0, q) beforehand
+    /// - the `(signature id, index, byte)` entries of the compressed s1, zero padded to the
+    ///   bytes of an instance of the decompression and offset like the coefficients
+    /// - the message block entries of the sponge steps absorbing `nonce || message` and
+    ///   squeezing `squeeze_blocks` blocks
+    pub fn logup_sum(
+        &self,
+        lookup_elements: &LookupElements,
+        instance: usize,
+        squeeze_blocks: u32,
+    ) -> SecureField {
+        let offset = instance * self.pk.len();
+        let mut sum = SecureField::zero();
+        for (index, value) in self.pk.iter().enumerate() {
+            let denom: SecureField = lookup_elements.public_input.combine(&[
+                M31(self.pk_form.id()),
+                M31((offset + index) as u32),
+                M31(*value),
+            ]);
+            sum += denom.inverse();
+        }
+        let bytes_per_instance = (1 << instance_log_size(self.pk.len().ilog2())) / 8;
+        let offset = instance * bytes_per_instance;
+        let compressed = self.compressed();
+        for index in 0..bytes_per_instance {
+            let byte = compressed.get(index).copied().unwrap_or(0);
+            let denom: SecureField = lookup_elements.public_input.combine(&[
+                M31(SIGNATURE_ID),
+                M31((offset + index) as u32),
+                M31(byte as u32),
+            ]);
+            sum += denom.inverse();
+        }
+        for step in sponge_steps(self.nonce(), &self.message, squeeze_blocks) {
+            let denom: SecureField = lookup_elements
+                .message_block
+                .combine(&message_block_entry(instance as u32, &step));
+            sum += denom.inverse();
+        }
+        sum
+    }
+}
+
+/// Returns the number of instances a batch of `n_instances` is padded to, as a log size.
+///
+/// The traces of the instances are stacked, so the batch is padded to the next power
+/// of two by repeating its last instance.
+pub fn log_n_instances(n_instances: usize) -> u32 {
+    assert!(
+        n_instances > 0,
+        "a batch must contain at least one instance"
+    );
+    n_instances.next_power_of_two().ilog2()
+}
+
+/// Pads a batch to the next power of two by repeating its last element.
+pub fn pad_batch<T>(batch: &[T]) -> impl Iterator<Item = &T> {
+    let padded_len = 1 << log_n_instances(batch.len());
+    batch
+        .iter()
+        .chain(std::iter::repeat(batch.last().unwrap()))
+        .take(padded_len)
+}
+
+/// Mixes a batch of public inputs into the Fiat-Shamir channel, preceded by its length.
+pub fn mix_batch_into(public_inputs: &[PublicInputs], channel: &mut impl Channel) {
+    channel.mix_u64(public_inputs.len() as u64);
+    for instance in public_inputs {
+        instance.mix_into(channel);
+    }
+}
+
+/// Returns the contribution of a batch of public inputs to the logup sum, padded like
+/// the traces of the batch.
+pub fn batch_logup_sum(
+    public_inputs: &[PublicInputs],
+    lookup_elements: &LookupElements,
+    squeeze_blocks: u32,
+) -> SecureField {
+    pad_batch(public_inputs).enumerate().fold(
+        SecureField::zero(),
+        |sum, (instance, public_inputs)| {
+            sum + public_inputs.logup_sum(lookup_elements, instance, squeeze_blocks)
+        },
+    )
+}
+
+/// Preprocessed column holding, for each row, the index of the coefficient stored in it.
+///
+/// Components storing one coefficient per row in natural order use the plain indices,
+/// permuted like their trace if it is read at an offset, while the NTT butterfly stores
+/// each polynomial two coefficients per row after a bit-reversal permutation and uses the
+/// bit-reversed indices: row `k * poly_size / 2 + r` holds coefficient `bitrev(r)` of
+/// instance `k`, whose index is `k * poly_size + bitrev(r)`. Components storing two
+/// coefficients per row use the plain indices as row indices.
+#[derive(Debug, Clone)]
+pub struct Indices {
+    pub log_size: u32,
+    /// The log size of the bit-reversed polynomials, if any
+    pub bit_reversed_poly_log_size: Option<u32>,
+    /// Whether the indices are permuted to the circle domain order
+    pub circle_domain_order: bool,
+}
+
+impl Indices {
+    /// Indices of coefficients stored one per row, in natural order.
+    pub fn natural(log_size: u32) -> Self {
+        Self {
+            log_size,
+            bit_reversed_poly_log_size: None,
+            circle_domain_order: false,
+        }
+    }
+
+    /// Indices of coefficients stored one per row in natural order, in a trace permuted
+    /// to the circle domain order because it is read at an offset.
+    pub fn natural_circle_domain_order(log_size: u32) -> Self {
+        Self {
+            log_size,
+            bit_reversed_poly_log_size: None,
+            circle_domain_order: true,
+        }
+    }
+
+    /// Indices of polynomials of `2^poly_log_size` coefficients stored two coefficients
+    /// per row after a bit-reversal permutation, one polynomial after the other.
+    pub fn bit_reversed(log_size: u32, poly_log_size: u32) -> Self {
+        assert!(poly_log_size >= 1 && poly_log_size - 1 <= log_size);
+        Self {
+            log_size,
+            bit_reversed_poly_log_size: Some(poly_log_size),
+            circle_domain_order: false,
+        }
+    }
+
+    /// Generates the preprocessed column of indices.
+    pub fn gen_column_simd(&self) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+        let mut indices = (0..(1 << self.log_size)).collect_vec();
+        if let Some(poly_log_size) = self.bit_reversed_poly_log_size {
+            let rows_per_poly = 1 << (poly_log_size - 1);
+            for (instance, chunk) in indices.chunks_mut(rows_per_poly).enumerate() {
+                bit_reverse(chunk);
+                for index in chunk.iter_mut() {
+                    *index = (*index % rows_per_poly as u32) + ((instance as u32) << poly_log_size);
+                }
+            }
+        }
+        if self.circle_domain_order {
+            bit_reverse_coset_to_circle_domain_order(&mut indices);
+        }
+        CircleEvaluation::new(
+            CanonicCoset::new(self.log_size).circle_domain(),
+            BaseColumn::from_iter(indices.into_iter().map(M31)),
+        )
+    }
+
+    /// Returns the unique identifier for this preprocessed column.
+    pub fn id(&self) -> PreProcessedColumnId {
+        PreProcessedColumnId {
+            id: match self.bit_reversed_poly_log_size {
+                Some(poly_log_size) => {
+                    format!("bit_reversed_indices_{}_{}", self.log_size, poly_log_size)
+                }
+                None if self.circle_domain_order => {
+                    format!("circle_domain_order_indices_{}", self.log_size)
+                }
+                None => format!("indices_{}", self.log_size),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_reversed_indices_are_offset_per_instance() {
+        // Two polynomials of 16 coefficients, 8 rows each
+        let column = Indices::bit_reversed(4, 4).gen_column_simd();
+        let indices = column.values.to_cpu().iter().map(|v| v.0).collect_vec();
+        assert_eq!(
+            indices,
+            vec![0, 4, 2, 6, 1, 5, 3, 7, 16, 20, 18, 22, 17, 21, 19, 23]
+        );
+    }
+
+    #[test]
+    fn test_pad_batch_repeats_last_instance() {
+        let padded = pad_batch(&[1, 2, 3]).copied().collect_vec();
+        assert_eq!(padded, vec![1, 2, 3, 3]);
+        assert_eq!(pad_batch(&[1]).count(), 1);
+    }
+}
@@ -18,16 +18,37 @@ use stwo_constraint_framework::relation;
 
 use crate::enum_relation;
 
+/// Identifier of the polynomial `s1` transformed by the forward NTT.
+pub const F_POLY_ID: u32 = 0;
+/// Identifier of the public key `h` transformed by the forward NTT.
+pub const G_POLY_ID: u32 = 1;
+/// Identifier of the product `s1 * h`, from the multiplication to the inverse NTT.
+pub const PRODUCT_POLY_ID: u32 = 2;
+/// Identifier of `s0 = c - s1 * h`, from the subtraction to the Euclidean norm.
+pub const S0_POLY_ID: u32 = 3;
+
 relation!(RCLookupElements, 1);
-relation!(FNTTLookupElements, 1);
-relation!(GNTTLookupElements, 1);
-relation!(ButterflyLookupElements, 1);
-relation!(MulLookupElements, 1);
-relation!(INTTLookupElements, 1);
-relation!(IButterflyLookupElements, 1);
-relation!(SubLookupElements, 1);
+// The relations between the NTT, multiplication, INTT and subtraction stages have entries
+// `(poly id, stage, index, value)`: `stage` is the number of NTT layers applied to the
+// values, so that the forward NTT adds one per butterfly or merge and the inverse NTT
+// removes one per split or inverse butterfly, and `index` is the position of the value
+// once the polynomials of the stage are concatenated, over the whole batch.
+relation!(FNTTLookupElements, 4);
+relation!(GNTTLookupElements, 4);
+relation!(ButterflyLookupElements, 4);
+relation!(MulLookupElements, 4);
+relation!(INTTLookupElements, 4);
+relation!(IButterflyLookupElements, 4);
+relation!(SubLookupElements, 4);
 relation!(RootsLookupElements, 2);
 relation!(InvRootsLookupElements, 2);
+relation!(PublicInputLookupElements, 3);
+relation!(KeccakInputLookupElements, 102);
+relation!(KeccakOutputLookupElements, 102);
+relation!(MessageBlockLookupElements, 74);
+relation!(ChunkLookupElements, 3);
+relation!(MsgPointLookupElements, 2);
+relation!(S1LookupElements, 2);
 
 enum_relation!(
     #[derive(Debug, Clone)]
@@ -44,6 +65,14 @@ impl NTTLookupElements {
     pub fn g_draw(channel: &mut impl Channel) -> Self {
         Self::G(GNTTLookupElements::draw(channel))
     }
+
+    /// Returns the identifier of the polynomial transformed with these elements.
+    pub fn poly_id(&self) -> u32 {
+        match self {
+            Self::F(_) => F_POLY_ID,
+            Self::G(_) => G_POLY_ID,
+        }
+    }
 }
 
 enum_relation!(
@@ -78,6 +107,15 @@ pub struct LookupElements {
     pub high_sig_bound_check: RCLookupElements,
     pub roots: RootsLookupElements,
     pub inv_roots: InvRootsLookupElements,
+    pub public_input: PublicInputLookupElements,
+    pub keccak_input: KeccakInputLookupElements,
+    pub keccak_output: KeccakOutputLookupElements,
+    pub message_block: MessageBlockLookupElements,
+    pub chunk: ChunkLookupElements,
+    pub msg_point: MsgPointLookupElements,
+    pub byte_range_check: RCLookupElements,
+    pub rejection_range_check: RCLookupElements,
+    pub s1: S1LookupElements,
 }
 
 impl LookupElements {
@@ -97,6 +135,15 @@ impl LookupElements {
             high_sig_bound_check: RCLookupElements::draw(channel),
             roots: RootsLookupElements::draw(channel),
             inv_roots: InvRootsLookupElements::draw(channel),
+            public_input: PublicInputLookupElements::draw(channel),
+            keccak_input: KeccakInputLookupElements::draw(channel),
+            keccak_output: KeccakOutputLookupElements::draw(channel),
+            message_block: MessageBlockLookupElements::draw(channel),
+            chunk: ChunkLookupElements::draw(channel),
+            msg_point: MsgPointLookupElements::draw(channel),
+            byte_range_check: RCLookupElements::draw(channel),
+            rejection_range_check: RCLookupElements::draw(channel),
+            s1: S1LookupElements::draw(channel),
         }
     }
 }
@@ -11,6 +11,8 @@
 //! - **Mock Commitment Scheme**: Provides a simplified commitment scheme for testing
 //! - **Component Testing**: Comprehensive testing of all proof components
 //! - **Debug Assertions**: Helper functions for development and debugging
+//! - **Witness Mutations**: Checks that the components reject mutated witnesses, see [`mutation`]
+//! - **Row Evaluation**: Evaluates a component on a trace held in memory, see [`row_evaluator`]
 //!
 //! # Usage
 //!
@@ -28,7 +30,9 @@
 //! - Checks range checking and signature bound validation
 //! - Verifies lookup relations and interaction claims
 
+pub mod mutation;
 pub mod relation_tracker;
+pub mod row_evaluator;
 
 use std::ops::Deref;
 
@@ -50,14 +54,21 @@ use stwo_constraint_framework::{
 };
 
 use crate::big_air::{
-    claim::BigClaim, interaction_claim::BigInteractionClaim, relation::LookupElements,
+    FalconInstance,
+    claim::{AllTraces, BigClaim},
+    components::BigComponents,
+    interaction_claim::BigInteractionClaim,
+    public_inputs::{batch_logup_sum, pad_batch},
+    relation::LookupElements,
 };
+use crate::encoding::decompression;
+use crate::hash_to_point::{keccak, sampler, sponge};
 use crate::ntts::{intt, ntt, roots};
 use crate::polys::sub;
 use crate::polys::{euclidean_norm, mul};
 
-use crate::zq::{Q, range_check};
-use crate::{HIGH_SIG_BOUND, LOW_SIG_BOUND, POLY_SIZE};
+use crate::CircuitParams;
+use crate::zq::range_check;
 
 /// Asserts that all constraints are satisfied for the given Falcon signature inputs.
 ///
@@ -67,9 +78,11 @@ use crate::{HIGH_SIG_BOUND, LOW_SIG_BOUND, POLY_SIZE};
 ///
 /// # Parameters
 ///
-/// - `s1`: The signature polynomial S1 with coefficients in [0, Q)
+/// - `signature`: The signature in its standard compressed encoding
 /// - `pk`: The public key polynomial with coefficients in [0, Q)
-/// - `msg_point`: The message point polynomial with coefficients in [0, Q)
+/// - `message`: The signed message
+///
+/// The degree of the polynomials is inferred from the number of coefficients of `pk`.
 ///
 /// # What This Function Tests
 ///
@@ -83,26 +96,78 @@ use crate::{HIGH_SIG_BOUND, LOW_SIG_BOUND, POLY_SIZE};
 ///
 /// This function will panic if any constraint is violated, providing detailed
 /// error information about which component failed validation.
-pub fn assert_constraints(
-    s1: &[u32; POLY_SIZE],
-    pk: &[u32; POLY_SIZE],
-    msg_point: &[u32; POLY_SIZE],
+pub fn assert_constraints(signature: &[u8], pk: &[u32], message: &[u8]) {
+    assert_constraints_batch(&[FalconInstance::new(signature, pk, message)]);
+}
+
+/// Asserts that all constraints are satisfied for a batch of Falcon signatures stacked
+/// in the same traces, padded like [`crate::big_air::prove_falcon_batch`] does.
+///
+/// # Panics
+///
+/// This function will panic if any constraint is violated.
+pub fn assert_constraints_batch(instances: &[FalconInstance]) {
+    assert_constraints_batch_with(instances, |_| {});
+}
+
+/// Asserts that all constraints are satisfied for a batch of Falcon signatures, once
+/// `tamper` has modified their main traces.
+///
+/// The committed trace and the interaction trace are both derived from the modified
+/// traces, as a malicious prover would do, so that this checks whether the AIR rejects
+/// a witness rather than whether the witness matches the honest one.
+///
+/// # Panics
+///
+/// This function will panic if any constraint is violated.
+pub fn assert_constraints_batch_with(
+    instances: &[FalconInstance],
+    tamper: impl FnOnce(&mut AllTraces),
+) {
+    let params = CircuitParams::from_poly_size(instances[0].pk.len())
+        .expect("unsupported polynomial degree");
+    assert_constraints_batch_for(params, instances, tamper);
+}
+
+/// Asserts that all constraints of the circuit of `params` are satisfied for a batch of
+/// Falcon signatures, once `tamper` has modified their main traces.
+///
+/// This is [`assert_constraints_batch_with`] for parameters whose signature bound is not
+/// the one of the degree, see [`CircuitParams::with_signature_bound`].
+///
+/// # Panics
+///
+/// This function will panic if any constraint is violated or if the squared norm of a
+/// signature exceeds the bound.
+pub fn assert_constraints_batch_for(
+    params: CircuitParams,
+    instances: &[FalconInstance],
+    tamper: impl FnOnce(&mut AllTraces),
 ) {
+    let public_inputs = instances
+        .iter()
+        .map(FalconInstance::public_inputs)
+        .collect_vec();
+    let squeeze_blocks = instances
+        .iter()
+        .map(FalconInstance::squeeze_blocks)
+        .max()
+        .unwrap();
+    let claim = BigClaim::new(params, &public_inputs, squeeze_blocks);
     let mut commitment_scheme = MockCommitmentScheme::default();
 
     // Preprocessed trace.
-    let (preprocessed_columns, preprocessed_columns_ids) =
-        crate::big_air::claim::BigClaim::create_preprocessed_columns();
+    let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
 
     let mut tree_builder = commitment_scheme.tree_builder();
     tree_builder.extend_evals(preprocessed_columns);
     tree_builder.finalize_interaction();
 
     // Generate and commit to main traces
-    let claim = BigClaim::new_standard();
-    let (trace, traces) = claim.gen_trace(s1, pk, msg_point);
+    let (_, mut traces) = claim.gen_trace(&pad_batch(instances).copied().collect_vec());
+    tamper(&mut traces);
     let mut tree_builder = commitment_scheme.tree_builder();
-    tree_builder.extend_evals(trace);
+    tree_builder.extend_evals(traces.columns());
     tree_builder.finalize_interaction();
 
     // Interaction trace.
@@ -111,7 +176,7 @@ pub fn assert_constraints(
     let lookup_elements = LookupElements::draw(&mut dummy_channel);
     let mut tree_builder = commitment_scheme.tree_builder();
     let (interaction_trace, interaction_claim) =
-        BigInteractionClaim::gen_interaction_trace(&lookup_elements, &traces);
+        BigInteractionClaim::gen_interaction_trace(&claim, &lookup_elements, &traces);
     tree_builder.extend_evals(interaction_trace);
     tree_builder.finalize_interaction();
 
@@ -147,11 +212,29 @@ pub fn assert_constraints(
         &interaction_claim,
         &mut tree_span_provider,
     );
+    let (
+        keccak_component,
+        sponge_component,
+        sampler_component,
+        byte_range_check_component,
+        rejection_range_check_component,
+    ) = BigClaim::create_hash_to_point_components(
+        &claim,
+        &lookup_elements,
+        &interaction_claim,
+        &mut tree_span_provider,
+    );
+    let decompression_component = BigClaim::create_decompression_component(
+        &claim,
+        &lookup_elements,
+        &interaction_claim,
+        &mut tree_span_provider,
+    );
 
     let components = (
         &f_ntt_butterfly_component,
         f_ntt_merges_components.as_slice(),
-        &g_ntt_butterfly_component,
+        g_ntt_butterfly_component.as_ref(),
         g_ntt_merges_components.as_slice(),
         &mul_component,
         intt_components.as_slice(),
@@ -164,16 +247,152 @@ pub fn assert_constraints(
         &range_check_component,
         roots_components.as_slice(),
         inv_roots_components.as_slice(),
+        &keccak_component,
+        &sponge_component,
+        &sampler_component,
+        &byte_range_check_component,
+        &rejection_range_check_component,
+        &decompression_component,
     );
 
     assert_components(commitment_scheme.trace_domain_evaluations(), components);
     assert_eq!(
-        interaction_claim.claimed_sum(),
+        interaction_claim.claimed_sum()
+            + batch_logup_sum(&public_inputs, &lookup_elements, squeeze_blocks),
         QM31::zero(),
         "invalid logup sum"
     );
 }
 
+/// Asserts that the constraints of the forward NTTs, the multiplication and the inverse
+/// NTT hold on the traces their generators output for `s1 * pk`, in the circuit of
+/// `instance`.
+///
+/// `s1` and `pk` can be any polynomials of the degree of `instance`: only the traces of
+/// these components are generated from them, the other ones are the traces of
+/// `instance`. The lookups the components share with the rest of the circuit, such as
+/// the range checks of their remainders, are therefore not checked. A public key of
+/// `instance` in NTT form skips the G-NTT, and the multiplication reads the NTT of `pk`.
+///
+/// # Returns
+///
+/// Returns the product s1 * pk in Z_q[x]/(x^n + 1) output by the inverse NTT.
+///
+/// # Panics
+///
+/// This function will panic if any constraint is violated, if `instance` is not a valid
+/// signature verification of a supported degree or if `s1` or `pk` does not have its
+/// degree.
+pub fn assert_transform_constraints(instance: FalconInstance, s1: &[u32], pk: &[u32]) -> Vec<u32> {
+    let params =
+        CircuitParams::from_poly_size(instance.pk.len()).expect("unsupported polynomial degree");
+    let poly_size = params.poly_size();
+    assert_eq!(s1.len(), poly_size, "s1 must have {poly_size} coefficients");
+    assert_eq!(pk.len(), poly_size, "pk must have {poly_size} coefficients");
+    let claim = BigClaim::new(
+        params,
+        &[instance.public_inputs()],
+        instance.squeeze_blocks(),
+    );
+    let mut commitment_scheme = MockCommitmentScheme::default();
+
+    let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(preprocessed_columns);
+    tree_builder.finalize_interaction();
+
+    // The traces of the transform components are replaced by the ones of s1 and pk
+    let (_, mut traces) = claim.gen_trace(&[instance]);
+    let product = gen_transform_traces(&claim, s1, pk, &mut traces);
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(traces.columns());
+    tree_builder.finalize_interaction();
+
+    let mut dummy_channel = Blake2sChannel::default();
+    let lookup_elements = LookupElements::draw(&mut dummy_channel);
+    let mut tree_builder = commitment_scheme.tree_builder();
+    let (interaction_trace, interaction_claim) =
+        BigInteractionClaim::gen_interaction_trace(&claim, &lookup_elements, &traces);
+    tree_builder.extend_evals(interaction_trace);
+    tree_builder.finalize_interaction();
+
+    let components = BigComponents::new(
+        &claim,
+        &lookup_elements,
+        &interaction_claim,
+        &preprocessed_columns_ids,
+    );
+    let trace = commitment_scheme.trace_domain_evaluations();
+    assert_component(&components.f_ntt_butterfly, &trace);
+    for merge in &components.f_ntt_merges {
+        assert_component(merge, &trace);
+    }
+    if let Some(g_ntt_butterfly) = &components.g_ntt_butterfly {
+        assert_component(g_ntt_butterfly, &trace);
+    }
+    for merge in &components.g_ntt_merges {
+        assert_component(merge, &trace);
+    }
+    assert_component(&components.mul, &trace);
+    for split in &components.intt_merges {
+        assert_component(split, &trace);
+    }
+    assert_component(&components.ibutterfly, &trace);
+    product
+}
+
+/// Generates the traces of the forward NTTs of `s1` and `pk`, of their multiplication and
+/// of the inverse NTT of the product in `traces`, like [`BigClaim::gen_trace`] does.
+///
+/// # Returns
+///
+/// Returns the product output by the inverse NTT.
+fn gen_transform_traces(
+    claim: &BigClaim,
+    s1: &[u32],
+    pk: &[u32],
+    traces: &mut AllTraces,
+) -> Vec<u32> {
+    let poly_size = claim.params.poly_size();
+    let forward = |butterfly: &ntt::butterfly::Claim, merges: &[ntt::Claim], poly: &[u32]| {
+        let (butterfly_trace, _, mut outputs) = butterfly.gen_trace(poly, poly_size);
+        let mut merge_traces = vec![];
+        for (i, merge) in merges.iter().enumerate() {
+            let (merge_trace, _, merge_outputs, _) = merge.gen_trace(&outputs, i + 1);
+            merge_traces.push(merge_trace);
+            outputs = merge_outputs;
+        }
+        (butterfly_trace, merge_traces, outputs.concat())
+    };
+
+    let (butterfly_trace, merge_traces, s1_ntt) =
+        forward(&claim.f_ntt_butterfly, &claim.f_ntt_merges, s1);
+    traces.f_ntt_butterfly = butterfly_trace;
+    traces.f_ntt_merges = merge_traces;
+    let pk_ntt = match &claim.g_ntt_butterfly {
+        Some(g_ntt_butterfly) => {
+            let (butterfly_trace, merge_traces, pk_ntt) =
+                forward(g_ntt_butterfly, &claim.g_ntt_merges, pk);
+            traces.g_ntt_butterfly = Some(butterfly_trace);
+            traces.g_ntt_merges = merge_traces;
+            pk_ntt
+        }
+        None => crate::native::ntt(pk),
+    };
+
+    let (mul_trace, mul_remainders) = claim.mul.gen_trace(&s1_ntt, &pk_ntt);
+    traces.mul = mul_trace;
+    let mut outputs = vec![mul_remainders.into_iter().map(|r| r.0).collect_vec()];
+    for (split, split_trace) in claim.intt_merges.iter().zip_eq(&mut traces.intt_merges) {
+        let (trace, _, split_outputs, _) = split.gen_trace(&outputs);
+        *split_trace = trace;
+        outputs = split_outputs;
+    }
+    let (ibutterfly_trace, _, product) = claim.ibutterfly.gen_trace(&outputs, poly_size);
+    traces.ibutterfly = ibutterfly_trace;
+    product
+}
+
 /// Mock commitment scheme for testing and debugging purposes.
 ///
 /// This struct provides a simplified commitment scheme that avoids the complexity
@@ -309,6 +528,8 @@ impl<B: BackendForChannel<MC>, MC: MerkleChannel> TreeBuilder<B>
 /// 2. **Arithmetic Components**: Multiplication, subtraction, and Euclidean norm
 /// 3. **Range Check Components**: Various range checking operations
 /// 4. **Root Components**: Precomputed roots of unity validation
+/// 5. **HashToPoint Components**: Keccak-f[1600], sponge and rejection sampling
+/// 6. **Decompression Component**: Decoding of the compressed signature
 ///
 /// # Testing Process
 ///
@@ -328,19 +549,25 @@ fn assert_components(
     components: (
         &FrameworkComponent<ntt::butterfly::Eval>,
         &[FrameworkComponent<ntt::Eval>],
-        &FrameworkComponent<ntt::butterfly::Eval>,
+        Option<&FrameworkComponent<ntt::butterfly::Eval>>,
         &[FrameworkComponent<ntt::Eval>],
         &FrameworkComponent<mul::Eval>,
         &[FrameworkComponent<intt::Eval>],
         &FrameworkComponent<intt::ibutterfly::Eval>,
         &FrameworkComponent<sub::Eval>,
         &FrameworkComponent<euclidean_norm::Eval>,
-        &FrameworkComponent<range_check::Eval<{ Q / 2 }>>,
-        &FrameworkComponent<range_check::Eval<LOW_SIG_BOUND>>,
-        &FrameworkComponent<range_check::Eval<HIGH_SIG_BOUND>>,
-        &FrameworkComponent<range_check::Eval<Q>>,
+        &FrameworkComponent<range_check::Eval>,
+        &FrameworkComponent<range_check::Eval>,
+        &FrameworkComponent<range_check::Eval>,
+        &FrameworkComponent<range_check::Eval>,
         &[FrameworkComponent<roots::preprocessed::Eval>],
         &[FrameworkComponent<roots::inv_preprocessed::Eval>],
+        &FrameworkComponent<keccak::Eval>,
+        &FrameworkComponent<sponge::Eval>,
+        &FrameworkComponent<sampler::Eval>,
+        &FrameworkComponent<range_check::Eval>,
+        &FrameworkComponent<range_check::Eval>,
+        &FrameworkComponent<decompression::Eval>,
     ),
 ) {
     let (
@@ -359,6 +586,12 @@ fn assert_components(
         range_check,
         roots,
         inv_roots,
+        keccak,
+        sponge,
+        sampler,
+        byte_range_check,
+        rejection_range_check,
+        decompression,
     ) = components;
     // Test forward NTT butterfly operations for F polynomial
     println!("f_ntt_butterfly");
@@ -372,8 +605,10 @@ fn assert_components(
     }
 
     // Test forward NTT butterfly operations for G polynomial
-    println!("g_ntt_butterfly");
-    assert_component(g_ntt_butterfly, &trace);
+    if let Some(g_ntt_butterfly) = g_ntt_butterfly {
+        println!("g_ntt_butterfly");
+        assert_component(g_ntt_butterfly, &trace);
+    }
 
     // Test forward NTT merge operations for G polynomial
     println!("g_ntt_merges");
@@ -434,6 +669,30 @@ fn assert_components(
         println!("inv_root {}", i);
         assert_component(inv_root, &trace);
     }
+
+    // Test the Keccak-f[1600] rounds of HashToPoint
+    println!("keccak");
+    assert_component(keccak, &trace);
+
+    // Test the SHAKE256 sponge steps
+    println!("sponge");
+    assert_component(sponge, &trace);
+
+    // Test the rejection sampling of the message point
+    println!("sampler");
+    assert_component(sampler, &trace);
+
+    // Test byte range checking (0 to 256)
+    println!("byte_range_check");
+    assert_component(byte_range_check, &trace);
+
+    // Test rejected chunks range checking (0 to 4096)
+    println!("rejection_range_check");
+    assert_component(rejection_range_check, &trace);
+
+    // Test the decompression of the signatures
+    println!("decompression");
+    assert_component(decompression, &trace);
 }
 
 /// Asserts that a single proof component satisfies all its constraints.
@@ -0,0 +1,668 @@
+//! # Witness Mutations
+//!
+//! This module checks the soundness of the Big AIR and key AIR components against
+//! adversarial witnesses: it mutates every prover-controlled column of a component, one
+//! cell at a time, and checks that the component rejects the mutated trace.
+//!
+//! # Mutations
+//!
+//! - **Increment**: Adds 1 to a cell, changing a quotient or a borrow
+//! - **ShiftByQ**: Adds q to a cell, shifting a remainder by the modulus
+//! - **FlipBit**: Replaces a cell `v` with `1 - v`, flipping a selector
+//! - **SwapRows**: Swaps a cell with the cell of another row holding a different value
+//!
+//! Every mutation is applied to a sample of rows of each column, see [`SampledRow`]: the
+//! first and last rows, where the transition constraints wrap around, a row drawn at
+//! random in between and, for the components whose traces are padded, the first padding
+//! row. A mutation that leaves the column unchanged is recorded as skipped.
+//!
+//! A mutation is caught if a constraint of the component no longer vanishes or if the
+//! sum of its logup fractions changes: the other components read the same entries as
+//! in the honest trace, so the global logup sum is then non-zero. The component is
+//! evaluated on the mutated trace with the lookup elements of the honest one, as a
+//! malicious prover cannot choose them. The components read their neighbour rows at
+//! offsets -1 and 1 at most, so only the rows next to a mutated cell are evaluated again;
+//! a constraint reaching further would leave the mutation unbroken in the report rather
+//! than hide it.
+//!
+//! Every component of both AIRs is covered: [`mutate_components`] and
+//! [`mutate_key_components`] check that none is left out.
+
+use std::fmt;
+
+use itertools::Itertools;
+use num_traits::{One, Zero};
+use stwo::core::channel::{Blake2sChannel, Channel};
+use stwo::core::fields::FieldExpOps;
+use stwo::core::fields::m31::M31;
+use stwo::core::fields::qm31::SecureField;
+use stwo::core::lookups::utils::Fraction;
+use stwo::core::pcs::TreeVec;
+use stwo::core::utils::{
+    bit_reverse_index, coset_index_to_circle_domain_index, offset_bit_reversed_circle_domain_index,
+};
+use stwo_constraint_framework::{FrameworkComponent, FrameworkEval, ORIGINAL_TRACE_IDX};
+
+use crate::CircuitParams;
+use crate::big_air::{
+    FalconInstance, claim::BigClaim, components::BigComponents,
+    interaction_claim::BigInteractionClaim, public_inputs::pad_batch, relation::LookupElements,
+};
+use crate::debug::{
+    MockCommitmentScheme,
+    row_evaluator::{evaluate_row, evaluate_rows},
+};
+use crate::key_air::{
+    KeyInstance, claim::KeyClaim, components::KeyComponents,
+    interaction_claim::KeyInteractionClaim, relation::KeyLookupElements,
+};
+use crate::zq::Q;
+
+/// A mutation of one cell of a main trace column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// Adds 1 to the cell
+    Increment,
+    /// Adds q to the cell
+    ShiftByQ,
+    /// Replaces the cell `v` with `1 - v`
+    FlipBit,
+    /// Swaps the cell with the next one holding a different value
+    SwapRows,
+}
+
+impl Mutation {
+    /// All the mutations, in the order they are applied.
+    pub const ALL: [Self; 4] = [
+        Self::Increment,
+        Self::ShiftByQ,
+        Self::FlipBit,
+        Self::SwapRows,
+    ];
+
+    /// Applies the mutation to the cell of `column` at `row`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the mutated column, or `None` if the mutation leaves it unchanged.
+    pub fn apply(self, column: &[M31], row: usize) -> Option<Vec<M31>> {
+        let mut mutated = column.to_vec();
+        match self {
+            Self::Increment => mutated[row] += M31::one(),
+            Self::ShiftByQ => mutated[row] += M31(Q),
+            Self::FlipBit => mutated[row] = M31::one() - mutated[row],
+            Self::SwapRows => {
+                let other = (row + 1..column.len())
+                    .chain(0..row)
+                    .find(|other| column[*other] != column[row])?;
+                mutated.swap(row, other);
+            }
+        }
+        (mutated != column).then_some(mutated)
+    }
+}
+
+/// A row of a column that the mutations are applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampledRow {
+    /// The first row of the trace
+    First,
+    /// The last row of the trace
+    Last,
+    /// A row drawn at random between the first and the last ones
+    Interior,
+    /// The first row after the real rows of a padded trace
+    Padding,
+}
+
+/// How a component reacts to a mutated trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationOutcome {
+    /// A constraint does not vanish on some row
+    ConstraintViolated,
+    /// The constraints hold but the logup sum of the component changed
+    LogupSumChanged,
+    /// The component accepts the mutated trace with the honest logup sum
+    Unbroken,
+    /// The mutation leaves the column unchanged, e.g. a swap in a constant column
+    Skipped,
+}
+
+/// The outcome of a mutation of one column of a component.
+#[derive(Debug, Clone)]
+pub struct MutationResult {
+    pub component: String,
+    /// The index of the column among the main columns of the component
+    pub column: usize,
+    pub mutation: Mutation,
+    pub sample: SampledRow,
+    /// The mutated row, in coset order
+    pub row: usize,
+    pub outcome: MutationOutcome,
+}
+
+/// The outcomes of all the mutations applied to the components.
+#[derive(Debug, Clone, Default)]
+pub struct MutationReport {
+    pub results: Vec<MutationResult>,
+}
+
+impl MutationReport {
+    /// Returns the mutations that no component rejects.
+    pub fn unbroken(&self) -> impl Iterator<Item = &MutationResult> {
+        self.results
+            .iter()
+            .filter(|result| result.outcome == MutationOutcome::Unbroken)
+    }
+
+    /// Returns the mutations that were not applied, as they leave their column unchanged.
+    pub fn skipped(&self) -> impl Iterator<Item = &MutationResult> {
+        self.results
+            .iter()
+            .filter(|result| result.outcome == MutationOutcome::Skipped)
+    }
+
+    /// Returns the names of the mutated components, in the order they were mutated.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.results
+            .iter()
+            .map(|result| result.component.as_str())
+            .dedup()
+    }
+
+    /// Applies every mutation to a sample of rows of every main column of `component` and
+    /// records the outcomes.
+    ///
+    /// The sampled rows are the first and last rows, a row drawn at random in between and
+    /// `padding_row`, the first row in coset order after the real rows of the component if
+    /// its trace is padded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component rejects the honest trace.
+    pub fn mutate<E: FrameworkEval>(
+        &mut self,
+        name: &str,
+        component: &FrameworkComponent<E>,
+        trace: &TreeVec<Vec<&Vec<M31>>>,
+        padding_row: Option<usize>,
+    ) {
+        let log_size = component.log_size();
+        let (rows, n_violated_rows) = evaluate_rows(component, trace);
+        assert_eq!(n_violated_rows, 0, "{name} rejects the honest trace");
+
+        let location = component
+            .trace_locations()
+            .iter()
+            .find(|location| location.tree_index == ORIGINAL_TRACE_IDX)
+            .unwrap();
+        for column in location.col_start..location.col_end {
+            let values = trace[ORIGINAL_TRACE_IDX][column];
+            let samples = sampled_rows(name, column - location.col_start, log_size, padding_row);
+            for ((sample, row), mutation) in samples.into_iter().cartesian_product(Mutation::ALL) {
+                let outcome = match mutation.apply(values, trace_index(row, log_size)) {
+                    None => MutationOutcome::Skipped,
+                    Some(mutated) => {
+                        // Only the rows reading a mutated cell, at offsets -1, 0 or 1, change
+                        let evaluated_rows = (0..values.len())
+                            .filter(|index| mutated[*index] != values[*index])
+                            .flat_map(|index| {
+                                [-1, 0, 1].map(|offset| {
+                                    offset_bit_reversed_circle_domain_index(
+                                        index, log_size, log_size, offset,
+                                    )
+                                })
+                            })
+                            .unique()
+                            .collect_vec();
+                        let mut mutated_trace = trace.clone();
+                        mutated_trace[ORIGINAL_TRACE_IDX][column] = &mutated;
+
+                        let evaluations = evaluated_rows
+                            .iter()
+                            .map(|index| evaluate_row(component, &mutated_trace, *index))
+                            .collect_vec();
+                        let honest_sum =
+                            logup_sum(evaluated_rows.iter().map(|index| &rows[*index]));
+                        let mutated_sum =
+                            logup_sum(evaluations.iter().map(|(fractions, _)| fractions));
+                        if evaluations.iter().any(|(_, is_violated)| *is_violated) {
+                            MutationOutcome::ConstraintViolated
+                        } else if mutated_sum != honest_sum {
+                            MutationOutcome::LogupSumChanged
+                        } else {
+                            MutationOutcome::Unbroken
+                        }
+                    }
+                };
+                self.results.push(MutationResult {
+                    component: name.to_string(),
+                    column: column - location.col_start,
+                    mutation,
+                    sample,
+                    row,
+                    outcome,
+                });
+            }
+        }
+    }
+}
+
+/// Prints the coverage of the mutations, component by component, followed by the
+/// columns that a mutation fails to break.
+impl fmt::Display for MutationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (component, results) in &self.results.iter().chunk_by(|result| &result.component) {
+            let results = results.collect_vec();
+            let count = |outcome| {
+                results
+                    .iter()
+                    .filter(|result| result.outcome == outcome)
+                    .count()
+            };
+            writeln!(
+                f,
+                "{component}: {} mutations, {} violate a constraint, {} change the logup sum, {} skipped, {} unbroken",
+                results.len(),
+                count(MutationOutcome::ConstraintViolated),
+                count(MutationOutcome::LogupSumChanged),
+                count(MutationOutcome::Skipped),
+                count(MutationOutcome::Unbroken),
+            )?;
+        }
+        for result in self.unbroken() {
+            writeln!(
+                f,
+                "unbroken: {} column {} by {:?} at the {:?} row {}",
+                result.component, result.column, result.mutation, result.sample, result.row
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Applies every mutation to every column of the Big AIR components for a batch of Falcon
+/// signatures, stacked in the same traces like [`crate::big_air::prove_falcon_batch`]
+/// does.
+///
+/// The per-signature components are padded with copies of the last instance if the batch
+/// size is not a power of two, and the HashToPoint components with disabled rows.
+///
+/// # Panics
+///
+/// Panics if the degree of the public keys is not supported, if a component rejects the
+/// honest trace or if a component is not mutated.
+pub fn mutate_components(instances: &[FalconInstance]) -> MutationReport {
+    let params = CircuitParams::from_poly_size(instances[0].pk.len())
+        .expect("unsupported polynomial degree");
+    let public_inputs = instances
+        .iter()
+        .map(FalconInstance::public_inputs)
+        .collect_vec();
+    let squeeze_blocks = instances
+        .iter()
+        .map(FalconInstance::squeeze_blocks)
+        .max()
+        .unwrap();
+    let claim = BigClaim::new(params, &public_inputs, squeeze_blocks);
+    let mut commitment_scheme = MockCommitmentScheme::default();
+
+    let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(preprocessed_columns);
+    tree_builder.finalize_interaction();
+
+    let (_, traces) = claim.gen_trace(&pad_batch(instances).copied().collect_vec());
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(traces.columns());
+    tree_builder.finalize_interaction();
+
+    let mut dummy_channel = Blake2sChannel::default();
+    let lookup_elements = LookupElements::draw(&mut dummy_channel);
+    let (_, interaction_claim) =
+        BigInteractionClaim::gen_interaction_trace(&claim, &lookup_elements, &traces);
+    let components = BigComponents::new(
+        &claim,
+        &lookup_elements,
+        &interaction_claim,
+        &preprocessed_columns_ids,
+    );
+    let trace = commitment_scheme.trace_domain_evaluations();
+
+    // The padding instances follow the real ones in every per-signature trace
+    let n_padded_instances = 1 << claim.log_n_instances();
+    let instance_padding_row = |log_size: u32| {
+        (instances.len() < n_padded_instances)
+            .then(|| (instances.len() << log_size) / n_padded_instances)
+    };
+    let sampler_padding_row = Some(instances.len() * claim.sampler.n_chunks())
+        .filter(|row| *row < 1 << components.sampler.log_size());
+
+    let mut report = MutationReport::default();
+    let f_ntt_butterfly = &components.f_ntt_butterfly;
+    report.mutate(
+        "f_ntt_butterfly",
+        f_ntt_butterfly,
+        &trace,
+        instance_padding_row(f_ntt_butterfly.log_size()),
+    );
+    for (i, merge) in components.f_ntt_merges.iter().enumerate() {
+        report.mutate(
+            &format!("f_ntt_merge {i}"),
+            merge,
+            &trace,
+            instance_padding_row(merge.log_size()),
+        );
+    }
+    if let Some(g_ntt_butterfly) = &components.g_ntt_butterfly {
+        report.mutate(
+            "g_ntt_butterfly",
+            g_ntt_butterfly,
+            &trace,
+            instance_padding_row(g_ntt_butterfly.log_size()),
+        );
+    }
+    for (i, merge) in components.g_ntt_merges.iter().enumerate() {
+        report.mutate(
+            &format!("g_ntt_merge {i}"),
+            merge,
+            &trace,
+            instance_padding_row(merge.log_size()),
+        );
+    }
+    report.mutate(
+        "mul",
+        &components.mul,
+        &trace,
+        instance_padding_row(components.mul.log_size()),
+    );
+    for (i, split) in components.intt_merges.iter().enumerate() {
+        report.mutate(
+            &format!("intt_split {i}"),
+            split,
+            &trace,
+            instance_padding_row(split.log_size()),
+        );
+    }
+    report.mutate(
+        "ibutterfly",
+        &components.ibutterfly,
+        &trace,
+        instance_padding_row(components.ibutterfly.log_size()),
+    );
+    report.mutate(
+        "sub",
+        &components.sub,
+        &trace,
+        instance_padding_row(components.sub.log_size()),
+    );
+    report.mutate(
+        "euclidean_norm",
+        &components.euclidean_norm,
+        &trace,
+        instance_padding_row(components.euclidean_norm.log_size()),
+    );
+    // The range checks and the roots are tables, with no padding rows
+    report.mutate(
+        "half_range_check",
+        &components.half_range_check,
+        &trace,
+        None,
+    );
+    report.mutate(
+        "low_sig_bound_check",
+        &components.low_sig_bound_check,
+        &trace,
+        None,
+    );
+    report.mutate(
+        "high_sig_bound_check",
+        &components.high_sig_bound_check,
+        &trace,
+        None,
+    );
+    report.mutate("range_check", &components.range_check, &trace, None);
+    for (i, roots) in components.roots.iter().enumerate() {
+        report.mutate(&format!("roots {i}"), roots, &trace, None);
+    }
+    for (i, inv_roots) in components.inv_roots.iter().enumerate() {
+        report.mutate(&format!("inv_roots {i}"), inv_roots, &trace, None);
+    }
+    report.mutate(
+        "keccak",
+        &components.keccak,
+        &trace,
+        first_disabled_row(&components.keccak, &trace),
+    );
+    report.mutate(
+        "sponge",
+        &components.sponge,
+        &trace,
+        first_disabled_row(&components.sponge, &trace),
+    );
+    report.mutate("sampler", &components.sampler, &trace, sampler_padding_row);
+    report.mutate(
+        "byte_range_check",
+        &components.byte_range_check,
+        &trace,
+        None,
+    );
+    report.mutate(
+        "rejection_range_check",
+        &components.rejection_range_check,
+        &trace,
+        None,
+    );
+    report.mutate(
+        "decompression",
+        &components.decompression,
+        &trace,
+        instance_padding_row(components.decompression.log_size()),
+    );
+    assert_eq!(
+        report.components().count(),
+        components.components().len(),
+        "a component is not mutated"
+    );
+    report
+}
+
+/// Applies every mutation to every column of the key AIR components for a signing key.
+///
+/// # Panics
+///
+/// Panics if the degree of the public key is not supported, if a component rejects the
+/// honest trace or if a component is not mutated.
+pub fn mutate_key_components(instance: &KeyInstance) -> MutationReport {
+    let params =
+        CircuitParams::from_poly_size(instance.pk.len()).expect("unsupported polynomial degree");
+    let claim = KeyClaim::new(params);
+    let mut commitment_scheme = MockCommitmentScheme::default();
+
+    let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(preprocessed_columns);
+    tree_builder.finalize_interaction();
+
+    let (trace, traces) = claim.gen_trace(&instance.f_mod_q(), instance.pk);
+    let mut tree_builder = commitment_scheme.tree_builder();
+    tree_builder.extend_evals(trace);
+    tree_builder.finalize_interaction();
+
+    let mut dummy_channel = Blake2sChannel::default();
+    let lookup_elements = KeyLookupElements::draw(&mut dummy_channel);
+    let (_, interaction_claim) =
+        KeyInteractionClaim::gen_interaction_trace(&claim, &lookup_elements, &traces);
+    let components = KeyComponents::new(
+        &claim,
+        &lookup_elements,
+        &interaction_claim,
+        &preprocessed_columns_ids,
+    );
+    let trace = commitment_scheme.trace_domain_evaluations();
+
+    // A single key fills every trace, so no component has padding rows
+    let mut report = MutationReport::default();
+    report.mutate("f_ntt_butterfly", &components.f_ntt_butterfly, &trace, None);
+    for (i, merge) in components.f_ntt_merges.iter().enumerate() {
+        report.mutate(&format!("f_ntt_merge {i}"), merge, &trace, None);
+    }
+    report.mutate("h_ntt_butterfly", &components.h_ntt_butterfly, &trace, None);
+    for (i, merge) in components.h_ntt_merges.iter().enumerate() {
+        report.mutate(&format!("h_ntt_merge {i}"), merge, &trace, None);
+    }
+    report.mutate("invertibility", &components.invertibility, &trace, None);
+    report.mutate("mul", &components.mul, &trace, None);
+    for (i, split) in components.intt_merges.iter().enumerate() {
+        report.mutate(&format!("intt_split {i}"), split, &trace, None);
+    }
+    report.mutate("ibutterfly", &components.ibutterfly, &trace, None);
+    report.mutate("sub", &components.sub, &trace, None);
+    report.mutate("norm", &components.norm, &trace, None);
+    report.mutate(
+        "coeff_range_check",
+        &components.coeff_range_check,
+        &trace,
+        None,
+    );
+    report.mutate("key_bound_check", &components.key_bound_check, &trace, None);
+    report.mutate("range_check", &components.range_check, &trace, None);
+    for (i, roots) in components.roots.iter().enumerate() {
+        report.mutate(&format!("roots {i}"), roots, &trace, None);
+    }
+    for (i, inv_roots) in components.inv_roots.iter().enumerate() {
+        report.mutate(&format!("inv_roots {i}"), inv_roots, &trace, None);
+    }
+    assert_eq!(
+        report.components().count(),
+        components.components().len(),
+        "a component is not mutated"
+    );
+    report
+}
+
+/// Returns the rows of a column of a component of `2^log_size` rows that the mutations
+/// are applied to, in coset order.
+///
+/// The interior row is drawn from a channel seeded with the component and the column, so
+/// that the report can be reproduced.
+fn sampled_rows(
+    name: &str,
+    column: usize,
+    log_size: u32,
+    padding_row: Option<usize>,
+) -> Vec<(SampledRow, usize)> {
+    let n_rows = 1 << log_size;
+    let mut channel = Blake2sChannel::default();
+    channel.mix_u32s(&name.bytes().map(u32::from).collect_vec());
+    channel.mix_u64(column as u64);
+    let interior = 1 + channel.draw_secure_felt().to_m31_array()[0].0 as usize % (n_rows - 2);
+    [
+        (SampledRow::First, 0),
+        (SampledRow::Last, n_rows - 1),
+        (SampledRow::Interior, interior),
+    ]
+    .into_iter()
+    .chain(padding_row.map(|row| (SampledRow::Padding, row)))
+    .collect_vec()
+}
+
+/// Returns the index in bit-reversed circle domain order of the row `row` in coset
+/// order of a trace of `2^log_size` rows.
+fn trace_index(row: usize, log_size: u32) -> usize {
+    bit_reverse_index(coset_index_to_circle_domain_index(row, log_size), log_size)
+}
+
+/// Returns the first row in coset order where the enabler of a HashToPoint component, its
+/// first main column, is zero.
+fn first_disabled_row<E: FrameworkEval>(
+    component: &FrameworkComponent<E>,
+    trace: &TreeVec<Vec<&Vec<M31>>>,
+) -> Option<usize> {
+    let log_size = component.log_size();
+    let location = component
+        .trace_locations()
+        .iter()
+        .find(|location| location.tree_index == ORIGINAL_TRACE_IDX)
+        .unwrap();
+    let enabler = trace[ORIGINAL_TRACE_IDX][location.col_start];
+    (0..1 << log_size).find(|row| enabler[trace_index(*row, log_size)].is_zero())
+}
+
+/// Returns the sum of the logup fractions of some rows of a component.
+fn logup_sum<'a>(
+    rows: impl IntoIterator<Item = &'a Vec<Fraction<SecureField, SecureField>>>,
+) -> SecureField {
+    rows.into_iter()
+        .flatten()
+        .map(|fraction| fraction.numerator * fraction.denominator.inverse())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutation_apply() {
+        let column = [0, 1, 1, 5].map(M31).to_vec();
+
+        assert_eq!(
+            Mutation::Increment.apply(&column, 1),
+            Some([0, 2, 1, 5].map(M31).to_vec())
+        );
+        assert_eq!(
+            Mutation::ShiftByQ.apply(&column, 3),
+            Some([0, 1, 1, 5 + Q].map(M31).to_vec())
+        );
+        assert_eq!(
+            Mutation::FlipBit.apply(&column, 0),
+            Some([1, 1, 1, 5].map(M31).to_vec())
+        );
+        assert_eq!(
+            Mutation::SwapRows.apply(&column, 1),
+            Some([0, 1, 5, 1].map(M31).to_vec())
+        );
+        assert_eq!(Mutation::SwapRows.apply(&[M31(3); 4], 1), None);
+    }
+
+    #[test]
+    fn test_mutations_break_every_column() {
+        use crate::input::falcon_512;
+
+        // Three signatures are padded with a fourth one, so the padding rows are sampled
+        let instance =
+            FalconInstance::new(falcon_512::SIGNATURE, falcon_512::PK, falcon_512::MESSAGE);
+        let report = mutate_components(&[instance; 3]);
+
+        assert!(
+            report
+                .results
+                .iter()
+                .any(|result| result.sample == SampledRow::Padding)
+        );
+        assert_eq!(
+            report.unbroken().count(),
+            0,
+            "unbroken mutations:\n{report}"
+        );
+    }
+
+    #[test]
+    fn test_mutations_break_every_key_column() {
+        use crate::input::{F, G, PK};
+
+        let report = mutate_key_components(&KeyInstance::new(PK, F, G));
+
+        assert!(
+            report
+                .components()
+                .any(|component| component == "invertibility")
+        );
+        assert_eq!(
+            report.unbroken().count(),
+            0,
+            "unbroken mutations:\n{report}"
+        );
+    }
+}
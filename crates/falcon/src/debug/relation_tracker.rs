@@ -19,22 +19,21 @@
 //!
 //! This module is used to ensure that all lookup relations are properly balanced
 //! and that the proof system maintains soundness across all components.
+use std::collections::HashMap;
+
 use itertools::Itertools;
+use stwo::core::channel::MerkleChannel;
 use stwo::core::fields::m31::M31;
 use stwo::core::pcs::TreeVec;
 use stwo::core::poly::circle::CanonicCoset;
-use stwo::core::vcs::blake2_merkle::Blake2sMerkleChannel;
 use stwo::prover::CommitmentSchemeProver;
-use stwo::prover::backend::Column;
-use stwo::prover::backend::simd::SimdBackend;
+use stwo::prover::backend::{BackendForChannel, Column};
 
 use stwo_constraint_framework::FrameworkComponent;
 use stwo_constraint_framework::relation_tracker::{
     RelationSummary, RelationTrackerEntry, add_to_relation_entries,
 };
 
-use crate::{HIGH_SIG_BOUND, LOW_SIG_BOUND, zq::Q};
-
 /// Groups all framework components exposed by the Big AIR system.
 ///
 /// This struct provides a comprehensive view of all proof components
@@ -63,19 +62,25 @@ use crate::{HIGH_SIG_BOUND, LOW_SIG_BOUND, zq::Q};
 pub struct BigAirComponents<'a> {
     pub f_ntt_butterfly: &'a FrameworkComponent<crate::ntts::ntt::butterfly::Eval>,
     pub f_ntt_merges: &'a [FrameworkComponent<crate::ntts::ntt::Eval>],
-    pub g_ntt_butterfly: &'a FrameworkComponent<crate::ntts::ntt::butterfly::Eval>,
+    pub g_ntt_butterfly: Option<&'a FrameworkComponent<crate::ntts::ntt::butterfly::Eval>>,
     pub g_ntt_merges: &'a [FrameworkComponent<crate::ntts::ntt::Eval>],
     pub mul: &'a FrameworkComponent<crate::polys::mul::Eval>,
     pub intt_merges: &'a [FrameworkComponent<crate::ntts::intt::Eval>],
     pub ibutterfly: &'a FrameworkComponent<crate::ntts::intt::ibutterfly::Eval>,
     pub sub: &'a FrameworkComponent<crate::polys::sub::Eval>,
     pub euclidean_norm: &'a FrameworkComponent<crate::polys::euclidean_norm::Eval>,
-    pub half_range_check: &'a FrameworkComponent<crate::zq::range_check::Eval<{ Q / 2 }>>,
-    pub low_sig_bound_check: &'a FrameworkComponent<crate::zq::range_check::Eval<LOW_SIG_BOUND>>,
-    pub high_sig_bound_check: &'a FrameworkComponent<crate::zq::range_check::Eval<HIGH_SIG_BOUND>>,
-    pub range_check: &'a FrameworkComponent<crate::zq::range_check::Eval<Q>>,
+    pub half_range_check: &'a FrameworkComponent<crate::zq::range_check::Eval>,
+    pub low_sig_bound_check: &'a FrameworkComponent<crate::zq::range_check::Eval>,
+    pub high_sig_bound_check: &'a FrameworkComponent<crate::zq::range_check::Eval>,
+    pub range_check: &'a FrameworkComponent<crate::zq::range_check::Eval>,
     pub roots: &'a [FrameworkComponent<crate::ntts::roots::preprocessed::Eval>],
     pub inv_roots: &'a [FrameworkComponent<crate::ntts::roots::inv_preprocessed::Eval>],
+    pub keccak: &'a FrameworkComponent<crate::hash_to_point::keccak::Eval>,
+    pub sponge: &'a FrameworkComponent<crate::hash_to_point::sponge::Eval>,
+    pub sampler: &'a FrameworkComponent<crate::hash_to_point::sampler::Eval>,
+    pub byte_range_check: &'a FrameworkComponent<crate::zq::range_check::Eval>,
+    pub rejection_range_check: &'a FrameworkComponent<crate::zq::range_check::Eval>,
+    pub decompression: &'a FrameworkComponent<crate::encoding::decompression::Eval>,
 }
 
 /// Evaluates the committed trace on the circle domain and summarizes relation entries.
@@ -105,14 +110,37 @@ pub struct BigAirComponents<'a> {
 ///
 /// This function is intentionally slow as it evaluates each committed polynomial
 /// over its full circle domain for comprehensive analysis.
-pub fn track_and_summarize_big_air_relations(
-    commitment_scheme: &CommitmentSchemeProver<'_, SimdBackend, Blake2sMerkleChannel>,
+pub fn track_and_summarize_big_air_relations<B, MC>(
+    commitment_scheme: &CommitmentSchemeProver<'_, B, MC>,
     components: &BigAirComponents,
-) -> RelationSummary {
+) -> RelationSummary
+where
+    B: BackendForChannel<MC>,
+    MC: MerkleChannel,
+{
     let entries = track_big_air_relations(commitment_scheme, components);
     RelationSummary::summarize_relations(&entries).cleaned()
 }
 
+/// Returns the names of the relations whose entries do not cancel out, sorted.
+///
+/// The multiplicities of the entries are summed per relation and per value. Relations
+/// with entries consumed from the public inputs never cancel out within the trace, since
+/// the verifier adds these entries back itself.
+pub fn unbalanced_relations(entries: &[RelationTrackerEntry]) -> Vec<String> {
+    let mut sums: HashMap<(&str, Vec<u32>), M31> = HashMap::new();
+    for entry in entries {
+        let values = entry.values.iter().map(|value| value.0).collect_vec();
+        *sums.entry((entry.relation.as_str(), values)).or_default() += entry.mult;
+    }
+    sums.into_iter()
+        .filter(|(_, mult)| *mult != M31(0))
+        .map(|((relation, _), _)| relation.to_string())
+        .sorted()
+        .dedup()
+        .collect()
+}
+
 /// Evaluates the committed trace and returns raw relation entries for detailed inspection.
 ///
 /// This function is similar to `track_and_summarize_big_air_relations` but returns
@@ -135,10 +163,14 @@ pub fn track_and_summarize_big_air_relations(
 /// - **Row-by-Row Analysis**: Analyze specific trace rows
 /// - **Custom Summarization**: Create custom relation summaries
 /// - **Verification**: Validate specific component behavior
-pub fn track_big_air_relations(
-    commitment_scheme: &CommitmentSchemeProver<'_, SimdBackend, Blake2sMerkleChannel>,
+pub fn track_big_air_relations<B, MC>(
+    commitment_scheme: &CommitmentSchemeProver<'_, B, MC>,
     components: &BigAirComponents,
-) -> Vec<RelationTrackerEntry> {
+) -> Vec<RelationTrackerEntry>
+where
+    B: BackendForChannel<MC>,
+    MC: MerkleChannel,
+{
     // ⚠️ This is intentionally slow — it evaluates each committed poly over its circle domain.
     // This comprehensive evaluation is necessary for accurate relation tracking and validation.
     let evals = commitment_scheme.trace().polys.map(|tree| {
@@ -182,6 +214,7 @@ pub fn track_big_air_relations(
 /// - Arithmetic operations (multiplication, subtraction, Euclidean norm)
 /// - Range checking operations
 /// - Root of unity validation
+/// - HashToPoint (Keccak-f[1600], sponge and rejection sampling)
 fn big_air_relation_entries(
     components: &BigAirComponents,
     trace: &TreeVec<Vec<&Vec<M31>>>,
@@ -198,7 +231,9 @@ fn big_air_relation_entries(
     }
 
     // Collect relation entries from forward NTT butterfly operations for G polynomial
-    entries.extend(add_to_relation_entries(components.g_ntt_butterfly, trace));
+    for butterfly in components.g_ntt_butterfly {
+        entries.extend(add_to_relation_entries(butterfly, trace));
+    }
 
     // Collect relation entries from forward NTT merge operations for G polynomial
     for merge in components.g_ntt_merges.iter() {
@@ -250,6 +285,20 @@ fn big_air_relation_entries(
         entries.extend(add_to_relation_entries(inv_root, trace));
     }
 
+    // Collect relation entries from HashToPoint: the Keccak-f[1600] rounds, the sponge
+    // steps, the rejection sampling and their range checks
+    entries.extend(add_to_relation_entries(components.keccak, trace));
+    entries.extend(add_to_relation_entries(components.sponge, trace));
+    entries.extend(add_to_relation_entries(components.sampler, trace));
+    entries.extend(add_to_relation_entries(components.byte_range_check, trace));
+    entries.extend(add_to_relation_entries(
+        components.rejection_range_check,
+        trace,
+    ));
+
+    // Collect relation entries from the signature decompression
+    entries.extend(add_to_relation_entries(components.decompression, trace));
+
     // Return all collected relation entries for analysis and validation
     entries
 }
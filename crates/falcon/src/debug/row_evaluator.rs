@@ -0,0 +1,135 @@
+//! # Row Evaluator
+//!
+//! This module evaluates a framework component row by row on a trace held in memory,
+//! collecting the logup fractions of every row and the rows where a constraint does not
+//! vanish. The witness mutations and the soundness tests of single components use it to
+//! check a component against a tampered trace without running the prover.
+
+use itertools::Itertools;
+use num_traits::Zero;
+use stwo::core::{
+    fields::{
+        m31::M31,
+        qm31::{SECURE_EXTENSION_DEGREE, SecureField},
+    },
+    lookups::utils::Fraction,
+    pcs::TreeVec,
+    utils::offset_bit_reversed_circle_domain_index,
+};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, ORIGINAL_TRACE_IDX, PREPROCESSED_TRACE_IDX,
+};
+
+/// Evaluates a component on every row of `trace`, holding the preprocessed and main trees
+/// in bit-reversed circle domain order.
+///
+/// # Returns
+///
+/// Returns the fractions of every row and the number of rows where a constraint does not
+/// vanish.
+pub(crate) fn evaluate_rows<E: FrameworkEval>(
+    component: &FrameworkComponent<E>,
+    trace: &TreeVec<Vec<&Vec<M31>>>,
+) -> (Vec<Vec<Fraction<SecureField, SecureField>>>, usize) {
+    let mut n_violated_rows = 0;
+    let rows = (0..1 << component.log_size())
+        .map(|row| {
+            let (fractions, is_violated) = evaluate_row(component, trace, row);
+            n_violated_rows += is_violated as usize;
+            fractions
+        })
+        .collect_vec();
+    (rows, n_violated_rows)
+}
+
+/// Evaluates a component on one row of `trace`, laid out like in [`evaluate_rows`].
+///
+/// `row` is in bit-reversed circle domain order.
+///
+/// # Returns
+///
+/// Returns the fractions of the row and whether a constraint does not vanish on it.
+pub(crate) fn evaluate_row<E: FrameworkEval>(
+    component: &FrameworkComponent<E>,
+    trace: &TreeVec<Vec<&Vec<M31>>>,
+    row: usize,
+) -> (Vec<Fraction<SecureField, SecureField>>, bool) {
+    let main_location = component
+        .trace_locations()
+        .iter()
+        .find(|location| location.tree_index == ORIGINAL_TRACE_IDX)
+        .unwrap();
+    let main = &trace[ORIGINAL_TRACE_IDX][main_location.col_start..main_location.col_end];
+    let preprocessed = component
+        .preproccessed_column_indices()
+        .iter()
+        .map(|index| trace[PREPROCESSED_TRACE_IDX][*index])
+        .collect_vec();
+    let evaluator = component.evaluate(FractionEvaluator {
+        columns: [preprocessed.as_slice(), main],
+        column_index: [0, 0],
+        row,
+        log_size: component.log_size(),
+        fractions: vec![],
+        is_violated: false,
+    });
+    (evaluator.fractions, evaluator.is_violated)
+}
+
+/// Evaluates a component on one row of its trace, collects its fractions and checks its
+/// constraints.
+struct FractionEvaluator<'a> {
+    /// The preprocessed and main columns of the component
+    columns: [&'a [&'a Vec<M31>]; 2],
+    /// The next column to read in each tree
+    column_index: [usize; 2],
+    /// The row, in bit-reversed circle domain order
+    row: usize,
+    log_size: u32,
+    fractions: Vec<Fraction<SecureField, SecureField>>,
+    /// Whether a constraint does not vanish on the row
+    is_violated: bool,
+}
+
+impl EvalAtRow for FractionEvaluator<'_> {
+    type F = M31;
+    type EF = SecureField;
+
+    fn next_interaction_mask<const N: usize>(
+        &mut self,
+        interaction: usize,
+        offsets: [isize; N],
+    ) -> [Self::F; N] {
+        assert!(
+            interaction == PREPROCESSED_TRACE_IDX || interaction == ORIGINAL_TRACE_IDX,
+            "the interaction trace is not evaluated"
+        );
+        let column = self.columns[interaction][self.column_index[interaction]];
+        self.column_index[interaction] += 1;
+        offsets.map(|offset| {
+            column[offset_bit_reversed_circle_domain_index(
+                self.row,
+                self.log_size,
+                self.log_size,
+                offset as i32,
+            )]
+        })
+    }
+
+    fn add_constraint<G>(&mut self, constraint: G)
+    where
+        Self::EF: std::ops::Mul<G, Output = Self::EF> + From<G>,
+    {
+        self.is_violated |= Self::EF::from(constraint) != SecureField::zero();
+    }
+
+    fn combine_ef(values: [Self::F; SECURE_EXTENSION_DEGREE]) -> Self::EF {
+        SecureField::from_m31_array(values)
+    }
+
+    fn write_logup_frac(&mut self, fraction: Fraction<Self::EF, Self::EF>) {
+        self.fractions.push(fraction);
+    }
+
+    fn finalize_logup(&mut self) {}
+}
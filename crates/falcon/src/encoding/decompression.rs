@@ -0,0 +1,668 @@
+//! # Signature Decompression Component
+//!
+//! This module implements the STARK proof component decoding the Golomb-Rice compressed
+//! s2 of a signature from its bytes, committed as public input, into the coefficients of
+//! the `s1` polynomial of the circuit.
+//!
+//! # Trace Structure
+//!
+//! Every bit of the compressed s2 takes one row, most significant bit first, and every
+//! instance takes `2^instance_log_size` rows, enough for the largest compressed s2 of the
+//! degree. The bytes after the end of the signature are zeros. Preprocessed columns give
+//! the first and last row of every instance, the first and last bit of every byte, the
+//! index of the byte of every row and the offset of the coefficients of the instance.
+//! Each row holds:
+//! - `b`: the bit
+//! - `is_sign`, `is_low`, `is_unary`, `is_done`: the part of the encoding the bit belongs
+//!   to, `is_done` marking the bits after the last coefficient
+//! - `is_end`: whether the bit is the stop bit of the unary part, ending a coefficient
+//! - `sign`, `low`, `n_low`, `high`: the sign, the low bits, the number of low bits and
+//!   the high part of the coefficient read so far
+//! - `index`: the number of coefficients ended before the row
+//! - `value`: the coefficient read so far, mapped into [0, q)
+//! - `byte`: the bits of the byte read so far
+//!
+//! # Constraints
+//!
+//! - A coefficient is a sign bit, 7 low bits and a unary high part ended by a 1, and
+//!   follows the first row or the end of the previous coefficient
+//! - The last row of an instance is either the end of its n-th coefficient or after it,
+//!   and the bits after the last coefficient are zeros
+//! - `value = m` for a positive coefficient and `q - m` for a negative one, where
+//!   `m = low + 128 * high`
+//!
+//! # Lookups
+//!
+//! - The last bit of every byte consumes the byte from the public input relation
+//! - Every ended coefficient emits `(offset + index, value)` to the s1 relation, once for
+//!   the forward NTT butterfly and once for the Euclidean norm
+//! - `value` is range checked in [0, q), which rejects a negative zero, and `16 * high`
+//!   in [0, 256), which bounds the absolute value of the coefficient by 2047
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{
+        ColumnVec,
+        channel::Channel,
+        fields::{
+            m31::{BaseField, M31},
+            qm31::SecureField,
+        },
+        pcs::TreeVec,
+        poly::circle::CanonicCoset,
+        utils::bit_reverse_coset_to_circle_domain_order,
+    },
+    prover::{
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, LogupTraceGenerator, ORIGINAL_TRACE_IDX,
+    Relation, RelationEntry, preprocessed_columns::PreProcessedColumnId,
+};
+
+use crate::{
+    big_air::{
+        public_inputs::SIGNATURE_ID,
+        relation::{LookupElements, PublicInputLookupElements, RCLookupElements, S1LookupElements},
+    },
+    encoding::{NONCE_LEN, padded_signature_size},
+    zq::Q,
+};
+
+/// Number of low bits of a compressed coefficient.
+const N_LOW_BITS: u32 = 7;
+
+/// Index of the bit column.
+const B: usize = 0;
+/// Index of the sign bit flag column.
+const IS_SIGN: usize = 1;
+/// Index of the low bits flag column.
+const IS_LOW: usize = 2;
+/// Index of the unary part flag column.
+const IS_UNARY: usize = 3;
+/// Index of the stop bit flag column.
+const IS_END: usize = 4;
+/// Index of the done flag column.
+const IS_DONE: usize = 5;
+/// Index of the sign column.
+const SIGN: usize = 6;
+/// Index of the low bits column.
+const LOW: usize = 7;
+/// Index of the number of low bits column.
+const N_LOW: usize = 8;
+/// Index of the high part column.
+const HIGH: usize = 9;
+/// Index of the coefficient index column.
+const INDEX: usize = 10;
+/// Index of the value column.
+const VALUE: usize = 11;
+/// Index of the byte column.
+const BYTE: usize = 12;
+/// Number of columns of the component.
+pub const N_COLUMNS: usize = 13;
+
+/// Returns the log of the number of rows of an instance of degree 2^poly_log_size.
+///
+/// Every bit of the largest compressed s2, the padded signature without its header and
+/// nonce, fits in the rows of an instance.
+pub fn instance_log_size(poly_log_size: u32) -> u32 {
+    (8 * (padded_signature_size(poly_log_size) - 1 - NONCE_LEN))
+        .next_power_of_two()
+        .ilog2()
+}
+
+/// The preprocessed columns describing the bit of each row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitColumn {
+    /// 1 on the first row of every instance
+    IsFirst,
+    /// 1 on the last row of every instance
+    IsLast,
+    /// 1 on the first bit of every byte
+    IsByteStart,
+    /// 1 on the last bit of every byte
+    IsByteEnd,
+    /// The index of the byte of the row among the bytes of all the instances
+    ByteIndex,
+    /// The index of the first coefficient of the instance
+    Offset,
+}
+
+/// Preprocessed column of the bit layout of a trace of `2^log_size` rows, holding
+/// instances of `2^instance_log_size` rows and `poly_size` coefficients.
+#[derive(Debug, Clone)]
+pub struct BitFlags {
+    pub log_size: u32,
+    pub instance_log_size: u32,
+    pub poly_size: usize,
+    pub column: BitColumn,
+}
+
+impl BitFlags {
+    pub fn new(claim: &Claim, poly_size: usize, column: BitColumn) -> Self {
+        Self {
+            log_size: claim.log_size,
+            instance_log_size: claim.instance_log_size,
+            poly_size,
+            column,
+        }
+    }
+
+    /// Returns all the bit layout columns of the claim.
+    pub fn all(claim: &Claim, poly_size: usize) -> Vec<Self> {
+        [
+            BitColumn::IsFirst,
+            BitColumn::IsLast,
+            BitColumn::IsByteStart,
+            BitColumn::IsByteEnd,
+            BitColumn::ByteIndex,
+            BitColumn::Offset,
+        ]
+        .into_iter()
+        .map(|column| Self::new(claim, poly_size, column))
+        .collect()
+    }
+
+    /// Generates the preprocessed column.
+    ///
+    /// The column is permuted like the trace, which is read at offset -1.
+    pub fn gen_column_simd(&self) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+        let n_rows = 1 << self.log_size;
+        let rows_per_instance = 1 << self.instance_log_size;
+        let mut values = (0..n_rows)
+            .map(|row| {
+                let (instance, bit) = (row >> self.instance_log_size, row % rows_per_instance);
+                M31(match self.column {
+                    BitColumn::IsFirst => (bit == 0) as u32,
+                    BitColumn::IsLast => (bit == rows_per_instance - 1) as u32,
+                    BitColumn::IsByteStart => (bit % 8 == 0) as u32,
+                    BitColumn::IsByteEnd => (bit % 8 == 7) as u32,
+                    BitColumn::ByteIndex => (row / 8) as u32,
+                    BitColumn::Offset => (instance * self.poly_size) as u32,
+                })
+            })
+            .collect::<Vec<_>>();
+        bit_reverse_coset_to_circle_domain_order(&mut values);
+        CircleEvaluation::new(
+            CanonicCoset::new(self.log_size).circle_domain(),
+            BaseColumn::from_iter(values),
+        )
+    }
+
+    /// Returns the unique identifier for this preprocessed column.
+    pub fn id(&self) -> PreProcessedColumnId {
+        let name = match self.column {
+            BitColumn::IsFirst => "is_first",
+            BitColumn::IsLast => "is_last",
+            BitColumn::IsByteStart => "is_byte_start",
+            BitColumn::IsByteEnd => "is_byte_end",
+            BitColumn::ByteIndex => "byte_index",
+            BitColumn::Offset => "offset",
+        };
+        PreProcessedColumnId {
+            id: format!(
+                "decompression_{}_{}_{}_{}",
+                name, self.log_size, self.instance_log_size, self.poly_size
+            ),
+        }
+    }
+}
+
+/// Claim parameters for the decompression component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    /// The log base 2 of the trace size
+    pub log_size: u32,
+    /// The log base 2 of the number of rows of every instance
+    pub instance_log_size: u32,
+}
+
+impl Claim {
+    /// Returns the claim of a trace decompressing `2^log_n_instances` signatures of
+    /// degree 2^poly_log_size.
+    pub fn new(poly_log_size: u32, log_n_instances: u32) -> Self {
+        let instance_log_size = instance_log_size(poly_log_size);
+        Self {
+            log_size: instance_log_size + log_n_instances,
+            instance_log_size,
+        }
+    }
+
+    /// Returns the number of bytes of every instance.
+    pub fn bytes_per_instance(&self) -> usize {
+        (1 << self.instance_log_size) / 8
+    }
+
+    /// Returns the log sizes for the traces.
+    ///
+    /// [preprocessed_trace, trace, interaction_trace]
+    pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
+        let trace_log_sizes = vec![self.log_size; N_COLUMNS];
+        TreeVec::new(vec![vec![], trace_log_sizes, vec![]])
+    }
+
+    /// Mixes the claim parameters into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size as u64);
+        channel.mix_u64(self.instance_log_size as u64);
+    }
+
+    /// Generates the trace for the decompression component.
+    ///
+    /// # Parameters
+    ///
+    /// - `compressed`: The compressed s2 of every instance, the signature without its
+    ///   header and nonce
+    /// - `poly_size`: The number of coefficients of every signature
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing:
+    /// - The trace columns
+    /// - The coefficients of the s1 polynomials of all the instances, one after the other
+    /// - The values of the ended coefficients, for the range check against Q
+    /// - The high parts of the ended coefficients times 16, for the byte range check
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trace does not hold exactly the instances, or if a compressed s2
+    /// does not fit in the rows of an instance or does not decode to `poly_size`
+    /// coefficients followed by zero bits.
+    #[allow(clippy::type_complexity)]
+    pub fn gen_trace(
+        &self,
+        compressed: &[&[u8]],
+        poly_size: usize,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        Vec<u32>,
+        Vec<M31>,
+        Vec<M31>,
+    ) {
+        let n_rows = 1 << self.log_size;
+        let rows_per_instance = 1 << self.instance_log_size;
+        assert_eq!(compressed.len() * rows_per_instance, n_rows);
+        let mut columns = vec![vec![M31(0); n_rows]; N_COLUMNS];
+        let mut s1 = vec![];
+        let mut values = vec![];
+        let mut highs = vec![];
+        for (instance, bytes) in compressed.iter().enumerate() {
+            assert!(
+                bytes.len() <= self.bytes_per_instance(),
+                "compressed signature too long"
+            );
+            let bits = (0..rows_per_instance).map(|bit| {
+                bytes
+                    .get(bit / 8)
+                    .map_or(0, |byte| ((byte >> (7 - bit % 8)) & 1) as u32)
+            });
+            let (mut sign, mut low, mut n_low, mut high) = (0, 0, 0, 0);
+            let (mut index, mut byte) = (0, 0);
+            let mut part = IS_SIGN;
+            for (bit, b) in bits.enumerate() {
+                let row = instance * rows_per_instance + bit;
+                byte = if bit % 8 == 0 { b } else { 2 * byte + b };
+                let mut is_end = false;
+                match part {
+                    IS_SIGN => (sign, low, n_low, high) = (b, 0, 0, 0),
+                    IS_LOW => (low, n_low) = (2 * low + b, n_low + 1),
+                    IS_UNARY => {
+                        is_end = b == 1;
+                        high += 1 - b;
+                    }
+                    _ => {
+                        assert_eq!(b, 0, "non-zero bits after the last coefficient");
+                        (sign, low, n_low, high) = (0, 0, 0, 0);
+                    }
+                }
+                let m = M31(low + 128 * high);
+                let value = if sign == 1 { M31(Q) - m } else { m };
+                for (column, cell) in [
+                    (B, b),
+                    (part, 1),
+                    (IS_END, is_end as u32),
+                    (SIGN, sign),
+                    (LOW, low),
+                    (N_LOW, n_low),
+                    (HIGH, high),
+                    (INDEX, index),
+                    (VALUE, value.0),
+                    (BYTE, byte),
+                ] {
+                    columns[column][row] = M31(cell);
+                }
+
+                part = match part {
+                    IS_SIGN => IS_LOW,
+                    IS_LOW if n_low < N_LOW_BITS => IS_LOW,
+                    IS_LOW => IS_UNARY,
+                    IS_UNARY if !is_end => IS_UNARY,
+                    IS_UNARY => {
+                        s1.push(value.0);
+                        values.push(value);
+                        highs.push(M31(16 * high));
+                        index += 1;
+                        if index as usize == poly_size {
+                            IS_DONE
+                        } else {
+                            IS_SIGN
+                        }
+                    }
+                    _ => IS_DONE,
+                };
+            }
+            assert_eq!(
+                index as usize, poly_size,
+                "the signature does not encode {poly_size} coefficients"
+            );
+        }
+
+        let domain = CanonicCoset::new(self.log_size).circle_domain();
+        (
+            columns
+                .into_iter()
+                .map(|mut column| {
+                    // The flags and the coefficient read so far are read at offset -1
+                    bit_reverse_coset_to_circle_domain_order(&mut column);
+                    CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
+                        domain,
+                        BaseColumn::from_iter(column),
+                    )
+                })
+                .collect(),
+            s1,
+            values,
+            highs,
+        )
+    }
+}
+
+/// Component used in the framework for the signature decompression.
+#[derive(Debug, Clone)]
+pub struct Eval {
+    /// The claim parameters
+    pub claim: Claim,
+    /// The number of coefficients of every signature
+    pub poly_size: usize,
+    /// Lookup elements for the bytes of the signatures
+    pub public_input_lookup_elements: PublicInputLookupElements,
+    /// Lookup elements for the coefficients of s1
+    pub s1_lookup_elements: S1LookupElements,
+    /// Lookup elements for range checking against Q
+    pub rc_lookup_elements: RCLookupElements,
+    /// Lookup elements for the byte range check
+    pub byte_rc_lookup_elements: RCLookupElements,
+}
+
+impl FrameworkEval for Eval {
+    fn log_size(&self) -> u32 {
+        self.claim.log_size
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.claim.log_size + 1
+    }
+
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        let [
+            is_first,
+            is_last,
+            is_byte_start,
+            is_byte_end,
+            byte_index,
+            offset,
+        ] = [
+            BitColumn::IsFirst,
+            BitColumn::IsLast,
+            BitColumn::IsByteStart,
+            BitColumn::IsByteEnd,
+            BitColumn::ByteIndex,
+            BitColumn::Offset,
+        ]
+        .map(|column| {
+            eval.get_preprocessed_column(BitFlags::new(&self.claim, self.poly_size, column).id())
+        });
+
+        let b = eval.next_trace_mask();
+        let [is_sign_prev, is_sign] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [is_low_prev, is_low] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [is_unary_prev, is_unary] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [is_end_prev, is_end] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [is_done_prev, is_done] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [sign_prev, sign] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [low_prev, low] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [n_low_prev, n_low] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [high_prev, high] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let [index_prev, index] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+        let value = eval.next_trace_mask();
+        let [byte_prev, byte] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+
+        let one = E::F::one();
+        let two = E::F::from(M31(2));
+        let n = E::F::from(M31(self.poly_size as u32));
+        let n_low_bits = E::F::from(M31(N_LOW_BITS));
+        for bit in [&b, &is_sign, &is_low, &is_unary, &is_done] {
+            eval.add_constraint(bit.clone() * (bit.clone() - one.clone()));
+        }
+        eval.add_constraint(
+            is_sign.clone() + is_low.clone() + is_unary.clone() + is_done.clone() - one.clone(),
+        );
+        eval.add_constraint(is_end.clone() - is_unary.clone() * b.clone());
+
+        // An instance starts with a sign bit, and a sign bit follows the end of the
+        // previous coefficient (the first row is a sign bit, so this is
+        // (1 - is_first) * is_sign * (1 - is_end_prev))
+        eval.add_constraint(is_first.clone() * (one.clone() - is_sign.clone()));
+        eval.add_constraint(
+            (is_sign.clone() - is_first.clone()) * (one.clone() - is_end_prev.clone()),
+        );
+        eval.add_constraint(is_sign.clone() * (sign.clone() - b.clone()));
+        eval.add_constraint(is_sign.clone() * low.clone());
+        eval.add_constraint(is_sign.clone() * n_low.clone());
+        eval.add_constraint(is_sign.clone() * high.clone());
+
+        // The low bits follow the sign bit
+        eval.add_constraint(is_low.clone() * (one.clone() - is_sign_prev - is_low_prev.clone()));
+        eval.add_constraint(is_low.clone() * (sign.clone() - sign_prev.clone()));
+        eval.add_constraint(is_low.clone() * (low.clone() - two * low_prev.clone() - b.clone()));
+        eval.add_constraint(is_low.clone() * (n_low.clone() - n_low_prev.clone() - one.clone()));
+        eval.add_constraint(is_low * high.clone());
+
+        // The unary part follows the 7 low bits, and counts the zeros before the stop bit
+        eval.add_constraint(
+            is_unary.clone() * (one.clone() - is_low_prev - is_unary_prev + is_end_prev.clone()),
+        );
+        eval.add_constraint(is_unary.clone() * (n_low_prev - n_low_bits.clone()));
+        eval.add_constraint(is_unary.clone() * (n_low - n_low_bits));
+        eval.add_constraint(is_unary.clone() * (sign.clone() - sign_prev));
+        eval.add_constraint(is_unary.clone() * (low.clone() - low_prev));
+        eval.add_constraint(is_unary * (high.clone() - high_prev - one.clone() + b.clone()));
+
+        // The bits after the last coefficient are zeros
+        eval.add_constraint(is_done.clone() * (one.clone() - is_end_prev.clone() - is_done_prev));
+        eval.add_constraint(is_done.clone() * b.clone());
+
+        // The coefficients are counted, and an instance ends with its n-th coefficient
+        eval.add_constraint(is_first.clone() * index.clone());
+        eval.add_constraint((one.clone() - is_first) * (index.clone() - index_prev - is_end_prev));
+        eval.add_constraint(is_last.clone() * (index.clone() + is_end.clone() - n));
+        eval.add_constraint(is_last * (one.clone() - is_done - is_end.clone()));
+
+        // value = m for a positive coefficient and Q - m for a negative one
+        let m = low + E::F::from(M31(128)) * high.clone();
+        eval.add_constraint(
+            value.clone() - m.clone() - sign * (E::F::from(M31(Q)) - m.clone() - m),
+        );
+
+        // The bits of every byte are accumulated from its first bit
+        eval.add_constraint(
+            byte.clone() - b - (one - is_byte_start) * E::F::from(M31(2)) * byte_prev,
+        );
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.public_input_lookup_elements,
+            -E::EF::from(is_byte_end),
+            &[E::F::from(M31(SIGNATURE_ID)), byte_index, byte],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.s1_lookup_elements,
+            -E::EF::from(E::F::from(M31(2)) * is_end.clone()),
+            &[offset + index, value.clone()],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.rc_lookup_elements,
+            E::EF::from(is_end.clone()),
+            &[value],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.byte_rc_lookup_elements,
+            E::EF::from(is_end),
+            &[E::F::from(M31(16)) * high],
+        ));
+
+        eval.finalize_logup();
+        eval
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionClaim {
+    /// The claimed sum for the interaction
+    pub claimed_sum: SecureField,
+}
+
+impl InteractionClaim {
+    /// Mixes the interaction claim into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_felts(&[self.claimed_sum]);
+    }
+
+    /// Generates the interaction trace for the decompression component.
+    ///
+    /// # Parameters
+    ///
+    /// - `trace`: The trace columns of the component
+    /// - `claim`: The claim the trace was generated from
+    /// - `poly_size`: The number of coefficients of every signature
+    /// - `lookup_elements`: The lookup elements of the Big AIR
+    pub fn gen_interaction_trace(
+        trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+        claim: &Claim,
+        poly_size: usize,
+        lookup_elements: &LookupElements,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        InteractionClaim,
+    ) {
+        let log_size = claim.log_size;
+        let n_vec_rows = 1 << (log_size - LOG_N_LANES);
+        let flag = |column| BitFlags::new(claim, poly_size, column).gen_column_simd();
+        let is_byte_end = flag(BitColumn::IsByteEnd);
+        let byte_index = flag(BitColumn::ByteIndex);
+        let offset = flag(BitColumn::Offset);
+        let signature_id = PackedM31::broadcast(M31(SIGNATURE_ID));
+        let two = PackedM31::broadcast(M31(2));
+        let sixteen = PackedM31::broadcast(M31(16));
+        let mut logup_gen = LogupTraceGenerator::new(log_size);
+
+        // Bytes of the signatures
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let denom: PackedQM31 = lookup_elements.public_input.combine(&[
+                signature_id,
+                byte_index.data[vec_row],
+                trace[BYTE].data[vec_row],
+            ]);
+            col_gen.write_frac(vec_row, -PackedQM31::from(is_byte_end.data[vec_row]), denom);
+        }
+        col_gen.finalize_col();
+
+        // Coefficients of s1
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let denom: PackedQM31 = lookup_elements.s1.combine(&[
+                offset.data[vec_row] + trace[INDEX].data[vec_row],
+                trace[VALUE].data[vec_row],
+            ]);
+            let numerator = -PackedQM31::from(two * trace[IS_END].data[vec_row]);
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        // Values and high parts range checks
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let denom: PackedQM31 = lookup_elements.rc.combine(&[trace[VALUE].data[vec_row]]);
+            col_gen.write_frac(
+                vec_row,
+                PackedQM31::from(trace[IS_END].data[vec_row]),
+                denom,
+            );
+        }
+        col_gen.finalize_col();
+
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let denom: PackedQM31 = lookup_elements
+                .byte_range_check
+                .combine(&[sixteen * trace[HIGH].data[vec_row]]);
+            col_gen.write_frac(
+                vec_row,
+                PackedQM31::from(trace[IS_END].data[vec_row]),
+                denom,
+            );
+        }
+        col_gen.finalize_col();
+
+        let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
+        (interaction_trace, InteractionClaim { claimed_sum })
+    }
+}
+
+/// Type alias for the decompression component.
+pub type Component = FrameworkComponent<Eval>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{SIGNATURE, TEST_S1, falcon_512};
+
+    #[test]
+    fn test_instance_fits_padded_signature() {
+        assert_eq!(instance_log_size(9), 13);
+        assert_eq!(instance_log_size(10), 14);
+    }
+
+    #[test]
+    fn test_decompressed_coefficients_match_test_vectors() {
+        for (signature, s1) in [
+            (SIGNATURE, TEST_S1.as_slice()),
+            (falcon_512::SIGNATURE, falcon_512::TEST_S1.as_slice()),
+        ] {
+            let claim = Claim::new(s1.len().ilog2(), 1);
+            let compressed = &signature[1 + NONCE_LEN..];
+            let (trace, coefficients, values, highs) =
+                claim.gen_trace(&[compressed, compressed], s1.len());
+            assert_eq!(trace.len(), N_COLUMNS);
+            assert_eq!(coefficients, [s1, s1].concat());
+            assert_eq!(values.len(), 2 * s1.len());
+            assert!(highs.iter().all(|high| high.0 < 256));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "coefficients")]
+    fn test_truncated_signature_does_not_decompress() {
+        let claim = Claim::new(9, 0);
+        let compressed = &falcon_512::SIGNATURE[1 + NONCE_LEN..];
+        claim.gen_trace(&[&compressed[..compressed.len() - 1]], 512);
+    }
+}
@@ -0,0 +1,622 @@
+//! # Falcon Encodings
+//!
+//! This module decodes the standard Falcon public key and signature formats into the
+//! polynomials taken by [`crate::big_air::prove_falcon`].
+//!
+//! # Formats
+//!
+//! - **Public key**: A header byte `0000nnnn` (n = log2 of the degree) followed by the
+//!   coefficients of h packed on 14 bits, most significant bit first
+//! - **Signature**: A header byte `0011nnnn`, the 40-byte nonce and the Golomb-Rice
+//!   compressed s2, optionally zero padded to the fixed padded size of the degree
+//! - **Signed message**: The NIST API layout `sig_len || nonce || message || esig`, where
+//!   `esig` is a header byte `0010nnnn` followed by the compressed s2
+//!
+//! Signed coefficients are mapped into [0, q). Decoding is strict: any encoding that is
+//! not the canonical one of its polynomial is rejected, so that a signature has a
+//! single valid encoding.
+//!
+//! # Components
+//!
+//! - **Decompression**: One row per bit of the compressed s2 of a signature, emitting
+//!   the coefficients of the `s1` polynomial consumed by the forward NTT and the
+//!   Euclidean norm
+
+pub mod decompression;
+
+use thiserror::Error;
+
+use crate::{CircuitParams, zq::Q};
+
+/// Number of bytes of the nonce of a signature.
+pub const NONCE_LEN: usize = 40;
+
+/// Number of bits of a packed public key coefficient.
+const PK_COEFFICIENT_BITS: usize = 14;
+
+/// Largest absolute value of a compressed signature coefficient.
+const MAX_SIGNATURE_COEFFICIENT: u32 = 2047;
+
+/// Header of a public key, before the degree nibble.
+const PK_HEADER: u8 = 0x00;
+
+/// Header of a compressed (or padded) signature, before the degree nibble.
+const SIGNATURE_HEADER: u8 = 0x30;
+
+/// Header of the signature of a NIST API signed message, before the degree nibble.
+const SIGNED_MESSAGE_HEADER: u8 = 0x20;
+
+/// Errors that can occur when decoding a Falcon public key or signature.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EncodingError {
+    #[error("encoding is empty")]
+    Empty,
+    #[error("invalid header byte {0:#04x}")]
+    InvalidHeader(u8),
+    #[error("unsupported polynomial size 2^{0}")]
+    UnsupportedDegree(u32),
+    #[error("invalid length: expected {expected} bytes, found {found}")]
+    InvalidLength { expected: usize, found: usize },
+    #[error("encoding is too short for its declared length")]
+    Truncated,
+    #[error("coefficient {index} is {value}, expected a value in [0, q)")]
+    CoefficientOutOfRange { index: usize, value: u32 },
+    #[error("coefficient {index} exceeds {MAX_SIGNATURE_COEFFICIENT} in absolute value")]
+    CoefficientTooLarge { index: usize },
+    #[error("coefficient {index} is encoded as a negative zero")]
+    NegativeZero { index: usize },
+    #[error("unused bits of the last byte are not zero")]
+    NonZeroTrailingBits,
+    #[error("{0} unexpected bytes after the compressed signature")]
+    TrailingBytes(usize),
+}
+
+/// A decoded Falcon public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    /// The log base 2 of the degree
+    pub log_n: u32,
+    /// The public key polynomial h, with coefficients in [0, Q)
+    pub h: Vec<u32>,
+}
+
+/// A decoded Falcon signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    /// The log base 2 of the degree
+    pub log_n: u32,
+    /// The nonce r of the signature
+    pub nonce: [u8; NONCE_LEN],
+    /// The signature polynomial s2 (the `s1` of the circuit), with coefficients in [0, Q)
+    pub s1: Vec<u32>,
+}
+
+/// Returns the size in bytes of an encoded public key of degree 2^log_n.
+pub fn public_key_size(log_n: u32) -> usize {
+    1 + (PK_COEFFICIENT_BITS << log_n) / 8
+}
+
+/// Returns the size in bytes of a padded signature of degree 2^log_n.
+///
+/// This is also the largest size of a compressed signature.
+pub fn padded_signature_size(log_n: u32) -> usize {
+    match log_n {
+        9 => 666,
+        10 => 1280,
+        _ => unreachable!("unsupported polynomial size 2^{log_n}"),
+    }
+}
+
+/// Maps a signed coefficient into [0, Q).
+pub fn to_zq(x: i32) -> u32 {
+    x.rem_euclid(Q as i32) as u32
+}
+
+/// Decodes the degree nibble of a header byte whose high nibble must be `header`.
+fn decode_header(byte: u8, header: u8) -> Result<u32, EncodingError> {
+    if byte & 0xF0 != header {
+        return Err(EncodingError::InvalidHeader(byte));
+    }
+    let log_n = (byte & 0x0F) as u32;
+    CircuitParams::from_poly_log_size(log_n).ok_or(EncodingError::UnsupportedDegree(log_n))?;
+    Ok(log_n)
+}
+
+/// Decodes a public key: a header byte followed by the 14-bit packed coefficients of h.
+///
+/// # Errors
+///
+/// Returns `EncodingError` if the header or the length is invalid, the degree is not
+/// supported or a coefficient is not in [0, Q).
+pub fn decode_public_key(bytes: &[u8]) -> Result<PublicKey, EncodingError> {
+    let (&header, packed) = bytes.split_first().ok_or(EncodingError::Empty)?;
+    let log_n = decode_header(header, PK_HEADER)?;
+    let expected = public_key_size(log_n);
+    if bytes.len() != expected {
+        return Err(EncodingError::InvalidLength {
+            expected,
+            found: bytes.len(),
+        });
+    }
+
+    // 14 * n is a multiple of 8 for every supported degree, so no bit is left over
+    let mut h = Vec::with_capacity(1 << log_n);
+    let (mut acc, mut acc_len) = (0u32, 0);
+    for &byte in packed {
+        acc = (acc << 8) | byte as u32;
+        acc_len += 8;
+        if acc_len >= PK_COEFFICIENT_BITS {
+            acc_len -= PK_COEFFICIENT_BITS;
+            let value = (acc >> acc_len) & ((1 << PK_COEFFICIENT_BITS) - 1);
+            if value >= Q {
+                return Err(EncodingError::CoefficientOutOfRange {
+                    index: h.len(),
+                    value,
+                });
+            }
+            h.push(value);
+            acc &= (1 << acc_len) - 1;
+        }
+    }
+    Ok(PublicKey { log_n, h })
+}
+
+/// Decodes a signature: a header byte, the nonce and the compressed s2, optionally zero
+/// padded to [`padded_signature_size`].
+///
+/// # Errors
+///
+/// Returns `EncodingError` if the header or the length is invalid, the degree is not
+/// supported or the compressed s2 is not canonical.
+pub fn decode_signature(bytes: &[u8]) -> Result<Signature, EncodingError> {
+    let (&header, rest) = bytes.split_first().ok_or(EncodingError::Empty)?;
+    let log_n = decode_header(header, SIGNATURE_HEADER)?;
+    let padded_size = padded_signature_size(log_n);
+    if bytes.len() > padded_size {
+        return Err(EncodingError::InvalidLength {
+            expected: padded_size,
+            found: bytes.len(),
+        });
+    }
+    if rest.len() < NONCE_LEN {
+        return Err(EncodingError::Truncated);
+    }
+    let (nonce, compressed) = rest.split_at(NONCE_LEN);
+
+    let (s2, used) = decompress(compressed, log_n)?;
+    // Only a padded signature may carry bytes after the compressed s2, and they are zeros
+    let trailing = &compressed[used..];
+    let is_padding = bytes.len() == padded_size && trailing.iter().all(|&byte| byte == 0);
+    if !trailing.is_empty() && !is_padding {
+        return Err(EncodingError::TrailingBytes(trailing.len()));
+    }
+
+    Ok(Signature {
+        log_n,
+        nonce: nonce.try_into().unwrap(),
+        s1: s2.into_iter().map(to_zq).collect(),
+    })
+}
+
+/// Checks the parts of a signature encoding that the decompression component does not
+/// prove: its header, its length and, unless it is padded, that it does not end with a
+/// zero byte.
+///
+/// The compressed s2 ends with the stop bit of its last coefficient, so a signature
+/// passing this check and decompressed in the circuit is exactly one that
+/// [`decode_signature`] accepts.
+///
+/// # Errors
+///
+/// Returns `EncodingError` if the header is not the one of a signature of degree
+/// 2^log_n, the signature is longer than the padded size or shorter than its nonce, or
+/// it ends with a zero byte without being padded.
+pub fn check_signature_layout(bytes: &[u8], log_n: u32) -> Result<(), EncodingError> {
+    let (&header, _) = bytes.split_first().ok_or(EncodingError::Empty)?;
+    if header != SIGNATURE_HEADER | log_n as u8 {
+        return Err(EncodingError::InvalidHeader(header));
+    }
+    let padded_size = padded_signature_size(log_n);
+    if bytes.len() > padded_size {
+        return Err(EncodingError::InvalidLength {
+            expected: padded_size,
+            found: bytes.len(),
+        });
+    }
+    if bytes.len() <= 1 + NONCE_LEN {
+        return Err(EncodingError::Truncated);
+    }
+    let trailing_zeros = bytes.iter().rev().take_while(|&&byte| byte == 0).count();
+    if trailing_zeros > 0 && bytes.len() != padded_size {
+        return Err(EncodingError::TrailingBytes(trailing_zeros));
+    }
+    Ok(())
+}
+
+/// Decodes a signed message of the NIST API (the `sm` field of the `.rsp` test vectors) into its
+/// signature and message.
+///
+/// # Errors
+///
+/// Returns `EncodingError` if the lengths are inconsistent, the header is invalid, the
+/// degree is not supported or the compressed s2 is not canonical.
+pub fn decode_signed_message(sm: &[u8]) -> Result<(Signature, Vec<u8>), EncodingError> {
+    if sm.len() < 2 + NONCE_LEN {
+        return Err(EncodingError::Truncated);
+    }
+    let sig_len = u16::from_be_bytes([sm[0], sm[1]]) as usize;
+    let nonce = &sm[2..2 + NONCE_LEN];
+    let body = &sm[2 + NONCE_LEN..];
+    if sig_len == 0 || sig_len > body.len() {
+        return Err(EncodingError::Truncated);
+    }
+    let (message, esig) = body.split_at(body.len() - sig_len);
+
+    let log_n = decode_header(esig[0], SIGNED_MESSAGE_HEADER)?;
+    let (s2, used) = decompress(&esig[1..], log_n)?;
+    if used != esig.len() - 1 {
+        return Err(EncodingError::TrailingBytes(esig.len() - 1 - used));
+    }
+
+    let signature = Signature {
+        log_n,
+        nonce: nonce.try_into().unwrap(),
+        s1: s2.into_iter().map(to_zq).collect(),
+    };
+    Ok((signature, message.to_vec()))
+}
+
+/// Decodes 2^log_n Golomb-Rice compressed coefficients.
+///
+/// Each coefficient is a sign bit, the 7 low bits of its absolute value and the high
+/// bits of its absolute value in unary (as many zeros, then a one). Returns the
+/// coefficients and the number of bytes read.
+fn decompress(bytes: &[u8], log_n: u32) -> Result<(Vec<i32>, usize), EncodingError> {
+    let mut bytes_iter = bytes.iter();
+    let mut next_byte = || {
+        bytes_iter
+            .next()
+            .map(|&b| b as u32)
+            .ok_or(EncodingError::Truncated)
+    };
+
+    let mut coefficients = Vec::with_capacity(1 << log_n);
+    let (mut acc, mut acc_len) = (0u32, 0);
+    for index in 0..1 << log_n {
+        // Sign bit and 7 low bits
+        acc = (acc << 8) | next_byte()?;
+        let low = acc >> acc_len;
+        let negative = low & 0x80 != 0;
+        let mut magnitude = low & 0x7F;
+
+        // High bits in unary
+        loop {
+            if acc_len == 0 {
+                acc = (acc << 8) | next_byte()?;
+                acc_len = 8;
+            }
+            acc_len -= 1;
+            if (acc >> acc_len) & 1 == 1 {
+                break;
+            }
+            magnitude += 128;
+            if magnitude > MAX_SIGNATURE_COEFFICIENT {
+                return Err(EncodingError::CoefficientTooLarge { index });
+            }
+        }
+
+        if negative && magnitude == 0 {
+            return Err(EncodingError::NegativeZero { index });
+        }
+        coefficients.push(if negative {
+            -(magnitude as i32)
+        } else {
+            magnitude as i32
+        });
+        acc &= (1 << acc_len) - 1;
+    }
+
+    if acc != 0 {
+        return Err(EncodingError::NonZeroTrailingBits);
+    }
+    let used = bytes.len() - bytes_iter.len();
+    Ok((coefficients, used))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        big_air::{config::ProverConfig, prove_falcon, public_inputs::PublicInputs, verify_falcon},
+        input::{self, falcon_512},
+    };
+    use stwo::core::vcs::blake2_merkle::Blake2sMerkleChannel;
+
+    const FALCON_512_VECTORS: &str = include_str!("../../vectors/falcon512.rsp");
+    const FALCON_1024_VECTORS: &str = include_str!("../../vectors/falcon1024.rsp");
+
+    /// Parses the `key = value` entries of a test vector file.
+    fn parse_vectors(vectors: &str) -> Vec<HashMap<&str, &str>> {
+        vectors
+            .split("\n\n")
+            .map(|entry| {
+                entry
+                    .lines()
+                    .filter_map(|line| line.split_once(" = "))
+                    .collect::<HashMap<_, _>>()
+            })
+            .filter(|entry| !entry.is_empty())
+            .collect()
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Decodes the public key, signature and message of a test vector.
+    fn decode_vector(entry: &HashMap<&str, &str>) -> (PublicKey, Signature, Vec<u8>) {
+        let pk = decode_public_key(&hex(entry["pk"])).unwrap();
+        let (signature, message) = decode_signed_message(&hex(entry["sm"])).unwrap();
+        assert_eq!(message, hex(entry["msg"]));
+        assert_eq!(message.len(), entry["mlen"].parse::<usize>().unwrap());
+        (pk, signature, message)
+    }
+
+    /// Re-encodes a NIST API signed message as a standard compressed signature.
+    fn to_signature(sm: &[u8]) -> Vec<u8> {
+        let sig_len = u16::from_be_bytes([sm[0], sm[1]]) as usize;
+        let esig = &sm[sm.len() - sig_len..];
+        let mut signature = vec![SIGNATURE_HEADER | (esig[0] & 0x0F)];
+        signature.extend_from_slice(&sm[2..2 + NONCE_LEN]);
+        signature.extend_from_slice(&esig[1..]);
+        signature
+    }
+
+    /// Writes bits most significant first, to craft compressed signatures.
+    #[derive(Default)]
+    struct BitWriter {
+        bytes: Vec<u8>,
+        n_bits: usize,
+    }
+
+    impl BitWriter {
+        fn push(&mut self, value: u32, n_bits: usize) {
+            for i in (0..n_bits).rev() {
+                if self.n_bits % 8 == 0 {
+                    self.bytes.push(0);
+                }
+                let bit = ((value >> i) & 1) as u8;
+                *self.bytes.last_mut().unwrap() |= bit << (7 - self.n_bits % 8);
+                self.n_bits += 1;
+            }
+        }
+
+        /// Pushes a compressed coefficient given by its sign and absolute value.
+        fn push_coefficient(&mut self, negative: bool, magnitude: u32) {
+            self.push(negative as u32, 1);
+            self.push(magnitude & 0x7F, 7);
+            self.push(1, (magnitude >> 7) as usize + 1);
+        }
+    }
+
+    /// Returns a Falcon-512 signature whose s2 is `head` followed by zeros.
+    fn signature_with(head: &[(bool, u32)]) -> BitWriter {
+        let mut writer = BitWriter::default();
+        writer.bytes.push(SIGNATURE_HEADER | 9);
+        writer.bytes.extend_from_slice(falcon_512::NONCE);
+        writer.n_bits = 8 * writer.bytes.len();
+        for &(negative, magnitude) in head {
+            writer.push_coefficient(negative, magnitude);
+        }
+        for _ in head.len()..512 {
+            writer.push_coefficient(false, 0);
+        }
+        writer
+    }
+
+    #[test]
+    fn test_decode_vector_files() {
+        for (vectors, log_n) in [(FALCON_512_VECTORS, 9), (FALCON_1024_VECTORS, 10)] {
+            let entries = parse_vectors(vectors);
+            assert!(!entries.is_empty());
+            for entry in entries.iter() {
+                let (pk, signature, _) = decode_vector(entry);
+                assert_eq!((pk.log_n, signature.log_n), (log_n, log_n));
+                assert_eq!(pk.h.len(), 1 << log_n);
+                assert_eq!(signature.s1.len(), 1 << log_n);
+
+                // The same signature in the standard compressed format
+                let sm = hex(entry["sm"]);
+                assert_eq!(decode_signature(&to_signature(&sm)).unwrap(), signature);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vector_files_match_input() {
+        let entry = &parse_vectors(FALCON_1024_VECTORS)[0];
+        assert_eq!(to_signature(&hex(entry["sm"])), input::SIGNATURE);
+        let (pk, signature, message) = decode_vector(entry);
+        assert_eq!(pk.h, input::PK);
+        assert_eq!(signature.s1, input::TEST_S1);
+        assert_eq!(&signature.nonce, input::NONCE);
+        assert_eq!(message, input::MESSAGE);
+
+        let entry = &parse_vectors(FALCON_512_VECTORS)[0];
+        assert_eq!(to_signature(&hex(entry["sm"])), falcon_512::SIGNATURE);
+        let (pk, signature, message) = decode_vector(entry);
+        assert_eq!(pk.h, falcon_512::PK);
+        assert_eq!(signature.s1, falcon_512::TEST_S1);
+        assert_eq!(&signature.nonce, falcon_512::NONCE);
+        assert_eq!(message, falcon_512::MESSAGE);
+    }
+
+    #[test]
+    fn test_prove_falcon_from_vector_file() {
+        let entry = &parse_vectors(FALCON_512_VECTORS)[1];
+        let (pk, _, message) = decode_vector(entry);
+        let signature = to_signature(&hex(entry["sm"]));
+        let proof = prove_falcon::<Blake2sMerkleChannel>(&signature, &pk.h, &message).unwrap();
+        verify_falcon(
+            proof,
+            &PublicInputs::new(&pk.h, &signature, &message),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_check_signature_layout() {
+        let signature = falcon_512::SIGNATURE;
+        assert_eq!(check_signature_layout(signature, 9), Ok(()));
+        let mut padded = signature.to_vec();
+        padded.resize(padded_signature_size(9), 0);
+        assert_eq!(check_signature_layout(&padded, 9), Ok(()));
+
+        // Another degree
+        assert_eq!(
+            check_signature_layout(signature, 10),
+            Err(EncodingError::InvalidHeader(signature[0]))
+        );
+
+        // A zero byte after a signature that is not padded
+        let mut bad = signature.to_vec();
+        bad.push(0);
+        assert_eq!(
+            check_signature_layout(&bad, 9),
+            Err(EncodingError::TrailingBytes(1))
+        );
+
+        // Longer than the padded size, or without a compressed s2
+        padded.push(0);
+        assert!(matches!(
+            check_signature_layout(&padded, 9),
+            Err(EncodingError::InvalidLength { .. })
+        ));
+        assert_eq!(
+            check_signature_layout(&signature[..1 + NONCE_LEN], 9),
+            Err(EncodingError::Truncated)
+        );
+    }
+
+    #[test]
+    fn test_padded_signature() {
+        let sm = hex(parse_vectors(FALCON_512_VECTORS)[0]["sm"]);
+        let compressed = to_signature(&sm);
+        let expected = decode_signature(&compressed).unwrap();
+
+        let mut padded = compressed.clone();
+        padded.resize(padded_signature_size(9), 0);
+        assert_eq!(decode_signature(&padded).unwrap(), expected);
+
+        // Non-zero padding
+        *padded.last_mut().unwrap() = 1;
+        assert!(decode_signature(&padded).is_err());
+
+        // Longer than the padded size
+        let mut long = compressed;
+        long.resize(padded_signature_size(9) + 1, 0);
+        assert!(matches!(
+            decode_signature(&long),
+            Err(EncodingError::InvalidLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_public_key_rejects_invalid_encodings() {
+        let pk = hex(parse_vectors(FALCON_512_VECTORS)[0]["pk"]);
+        assert!(decode_public_key(&pk).is_ok());
+
+        assert_eq!(decode_public_key(&[]), Err(EncodingError::Empty));
+
+        let mut bad = pk.clone();
+        bad[0] = 0x19;
+        assert_eq!(
+            decode_public_key(&bad),
+            Err(EncodingError::InvalidHeader(0x19))
+        );
+
+        let mut bad = pk.clone();
+        bad[0] = 0x08;
+        assert_eq!(
+            decode_public_key(&bad),
+            Err(EncodingError::UnsupportedDegree(8))
+        );
+
+        assert!(matches!(
+            decode_public_key(&pk[..pk.len() - 1]),
+            Err(EncodingError::InvalidLength { .. })
+        ));
+
+        // First coefficient set to 0x3FFF >= q
+        let mut bad = pk;
+        bad[1] = 0xFF;
+        bad[2] |= 0xFC;
+        assert_eq!(
+            decode_public_key(&bad),
+            Err(EncodingError::CoefficientOutOfRange {
+                index: 0,
+                value: 0x3FFF
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_signature_rejects_non_canonical_encodings() {
+        // A valid crafted signature decodes to its coefficients, mapped into [0, q)
+        let writer = signature_with(&[(true, 5), (false, 300), (true, MAX_SIGNATURE_COEFFICIENT)]);
+        let signature = decode_signature(&writer.bytes).unwrap();
+        assert_eq!(signature.s1[..4], [Q - 5, 300, Q - 2047, 0]);
+
+        // Negative zero
+        let writer = signature_with(&[(false, 1), (true, 0)]);
+        assert_eq!(
+            decode_signature(&writer.bytes),
+            Err(EncodingError::NegativeZero { index: 1 })
+        );
+
+        // Coefficient above the compression bound
+        let writer = signature_with(&[(false, MAX_SIGNATURE_COEFFICIENT + 1)]);
+        assert_eq!(
+            decode_signature(&writer.bytes),
+            Err(EncodingError::CoefficientTooLarge { index: 0 })
+        );
+
+        // Non-zero unused bits in the last byte
+        let mut writer = signature_with(&[(false, 128)]);
+        assert_ne!(writer.n_bits % 8, 0);
+        writer.push(1, 8 - writer.n_bits % 8);
+        assert_eq!(
+            decode_signature(&writer.bytes),
+            Err(EncodingError::NonZeroTrailingBits)
+        );
+
+        // Trailing bytes after a compressed signature
+        let mut bytes = signature_with(&[]).bytes;
+        bytes.push(0);
+        assert_eq!(
+            decode_signature(&bytes),
+            Err(EncodingError::TrailingBytes(1))
+        );
+
+        // Truncated signature
+        let bytes = signature_with(&[]).bytes;
+        assert_eq!(
+            decode_signature(&bytes[..bytes.len() - 1]),
+            Err(EncodingError::Truncated)
+        );
+
+        // Other signature formats
+        let mut bytes = signature_with(&[]).bytes;
+        bytes[0] = 0x59;
+        assert_eq!(
+            decode_signature(&bytes),
+            Err(EncodingError::InvalidHeader(0x59))
+        );
+    }
+}
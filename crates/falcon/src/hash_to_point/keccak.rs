@@ -0,0 +1,578 @@
+//! # Keccak-f[1600] Component
+//!
+//! This module implements the STARK proof component for the Keccak-f[1600] permutation
+//! used by the SHAKE256 sponge of HashToPoint.
+//!
+//! # Trace Structure
+//!
+//! Every permutation takes 24 consecutive rows, one per round. Each row holds:
+//! - `enabler`, `instance`, `step`: whether the permutation is used and its tag, constant
+//!   over the 24 rounds
+//! - `a[1600]`: the bits of the state at the start of the round
+//! - `h[640]`: for each column (x, z), two bits with `Σ_y a[x][y][z] = parity + 2 * (h0 + h1)`
+//! - `m[1600]`: the carries of the θ step, `a + c[x - 1][z] + c[x + 1][z - 1] = θ + 2 * m`
+//! - `out[1600]`: the bits of the state after the χ step, before the round constant
+//!
+//! The rows after the last full permutation are left at zero.
+//!
+//! # Constraints
+//!
+//! All the constraints have degree 2:
+//! - Every bit, column parity and θ bit is boolean
+//! - χ: `(out - b0)^2 = (1 - b1) * b2`, where `b` are the θ bits moved by the ρ and π steps
+//! - ι and chaining: on every round but the last, `a` of the next row is `out` XORed with
+//!   the round constant, which preprocessed columns provide
+//! - The tags and the enabler are constant over the permutation
+//!
+//! # Lookups
+//!
+//! An enabled permutation consumes its input state, on its first round, from the keccak
+//! input relation and emits its output state, on its last round, to the keccak output
+//! relation. Both are sent as `(instance, step, 100 limbs of 16 bits)`.
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{
+        ColumnVec,
+        channel::Channel,
+        fields::{
+            m31::{BaseField, M31},
+            qm31::SecureField,
+        },
+        pcs::TreeVec,
+        poly::circle::CanonicCoset,
+        utils::bit_reverse_coset_to_circle_domain_order,
+    },
+    prover::{
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, LogupTraceGenerator, ORIGINAL_TRACE_IDX,
+    Relation, RelationEntry, preprocessed_columns::PreProcessedColumnId,
+};
+
+use crate::{
+    big_air::relation::{KeccakInputLookupElements, KeccakOutputLookupElements},
+    hash_to_point::{
+        LIMB_BITS, N_ROUNDS, ROTATIONS, ROUND_CONSTANT_BITS, ROUND_CONSTANTS, STATE_BITS,
+        STATE_LIMBS, bit_index, chi, limbs, rho_pi, theta,
+    },
+};
+
+/// Index of the enabler column.
+const ENABLER: usize = 0;
+/// Index of the instance tag column.
+const INSTANCE: usize = 1;
+/// Index of the step tag column.
+const STEP: usize = 2;
+/// Index of the first state bit column.
+const A: usize = 3;
+/// Index of the first column parity helper column.
+const H: usize = A + STATE_BITS;
+/// Index of the first θ carry column.
+const M: usize = H + 2 * 320;
+/// Index of the first χ output column.
+const OUT: usize = M + STATE_BITS;
+/// Number of columns of the component.
+pub const N_COLUMNS: usize = OUT + STATE_BITS;
+
+/// A Keccak-f[1600] permutation of the sponge of an instance.
+#[derive(Debug, Clone)]
+pub struct Permutation {
+    /// Index of the instance in the batch
+    pub instance: u32,
+    /// Index of the sponge step that feeds the permutation
+    pub step: u32,
+    /// The input state
+    pub input: [u64; 25],
+}
+
+/// The preprocessed columns describing the round of each row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundColumn {
+    /// 1 on the first round of every permutation
+    IsFirst,
+    /// 1 on the last round of every permutation
+    IsLast,
+    /// 1 on every round of a permutation but the last one
+    NotLast,
+    /// Bit `b` of the round constant of the row, on every round but the last one
+    RoundConstantBit(usize),
+}
+
+/// Preprocessed column of the round flags of a trace of `2^log_size` rows.
+#[derive(Debug, Clone)]
+pub struct RoundFlags {
+    pub log_size: u32,
+    pub column: RoundColumn,
+}
+
+impl RoundFlags {
+    pub fn new(log_size: u32, column: RoundColumn) -> Self {
+        Self { log_size, column }
+    }
+
+    /// Returns all the round flag columns of a trace of `2^log_size` rows.
+    pub fn all(log_size: u32) -> Vec<Self> {
+        [
+            RoundColumn::IsFirst,
+            RoundColumn::IsLast,
+            RoundColumn::NotLast,
+        ]
+        .into_iter()
+        .chain(ROUND_CONSTANT_BITS.map(RoundColumn::RoundConstantBit))
+        .map(|column| Self::new(log_size, column))
+        .collect()
+    }
+
+    /// Generates the preprocessed column.
+    ///
+    /// Row `r` is round `r % 24` of permutation `r / 24`, the rows after the last full
+    /// permutation have no round. The column is permuted like the trace, which is read at
+    /// offset 1.
+    pub fn gen_column_simd(&self) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+        let n_rows = 1 << self.log_size;
+        let n_round_rows = n_rows / N_ROUNDS * N_ROUNDS;
+        let mut values = (0..n_rows)
+            .map(|row| {
+                let round = row % N_ROUNDS;
+                let value = row < n_round_rows
+                    && match self.column {
+                        RoundColumn::IsFirst => round == 0,
+                        RoundColumn::IsLast => round == N_ROUNDS - 1,
+                        RoundColumn::NotLast => round != N_ROUNDS - 1,
+                        RoundColumn::RoundConstantBit(bit) => {
+                            round != N_ROUNDS - 1 && (ROUND_CONSTANTS[round] >> bit) & 1 == 1
+                        }
+                    };
+                M31(value as u32)
+            })
+            .collect::<Vec<_>>();
+        bit_reverse_coset_to_circle_domain_order(&mut values);
+        CircleEvaluation::new(
+            CanonicCoset::new(self.log_size).circle_domain(),
+            BaseColumn::from_iter(values),
+        )
+    }
+
+    /// Returns the unique identifier for this preprocessed column.
+    pub fn id(&self) -> PreProcessedColumnId {
+        let name = match self.column {
+            RoundColumn::IsFirst => "is_first".to_string(),
+            RoundColumn::IsLast => "is_last".to_string(),
+            RoundColumn::NotLast => "not_last".to_string(),
+            RoundColumn::RoundConstantBit(bit) => format!("round_constant_bit_{bit}"),
+        };
+        PreProcessedColumnId {
+            id: format!("keccak_{}_{}", name, self.log_size),
+        }
+    }
+}
+
+/// Claim parameters for the Keccak-f[1600] component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    /// The log base 2 of the trace size
+    pub log_size: u32,
+}
+
+impl Claim {
+    /// Returns the claim of a trace holding `n_permutations` permutations.
+    pub fn new(n_permutations: usize) -> Self {
+        Self {
+            log_size: (N_ROUNDS * n_permutations)
+                .next_power_of_two()
+                .ilog2()
+                .max(LOG_N_LANES),
+        }
+    }
+
+    /// Returns the log sizes for the traces.
+    ///
+    /// [preprocessed_trace, trace, interaction_trace]
+    pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
+        let trace_log_sizes = vec![self.log_size; N_COLUMNS];
+        TreeVec::new(vec![vec![], trace_log_sizes, vec![]])
+    }
+
+    /// Mixes the claim parameters into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size as u64);
+    }
+
+    /// Generates the trace for the Keccak-f[1600] component.
+    ///
+    /// The permutations are laid out one after the other, and the remaining full
+    /// permutations of the trace permute the zero state with a zero enabler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the permutations do not fit in the trace.
+    pub fn gen_trace(
+        &self,
+        permutations: &[Permutation],
+    ) -> ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>> {
+        let n_rows = 1 << self.log_size;
+        let n_permutations = n_rows / N_ROUNDS;
+        assert!(
+            permutations.len() <= n_permutations,
+            "too many permutations"
+        );
+        let mut columns = vec![vec![M31(0); n_rows]; N_COLUMNS];
+        let padding = Permutation {
+            instance: 0,
+            step: 0,
+            input: [0; 25],
+        };
+        for (index, permutation) in permutations
+            .iter()
+            .map(|permutation| (true, permutation))
+            .chain(std::iter::repeat((false, &padding)))
+            .take(n_permutations)
+            .enumerate()
+        {
+            let (enabled, permutation) = permutation;
+            let mut state = permutation.input;
+            for round in 0..N_ROUNDS {
+                let row = index * N_ROUNDS + round;
+                columns[ENABLER][row] = M31(enabled as u32);
+                columns[INSTANCE][row] = M31(permutation.instance);
+                columns[STEP][row] = M31(permutation.step);
+                state = write_round(&mut columns, row, &state, round);
+            }
+        }
+        let domain = CanonicCoset::new(self.log_size).circle_domain();
+        columns
+            .into_iter()
+            .map(|mut column| {
+                // The state is read at offset 1
+                bit_reverse_coset_to_circle_domain_order(&mut column);
+                CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
+                    domain,
+                    BaseColumn::from_iter(column),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Writes the witness of round `round` applied to `state` on `row`, and returns the
+/// state after the round.
+fn write_round(columns: &mut [Vec<M31>], row: usize, state: &[u64; 25], round: usize) -> [u64; 25] {
+    let bit = |lanes: &[u64; 25], x: usize, y: usize, z: usize| (lanes[x + 5 * y] >> z) & 1;
+    let parities: [u64; 5] =
+        std::array::from_fn(|x| (0..5).fold(0, |acc, y| acc ^ state[x + 5 * y]));
+    let out = chi(&rho_pi(&theta(state)));
+    for x in 0..5 {
+        for z in 0..64 {
+            let count = (0..5).map(|y| bit(state, x, y, z)).sum::<u64>();
+            columns[H + 2 * (64 * x + z)][row] = M31((count >= 2) as u32);
+            columns[H + 2 * (64 * x + z) + 1][row] = M31((count >= 4) as u32);
+            for y in 0..5 {
+                let index = bit_index(x, y, z);
+                let carry = bit(state, x, y, z)
+                    + ((parities[(x + 4) % 5] >> z) & 1)
+                    + ((parities[(x + 1) % 5] >> ((z + 63) % 64)) & 1);
+                columns[A + index][row] = M31(bit(state, x, y, z) as u32);
+                columns[M + index][row] = M31((carry >= 2) as u32);
+                columns[OUT + index][row] = M31(bit(&out, x, y, z) as u32);
+            }
+        }
+    }
+    let mut next = out;
+    next[0] ^= ROUND_CONSTANTS[round];
+    next
+}
+
+/// Component used in the framework for the Keccak-f[1600] permutation.
+#[derive(Debug, Clone)]
+pub struct Eval {
+    /// The claim parameters
+    pub claim: Claim,
+    /// Lookup elements for the input states of the permutations
+    pub keccak_input_lookup_elements: KeccakInputLookupElements,
+    /// Lookup elements for the output states of the permutations
+    pub keccak_output_lookup_elements: KeccakOutputLookupElements,
+}
+
+impl FrameworkEval for Eval {
+    fn log_size(&self) -> u32 {
+        self.claim.log_size
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.claim.log_size + 1
+    }
+
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        let log_size = self.claim.log_size;
+        let is_first =
+            eval.get_preprocessed_column(RoundFlags::new(log_size, RoundColumn::IsFirst).id());
+        let is_last =
+            eval.get_preprocessed_column(RoundFlags::new(log_size, RoundColumn::IsLast).id());
+        let not_last =
+            eval.get_preprocessed_column(RoundFlags::new(log_size, RoundColumn::NotLast).id());
+        let round_constant_bits = ROUND_CONSTANT_BITS.map(|bit| {
+            eval.get_preprocessed_column(
+                RoundFlags::new(log_size, RoundColumn::RoundConstantBit(bit)).id(),
+            )
+        });
+
+        let [enabler, enabler_next] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [instance, instance_next] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let [step, step_next] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let (a, a_next): (Vec<_>, Vec<_>) = (0..STATE_BITS)
+            .map(|_| {
+                let [current, next] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+                (current, next)
+            })
+            .unzip();
+        let h = (0..2 * 320)
+            .map(|_| eval.next_trace_mask())
+            .collect::<Vec<_>>();
+        let m = (0..STATE_BITS)
+            .map(|_| eval.next_trace_mask())
+            .collect::<Vec<_>>();
+        let out = (0..STATE_BITS)
+            .map(|_| eval.next_trace_mask())
+            .collect::<Vec<_>>();
+
+        let one = E::F::one();
+        let two = E::F::from(M31(2));
+        let boolean = |x: &E::F| x.clone() * (x.clone() - one.clone());
+
+        // The tags and the enabler are constant over the permutation
+        eval.add_constraint(boolean(&enabler));
+        for (current, next) in [
+            (enabler.clone(), enabler_next),
+            (instance.clone(), instance_next),
+            (step.clone(), step_next),
+        ] {
+            eval.add_constraint(not_last.clone() * (next - current));
+        }
+
+        for bit in a.iter().chain(h.iter()).chain(m.iter()).chain(out.iter()) {
+            eval.add_constraint(boolean(bit));
+        }
+
+        // θ: the parity of column (x, z) is Σ_y a[x][y][z] - 2 * (h0 + h1)
+        let parities = (0..320)
+            .map(|i| {
+                let (x, z) = (i / 64, i % 64);
+                (0..5)
+                    .map(|y| a[bit_index(x, y, z)].clone())
+                    .reduce(|acc, bit| acc + bit)
+                    .unwrap()
+                    - two.clone() * (h[2 * i].clone() + h[2 * i + 1].clone())
+            })
+            .collect::<Vec<_>>();
+        for parity in parities.iter() {
+            eval.add_constraint(boolean(parity));
+        }
+        let parity = |x: usize, z: usize| parities[64 * (x % 5) + (z % 64)].clone();
+        let theta = (0..STATE_BITS)
+            .map(|index| {
+                let (x, z) = ((index / 64) % 5, index % 64);
+                a[index].clone() + parity(x + 4, z) + parity(x + 1, z + 63)
+                    - two.clone() * m[index].clone()
+            })
+            .collect::<Vec<_>>();
+        for bit in theta.iter() {
+            eval.add_constraint(boolean(bit));
+        }
+
+        // ρ and π: lane (X, Y) after the move is lane (x, y) = (((Y - 3X) / 2) mod 5, X)
+        // rotated by the offset of (x, y)
+        let moved = |x_to: usize, y_to: usize, z: usize| {
+            let (x_to, y_to) = (x_to % 5, y_to % 5);
+            let y = x_to;
+            let x = ((y_to + 15 - 3 * y) * 3) % 5;
+            theta[bit_index(x, y, z + 64 - ROTATIONS[x + 5 * y] as usize)].clone()
+        };
+
+        // χ: out = b0 XOR ((1 - b1) AND b2), i.e. (out - b0)^2 = (1 - b1) * b2
+        for y in 0..5 {
+            for x in 0..5 {
+                for z in 0..64 {
+                    let out = out[bit_index(x, y, z)].clone();
+                    let b0 = moved(x, y, z);
+                    let b1 = moved(x + 1, y, z);
+                    let b2 = moved(x + 2, y, z);
+                    eval.add_constraint(
+                        (out.clone() - b0.clone()) * (out - b0) - (one.clone() - b1) * b2,
+                    );
+                }
+            }
+        }
+
+        // ι and chaining: the next round starts from out XOR the round constant
+        for (index, (next, out)) in a_next.into_iter().zip(out.iter()).enumerate() {
+            let mut constraint = not_last.clone() * (next - out.clone());
+            if let Some(position) = ROUND_CONSTANT_BITS.iter().position(|&b| b == index) {
+                constraint = constraint
+                    - round_constant_bits[position].clone()
+                        * (one.clone() - two.clone() * out.clone());
+            }
+            eval.add_constraint(constraint);
+        }
+
+        // The input state is consumed on the first round
+        let input = [instance.clone(), step.clone()]
+            .into_iter()
+            .chain(limbs(&a))
+            .collect::<Vec<_>>();
+        eval.add_to_relation(RelationEntry::new(
+            &self.keccak_input_lookup_elements,
+            E::EF::from(enabler.clone() * is_first),
+            &input,
+        ));
+
+        // The output state, with the constant of the last round, is emitted on the last round
+        let output_bits = out
+            .iter()
+            .enumerate()
+            .map(|(index, bit)| {
+                if index < 64 && (ROUND_CONSTANTS[N_ROUNDS - 1] >> index) & 1 == 1 {
+                    one.clone() - bit.clone()
+                } else {
+                    bit.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        let output = [instance, step]
+            .into_iter()
+            .chain(limbs(&output_bits))
+            .collect::<Vec<_>>();
+        eval.add_to_relation(RelationEntry::new(
+            &self.keccak_output_lookup_elements,
+            -E::EF::from(enabler * is_last),
+            &output,
+        ));
+
+        eval.finalize_logup();
+        eval
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionClaim {
+    /// The claimed sum for the interaction
+    pub claimed_sum: SecureField,
+}
+
+impl InteractionClaim {
+    /// Mixes the interaction claim into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_felts(&[self.claimed_sum]);
+    }
+
+    /// Generates the interaction trace for the Keccak-f[1600] component.
+    ///
+    /// # Parameters
+    ///
+    /// - `trace`: The trace columns of the component
+    /// - `keccak_input_lookup_elements`: The lookup elements for the input states
+    /// - `keccak_output_lookup_elements`: The lookup elements for the output states
+    pub fn gen_interaction_trace(
+        trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+        keccak_input_lookup_elements: &KeccakInputLookupElements,
+        keccak_output_lookup_elements: &KeccakOutputLookupElements,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        InteractionClaim,
+    ) {
+        let log_size = trace[0].domain.log_size();
+        let is_first = RoundFlags::new(log_size, RoundColumn::IsFirst).gen_column_simd();
+        let is_last = RoundFlags::new(log_size, RoundColumn::IsLast).gen_column_simd();
+        let mut logup_gen = LogupTraceGenerator::new(log_size);
+
+        let packed_limbs = |vec_row: usize, start: usize, flip_round_constant: bool| {
+            (0..STATE_LIMBS).map(move |limb| {
+                (0..LIMB_BITS).fold(PackedM31::broadcast(M31(0)), |acc, i| {
+                    let index = LIMB_BITS * limb + i;
+                    let mut bit = trace[start + index].data[vec_row];
+                    if flip_round_constant
+                        && index < 64
+                        && (ROUND_CONSTANTS[N_ROUNDS - 1] >> index) & 1 == 1
+                    {
+                        bit = PackedM31::broadcast(M31(1)) - bit;
+                    }
+                    acc + PackedM31::broadcast(M31(1 << i)) * bit
+                })
+            })
+        };
+
+        // Input states, consumed on the first round
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+            let values = [trace[INSTANCE].data[vec_row], trace[STEP].data[vec_row]]
+                .into_iter()
+                .chain(packed_limbs(vec_row, A, false))
+                .collect::<Vec<_>>();
+            let denom: PackedQM31 = keccak_input_lookup_elements.combine(&values);
+            let numerator = PackedQM31::from(trace[ENABLER].data[vec_row] * is_first.data[vec_row]);
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        // Output states, emitted on the last round
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+            let values = [trace[INSTANCE].data[vec_row], trace[STEP].data[vec_row]]
+                .into_iter()
+                .chain(packed_limbs(vec_row, OUT, true))
+                .collect::<Vec<_>>();
+            let denom: PackedQM31 = keccak_output_lookup_elements.combine(&values);
+            let numerator = -PackedQM31::from(trace[ENABLER].data[vec_row] * is_last.data[vec_row]);
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
+        (interaction_trace, InteractionClaim { claimed_sum })
+    }
+}
+
+/// Type alias for the Keccak-f[1600] component.
+pub type Component = FrameworkComponent<Eval>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_point::keccak_f;
+
+    #[test]
+    fn test_trace_chains_the_rounds() {
+        let mut input = [0; 25];
+        input[3] = 0x0123_4567_89AB_CDEF;
+        let mut expected = input;
+        keccak_f(&mut expected);
+
+        let claim = Claim::new(1);
+        let mut columns = vec![vec![M31(0); N_ROUNDS]; N_COLUMNS];
+        let mut state = input;
+        for round in 0..N_ROUNDS {
+            state = write_round(&mut columns, round, &state, round);
+        }
+        assert_eq!(state, expected);
+        assert_eq!(claim.log_size, 5);
+
+        // The first row holds the input state
+        let row_state = |row: usize| -> [u64; 25] {
+            std::array::from_fn(|lane| {
+                (0..64).fold(0, |acc, z| {
+                    acc | ((columns[A + 64 * lane + z][row].0 as u64) << z)
+                })
+            })
+        };
+        assert_eq!(row_state(0), input);
+    }
+}
@@ -0,0 +1,376 @@
+//! # HashToPoint
+//!
+//! This module proves Falcon's `HashToPoint`: the message point c is derived from the
+//! public nonce and message by absorbing `nonce || message` into SHAKE256 and rejection
+//! sampling its output stream.
+//!
+//! # Algorithm
+//!
+//! 1. The input is padded with the SHAKE domain separator (`0x1F ... 0x80`) and split in
+//!    blocks of 136 bytes, each XORed into the rate of a Keccak-f[1600] state and permuted
+//! 2. The rate of the state is squeezed block after block, permuting between blocks
+//! 3. The output stream is read as 16-bit big-endian chunks t: chunks with t < 5q are
+//!    accepted and reduced mod q, the others are rejected, until n coefficients are found
+//!
+//! # Components
+//!
+//! - **Keccak**: One row per round of Keccak-f[1600], one permutation every 24 rows
+//! - **Sponge**: One row per absorbed or squeezed block, chaining the permutations and
+//!   binding the padded message blocks to the public inputs
+//! - **Sampler**: One row per squeezed 16-bit chunk, emitting the accepted coefficients
+//!   of the message point consumed by the subtraction component
+//!
+//! # State Layout
+//!
+//! Bit `64 * (x + 5 * y) + z` of the state is bit z of lane (x, y), and limb `l` of the
+//! state is made of bits `16 * l..16 * (l + 1)`. The 68 first limbs are the rate, limb
+//! `t` holding bytes `2t` (low) and `2t + 1` (high) of the rate.
+
+pub mod keccak;
+pub mod sampler;
+pub mod sponge;
+
+use stwo::core::fields::m31::M31;
+
+use crate::zq::Q;
+
+/// Number of bytes absorbed or squeezed per permutation (the rate of SHAKE256).
+pub const RATE_BYTES: usize = 136;
+/// Number of lanes in the rate.
+pub const RATE_LANES: usize = RATE_BYTES / 8;
+/// Number of bits of the Keccak-f[1600] state.
+pub const STATE_BITS: usize = 1600;
+/// Number of bits of the rate.
+pub const RATE_BITS: usize = RATE_BYTES * 8;
+/// Number of bits per limb when the state is sent through a lookup relation.
+pub const LIMB_BITS: usize = 16;
+/// Number of 16-bit limbs of the state.
+pub const STATE_LIMBS: usize = STATE_BITS / LIMB_BITS;
+/// Number of 16-bit limbs of the rate, which is also the number of chunks per squeezed block.
+pub const RATE_LIMBS: usize = RATE_BITS / LIMB_BITS;
+/// Number of rounds of Keccak-f[1600].
+pub const N_ROUNDS: usize = 24;
+/// Exclusive bound of the accepted 16-bit chunks.
+pub const REJECTION_BOUND: u32 = 5 * Q;
+/// Largest number of squeezed blocks a proof may use.
+///
+/// Falcon-1024 needs 17 blocks on average, a proof needing more than 32 blocks is
+/// astronomically unlikely.
+pub const MAX_SQUEEZE_BLOCKS: u32 = 32;
+
+/// Round constants of Keccak-f[1600].
+pub const ROUND_CONSTANTS: [u64; N_ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The only bits of lane (0, 0) that the round constants can flip.
+pub const ROUND_CONSTANT_BITS: [usize; 7] = [0, 1, 3, 7, 15, 31, 63];
+
+/// Rotation offsets of the ρ step, indexed by `x + 5 * y`.
+pub const ROTATIONS: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// Returns the index of bit z of lane (x, y) in the state.
+pub fn bit_index(x: usize, y: usize, z: usize) -> usize {
+    64 * ((x % 5) + 5 * (y % 5)) + (z % 64)
+}
+
+/// Applies round `round` of Keccak-f[1600] to the state.
+pub fn keccak_round(state: &mut [u64; 25], round: usize) {
+    *state = chi(&rho_pi(&theta(state)));
+    state[0] ^= ROUND_CONSTANTS[round];
+}
+
+/// Applies the Keccak-f[1600] permutation to the state.
+pub fn keccak_f(state: &mut [u64; 25]) {
+    for round in 0..N_ROUNDS {
+        keccak_round(state, round);
+    }
+}
+
+/// The θ step: every bit is XORed with the parities of two neighbouring columns.
+pub(crate) fn theta(state: &[u64; 25]) -> [u64; 25] {
+    let parities: [u64; 5] =
+        std::array::from_fn(|x| (0..5).fold(0, |acc, y| acc ^ state[x + 5 * y]));
+    std::array::from_fn(|i| {
+        let x = i % 5;
+        state[i] ^ parities[(x + 4) % 5] ^ parities[(x + 1) % 5].rotate_left(1)
+    })
+}
+
+/// The ρ and π steps: lane (x, y) is rotated and moved to lane (y, 2x + 3y).
+pub(crate) fn rho_pi(state: &[u64; 25]) -> [u64; 25] {
+    let mut moved = [0; 25];
+    for x in 0..5 {
+        for y in 0..5 {
+            moved[y + 5 * ((2 * x + 3 * y) % 5)] =
+                state[x + 5 * y].rotate_left(ROTATIONS[x + 5 * y]);
+        }
+    }
+    moved
+}
+
+/// The χ step, without the round constant of the ι step.
+pub(crate) fn chi(state: &[u64; 25]) -> [u64; 25] {
+    std::array::from_fn(|i| {
+        let (x, y) = (i % 5, i / 5);
+        state[i] ^ (!state[(x + 1) % 5 + 5 * y] & state[(x + 2) % 5 + 5 * y])
+    })
+}
+
+/// Returns the 16-bit limbs of the state.
+pub fn state_limbs(state: &[u64; 25]) -> [u32; STATE_LIMBS] {
+    std::array::from_fn(|limb| ((state[limb / 4] >> (16 * (limb % 4))) & 0xFFFF) as u32)
+}
+
+/// Returns the 16-bit limbs of a block of the rate, limb `t` being `block[2t] + 256 * block[2t + 1]`.
+pub fn block_limbs(block: &[u8; RATE_BYTES]) -> [u32; RATE_LIMBS] {
+    std::array::from_fn(|limb| block[2 * limb] as u32 + ((block[2 * limb + 1] as u32) << 8))
+}
+
+/// Returns the 16-bit limbs of a state or a block given by its bits, as constraint
+/// expressions.
+pub(crate) fn limbs<F>(bits: &[F]) -> Vec<F>
+where
+    F: Clone + std::ops::Add<Output = F> + std::ops::Mul<Output = F> + From<M31>,
+{
+    bits.chunks(LIMB_BITS)
+        .map(|limb_bits| {
+            limb_bits
+                .iter()
+                .enumerate()
+                .map(|(i, bit)| F::from(M31(1 << i)) * bit.clone())
+                .reduce(|acc, term| acc + term)
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Returns the SHAKE256 padded blocks of `nonce || message`.
+pub fn padded_blocks(nonce: &[u8], message: &[u8]) -> Vec<[u8; RATE_BYTES]> {
+    let mut bytes = [nonce, message].concat();
+    bytes.push(0x1F);
+    bytes.resize(bytes.len().div_ceil(RATE_BYTES) * RATE_BYTES, 0);
+    *bytes.last_mut().unwrap() |= 0x80;
+    bytes
+        .chunks(RATE_BYTES)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect()
+}
+
+/// XORs a block into the rate of the state.
+pub fn xor_block(state: &mut [u64; 25], block: &[u8; RATE_BYTES]) {
+    for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+        *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+}
+
+/// Returns the rate of the state as bytes.
+pub fn squeeze_block(state: &[u64; 25]) -> [u8; RATE_BYTES] {
+    let mut block = [0; RATE_BYTES];
+    for (bytes, lane) in block.chunks_mut(8).zip(state.iter()) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    block
+}
+
+/// Returns the state of SHAKE256 after absorbing `input`, ready to squeeze its first block.
+fn absorb(input_blocks: &[[u8; RATE_BYTES]]) -> [u64; 25] {
+    let mut state = [0; 25];
+    for block in input_blocks {
+        xor_block(&mut state, block);
+        keccak_f(&mut state);
+    }
+    state
+}
+
+/// Returns the `output_len` first bytes of SHAKE256(input).
+pub fn shake256(input: &[u8], output_len: usize) -> Vec<u8> {
+    let mut state = absorb(&padded_blocks(input, &[]));
+    let mut output = vec![];
+    while output.len() < output_len {
+        if !output.is_empty() {
+            keccak_f(&mut state);
+        }
+        output.extend(squeeze_block(&state));
+    }
+    output.truncate(output_len);
+    output
+}
+
+/// Rejection samples the 16-bit chunks of the squeezed blocks of `nonce || message`.
+///
+/// Returns the `n` coefficients of the message point and the number of blocks squeezed
+/// to find them.
+fn sample(nonce: &[u8], message: &[u8], n: usize) -> (Vec<u32>, u32) {
+    let mut state = absorb(&padded_blocks(nonce, message));
+    let mut coefficients = Vec::with_capacity(n);
+    let mut n_blocks = 0;
+    while coefficients.len() < n {
+        if n_blocks > 0 {
+            keccak_f(&mut state);
+        }
+        n_blocks += 1;
+        for chunk in squeeze_block(&state).chunks(2) {
+            let t = ((chunk[0] as u32) << 8) | chunk[1] as u32;
+            if t < REJECTION_BOUND && coefficients.len() < n {
+                coefficients.push(t % Q);
+            }
+        }
+    }
+    (coefficients, n_blocks)
+}
+
+/// Falcon's HashToPoint: returns the `n` coefficients of the message point of
+/// `nonce || message`, in [0, Q).
+pub fn hash_to_point(nonce: &[u8], message: &[u8], n: usize) -> Vec<u32> {
+    sample(nonce, message, n).0
+}
+
+/// Returns the number of blocks to squeeze to find the `n` coefficients of the message
+/// point of `nonce || message`.
+pub fn squeeze_blocks(nonce: &[u8], message: &[u8], n: usize) -> u32 {
+    sample(nonce, message, n).1
+}
+
+/// One row of the sponge: the absorption of a padded block or the squeezing of a block.
+///
+/// Step `j` reads the output of permutation `j - 1` (or the zero state for the first
+/// step), XORs `block` into its rate and, if `has_next`, sends it to permutation `j`.
+/// The steps of an instance are fully determined by its public nonce and message and by
+/// the number of squeezed blocks, so the verifier rebuilds them itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpongeStep {
+    /// Index of the step within its instance
+    pub j: u32,
+    /// Whether this is the first step, which starts from the zero state
+    pub is_first: bool,
+    /// Whether this step squeezes a block
+    pub is_squeeze: bool,
+    /// Whether the state is permuted after this step
+    pub has_next: bool,
+    /// Index of the squeezed block, 0 for the absorbing steps
+    pub squeeze_index: u32,
+    /// The padded block absorbed by this step, zero for the squeezing steps
+    pub block: [u8; RATE_BYTES],
+}
+
+/// Returns the sponge steps absorbing `nonce || message` and squeezing `squeeze_blocks` blocks.
+pub fn sponge_steps(nonce: &[u8], message: &[u8], squeeze_blocks: u32) -> Vec<SpongeStep> {
+    assert!(squeeze_blocks > 0, "at least one block must be squeezed");
+    let absorbed = padded_blocks(nonce, message);
+    let n_steps = absorbed.len() as u32 + squeeze_blocks;
+    absorbed
+        .into_iter()
+        .map(Some)
+        .chain((0..squeeze_blocks).map(|_| None))
+        .enumerate()
+        .map(|(j, block)| {
+            let j = j as u32;
+            let is_squeeze = block.is_none();
+            SpongeStep {
+                j,
+                is_first: j == 0,
+                is_squeeze,
+                has_next: j + 1 < n_steps,
+                squeeze_index: if is_squeeze {
+                    j + squeeze_blocks - n_steps
+                } else {
+                    0
+                },
+                block: block.unwrap_or([0; RATE_BYTES]),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{self, falcon_512};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_shake256_known_answers() {
+        assert_eq!(
+            hex(&shake256(b"", 32)),
+            "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f"
+        );
+        assert_eq!(
+            hex(&shake256(b"abc", 32)),
+            "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739"
+        );
+        // Two absorbed blocks
+        let input = (0..200).map(|i| i as u8).collect::<Vec<_>>();
+        assert_eq!(
+            hex(&shake256(&input, 16)),
+            "4ee1ca03272b05d3bfb1e1c79a967f82"
+        );
+    }
+
+    #[test]
+    fn test_hash_to_point_matches_test_vectors() {
+        assert_eq!(
+            hash_to_point(input::NONCE, input::MESSAGE, 1024),
+            input::MSG_POINT
+        );
+        assert_eq!(squeeze_blocks(input::NONCE, input::MESSAGE, 1024), 17);
+        assert_eq!(
+            hash_to_point(falcon_512::NONCE, falcon_512::MESSAGE, 512),
+            falcon_512::MSG_POINT
+        );
+        assert_eq!(
+            squeeze_blocks(falcon_512::NONCE, falcon_512::MESSAGE, 512),
+            9
+        );
+    }
+
+    #[test]
+    fn test_sponge_steps() {
+        // 40 + 96 bytes of input fill a block, the padding goes to a second one
+        let steps = sponge_steps(&[0; 40], &[0; 96], 3);
+        assert_eq!(steps.len(), 5);
+        assert!(steps[0].is_first && !steps[1].is_first);
+        assert_eq!(steps[1].block[0], 0x1F);
+        assert_eq!(steps[1].block[RATE_BYTES - 1], 0x80);
+        assert_eq!(
+            steps.iter().map(|s| s.is_squeeze).collect::<Vec<_>>(),
+            [false, false, true, true, true]
+        );
+        assert_eq!(
+            steps.iter().map(|s| s.squeeze_index).collect::<Vec<_>>(),
+            [0, 0, 0, 1, 2]
+        );
+        assert_eq!(
+            steps.iter().map(|s| s.has_next).collect::<Vec<_>>(),
+            [true, true, true, true, false]
+        );
+    }
+}
@@ -0,0 +1,582 @@
+//! # HashToPoint Rejection Sampler Component
+//!
+//! This module implements the STARK proof component turning the squeezed 16-bit chunks
+//! of the SHAKE256 sponge into the coefficients of the message point.
+//!
+//! # Trace Structure
+//!
+//! Every squeezed chunk of every instance takes one row, the chunks of instance `k`
+//! starting on row `k * n_chunks`. Preprocessed columns give the `enabler`, the
+//! `instance`, the `position` of the chunk and the `is_first`, `is_last` and `not_last`
+//! flags of each row. Each row holds:
+//! - `b0`, `b1`: the two bytes of the chunk, in stream order
+//! - `accept`: whether `t = 256 * b0 + b1` is below 5q
+//! - `q0`, `q1`, `q2`: the bits of `t / q` when accepted
+//! - `value`: `t mod q` when accepted
+//! - `count`: the number of coefficients already sampled by the instance
+//! - `done`, `inv`: whether `count` reached n, and the inverse of `count - n` otherwise
+//! - `take`: whether the chunk is taken as a coefficient
+//!
+//! # Constraints
+//!
+//! - `accept = 1`: `t = (q0 + 2 * q1 + 4 * q2) * q + value` with a quotient of at most 4
+//!   and `value` range checked in [0, q)
+//! - `accept = 0`: `t - 5q` is range checked in [0, 4096)
+//! - `take = accept * (1 - done)`, and `count` starts at 0, grows by `take` and ends at n
+//!
+//! # Lookups
+//!
+//! An enabled row consumes its chunk from the sponge, and a taken chunk emits
+//! `(k * n + count, value)` to the message point relation consumed by the subtraction.
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{
+        ColumnVec,
+        channel::Channel,
+        fields::{
+            FieldExpOps,
+            m31::{BaseField, M31},
+            qm31::SecureField,
+        },
+        pcs::TreeVec,
+        poly::circle::CanonicCoset,
+        utils::bit_reverse_coset_to_circle_domain_order,
+    },
+    prover::{
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, LogupTraceGenerator, ORIGINAL_TRACE_IDX,
+    Relation, RelationEntry, preprocessed_columns::PreProcessedColumnId,
+};
+
+use crate::{
+    big_air::relation::{
+        ChunkLookupElements, LookupElements, MsgPointLookupElements, RCLookupElements,
+    },
+    hash_to_point::{RATE_LIMBS, REJECTION_BOUND},
+    zq::Q,
+};
+
+/// Exclusive bound of the bytes of a chunk.
+pub const BYTE_BOUND: u32 = 256;
+/// Exclusive bound of `t - 5q` for a rejected chunk: `65535 - 5q < 4096`.
+pub const REJECTION_RANGE_BOUND: u32 = 4096;
+
+/// Index of the first byte column.
+const B0: usize = 0;
+/// Index of the second byte column.
+const B1: usize = 1;
+/// Index of the accept flag column.
+const ACCEPT: usize = 2;
+/// Index of the first quotient bit column.
+const QUOTIENT: usize = 3;
+/// Index of the value column.
+const VALUE: usize = 6;
+/// Index of the count column.
+const COUNT: usize = 7;
+/// Index of the done flag column.
+const DONE: usize = 8;
+/// Index of the inverse column.
+const INV: usize = 9;
+/// Index of the take flag column.
+const TAKE: usize = 10;
+/// Number of columns of the component.
+pub const N_COLUMNS: usize = 11;
+
+/// The preprocessed columns describing the chunk of each row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkColumn {
+    /// 1 on the rows holding a chunk
+    Enabler,
+    /// The instance of the chunk
+    Instance,
+    /// The position of the chunk in the squeezed stream of its instance
+    Position,
+    /// 1 on the first chunk of every instance
+    IsFirst,
+    /// 1 on the last chunk of every instance
+    IsLast,
+    /// 1 on every chunk of an instance but the last one
+    NotLast,
+}
+
+/// Preprocessed column of the chunk layout of a trace of `2^log_size` rows holding
+/// `n_chunks` chunks for each of `n_instances` instances.
+#[derive(Debug, Clone)]
+pub struct ChunkFlags {
+    pub log_size: u32,
+    pub n_instances: usize,
+    pub n_chunks: usize,
+    pub column: ChunkColumn,
+}
+
+impl ChunkFlags {
+    pub fn new(claim: &Claim, n_instances: usize, column: ChunkColumn) -> Self {
+        Self {
+            log_size: claim.log_size,
+            n_instances,
+            n_chunks: claim.n_chunks(),
+            column,
+        }
+    }
+
+    /// Returns all the chunk layout columns of the claim.
+    pub fn all(claim: &Claim, n_instances: usize) -> Vec<Self> {
+        [
+            ChunkColumn::Enabler,
+            ChunkColumn::Instance,
+            ChunkColumn::Position,
+            ChunkColumn::IsFirst,
+            ChunkColumn::IsLast,
+            ChunkColumn::NotLast,
+        ]
+        .into_iter()
+        .map(|column| Self::new(claim, n_instances, column))
+        .collect()
+    }
+
+    /// Generates the preprocessed column.
+    ///
+    /// The column is permuted like the trace, which is read at offset 1.
+    pub fn gen_column_simd(&self) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+        let n_rows = 1 << self.log_size;
+        let mut values = (0..n_rows)
+            .map(|row| {
+                let (instance, position) = (row / self.n_chunks, row % self.n_chunks);
+                if instance >= self.n_instances {
+                    return M31(0);
+                }
+                M31(match self.column {
+                    ChunkColumn::Enabler => 1,
+                    ChunkColumn::Instance => instance as u32,
+                    ChunkColumn::Position => position as u32,
+                    ChunkColumn::IsFirst => (position == 0) as u32,
+                    ChunkColumn::IsLast => (position == self.n_chunks - 1) as u32,
+                    ChunkColumn::NotLast => (position != self.n_chunks - 1) as u32,
+                })
+            })
+            .collect::<Vec<_>>();
+        bit_reverse_coset_to_circle_domain_order(&mut values);
+        CircleEvaluation::new(
+            CanonicCoset::new(self.log_size).circle_domain(),
+            BaseColumn::from_iter(values),
+        )
+    }
+
+    /// Returns the unique identifier for this preprocessed column.
+    pub fn id(&self) -> PreProcessedColumnId {
+        let name = match self.column {
+            ChunkColumn::Enabler => "enabler",
+            ChunkColumn::Instance => "instance",
+            ChunkColumn::Position => "position",
+            ChunkColumn::IsFirst => "is_first",
+            ChunkColumn::IsLast => "is_last",
+            ChunkColumn::NotLast => "not_last",
+        };
+        PreProcessedColumnId {
+            id: format!(
+                "sampler_{}_{}_{}_{}",
+                name, self.log_size, self.n_instances, self.n_chunks
+            ),
+        }
+    }
+}
+
+/// Claim parameters for the sampler component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    /// The log base 2 of the trace size
+    pub log_size: u32,
+    /// The number of blocks squeezed by every instance
+    pub squeeze_blocks: u32,
+}
+
+impl Claim {
+    /// Returns the claim of a trace sampling `squeeze_blocks` blocks for each of
+    /// `n_instances` instances.
+    pub fn new(n_instances: usize, squeeze_blocks: u32) -> Self {
+        Self {
+            log_size: (n_instances * RATE_LIMBS * squeeze_blocks as usize)
+                .next_power_of_two()
+                .ilog2()
+                .max(LOG_N_LANES),
+            squeeze_blocks,
+        }
+    }
+
+    /// Returns the number of chunks squeezed by every instance.
+    pub fn n_chunks(&self) -> usize {
+        RATE_LIMBS * self.squeeze_blocks as usize
+    }
+
+    /// Returns the log sizes for the traces.
+    ///
+    /// [preprocessed_trace, trace, interaction_trace]
+    pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
+        let trace_log_sizes = vec![self.log_size; N_COLUMNS];
+        TreeVec::new(vec![vec![], trace_log_sizes, vec![]])
+    }
+
+    /// Mixes the claim parameters into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size as u64);
+        channel.mix_u64(self.squeeze_blocks as u64);
+    }
+
+    /// Generates the trace for the sampler component.
+    ///
+    /// # Parameters
+    ///
+    /// - `chunks`: The squeezed 16-bit chunks of every instance
+    /// - `poly_size`: The number of coefficients to sample per instance
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing:
+    /// - The trace columns
+    /// - The coefficients of the message points of all the instances, one after the other
+    /// - The bytes of the chunks, for the byte range check
+    /// - The sampled values, for the range check against Q
+    /// - The rejected chunks minus 5q, for the rejection range check
+    ///
+    /// # Panics
+    ///
+    /// Panics if an instance does not squeeze `n_chunks` chunks or does not find
+    /// `poly_size` coefficients in them.
+    #[allow(clippy::type_complexity)]
+    pub fn gen_trace(
+        &self,
+        chunks: &[Vec<u32>],
+        poly_size: usize,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        Vec<u32>,
+        Vec<M31>,
+        Vec<M31>,
+        Vec<M31>,
+    ) {
+        let n_rows = 1 << self.log_size;
+        assert!(chunks.len() * self.n_chunks() <= n_rows, "too many chunks");
+        let n = M31(poly_size as u32);
+        let mut columns = vec![vec![M31(0); n_rows]; N_COLUMNS];
+        let mut coefficients = vec![];
+        let mut rejections = vec![];
+        for (instance, chunks) in chunks.iter().enumerate() {
+            assert_eq!(chunks.len(), self.n_chunks(), "wrong number of chunks");
+            let mut count = 0;
+            for (position, limb) in chunks.iter().enumerate() {
+                let row = instance * self.n_chunks() + position;
+                let (b0, b1) = (limb & 0xFF, limb >> 8);
+                let t = (b0 << 8) | b1;
+                let accept = t < REJECTION_BOUND;
+                let done = count == poly_size;
+                let take = accept && !done;
+                let (quotient, value) = if accept { (t / Q, t % Q) } else { (0, 0) };
+                if !accept {
+                    rejections.push(M31(t - REJECTION_BOUND));
+                }
+                for (column, cell) in [
+                    (B0, b0),
+                    (B1, b1),
+                    (ACCEPT, accept as u32),
+                    (QUOTIENT, quotient & 1),
+                    (QUOTIENT + 1, (quotient >> 1) & 1),
+                    (QUOTIENT + 2, quotient >> 2),
+                    (VALUE, value),
+                    (COUNT, count as u32),
+                    (DONE, done as u32),
+                    (TAKE, take as u32),
+                ] {
+                    columns[column][row] = M31(cell);
+                }
+                if !done {
+                    columns[INV][row] = (M31(count as u32) - n).inverse();
+                }
+                if take {
+                    coefficients.push(value);
+                    count += 1;
+                }
+            }
+            assert_eq!(count, poly_size, "not enough squeezed blocks");
+        }
+        // The count of the unused rows stays at 0
+        for row in chunks.len() * self.n_chunks()..n_rows {
+            columns[INV][row] = (-n).inverse();
+        }
+
+        let bytes = columns[B0]
+            .iter()
+            .chain(columns[B1].iter())
+            .copied()
+            .collect();
+        let values = columns[VALUE].clone();
+        let domain = CanonicCoset::new(self.log_size).circle_domain();
+        (
+            columns
+                .into_iter()
+                .map(|mut column| {
+                    // The count is read at offset 1
+                    bit_reverse_coset_to_circle_domain_order(&mut column);
+                    CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
+                        domain,
+                        BaseColumn::from_iter(column),
+                    )
+                })
+                .collect(),
+            coefficients,
+            bytes,
+            values,
+            rejections,
+        )
+    }
+}
+
+/// Component used in the framework for the rejection sampler.
+#[derive(Debug, Clone)]
+pub struct Eval {
+    /// The claim parameters
+    pub claim: Claim,
+    /// The number of coefficients to sample per instance
+    pub poly_size: usize,
+    /// The number of instances of the batch
+    pub n_instances: usize,
+    /// Lookup elements for the squeezed chunks
+    pub chunk_lookup_elements: ChunkLookupElements,
+    /// Lookup elements for the byte range check
+    pub byte_rc_lookup_elements: RCLookupElements,
+    /// Lookup elements for range checking against Q
+    pub rc_lookup_elements: RCLookupElements,
+    /// Lookup elements for the rejection range check
+    pub rejection_rc_lookup_elements: RCLookupElements,
+    /// Lookup elements for the coefficients of the message point
+    pub msg_point_lookup_elements: MsgPointLookupElements,
+}
+
+impl FrameworkEval for Eval {
+    fn log_size(&self) -> u32 {
+        self.claim.log_size
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.claim.log_size + 1
+    }
+
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        let [enabler, instance, position, is_first, is_last, not_last] = [
+            ChunkColumn::Enabler,
+            ChunkColumn::Instance,
+            ChunkColumn::Position,
+            ChunkColumn::IsFirst,
+            ChunkColumn::IsLast,
+            ChunkColumn::NotLast,
+        ]
+        .map(|column| {
+            eval.get_preprocessed_column(
+                ChunkFlags::new(&self.claim, self.n_instances, column).id(),
+            )
+        });
+
+        let b0 = eval.next_trace_mask();
+        let b1 = eval.next_trace_mask();
+        let accept = eval.next_trace_mask();
+        let q0 = eval.next_trace_mask();
+        let q1 = eval.next_trace_mask();
+        let q2 = eval.next_trace_mask();
+        let value = eval.next_trace_mask();
+        let [count, count_next] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [0, 1]);
+        let done = eval.next_trace_mask();
+        let inv = eval.next_trace_mask();
+        let take = eval.next_trace_mask();
+
+        let one = E::F::one();
+        let n = E::F::from(M31(self.poly_size as u32));
+        for bit in [&accept, &q0, &q1, &q2] {
+            eval.add_constraint(bit.clone() * (bit.clone() - one.clone()));
+        }
+
+        // An accepted chunk is t = quotient * Q + value with a quotient of at most 4
+        let t = E::F::from(M31(256)) * b0.clone() + b1.clone();
+        let limb = b0.clone() + E::F::from(M31(256)) * b1.clone();
+        eval.add_constraint(q2.clone() * (q0.clone() + q1.clone()));
+        let quotient = q0 + E::F::from(M31(2)) * q1 + E::F::from(M31(4)) * q2;
+        eval.add_constraint(
+            accept.clone() * (t.clone() - quotient * E::F::from(M31(Q)) - value.clone()),
+        );
+
+        // done = (count == n), and the chunk is taken if accepted before the end
+        eval.add_constraint((count.clone() - n.clone()) * inv - (one.clone() - done.clone()));
+        eval.add_constraint(done.clone() * (count.clone() - n.clone()));
+        eval.add_constraint(take.clone() - accept.clone() * (one.clone() - done));
+
+        // The count starts at 0, grows with every taken chunk and ends at n
+        eval.add_constraint(is_first * count.clone());
+        eval.add_constraint(not_last * (count_next - count.clone() - take.clone()));
+        eval.add_constraint(is_last * (count.clone() + take.clone() - n.clone()));
+
+        eval.add_to_relation(RelationEntry::new(
+            &self.chunk_lookup_elements,
+            E::EF::from(enabler.clone()),
+            &[instance.clone(), position, limb],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.byte_rc_lookup_elements,
+            E::EF::one(),
+            &[b0],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.byte_rc_lookup_elements,
+            E::EF::one(),
+            &[b1],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.rc_lookup_elements,
+            E::EF::one(),
+            &[value.clone()],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.rejection_rc_lookup_elements,
+            E::EF::from(enabler.clone() * (one - accept)),
+            &[t - E::F::from(M31(REJECTION_BOUND))],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.msg_point_lookup_elements,
+            -E::EF::from(enabler * take),
+            &[instance * n + count, value],
+        ));
+
+        eval.finalize_logup();
+        eval
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionClaim {
+    /// The claimed sum for the interaction
+    pub claimed_sum: SecureField,
+}
+
+impl InteractionClaim {
+    /// Mixes the interaction claim into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_felts(&[self.claimed_sum]);
+    }
+
+    /// Generates the interaction trace for the sampler component.
+    ///
+    /// # Parameters
+    ///
+    /// - `trace`: The trace columns of the component
+    /// - `claim`: The claim the trace was generated from
+    /// - `poly_size`: The number of coefficients sampled per instance
+    /// - `n_instances`: The number of instances of the batch
+    /// - `lookup_elements`: The lookup elements of the Big AIR
+    pub fn gen_interaction_trace(
+        trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+        claim: &Claim,
+        poly_size: usize,
+        n_instances: usize,
+        lookup_elements: &LookupElements,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        InteractionClaim,
+    ) {
+        let log_size = claim.log_size;
+        let n_vec_rows = 1 << (log_size - LOG_N_LANES);
+        let flag = |column| ChunkFlags::new(claim, n_instances, column).gen_column_simd();
+        let enabler = flag(ChunkColumn::Enabler);
+        let instance = flag(ChunkColumn::Instance);
+        let position = flag(ChunkColumn::Position);
+        let one = PackedM31::broadcast(M31(1));
+        let byte = PackedM31::broadcast(M31(256));
+        let n = PackedM31::broadcast(M31(poly_size as u32));
+        let mut logup_gen = LogupTraceGenerator::new(log_size);
+
+        // Squeezed chunks
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let limb = trace[B0].data[vec_row] + byte * trace[B1].data[vec_row];
+            let denom: PackedQM31 = lookup_elements.chunk.combine(&[
+                instance.data[vec_row],
+                position.data[vec_row],
+                limb,
+            ]);
+            col_gen.write_frac(vec_row, PackedQM31::from(enabler.data[vec_row]), denom);
+        }
+        col_gen.finalize_col();
+
+        // Bytes and values range checks
+        for (column, lookup_elements) in [
+            (B0, &lookup_elements.byte_range_check),
+            (B1, &lookup_elements.byte_range_check),
+            (VALUE, &lookup_elements.rc),
+        ] {
+            let mut col_gen = logup_gen.new_col();
+            for vec_row in 0..n_vec_rows {
+                let denom: PackedQM31 = lookup_elements.combine(&[trace[column].data[vec_row]]);
+                col_gen.write_frac(vec_row, PackedQM31::one(), denom);
+            }
+            col_gen.finalize_col();
+        }
+
+        // Rejected chunks range check
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let t = byte * trace[B0].data[vec_row] + trace[B1].data[vec_row];
+            let denom: PackedQM31 = lookup_elements
+                .rejection_range_check
+                .combine(&[t - PackedM31::broadcast(M31(REJECTION_BOUND))]);
+            let numerator =
+                PackedQM31::from(enabler.data[vec_row] * (one - trace[ACCEPT].data[vec_row]));
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        // Coefficients of the message point
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let index = instance.data[vec_row] * n + trace[COUNT].data[vec_row];
+            let denom: PackedQM31 = lookup_elements
+                .msg_point
+                .combine(&[index, trace[VALUE].data[vec_row]]);
+            let numerator = -PackedQM31::from(enabler.data[vec_row] * trace[TAKE].data[vec_row]);
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
+        (interaction_trace, InteractionClaim { claimed_sum })
+    }
+}
+
+/// Type alias for the sampler component.
+pub type Component = FrameworkComponent<Eval>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_point::{hash_to_point, sponge::Claim as SpongeClaim, sponge_steps};
+
+    #[test]
+    fn test_sampled_coefficients_match_hash_to_point() {
+        let (nonce, message) = ([3; 40], b"sampler".to_vec());
+        let steps = sponge_steps(&nonce, &message, 1);
+        let (_, _, chunks) = SpongeClaim::new(steps.len()).gen_trace(&[steps]);
+
+        let claim = Claim::new(1, 1);
+        let (_, coefficients, bytes, values, rejections) = claim.gen_trace(&chunks, 16);
+        assert_eq!(coefficients, hash_to_point(&nonce, &message, 16));
+        assert_eq!(bytes.len(), 2 << claim.log_size);
+        assert_eq!(values.len(), 1 << claim.log_size);
+        assert!(rejections.iter().all(|r| r.0 < REJECTION_RANGE_BOUND));
+    }
+}
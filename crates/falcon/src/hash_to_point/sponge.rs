@@ -0,0 +1,510 @@
+//! # SHAKE256 Sponge Component
+//!
+//! This module implements the STARK proof component chaining the Keccak-f[1600]
+//! permutations of the SHAKE256 sponge of HashToPoint.
+//!
+//! # Trace Structure
+//!
+//! Every sponge step of every instance takes one row, see [`SpongeStep`]. Each row holds:
+//! - `enabler`, `instance`, `step`: whether the row is used and its tag
+//! - `is_first`, `is_squeeze`, `has_next`, `squeeze_index`: the flags of the step
+//! - `prev[1600]`: the bits of the state before the step
+//! - `block[1088]`: the bits of the absorbed block, zero when squeezing
+//! - `in[1088]`: the bits of the rate after the block is XORed in
+//!
+//! The rows after the last step are left at zero.
+//!
+//! # Constraints
+//!
+//! - Every flag and bit is boolean
+//! - XOR: `(in - prev)^2 = block`
+//! - The first step starts from the zero state
+//!
+//! # Lookups
+//!
+//! An enabled step:
+//! - consumes its flags and block from the message block relation, whose entries the
+//!   verifier adds back from the public nonce and message
+//! - consumes the output of permutation `step - 1` unless it is the first step
+//! - emits its new state as the input of permutation `step` if it has a next step
+//! - emits the 68 16-bit chunks of its state if it squeezes a block
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{
+        ColumnVec,
+        channel::Channel,
+        fields::{m31::M31, qm31::SecureField},
+        pcs::TreeVec,
+        poly::circle::CanonicCoset,
+    },
+    prover::{
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{
+    EvalAtRow, FrameworkComponent, FrameworkEval, LogupTraceGenerator, Relation, RelationEntry,
+};
+
+use crate::{
+    big_air::relation::{
+        ChunkLookupElements, KeccakInputLookupElements, KeccakOutputLookupElements,
+        MessageBlockLookupElements,
+    },
+    hash_to_point::{
+        LIMB_BITS, RATE_BITS, RATE_LIMBS, STATE_BITS, STATE_LIMBS, SpongeStep, block_limbs,
+        keccak::Permutation, keccak_f, limbs, state_limbs, xor_block,
+    },
+};
+
+/// Index of the enabler column.
+const ENABLER: usize = 0;
+/// Index of the instance tag column.
+const INSTANCE: usize = 1;
+/// Index of the step tag column.
+const STEP: usize = 2;
+/// Index of the first step flag column.
+const IS_FIRST: usize = 3;
+/// Index of the squeezing step flag column.
+const IS_SQUEEZE: usize = 4;
+/// Index of the next step flag column.
+const HAS_NEXT: usize = 5;
+/// Index of the squeezed block index column.
+const SQUEEZE_INDEX: usize = 6;
+/// Index of the first bit column of the state before the step.
+const PREV: usize = 7;
+/// Index of the first bit column of the absorbed block.
+const BLOCK: usize = PREV + STATE_BITS;
+/// Index of the first bit column of the rate after the step.
+const IN: usize = BLOCK + RATE_BITS;
+/// Number of columns of the component.
+pub const N_COLUMNS: usize = IN + RATE_BITS;
+
+/// Returns the values of the message block entry of a sponge step of an instance.
+///
+/// These are `(instance, step, is_first, is_squeeze, has_next, squeeze_index, 68 block
+/// limbs)`, consumed by the sponge and added back by the verifier.
+pub fn message_block_entry(instance: u32, step: &SpongeStep) -> Vec<M31> {
+    [
+        instance,
+        step.j,
+        step.is_first as u32,
+        step.is_squeeze as u32,
+        step.has_next as u32,
+        step.squeeze_index,
+    ]
+    .into_iter()
+    .chain(block_limbs(&step.block))
+    .map(M31)
+    .collect()
+}
+
+/// Claim parameters for the sponge component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    /// The log base 2 of the trace size
+    pub log_size: u32,
+}
+
+impl Claim {
+    /// Returns the claim of a trace holding `n_steps` sponge steps.
+    pub fn new(n_steps: usize) -> Self {
+        Self {
+            log_size: n_steps.next_power_of_two().ilog2().max(LOG_N_LANES),
+        }
+    }
+
+    /// Returns the log sizes for the traces.
+    ///
+    /// [preprocessed_trace, trace, interaction_trace]
+    pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
+        let trace_log_sizes = vec![self.log_size; N_COLUMNS];
+        TreeVec::new(vec![vec![], trace_log_sizes, vec![]])
+    }
+
+    /// Mixes the claim parameters into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size as u64);
+    }
+
+    /// Generates the trace for the sponge component.
+    ///
+    /// # Parameters
+    ///
+    /// - `steps`: The sponge steps of every instance of the batch
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing:
+    /// - The trace columns
+    /// - The permutations fed by the steps, for the Keccak component
+    /// - The squeezed 16-bit chunks of every instance, for the sampler component
+    ///
+    /// # Panics
+    ///
+    /// Panics if the steps do not fit in the trace.
+    #[allow(clippy::type_complexity)]
+    pub fn gen_trace(
+        &self,
+        steps: &[Vec<SpongeStep>],
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        Vec<Permutation>,
+        Vec<Vec<u32>>,
+    ) {
+        let n_rows = 1 << self.log_size;
+        assert!(
+            steps.iter().map(Vec::len).sum::<usize>() <= n_rows,
+            "too many sponge steps"
+        );
+        let mut columns = vec![vec![M31(0); n_rows]; N_COLUMNS];
+        let mut permutations = vec![];
+        let mut chunks = vec![];
+        let mut row = 0;
+        for (instance, steps) in steps.iter().enumerate() {
+            let mut state = [0; 25];
+            let mut instance_chunks = vec![];
+            for step in steps {
+                let prev = state;
+                xor_block(&mut state, &step.block);
+                let flags = [
+                    (ENABLER, 1),
+                    (INSTANCE, instance as u32),
+                    (STEP, step.j),
+                    (IS_FIRST, step.is_first as u32),
+                    (IS_SQUEEZE, step.is_squeeze as u32),
+                    (HAS_NEXT, step.has_next as u32),
+                    (SQUEEZE_INDEX, step.squeeze_index),
+                ];
+                for (column, value) in flags {
+                    columns[column][row] = M31(value);
+                }
+                for bit in 0..STATE_BITS {
+                    columns[PREV + bit][row] = M31(((prev[bit / 64] >> (bit % 64)) & 1) as u32);
+                }
+                for bit in 0..RATE_BITS {
+                    columns[BLOCK + bit][row] =
+                        M31(((step.block[bit / 8] >> (bit % 8)) & 1) as u32);
+                    columns[IN + bit][row] = M31(((state[bit / 64] >> (bit % 64)) & 1) as u32);
+                }
+                if step.is_squeeze {
+                    instance_chunks.extend_from_slice(&state_limbs(&prev)[..RATE_LIMBS]);
+                }
+                if step.has_next {
+                    permutations.push(Permutation {
+                        instance: instance as u32,
+                        step: step.j,
+                        input: state,
+                    });
+                    keccak_f(&mut state);
+                }
+                row += 1;
+            }
+            chunks.push(instance_chunks);
+        }
+        let domain = CanonicCoset::new(self.log_size).circle_domain();
+        (
+            columns
+                .into_iter()
+                .map(|column| {
+                    CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
+                        domain,
+                        BaseColumn::from_iter(column),
+                    )
+                })
+                .collect(),
+            permutations,
+            chunks,
+        )
+    }
+}
+
+/// Component used in the framework for the sponge.
+#[derive(Debug, Clone)]
+pub struct Eval {
+    /// The claim parameters
+    pub claim: Claim,
+    /// Lookup elements for the public message blocks
+    pub message_block_lookup_elements: MessageBlockLookupElements,
+    /// Lookup elements for the input states of the permutations
+    pub keccak_input_lookup_elements: KeccakInputLookupElements,
+    /// Lookup elements for the output states of the permutations
+    pub keccak_output_lookup_elements: KeccakOutputLookupElements,
+    /// Lookup elements for the squeezed chunks
+    pub chunk_lookup_elements: ChunkLookupElements,
+}
+
+impl FrameworkEval for Eval {
+    fn log_size(&self) -> u32 {
+        self.claim.log_size
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.claim.log_size + 1
+    }
+
+    fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
+        let enabler = eval.next_trace_mask();
+        let instance = eval.next_trace_mask();
+        let step = eval.next_trace_mask();
+        let is_first = eval.next_trace_mask();
+        let is_squeeze = eval.next_trace_mask();
+        let has_next = eval.next_trace_mask();
+        let squeeze_index = eval.next_trace_mask();
+        let prev = (0..STATE_BITS)
+            .map(|_| eval.next_trace_mask())
+            .collect::<Vec<_>>();
+        let block = (0..RATE_BITS)
+            .map(|_| eval.next_trace_mask())
+            .collect::<Vec<_>>();
+        let input = (0..RATE_BITS)
+            .map(|_| eval.next_trace_mask())
+            .collect::<Vec<_>>();
+
+        let one = E::F::one();
+        let boolean = |x: &E::F| x.clone() * (x.clone() - one.clone());
+        for flag in [&enabler, &is_first, &is_squeeze, &has_next] {
+            eval.add_constraint(boolean(flag));
+        }
+        for bit in prev.iter().chain(block.iter()).chain(input.iter()) {
+            eval.add_constraint(boolean(bit));
+        }
+
+        // XOR of the block into the rate
+        for ((input, prev), block) in input.iter().zip(prev.iter()).zip(block.iter()) {
+            let diff = input.clone() - prev.clone();
+            eval.add_constraint(diff.clone() * diff - block.clone());
+        }
+
+        // The first step starts from the zero state
+        for bit in prev.iter() {
+            eval.add_constraint(is_first.clone() * bit.clone());
+        }
+
+        let prev_limbs = limbs(&prev);
+
+        // The flags and the block come from the public message blocks
+        let message_block = [
+            instance.clone(),
+            step.clone(),
+            is_first.clone(),
+            is_squeeze.clone(),
+            has_next.clone(),
+            squeeze_index.clone(),
+        ]
+        .into_iter()
+        .chain(limbs(&block))
+        .collect::<Vec<_>>();
+        eval.add_to_relation(RelationEntry::new(
+            &self.message_block_lookup_elements,
+            -E::EF::from(enabler.clone()),
+            &message_block,
+        ));
+
+        // The state before the step is the output of the previous permutation
+        let keccak_output = [instance.clone(), step.clone() - one.clone()]
+            .into_iter()
+            .chain(prev_limbs.iter().cloned())
+            .collect::<Vec<_>>();
+        eval.add_to_relation(RelationEntry::new(
+            &self.keccak_output_lookup_elements,
+            E::EF::from(enabler.clone() * (one.clone() - is_first)),
+            &keccak_output,
+        ));
+
+        // The state after the step is the input of the next permutation
+        let keccak_input = [instance.clone(), step]
+            .into_iter()
+            .chain(limbs(&input))
+            .chain(prev_limbs[RATE_LIMBS..].iter().cloned())
+            .collect::<Vec<_>>();
+        eval.add_to_relation(RelationEntry::new(
+            &self.keccak_input_lookup_elements,
+            -E::EF::from(enabler.clone() * has_next),
+            &keccak_input,
+        ));
+
+        // The rate of a squeezed state is sent to the sampler, chunk by chunk
+        for (t, limb) in prev_limbs.into_iter().take(RATE_LIMBS).enumerate() {
+            let position = E::F::from(M31(RATE_LIMBS as u32)) * squeeze_index.clone()
+                + E::F::from(M31(t as u32));
+            eval.add_to_relation(RelationEntry::new(
+                &self.chunk_lookup_elements,
+                -E::EF::from(enabler.clone() * is_squeeze.clone()),
+                &[instance.clone(), position, limb],
+            ));
+        }
+
+        eval.finalize_logup();
+        eval
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionClaim {
+    /// The claimed sum for the interaction
+    pub claimed_sum: SecureField,
+}
+
+impl InteractionClaim {
+    /// Mixes the interaction claim into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_felts(&[self.claimed_sum]);
+    }
+
+    /// Generates the interaction trace for the sponge component.
+    ///
+    /// # Parameters
+    ///
+    /// - `trace`: The trace columns of the component
+    /// - `message_block_lookup_elements`: The lookup elements for the public message blocks
+    /// - `keccak_input_lookup_elements`: The lookup elements for the input states
+    /// - `keccak_output_lookup_elements`: The lookup elements for the output states
+    /// - `chunk_lookup_elements`: The lookup elements for the squeezed chunks
+    pub fn gen_interaction_trace(
+        trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+        message_block_lookup_elements: &MessageBlockLookupElements,
+        keccak_input_lookup_elements: &KeccakInputLookupElements,
+        keccak_output_lookup_elements: &KeccakOutputLookupElements,
+        chunk_lookup_elements: &ChunkLookupElements,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        InteractionClaim,
+    ) {
+        let log_size = trace[0].domain.log_size();
+        let n_vec_rows = 1 << (log_size - LOG_N_LANES);
+        let one = PackedM31::broadcast(M31(1));
+        let mut logup_gen = LogupTraceGenerator::new(log_size);
+
+        let packed_limbs = |vec_row: usize, start: usize, n_limbs: usize| {
+            (0..n_limbs)
+                .map(|limb| {
+                    (0..LIMB_BITS).fold(PackedM31::broadcast(M31(0)), |acc, i| {
+                        acc + PackedM31::broadcast(M31(1 << i))
+                            * trace[start + LIMB_BITS * limb + i].data[vec_row]
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        let prev_limbs = (0..n_vec_rows)
+            .map(|vec_row| packed_limbs(vec_row, PREV, STATE_LIMBS))
+            .collect::<Vec<_>>();
+
+        // Public message blocks
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..n_vec_rows {
+            let values = [
+                INSTANCE,
+                STEP,
+                IS_FIRST,
+                IS_SQUEEZE,
+                HAS_NEXT,
+                SQUEEZE_INDEX,
+            ]
+            .map(|column| trace[column].data[vec_row])
+            .into_iter()
+            .chain(packed_limbs(vec_row, BLOCK, RATE_LIMBS))
+            .collect::<Vec<_>>();
+            let denom: PackedQM31 = message_block_lookup_elements.combine(&values);
+            let numerator = -PackedQM31::from(trace[ENABLER].data[vec_row]);
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        // Output states of the previous permutations
+        let mut col_gen = logup_gen.new_col();
+        for (vec_row, prev_limbs) in prev_limbs.iter().enumerate() {
+            let values = [
+                trace[INSTANCE].data[vec_row],
+                trace[STEP].data[vec_row] - one,
+            ]
+            .into_iter()
+            .chain(prev_limbs.iter().copied())
+            .collect::<Vec<_>>();
+            let denom: PackedQM31 = keccak_output_lookup_elements.combine(&values);
+            let numerator = PackedQM31::from(
+                trace[ENABLER].data[vec_row] * (one - trace[IS_FIRST].data[vec_row]),
+            );
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        // Input states of the next permutations
+        let mut col_gen = logup_gen.new_col();
+        for (vec_row, prev_limbs) in prev_limbs.iter().enumerate() {
+            let values = [trace[INSTANCE].data[vec_row], trace[STEP].data[vec_row]]
+                .into_iter()
+                .chain(packed_limbs(vec_row, IN, RATE_LIMBS))
+                .chain(prev_limbs[RATE_LIMBS..].iter().copied())
+                .collect::<Vec<_>>();
+            let denom: PackedQM31 = keccak_input_lookup_elements.combine(&values);
+            let numerator =
+                -PackedQM31::from(trace[ENABLER].data[vec_row] * trace[HAS_NEXT].data[vec_row]);
+            col_gen.write_frac(vec_row, numerator, denom);
+        }
+        col_gen.finalize_col();
+
+        // Squeezed chunks
+        for t in 0..RATE_LIMBS {
+            let mut col_gen = logup_gen.new_col();
+            for (vec_row, prev_limbs) in prev_limbs.iter().enumerate() {
+                let position = PackedM31::broadcast(M31(RATE_LIMBS as u32))
+                    * trace[SQUEEZE_INDEX].data[vec_row]
+                    + PackedM31::broadcast(M31(t as u32));
+                let denom: PackedQM31 = chunk_lookup_elements.combine(&[
+                    trace[INSTANCE].data[vec_row],
+                    position,
+                    prev_limbs[t],
+                ]);
+                let numerator = -PackedQM31::from(
+                    trace[ENABLER].data[vec_row] * trace[IS_SQUEEZE].data[vec_row],
+                );
+                col_gen.write_frac(vec_row, numerator, denom);
+            }
+            col_gen.finalize_col();
+        }
+
+        let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
+        (interaction_trace, InteractionClaim { claimed_sum })
+    }
+}
+
+/// Type alias for the sponge component.
+pub type Component = FrameworkComponent<Eval>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_point::{hash_to_point, sponge_steps};
+
+    #[test]
+    fn test_squeezed_chunks_match_shake256() {
+        let (nonce, message) = ([7; 40], b"message".to_vec());
+        let steps = sponge_steps(&nonce, &message, 2);
+        let claim = Claim::new(steps.len());
+        let (_, permutations, chunks) = claim.gen_trace(&[steps]);
+
+        // One absorbed block and two squeezed blocks, chained by two permutations
+        assert_eq!(permutations.len(), 2);
+        assert_eq!(chunks[0].len(), 2 * RATE_LIMBS);
+
+        // The first coefficients of the message point are the accepted chunks, read
+        // big-endian
+        let expected = hash_to_point(&nonce, &message, 4);
+        let sampled = chunks[0]
+            .iter()
+            .map(|limb| ((limb & 0xFF) << 8) | (limb >> 8))
+            .filter(|t| *t < crate::hash_to_point::REJECTION_BOUND)
+            .map(|t| t % crate::zq::Q)
+            .take(4)
+            .collect::<Vec<_>>();
+        assert_eq!(sampled, expected);
+    }
+}
@@ -0,0 +1,388 @@
+//! # Key AIR Claims
+//!
+//! This module defines the claim of the key AIR, which combines the claims of all its
+//! components, and the traces generated from a key.
+//!
+//! The components are chained like the ones of the signature verification, with f in
+//! place of s1, h in place of the public key and the zero polynomial in place of the
+//! message point:
+//!
+//! 1. The key norm emits f, and the forward NTT transforms f and h
+//! 2. The invertibility component checks every coefficient of the NTT of f
+//! 3. The multiplication, the inverse NTT and the subtraction compute s0 = 0 - f * h
+//! 4. The key norm reads s0 = -g and bounds the squared norm of (f, g)
+
+use crate::{
+    CircuitParams,
+    big_air::public_inputs::Indices,
+    impl_mix_into,
+    key_air::{KEY_NORM_BOUND, invertibility, norm},
+    ntts::{intt, layout::ButterflyLayout, ntt, roots},
+    polys::{euclidean_norm, mul, sub},
+    zq::range_check,
+};
+use itertools::{Itertools, chain};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::m31::M31,
+    prover::{
+        backend::simd::SimdBackend,
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::preprocessed_columns::PreProcessedColumnId;
+
+impl_mix_into!(
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct KeyClaim {
+        pub params: CircuitParams,
+        pub f_ntt_butterfly: ntt::butterfly::Claim,
+        pub f_ntt_merges: Vec<ntt::Claim>,
+        pub h_ntt_butterfly: ntt::butterfly::Claim,
+        pub h_ntt_merges: Vec<ntt::Claim>,
+        pub invertibility: invertibility::Claim,
+        pub mul: mul::Claim,
+        pub intt_merges: Vec<intt::Claim>,
+        pub ibutterfly: intt::ibutterfly::Claim,
+        pub sub: sub::Claim,
+        pub norm: norm::Claim,
+        pub coeff_range_check: range_check::Claim,
+        pub key_bound_check: range_check::Claim,
+        pub range_check: range_check::Claim,
+        pub roots: Vec<roots::preprocessed::Claim>,
+        pub inv_roots: Vec<roots::inv_preprocessed::Claim>,
+    }
+);
+
+/// The traces of the key AIR, one field per component of [`KeyClaim`].
+#[derive(Debug, Clone)]
+pub struct KeyTraces {
+    pub f_ntt_butterfly: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub f_ntt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
+    pub h_ntt_butterfly: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub h_ntt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
+    pub invertibility: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub mul: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub intt_merges: Vec<Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>>,
+    pub ibutterfly: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub sub: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub norm: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub coeff_range_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
+    pub key_bound_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
+    pub range_check: CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
+    pub roots: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+    pub inv_roots: Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+}
+
+impl KeyTraces {
+    /// Returns all the trace columns, in commitment order.
+    pub fn columns(&self) -> Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>> {
+        chain!(
+            self.f_ntt_butterfly.clone(),
+            self.f_ntt_merges.iter().flatten().cloned(),
+            self.h_ntt_butterfly.clone(),
+            self.h_ntt_merges.iter().flatten().cloned(),
+            self.invertibility.clone(),
+            self.mul.clone(),
+            self.intt_merges.iter().flatten().cloned(),
+            self.ibutterfly.clone(),
+            self.sub.clone(),
+            self.norm.clone(),
+            [self.coeff_range_check.clone()],
+            [self.key_bound_check.clone()],
+            [self.range_check.clone()],
+            self.roots.clone(),
+            self.inv_roots.clone(),
+        )
+        .collect_vec()
+    }
+}
+
+impl KeyClaim {
+    /// Creates the claim of the key AIR for the degree of `params`.
+    ///
+    /// The claim only depends on the degree: every component holds one key, with one
+    /// coefficient per row or two for the butterflies, merges and splits.
+    pub fn new(params: CircuitParams) -> Self {
+        use stwo::prover::backend::simd::m31::LOG_N_LANES;
+
+        let poly_log_size = params.poly_log_size;
+        let merges = || {
+            (1..poly_log_size)
+                .map(|_| ntt::Claim {
+                    log_size: poly_log_size - 1,
+                })
+                .collect_vec()
+        };
+        Self {
+            params,
+            f_ntt_butterfly: ntt::butterfly::Claim {
+                log_size: poly_log_size - 1,
+            },
+            f_ntt_merges: merges(),
+            h_ntt_butterfly: ntt::butterfly::Claim {
+                log_size: poly_log_size - 1,
+            },
+            h_ntt_merges: merges(),
+            invertibility: invertibility::Claim {
+                log_size: poly_log_size,
+            },
+            mul: mul::Claim {
+                log_size: poly_log_size,
+            },
+            intt_merges: (1..poly_log_size)
+                .map(|_| intt::Claim {
+                    log_size: poly_log_size - 1,
+                })
+                .collect_vec(),
+            ibutterfly: intt::ibutterfly::Claim {
+                log_size: poly_log_size - 1,
+            },
+            sub: sub::Claim {
+                log_size: poly_log_size,
+            },
+            norm: norm::Claim {
+                log_size: poly_log_size,
+                bound: KEY_NORM_BOUND,
+            },
+            coeff_range_check: range_check::Claim::new(norm::KEY_COEFF_BOUND),
+            // bound - norm is in [0, bound]
+            key_bound_check: range_check::Claim::new(KEY_NORM_BOUND + 1),
+            range_check: range_check::Claim::new(params.q),
+            roots: (2..=poly_log_size)
+                .map(|i| roots::preprocessed::Claim {
+                    log_size: std::cmp::max(LOG_N_LANES, i),
+                })
+                .collect_vec(),
+            inv_roots: (2..=poly_log_size)
+                .rev()
+                .map(|i| roots::inv_preprocessed::Claim {
+                    log_size: std::cmp::max(LOG_N_LANES, i),
+                })
+                .collect_vec(),
+        }
+    }
+
+    /// Returns the log size of the largest trace or table.
+    pub fn max_log_size(&self) -> u32 {
+        [
+            self.range_check.log_size,
+            self.coeff_range_check.log_size,
+            self.key_bound_check.log_size,
+            self.sub.log_size,
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+
+    /// Generates the traces of all the components for the key (f, h).
+    ///
+    /// g is not an input: it is recomputed as -s0 = f * h, whose norm is bounded.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The coefficients of f, in [0, q)
+    /// - `pk`: The coefficients of the public key h, in [0, q)
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing all the trace columns in commitment order and the
+    /// traces of the individual components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if f or h do not have `params.poly_size()` coefficients, if f is not
+    /// invertible or if the key exceeds the norm bound.
+    pub fn gen_trace(
+        &self,
+        f: &[u32],
+        pk: &[u32],
+    ) -> (
+        Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        KeyTraces,
+    ) {
+        let poly_size = self.params.poly_size();
+        assert_eq!(f.len(), poly_size, "f must have {poly_size} coefficients");
+        assert_eq!(pk.len(), poly_size, "pk must have {poly_size} coefficients");
+
+        let mut range_check_input = vec![];
+        let (f_ntt_butterfly_trace, f_ntt_butterfly_remainders, f_ntt_butterfly_output) =
+            self.f_ntt_butterfly.gen_trace(f, poly_size);
+        range_check_input.extend(f_ntt_butterfly_remainders);
+
+        let mut f_ntt_outputs = vec![f_ntt_butterfly_output];
+        let mut f_ntt_traces = vec![];
+        let mut f_ntt_js = vec![];
+        for (i, merge) in self.f_ntt_merges.iter().enumerate() {
+            let (f_ntt_trace, f_ntt_remainders, f_ntt_output, js) =
+                merge.gen_trace(&f_ntt_outputs[i], i + 1);
+            range_check_input.extend(f_ntt_remainders);
+            f_ntt_outputs.push(f_ntt_output);
+            f_ntt_traces.push(f_ntt_trace);
+            f_ntt_js.push(js);
+        }
+
+        let (h_ntt_butterfly_trace, h_ntt_butterfly_remainders, h_ntt_butterfly_output) =
+            self.h_ntt_butterfly.gen_trace(pk, poly_size);
+        range_check_input.extend(h_ntt_butterfly_remainders);
+
+        let mut h_ntt_outputs = vec![h_ntt_butterfly_output];
+        let mut h_ntt_traces = vec![];
+        let mut h_ntt_js = vec![];
+        for (i, merge) in self.h_ntt_merges.iter().enumerate() {
+            let (h_ntt_trace, h_ntt_remainders, h_ntt_output, js) =
+                merge.gen_trace(&h_ntt_outputs[i], i + 1);
+            range_check_input.extend(h_ntt_remainders);
+            h_ntt_outputs.push(h_ntt_output);
+            h_ntt_traces.push(h_ntt_trace);
+            h_ntt_js.push(js);
+        }
+
+        // Every coefficient of the NTT of f is inverted before the multiplication
+        let f_ntt = f_ntt_outputs.last().unwrap().concat();
+        let (invertibility_trace, invertibility_range_checked) =
+            self.invertibility.gen_trace(&f_ntt);
+        range_check_input.push(invertibility_range_checked);
+
+        let (mul_trace, mul_remainders) = self
+            .mul
+            .gen_trace(&f_ntt, &h_ntt_outputs.last().unwrap().concat());
+        range_check_input.push(mul_remainders.clone());
+
+        let mut intt_outputs = vec![vec![mul_remainders.into_iter().map(|r| r.0).collect_vec()]];
+        let mut intt_traces = vec![];
+        let mut intt_js = vec![];
+        for (i, split) in self.intt_merges.iter().enumerate() {
+            let (intt_trace, intt_remainders, intt_output, js) = split.gen_trace(&intt_outputs[i]);
+            range_check_input.extend(intt_remainders);
+            intt_outputs.push(intt_output);
+            intt_traces.push(intt_trace);
+            intt_js.push(js);
+        }
+
+        let (ibutterfly_trace, ibutterfly_remainders, ibutterflied_poly) = self
+            .ibutterfly
+            .gen_trace(intt_outputs.last().unwrap(), poly_size);
+        range_check_input.extend(ibutterfly_remainders);
+
+        // The message point is zero, so that s0 = -f * h = -g
+        let (sub_trace, sub_remainders) =
+            self.sub.gen_trace(&vec![0; poly_size], &ibutterflied_poly);
+        range_check_input.push(sub_remainders.clone());
+
+        let (norm_trace, norm_remainders, norm_slack) = self
+            .norm
+            .gen_trace(&sub_remainders.iter().map(|r| r.0).collect_vec(), f);
+        let coeff_range_check_trace = self.coeff_range_check.gen_trace(&[norm_remainders]);
+        let key_bound_check_trace = self.key_bound_check.gen_trace(&[vec![norm_slack]]);
+        let range_check_trace = self.range_check.gen_trace(&range_check_input);
+
+        let roots = self
+            .roots
+            .iter()
+            .zip_eq(f_ntt_js)
+            .zip_eq(h_ntt_js)
+            .map(|((roots_claim, f_ntt_js), h_ntt_js)| {
+                roots_claim.gen_trace(&chain!(f_ntt_js, h_ntt_js).collect_vec())
+            })
+            .collect_vec();
+        let inv_roots = self
+            .inv_roots
+            .iter()
+            .zip_eq(intt_js)
+            .map(|(inv_roots_claim, intt_js)| inv_roots_claim.gen_trace(&intt_js))
+            .collect_vec();
+
+        let traces = KeyTraces {
+            f_ntt_butterfly: f_ntt_butterfly_trace,
+            f_ntt_merges: f_ntt_traces,
+            h_ntt_butterfly: h_ntt_butterfly_trace,
+            h_ntt_merges: h_ntt_traces,
+            invertibility: invertibility_trace,
+            mul: mul_trace,
+            intt_merges: intt_traces,
+            ibutterfly: ibutterfly_trace,
+            sub: sub_trace,
+            norm: norm_trace,
+            coeff_range_check: coeff_range_check_trace,
+            key_bound_check: key_bound_check_trace,
+            range_check: range_check_trace,
+            roots,
+            inv_roots,
+        };
+        (traces.columns(), traces)
+    }
+
+    /// Creates the preprocessed columns of the claim.
+    ///
+    /// These are the columns of the Big AIR for a single signature that the shared
+    /// components read, with the range check tables of the key norm in place of the
+    /// signature ones.
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing all the preprocessed columns and their ids.
+    pub fn create_preprocessed_columns(
+        &self,
+    ) -> (
+        Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        Vec<PreProcessedColumnId>,
+    ) {
+        let mut columns = vec![];
+        let mut ids = vec![];
+
+        let poly_log_size = self.params.poly_log_size;
+        for bound in [
+            self.range_check.bound,
+            self.coeff_range_check.bound,
+            self.key_bound_check.bound,
+        ] {
+            let range_check = range_check::RangeCheck::new(bound);
+            columns.push(range_check.gen_column_simd());
+            ids.push(range_check.id());
+        }
+
+        for i in 2..=poly_log_size {
+            let roots = roots::preprocessed::Roots::new(i as usize);
+            columns.extend(roots.gen_column_simd());
+            ids.push(roots.id());
+            let mut root_id = roots.id();
+            root_id.id.push_str("_root");
+            ids.push(root_id);
+        }
+        for i in (2..=poly_log_size).rev() {
+            let inv_roots = roots::inv_preprocessed::InvRoots::new(i as usize);
+            columns.extend(inv_roots.gen_column_simd());
+            ids.push(inv_roots.id());
+            let mut inv_root_id = inv_roots.id();
+            inv_root_id.id.push_str("_inv_root");
+            ids.push(inv_root_id);
+        }
+
+        // Coefficient indices of f and h in the butterflies, of the rows of the components
+        // holding one coefficient per row, of the key norm and of the rows of the
+        // components holding two coefficients per row
+        for indices in [
+            Indices::bit_reversed(poly_log_size - 1, poly_log_size),
+            Indices::natural(poly_log_size),
+            Indices::natural_circle_domain_order(poly_log_size),
+            Indices::natural(poly_log_size - 1),
+        ] {
+            columns.push(indices.gen_column_simd());
+            ids.push(indices.id());
+        }
+
+        for log_chunk_size in 1..poly_log_size {
+            for layout in ButterflyLayout::all(poly_log_size - 1, 1 << log_chunk_size) {
+                columns.push(layout.gen_column_simd());
+                ids.push(layout.id());
+            }
+        }
+        for norm_flags in euclidean_norm::NormFlags::all(poly_log_size, self.params.poly_size()) {
+            columns.push(norm_flags.gen_column_simd());
+            ids.push(norm_flags.id());
+        }
+        (columns, ids)
+    }
+}
@@ -0,0 +1,373 @@
+use itertools::Itertools;
+use stwo::{
+    core::{air::Component, pcs::TreeVec},
+    prover::ComponentProver,
+};
+use stwo_constraint_framework::{
+    PREPROCESSED_TRACE_IDX, TraceLocationAllocator, preprocessed_columns::PreProcessedColumnId,
+};
+
+use crate::{
+    big_air::{
+        backend::FalconBackend,
+        relation::{F_POLY_ID, G_POLY_ID, INTTInputLookupElements, InputLookupElements},
+    },
+    key_air::{
+        claim::KeyClaim, interaction_claim::KeyInteractionClaim, invertibility, norm,
+        relation::KeyLookupElements,
+    },
+    ntts::{intt, ntt, roots},
+    polys::{mul, sub},
+    zq::range_check,
+};
+
+pub struct KeyComponents {
+    pub f_ntt_butterfly: ntt::butterfly::Component,
+    pub f_ntt_merges: Vec<ntt::Component>,
+    pub h_ntt_butterfly: ntt::butterfly::Component,
+    pub h_ntt_merges: Vec<ntt::Component>,
+    pub invertibility: invertibility::Component,
+    pub mul: mul::Component,
+    pub intt_merges: Vec<intt::Component>,
+    pub ibutterfly: intt::ibutterfly::Component,
+    pub sub: sub::Component,
+    pub norm: norm::Component,
+    pub coeff_range_check: range_check::Component,
+    pub key_bound_check: range_check::Component,
+    pub range_check: range_check::Component,
+    pub roots: Vec<roots::preprocessed::Component>,
+    pub inv_roots: Vec<roots::inv_preprocessed::Component>,
+}
+
+impl KeyComponents {
+    pub fn new(
+        claim: &KeyClaim,
+        lookup_elements: &KeyLookupElements,
+        interaction_claim: &KeyInteractionClaim,
+        preprocessed_columns_ids: &[PreProcessedColumnId],
+    ) -> Self {
+        let mut tree_span_provider =
+            TraceLocationAllocator::new_with_preproccessed_columns(preprocessed_columns_ids);
+        let base = &lookup_elements.base;
+        let poly_log_size = claim.params.poly_log_size;
+        let poly_size = claim.params.poly_size();
+
+        let f_ntt_butterfly = ntt::butterfly::Component::new(
+            &mut tree_span_provider,
+            ntt::butterfly::Eval {
+                claim: claim.f_ntt_butterfly.clone(),
+                poly_size,
+                rc_lookup_elements: base.rc.clone(),
+                poly_id: F_POLY_ID,
+                butterfly_output_lookup_elements: base.f_ntt_butterfly.clone(),
+                public_input_lookup_elements: None,
+                s1_lookup_elements: Some(base.s1.clone()),
+            },
+            interaction_claim.f_ntt_butterfly.claimed_sum,
+        );
+        let f_ntt_merges = claim
+            .f_ntt_merges
+            .iter()
+            .zip_eq(interaction_claim.f_ntt_merges.iter())
+            .enumerate()
+            .map(|(i, (merge, interaction_claim))| {
+                ntt::Component::new(
+                    &mut tree_span_provider,
+                    ntt::Eval {
+                        claim: merge.clone(),
+                        rc_lookup_elements: base.rc.clone(),
+                        ntt_lookup_elements: base.f_ntt.clone(),
+                        input_lookup_elements: if i == 0 {
+                            InputLookupElements::Butterfly(base.f_ntt_butterfly.clone())
+                        } else {
+                            InputLookupElements::NTT(base.f_ntt.clone())
+                        },
+                        poly_size: 1 << (i + 1),
+                        roots_lookup_elements: base.roots.clone(),
+                    },
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect_vec();
+
+        let h_ntt_butterfly = ntt::butterfly::Component::new(
+            &mut tree_span_provider,
+            ntt::butterfly::Eval {
+                claim: claim.h_ntt_butterfly.clone(),
+                poly_size,
+                rc_lookup_elements: base.rc.clone(),
+                poly_id: G_POLY_ID,
+                butterfly_output_lookup_elements: base.g_ntt_butterfly.clone(),
+                public_input_lookup_elements: Some(base.public_input.clone()),
+                s1_lookup_elements: None,
+            },
+            interaction_claim.h_ntt_butterfly.claimed_sum,
+        );
+        let h_ntt_merges = claim
+            .h_ntt_merges
+            .iter()
+            .zip_eq(interaction_claim.h_ntt_merges.iter())
+            .enumerate()
+            .map(|(i, (merge, interaction_claim))| {
+                ntt::Component::new(
+                    &mut tree_span_provider,
+                    ntt::Eval {
+                        claim: merge.clone(),
+                        rc_lookup_elements: base.rc.clone(),
+                        ntt_lookup_elements: base.g_ntt.clone(),
+                        input_lookup_elements: if i == 0 {
+                            InputLookupElements::Butterfly(base.g_ntt_butterfly.clone())
+                        } else {
+                            InputLookupElements::NTT(base.g_ntt.clone())
+                        },
+                        poly_size: 1 << (i + 1),
+                        roots_lookup_elements: base.roots.clone(),
+                    },
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect_vec();
+
+        let invertibility = invertibility::Component::new(
+            &mut tree_span_provider,
+            invertibility::Eval {
+                claim: claim.invertibility.clone(),
+                rc_lookup_elements: base.rc.clone(),
+                f_ntt_lookup_elements: base.f_ntt.clone(),
+                checked_f_ntt_lookup_elements: lookup_elements.checked_f_ntt.clone(),
+                poly_log_size,
+            },
+            interaction_claim.invertibility.claimed_sum,
+        );
+        // The multiplication only reads the NTT of f once checked
+        let mul = mul::Component::new(
+            &mut tree_span_provider,
+            mul::Eval {
+                claim: claim.mul.clone(),
+                rc_lookup_elements: base.rc.clone(),
+                f_ntt_lookup_elements: lookup_elements.checked_f_ntt.clone(),
+                g_ntt_lookup_elements: base.g_ntt.clone(),
+                pk_ntt_lookup_elements: None,
+                mul_lookup_elements: base.mul.clone(),
+                poly_log_size,
+            },
+            interaction_claim.mul.claimed_sum,
+        );
+
+        let intt_merges = claim
+            .intt_merges
+            .iter()
+            .zip_eq(interaction_claim.intt_merges.iter())
+            .enumerate()
+            .map(|(i, (merge, interaction_claim))| {
+                intt::Component::new(
+                    &mut tree_span_provider,
+                    intt::Eval {
+                        claim: merge.clone(),
+                        rc_lookup_elements: base.rc.clone(),
+                        input_lookup_elements: if i == 0 {
+                            INTTInputLookupElements::Mul(base.mul.clone())
+                        } else {
+                            INTTInputLookupElements::INTTOutput(base.intt.clone())
+                        },
+                        intt_lookup_elements: base.intt.clone(),
+                        poly_size: 1 << (poly_log_size as usize - i),
+                        inv_roots_lookup_elements: base.inv_roots.clone(),
+                    },
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect_vec();
+        let ibutterfly = intt::ibutterfly::Component::new(
+            &mut tree_span_provider,
+            intt::ibutterfly::Eval {
+                claim: claim.ibutterfly.clone(),
+                rc_lookup_elements: base.rc.clone(),
+                intt_output_lookup_elements: base.intt.clone(),
+                ibutterfly_output_lookup_elements: base.ibutterfly.clone(),
+                poly_size,
+            },
+            interaction_claim.ibutterfly.claimed_sum,
+        );
+        let sub = sub::Component::new(
+            &mut tree_span_provider,
+            sub::Eval {
+                claim: claim.sub.clone(),
+                rc_lookup_elements: base.rc.clone(),
+                ibutterfly_lookup_elements: base.ibutterfly.clone(),
+                sub_lookup_elements: base.sub.clone(),
+                msg_point_lookup_elements: base.msg_point.clone(),
+            },
+            interaction_claim.sub.claimed_sum,
+        );
+
+        let norm = norm::Component::new(
+            &mut tree_span_provider,
+            norm::Eval {
+                claim: claim.norm.clone(),
+                coeff_rc_lookup_elements: lookup_elements.coeff_range_check.clone(),
+                s0_lookup_elements: base.sub.clone(),
+                key_bound_check_lookup_elements: lookup_elements.key_bound_check.clone(),
+                f_lookup_elements: base.s1.clone(),
+            },
+            interaction_claim.norm.claimed_sum,
+        );
+        let coeff_range_check = range_check::Component::new(
+            &mut tree_span_provider,
+            range_check::Eval {
+                claim: claim.coeff_range_check.clone(),
+                lookup_elements: lookup_elements.coeff_range_check.clone(),
+            },
+            interaction_claim.coeff_range_check.claimed_sum,
+        );
+        let key_bound_check = range_check::Component::new(
+            &mut tree_span_provider,
+            range_check::Eval {
+                claim: claim.key_bound_check.clone(),
+                lookup_elements: lookup_elements.key_bound_check.clone(),
+            },
+            interaction_claim.key_bound_check.claimed_sum,
+        );
+        let range_check = range_check::Component::new(
+            &mut tree_span_provider,
+            range_check::Eval {
+                claim: claim.range_check.clone(),
+                lookup_elements: base.rc.clone(),
+            },
+            interaction_claim.range_check.claimed_sum,
+        );
+
+        let roots = claim
+            .roots
+            .iter()
+            .zip_eq(interaction_claim.roots.iter())
+            .enumerate()
+            .map(|(i, (roots_claim, interaction_claim))| {
+                roots::preprocessed::Component::new(
+                    &mut tree_span_provider,
+                    roots::preprocessed::Eval {
+                        claim: roots_claim.clone(),
+                        lookup_elements: base.roots.clone(),
+                        poly_log_size: i + 2,
+                    },
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect_vec();
+        let inv_roots = claim
+            .inv_roots
+            .iter()
+            .zip_eq(interaction_claim.inv_roots.iter())
+            .enumerate()
+            .map(|(i, (inv_roots_claim, interaction_claim))| {
+                roots::inv_preprocessed::Component::new(
+                    &mut tree_span_provider,
+                    roots::inv_preprocessed::Eval {
+                        claim: inv_roots_claim.clone(),
+                        lookup_elements: base.inv_roots.clone(),
+                        poly_log_size: poly_log_size as usize - i,
+                    },
+                    interaction_claim.claimed_sum,
+                )
+            })
+            .collect_vec();
+
+        Self {
+            f_ntt_butterfly,
+            f_ntt_merges,
+            h_ntt_butterfly,
+            h_ntt_merges,
+            invertibility,
+            mul,
+            intt_merges,
+            ibutterfly,
+            sub,
+            norm,
+            coeff_range_check,
+            key_bound_check,
+            range_check,
+            roots,
+            inv_roots,
+        }
+    }
+
+    pub fn provers<B: FalconBackend>(&self) -> Vec<&dyn ComponentProver<B>> {
+        let mut components: Vec<&dyn ComponentProver<B>> = vec![];
+        components.push(B::as_prover(&self.f_ntt_butterfly));
+        for merge in self.f_ntt_merges.iter() {
+            components.push(B::as_prover(merge));
+        }
+        components.push(B::as_prover(&self.h_ntt_butterfly));
+        for merge in self.h_ntt_merges.iter() {
+            components.push(B::as_prover(merge));
+        }
+        components.push(B::as_prover(&self.invertibility));
+        components.push(B::as_prover(&self.mul));
+        for merge in self.intt_merges.iter() {
+            components.push(B::as_prover(merge));
+        }
+        components.push(B::as_prover(&self.ibutterfly));
+        components.push(B::as_prover(&self.sub));
+        components.push(B::as_prover(&self.norm));
+        components.push(B::as_prover(&self.coeff_range_check));
+        components.push(B::as_prover(&self.key_bound_check));
+        components.push(B::as_prover(&self.range_check));
+        for root in self.roots.iter() {
+            components.push(B::as_prover(root));
+        }
+        for inv_root in self.inv_roots.iter() {
+            components.push(B::as_prover(inv_root));
+        }
+        components
+    }
+
+    pub fn components(&self) -> Vec<&dyn Component> {
+        let mut components: Vec<&dyn Component> = vec![];
+        components.push(&self.f_ntt_butterfly);
+        for merge in self.f_ntt_merges.iter() {
+            components.push(merge);
+        }
+        components.push(&self.h_ntt_butterfly);
+        for merge in self.h_ntt_merges.iter() {
+            components.push(merge);
+        }
+        components.push(&self.invertibility);
+        components.push(&self.mul);
+        for merge in self.intt_merges.iter() {
+            components.push(merge);
+        }
+        components.push(&self.ibutterfly);
+        components.push(&self.sub);
+        components.push(&self.norm);
+        components.push(&self.coeff_range_check);
+        components.push(&self.key_bound_check);
+        components.push(&self.range_check);
+        for root in self.roots.iter() {
+            components.push(root);
+        }
+        for inv_root in self.inv_roots.iter() {
+            components.push(inv_root);
+        }
+        components
+    }
+
+    pub fn column_log_sizes(&self, preprocessed_log_sizes: Vec<u32>) -> TreeVec<Vec<u32>> {
+        let mut log_sizes = TreeVec::new(vec![vec![]; 3]);
+        for component in self.components() {
+            for (tree, tree_log_sizes) in component.trace_log_degree_bounds().iter().enumerate() {
+                log_sizes[tree].extend(tree_log_sizes.iter().copied());
+            }
+        }
+        log_sizes[PREPROCESSED_TRACE_IDX] = preprocessed_log_sizes;
+        log_sizes
+    }
+}
+
+pub fn preprocessed_log_sizes(claim: &KeyClaim) -> Vec<u32> {
+    claim
+        .create_preprocessed_columns()
+        .0
+        .iter()
+        .map(|column| column.domain.log_size())
+        .collect()
+}
@@ -0,0 +1,270 @@
+//! # Key AIR Interaction Claims
+//!
+//! This module defines the interaction claim of the key AIR, which combines the
+//! interaction claims of all its components, and generates their interaction traces.
+
+use crate::{
+    big_air::relation::{F_POLY_ID, G_POLY_ID, INTTInputLookupElements, InputLookupElements},
+    impl_big_ic,
+    key_air::{
+        claim::{KeyClaim, KeyTraces},
+        invertibility, norm,
+        relation::KeyLookupElements,
+    },
+    ntts::{intt, ntt, roots},
+    polys::{mul, sub},
+    zq::range_check,
+};
+use itertools::{Itertools, chain};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::fields::{m31::M31, qm31::QM31},
+    prover::{
+        backend::simd::SimdBackend,
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+
+impl_big_ic!(
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct KeyInteractionClaim {
+        pub f_ntt_butterfly: ntt::butterfly::InteractionClaim,
+        pub f_ntt_merges: Vec<ntt::InteractionClaim>,
+        pub h_ntt_butterfly: ntt::butterfly::InteractionClaim,
+        pub h_ntt_merges: Vec<ntt::InteractionClaim>,
+        pub invertibility: invertibility::InteractionClaim,
+        pub mul: mul::InteractionClaim,
+        pub intt_merges: Vec<intt::InteractionClaim>,
+        pub ibutterfly: intt::ibutterfly::InteractionClaim,
+        pub sub: sub::InteractionClaim,
+        pub norm: norm::InteractionClaim,
+        pub coeff_range_check: range_check::InteractionClaim,
+        pub key_bound_check: range_check::InteractionClaim,
+        pub range_check: range_check::InteractionClaim,
+        pub roots: Vec<roots::preprocessed::InteractionClaim>,
+        pub inv_roots: Vec<roots::inv_preprocessed::InteractionClaim>,
+    }
+);
+
+impl KeyInteractionClaim {
+    /// Generates the interaction traces of all the components of the key AIR.
+    ///
+    /// The shared components are wired as in the Big AIR, except that the f NTT is
+    /// read by the invertibility component and the multiplication reads its checked
+    /// copy.
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing all the interaction trace columns in commitment order
+    /// and the interaction claim.
+    pub fn gen_interaction_trace(
+        claim: &KeyClaim,
+        lookup_elements: &KeyLookupElements,
+        traces: &KeyTraces,
+    ) -> (
+        Vec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        Self,
+    ) {
+        let base = &lookup_elements.base;
+        let poly_log_size = claim.params.poly_log_size;
+        let poly_size = claim.params.poly_size();
+
+        // f is sent to the forward NTT by the key norm, on the relation s1 uses in the
+        // Big AIR
+        let (f_ntt_butterfly_interaction_trace, f_ntt_butterfly_interaction_claim) =
+            ntt::butterfly::InteractionClaim::gen_interaction_trace(
+                &traces.f_ntt_butterfly,
+                &base.rc,
+                &base.f_ntt_butterfly,
+                F_POLY_ID,
+                None,
+                Some(&base.s1),
+                poly_size,
+            );
+        let mut f_ntt_interaction_traces = vec![];
+        let mut f_ntt_interaction_claims = vec![];
+        for (i, merge) in traces.f_ntt_merges.iter().enumerate() {
+            let (f_ntt_interaction_trace, f_ntt_interaction_claim) =
+                ntt::InteractionClaim::gen_interaction_trace(
+                    merge,
+                    &base.rc,
+                    &base.f_ntt,
+                    &if i == 0 {
+                        InputLookupElements::Butterfly(base.f_ntt_butterfly.clone())
+                    } else {
+                        InputLookupElements::NTT(base.f_ntt.clone())
+                    },
+                    &base.roots,
+                    1 << (i + 1),
+                );
+            f_ntt_interaction_traces.push(f_ntt_interaction_trace);
+            f_ntt_interaction_claims.push(f_ntt_interaction_claim);
+        }
+
+        // h is the public input, on the relations of g
+        let (h_ntt_butterfly_interaction_trace, h_ntt_butterfly_interaction_claim) =
+            ntt::butterfly::InteractionClaim::gen_interaction_trace(
+                &traces.h_ntt_butterfly,
+                &base.rc,
+                &base.g_ntt_butterfly,
+                G_POLY_ID,
+                Some(&base.public_input),
+                None,
+                poly_size,
+            );
+        let mut h_ntt_interaction_traces = vec![];
+        let mut h_ntt_interaction_claims = vec![];
+        for (i, merge) in traces.h_ntt_merges.iter().enumerate() {
+            let (h_ntt_interaction_trace, h_ntt_interaction_claim) =
+                ntt::InteractionClaim::gen_interaction_trace(
+                    merge,
+                    &base.rc,
+                    &base.g_ntt,
+                    &if i == 0 {
+                        InputLookupElements::Butterfly(base.g_ntt_butterfly.clone())
+                    } else {
+                        InputLookupElements::NTT(base.g_ntt.clone())
+                    },
+                    &base.roots,
+                    1 << (i + 1),
+                );
+            h_ntt_interaction_traces.push(h_ntt_interaction_trace);
+            h_ntt_interaction_claims.push(h_ntt_interaction_claim);
+        }
+
+        let (invertibility_interaction_trace, invertibility_interaction_claim) =
+            invertibility::InteractionClaim::gen_interaction_trace(
+                &traces.invertibility,
+                &base.rc,
+                &base.f_ntt,
+                &lookup_elements.checked_f_ntt,
+                poly_log_size,
+            );
+        let (mul_interaction_trace, mul_interaction_claim) =
+            mul::InteractionClaim::gen_interaction_trace(
+                &traces.mul,
+                &lookup_elements.mul(),
+                poly_log_size,
+                None,
+            );
+
+        let mut intt_interaction_traces = vec![];
+        let mut intt_interaction_claims = vec![];
+        for (i, split) in traces.intt_merges.iter().enumerate() {
+            let (intt_interaction_trace, intt_interaction_claim) =
+                intt::InteractionClaim::gen_interaction_trace(
+                    split,
+                    &base.rc,
+                    &if i == 0 {
+                        INTTInputLookupElements::Mul(base.mul.clone())
+                    } else {
+                        INTTInputLookupElements::INTTOutput(base.intt.clone())
+                    },
+                    &base.intt,
+                    &base.inv_roots,
+                    1 << (poly_log_size as usize - i),
+                );
+            intt_interaction_traces.push(intt_interaction_trace);
+            intt_interaction_claims.push(intt_interaction_claim);
+        }
+        let (ibutterfly_interaction_trace, ibutterfly_interaction_claim) =
+            intt::ibutterfly::InteractionClaim::gen_interaction_trace(
+                &traces.ibutterfly,
+                base,
+                poly_size,
+            );
+        let (sub_interaction_trace, sub_interaction_claim) =
+            sub::InteractionClaim::gen_interaction_trace(&traces.sub, base);
+
+        let (norm_interaction_trace, norm_interaction_claim) =
+            norm::InteractionClaim::gen_interaction_trace(
+                &traces.norm,
+                &claim.norm,
+                &lookup_elements.coeff_range_check,
+                &base.sub,
+                &lookup_elements.key_bound_check,
+                &base.s1,
+            );
+        let (coeff_range_check_interaction_trace, coeff_range_check_interaction_claim) =
+            range_check::InteractionClaim::gen_interaction_trace(
+                &traces.coeff_range_check,
+                &lookup_elements.coeff_range_check,
+                claim.coeff_range_check.bound,
+            );
+        let (key_bound_check_interaction_trace, key_bound_check_interaction_claim) =
+            range_check::InteractionClaim::gen_interaction_trace(
+                &traces.key_bound_check,
+                &lookup_elements.key_bound_check,
+                claim.key_bound_check.bound,
+            );
+        let (range_check_interaction_trace, range_check_interaction_claim) =
+            range_check::InteractionClaim::gen_interaction_trace(
+                &traces.range_check,
+                &base.rc,
+                claim.range_check.bound,
+            );
+
+        let mut roots_interaction_traces = vec![];
+        let mut roots_interaction_claims = vec![];
+        for (stage, stage_root_trace) in traces.roots.iter().enumerate() {
+            let (roots_interaction_trace, roots_interaction_claim) =
+                roots::preprocessed::InteractionClaim::gen_interaction_trace(
+                    stage_root_trace,
+                    &base.roots,
+                    stage + 2,
+                );
+            roots_interaction_traces.push(roots_interaction_trace);
+            roots_interaction_claims.push(roots_interaction_claim);
+        }
+        let mut inv_roots_interaction_traces = vec![];
+        let mut inv_roots_interaction_claims = vec![];
+        for (stage, stage_root_trace) in traces.inv_roots.iter().enumerate() {
+            let (inv_roots_interaction_trace, inv_roots_interaction_claim) =
+                roots::inv_preprocessed::InteractionClaim::gen_interaction_trace(
+                    stage_root_trace,
+                    &base.inv_roots,
+                    poly_log_size as usize - stage,
+                );
+            inv_roots_interaction_traces.push(inv_roots_interaction_trace);
+            inv_roots_interaction_claims.push(inv_roots_interaction_claim);
+        }
+
+        (
+            chain!(
+                f_ntt_butterfly_interaction_trace,
+                f_ntt_interaction_traces.into_iter().flatten(),
+                h_ntt_butterfly_interaction_trace,
+                h_ntt_interaction_traces.into_iter().flatten(),
+                invertibility_interaction_trace,
+                mul_interaction_trace,
+                intt_interaction_traces.into_iter().flatten(),
+                ibutterfly_interaction_trace,
+                sub_interaction_trace,
+                norm_interaction_trace,
+                coeff_range_check_interaction_trace,
+                key_bound_check_interaction_trace,
+                range_check_interaction_trace,
+                roots_interaction_traces.into_iter().flatten(),
+                inv_roots_interaction_traces.into_iter().flatten(),
+            )
+            .collect_vec(),
+            Self {
+                f_ntt_butterfly: f_ntt_butterfly_interaction_claim,
+                f_ntt_merges: f_ntt_interaction_claims,
+                h_ntt_butterfly: h_ntt_butterfly_interaction_claim,
+                h_ntt_merges: h_ntt_interaction_claims,
+                invertibility: invertibility_interaction_claim,
+                mul: mul_interaction_claim,
+                intt_merges: intt_interaction_claims,
+                ibutterfly: ibutterfly_interaction_claim,
+                sub: sub_interaction_claim,
+                norm: norm_interaction_claim,
+                coeff_range_check: coeff_range_check_interaction_claim,
+                key_bound_check: key_bound_check_interaction_claim,
+                range_check: range_check_interaction_claim,
+                roots: roots_interaction_claims,
+                inv_roots: inv_roots_interaction_claims,
+            },
+        )
+    }
+}
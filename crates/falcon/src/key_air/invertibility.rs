@@ -0,0 +1,309 @@
+//! # Invertibility Component
+//!
+//! This module implements the STARK proof component checking that a polynomial f is
+//! invertible in Z_q[x]/(xⁿ + 1).
+//!
+//! # Mathematical Foundation
+//!
+//! Since 2n divides q - 1, xⁿ + 1 splits into n distinct linear factors over Z_q and
+//! the NTT of f holds its evaluations at their roots. f is therefore invertible if and
+//! only if none of these evaluations is zero, which is proven by exhibiting the inverse
+//! of each of them:
+//!
+//! a * inverse = quotient * q + 1
+//!
+//! where inverse and quotient are range checked in [0, q). As a is itself a range checked
+//! remainder of the NTT, both sides stay below q² and the equation holds over the
+//! integers, so that a * inverse ≡ 1 (mod q).
+//!
+//! # Lookups
+//!
+//! Each row consumes one coefficient of the NTT of f from the last merge and emits it
+//! again on a separate relation, the one the multiplication reads f from, so that no
+//! coefficient reaches the multiplication without going through this component.
+//!
+//! # Trace Structure
+//!
+//! The component generates traces with the following columns:
+//! - Column 0: The coefficient a of the NTT of f
+//! - Column 1: Its inverse modulo q
+//! - Column 2: The quotient of a * inverse by q
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{
+        ColumnVec,
+        channel::Channel,
+        fields::{m31::M31, qm31::SecureField},
+        poly::circle::CanonicCoset,
+    },
+    prover::{
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{
+    FrameworkComponent, FrameworkEval, LogupTraceGenerator, Relation, RelationEntry,
+};
+
+use crate::{
+    big_air::{
+        public_inputs::Indices,
+        relation::{F_POLY_ID, NTTLookupElements, RCLookupElements},
+    },
+    zq::{Q, inverses::INVERSES_MOD_Q},
+};
+
+/// Claim parameters for the invertibility circuit.
+///
+/// # Parameters
+///
+/// - `log_size`: The log base 2 of the trace size, one coefficient of the NTT per row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    /// The log base 2 of the trace size
+    pub log_size: u32,
+}
+
+impl Claim {
+    /// Mixes the claim parameters into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size as u64);
+    }
+
+    /// Generates the trace for the invertibility component.
+    ///
+    /// # Parameters
+    ///
+    /// - `a`: The coefficients of the NTT of f, with values in [0, Q)
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing:
+    /// - `ColumnVec<CircleEvaluation<...>>`: The trace columns a, inverse and quotient
+    /// - `Vec<M31>`: The inverses and the quotients, for range checking
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient is zero, i.e. if f is not invertible.
+    pub fn gen_trace(
+        &self,
+        a: &[u32],
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        Vec<M31>,
+    ) {
+        assert_eq!(a.len(), 1 << self.log_size);
+        let inverses = a
+            .iter()
+            .map(|a| {
+                assert_ne!(*a, 0, "f is not invertible");
+                INVERSES_MOD_Q[*a as usize]
+            })
+            .collect::<Vec<_>>();
+        let quotients = a
+            .iter()
+            .zip(inverses.iter())
+            .map(|(a, inverse)| M31::from_u32_unchecked(a * inverse / Q))
+            .collect::<Vec<_>>();
+        let inverses = inverses
+            .into_iter()
+            .map(M31::from_u32_unchecked)
+            .collect::<Vec<_>>();
+        let a = a
+            .iter()
+            .map(|a| M31::from_u32_unchecked(*a))
+            .collect::<Vec<_>>();
+        let domain = CanonicCoset::new(self.log_size).circle_domain();
+        let range_checked = inverses
+            .iter()
+            .chain(quotients.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        (
+            [a, inverses, quotients]
+                .into_iter()
+                .map(|col| {
+                    CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
+                        domain,
+                        BaseColumn::from_iter(col),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            range_checked,
+        )
+    }
+}
+
+// Actual component that is used in the framework
+#[derive(Debug, Clone)]
+pub struct Eval {
+    /// The claim parameters
+    pub claim: Claim,
+    /// Lookup elements for range checking
+    pub rc_lookup_elements: RCLookupElements,
+    /// Lookup elements for the NTT of f, as output by the last merge
+    pub f_ntt_lookup_elements: NTTLookupElements,
+    /// Lookup elements for the NTT of f, once checked, as read by the multiplication
+    pub checked_f_ntt_lookup_elements: NTTLookupElements,
+    /// The log size of the polynomials, which is the number of layers of their NTTs
+    pub poly_log_size: u32,
+}
+
+impl FrameworkEval for Eval {
+    fn log_size(&self) -> u32 {
+        self.claim.log_size
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.claim.log_size + 1
+    }
+
+    fn evaluate<E: stwo_constraint_framework::EvalAtRow>(&self, mut eval: E) -> E {
+        // Those values were filled during the trace generation
+        let a = eval.next_trace_mask();
+        let inverse = eval.next_trace_mask();
+        let quotient = eval.next_trace_mask();
+
+        // a * inverse = quotient * Q + 1, with inverse and quotient in [0, Q)
+        eval.add_constraint(
+            a.clone() * inverse.clone() - quotient.clone() * E::F::from(M31(Q)) - E::F::one(),
+        );
+        eval.add_to_relation(RelationEntry::new(
+            &self.rc_lookup_elements,
+            E::EF::one(),
+            &[inverse],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.rc_lookup_elements,
+            E::EF::one(),
+            &[quotient],
+        ));
+
+        // Row i checks the coefficient i of the NTT of f and passes it on unchanged
+        let index = eval.get_preprocessed_column(Indices::natural(self.claim.log_size).id());
+        let poly_id = E::F::from(M31::from_u32_unchecked(F_POLY_ID));
+        let stage = E::F::from(M31::from_u32_unchecked(self.poly_log_size));
+        eval.add_to_relation(RelationEntry::new(
+            &self.f_ntt_lookup_elements,
+            E::EF::one(),
+            &[poly_id.clone(), stage.clone(), index.clone(), a.clone()],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.checked_f_ntt_lookup_elements,
+            -E::EF::one(),
+            &[poly_id, stage, index, a],
+        ));
+        eval.finalize_logup();
+        eval
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionClaim {
+    /// The claimed sum for the interaction
+    pub claimed_sum: SecureField,
+}
+
+impl InteractionClaim {
+    /// Mixes the interaction claim into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_felts(&[self.claimed_sum]);
+    }
+
+    /// Generates the interaction trace for the invertibility check.
+    ///
+    /// # Parameters
+    ///
+    /// - `trace`: The trace columns from the invertibility component
+    /// - `rc_lookup_elements`: The lookup elements for range checking
+    /// - `f_ntt_lookup_elements`: The lookup elements of the last merge of f
+    /// - `checked_f_ntt_lookup_elements`: The lookup elements read by the multiplication
+    /// - `poly_log_size`: The log size of the polynomials, the stage of their NTTs
+    ///
+    /// # Returns
+    ///
+    /// Returns the interaction trace and the interaction claim.
+    pub fn gen_interaction_trace(
+        trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+        rc_lookup_elements: &RCLookupElements,
+        f_ntt_lookup_elements: &NTTLookupElements,
+        checked_f_ntt_lookup_elements: &NTTLookupElements,
+        poly_log_size: u32,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        InteractionClaim,
+    ) {
+        let log_size = trace[0].domain.log_size();
+        let mut logup_gen = LogupTraceGenerator::new(log_size);
+        let indices = Indices::natural(log_size).gen_column_simd();
+        let poly_id = PackedM31::broadcast(M31::from_u32_unchecked(F_POLY_ID));
+        let stage = PackedM31::broadcast(M31::from_u32_unchecked(poly_log_size));
+
+        // Range check the inverse and the quotient
+        for column in [&trace[1], &trace[2]] {
+            let mut col_gen = logup_gen.new_col();
+            for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+                let denom: PackedQM31 = rc_lookup_elements.combine(&[column.data[vec_row]]);
+                col_gen.write_frac(vec_row, PackedQM31::one(), denom);
+            }
+            col_gen.finalize_col();
+        }
+
+        // Consume the coefficient output by the last merge, then emit it for the
+        // multiplication
+        for (lookup_elements, numerator) in [
+            (f_ntt_lookup_elements, PackedQM31::one()),
+            (checked_f_ntt_lookup_elements, -PackedQM31::one()),
+        ] {
+            let mut col_gen = logup_gen.new_col();
+            for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+                let denom: PackedQM31 = lookup_elements.combine(&[
+                    poly_id,
+                    stage,
+                    indices.data[vec_row],
+                    trace[0].data[vec_row],
+                ]);
+                col_gen.write_frac(vec_row, numerator, denom);
+            }
+            col_gen.finalize_col();
+        }
+
+        let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
+        (interaction_trace, InteractionClaim { claimed_sum })
+    }
+}
+
+/// Type alias for the invertibility component.
+pub type Component = FrameworkComponent<Eval>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gen_trace_inverts_every_coefficient() {
+        let a = (1..=16).map(|i| i * 700 % Q).collect::<Vec<_>>();
+        let (trace, range_checked) = Claim { log_size: 4 }.gen_trace(&a);
+        assert_eq!(trace.len(), 3);
+        let (inverses, quotients) = range_checked.split_at(a.len());
+        for ((a, inverse), quotient) in a.iter().zip(inverses).zip(quotients) {
+            assert_eq!(a * inverse.0, quotient.0 * Q + 1);
+            assert!(inverse.0 < Q && quotient.0 < Q);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "f is not invertible")]
+    fn test_gen_trace_rejects_zero() {
+        let mut a = vec![1; 16];
+        a[3] = 0;
+        Claim { log_size: 4 }.gen_trace(&a);
+    }
+}
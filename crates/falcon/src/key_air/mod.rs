@@ -0,0 +1,593 @@
+//! # Key AIR - Proof of Possession of a Falcon Signing Key
+//!
+//! This module proves that the prover knows a well formed signing key (f, g) for a
+//! public key h, without revealing it:
+//! - h = g * f⁻¹ mod q, i.e. f * h = g in Z_q[x]/(xⁿ + 1)
+//! - f is invertible in Z_q[x]/(xⁿ + 1)
+//! - the squared norm of (f, g) is at most [`KEY_NORM_BOUND`], the bound of the Falcon
+//!   key generation
+//!
+//! The proof reuses the arithmetic components of the signature verification: h is the
+//! public input of the forward NTT, f takes the place of s1 and the zero polynomial the
+//! place of the message point, so that the subtraction outputs s0 = -f * h = -g. Two
+//! components are specific to keys:
+//! - the invertibility component, which exhibits the inverse of every coefficient of
+//!   the NTT of f before the multiplication reads it
+//! - the key norm, which bounds every centered coefficient of f and g and their squared
+//!   norm, and sends f to the forward NTT
+//!
+//! The secret key F, G completing the NTRU equation is not part of the statement.
+
+pub mod claim;
+pub mod components;
+pub mod interaction_claim;
+pub mod invertibility;
+pub mod norm;
+pub mod proof;
+pub mod prover;
+pub mod relation;
+
+use itertools::Itertools;
+use num_traits::Zero;
+use stwo::{
+    core::{
+        channel::Channel,
+        fields::{FieldExpOps, m31::M31, qm31::SecureField},
+        pcs::CommitmentSchemeVerifier,
+        verifier::{VerificationError, verify},
+    },
+    prover::{
+        ProvingError,
+        backend::{BackendForChannel, simd::SimdBackend},
+    },
+};
+use stwo_constraint_framework::{INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX, Relation};
+use thiserror::Error;
+
+use crate::{
+    CircuitParams,
+    big_air::{
+        backend::FalconBackend, check_config, check_preprocessed_root, config::ProverConfig,
+        proof::FalconMerkleChannel, prover::CircuitId, public_inputs::PK_ID,
+    },
+    key_air::{
+        claim::KeyClaim,
+        components::{KeyComponents, preprocessed_log_sizes},
+        norm::KEY_COEFF_BOUND,
+        proof::KeyProof,
+        prover::KeyProver,
+        relation::KeyLookupElements,
+    },
+    ntts::ROOTS,
+    zq::Q,
+};
+
+/// The bound on the squared norm of (f, g) accepted by the Falcon key generation.
+pub const KEY_NORM_BOUND: u32 = 16822;
+
+/// Errors that can occur when proving possession of a Falcon signing key.
+///
+/// Everything but [`KeyProveError::UnbalancedLookup`] and [`KeyProveError::Stwo`] is
+/// detected from the inputs before any trace is built.
+#[derive(Debug, Error)]
+pub enum KeyProveError {
+    #[error("unsupported polynomial size {0}")]
+    UnsupportedDegree(usize),
+    #[error("unsupported circuit parameters {0:?}")]
+    UnsupportedParams(CircuitParams),
+    #[error("{poly} has {found} coefficients, expected {expected}")]
+    DegreeMismatch {
+        poly: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("coefficient {index} of {poly} is {value}, expected a value in {range}")]
+    CoefficientOutOfRange {
+        poly: &'static str,
+        index: usize,
+        value: i64,
+        range: &'static str,
+    },
+    #[error("squared norm {norm} of the key exceeds the bound {bound}")]
+    NormExceedsBound { norm: u64, bound: u32 },
+    #[error("f is not invertible modulo xⁿ + 1")]
+    NotInvertible,
+    #[error("f * pk differs from g")]
+    KeyMismatch,
+    #[error("unsupported prover configuration {0:?}")]
+    UnsupportedConfig(ProverConfig),
+    #[error("lookup relations do not balance out")]
+    UnbalancedLookup,
+    #[error(transparent)]
+    Stwo(#[from] ProvingError),
+}
+
+/// A Falcon key pair to prove possession of.
+#[derive(Debug, Clone, Copy)]
+pub struct KeyInstance<'a> {
+    /// The public key polynomial h, with coefficients in [0, Q)
+    pub pk: &'a [u32],
+    /// The secret polynomial f, with signed coefficients
+    pub f: &'a [i16],
+    /// The secret polynomial g = f * h, with signed coefficients
+    pub g: &'a [i16],
+}
+
+impl<'a> KeyInstance<'a> {
+    pub fn new(pk: &'a [u32], f: &'a [i16], g: &'a [i16]) -> Self {
+        Self { pk, f, g }
+    }
+
+    /// Returns the coefficients of f in [0, q).
+    pub fn f_mod_q(&self) -> Vec<u32> {
+        self.f
+            .iter()
+            .map(|&x| (x as i32).rem_euclid(Q as i32) as u32)
+            .collect()
+    }
+
+    /// Checks that the instance is a well formed key of the degree of `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyProveError` if the polynomials do not have `params.poly_size()`
+    /// coefficients, the public key is not in [0, q), a coefficient of f or g is not
+    /// below [`KEY_COEFF_BOUND`] in absolute value, the squared norm of (f, g) exceeds
+    /// [`KEY_NORM_BOUND`], f is not invertible or f * h differs from g.
+    pub fn validate(&self, params: CircuitParams) -> Result<(), KeyProveError> {
+        let poly_size = params.poly_size();
+        for (poly, len) in [
+            ("pk", self.pk.len()),
+            ("f", self.f.len()),
+            ("g", self.g.len()),
+        ] {
+            if len != poly_size {
+                return Err(KeyProveError::DegreeMismatch {
+                    poly,
+                    expected: poly_size,
+                    found: len,
+                });
+            }
+        }
+        if let Some((index, &value)) = self.pk.iter().find_position(|&&value| value >= Q) {
+            return Err(KeyProveError::CoefficientOutOfRange {
+                poly: "pk",
+                index,
+                value: value as i64,
+                range: "[0, q)",
+            });
+        }
+        for (poly, coefficients) in [("f", self.f), ("g", self.g)] {
+            if let Some((index, &value)) = coefficients
+                .iter()
+                .find_position(|value| value.unsigned_abs() as u32 >= KEY_COEFF_BOUND)
+            {
+                return Err(KeyProveError::CoefficientOutOfRange {
+                    poly,
+                    index,
+                    value: value as i64,
+                    range: "(-256, 256)",
+                });
+            }
+        }
+        let norm = self
+            .f
+            .iter()
+            .chain(self.g)
+            .map(|&x| (x as i64).pow(2) as u64)
+            .sum::<u64>();
+        if norm > KEY_NORM_BOUND as u64 {
+            return Err(KeyProveError::NormExceedsBound {
+                norm,
+                bound: KEY_NORM_BOUND,
+            });
+        }
+
+        // The NTT of f holds its evaluations at the roots of xⁿ + 1
+        let f = self.f_mod_q();
+        let evaluate = |x: u32| {
+            f.iter()
+                .rev()
+                .fold(0, |acc, &c| (acc * x as u64 + c as u64) % Q as u64)
+        };
+        if ROOTS[params.poly_log_size as usize - 1]
+            .iter()
+            .any(|&root| evaluate(root) == 0)
+        {
+            return Err(KeyProveError::NotInvertible);
+        }
+
+        let g = self
+            .g
+            .iter()
+            .map(|&x| (x as i32).rem_euclid(Q as i32) as u32)
+            .collect_vec();
+        if mul_mod_q(&f, self.pk) != g {
+            return Err(KeyProveError::KeyMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Returns a * b in Z_q[x]/(xⁿ + 1), with a schoolbook multiplication.
+fn mul_mod_q(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let n = a.len();
+    let q = Q as u64;
+    let mut product = vec![0u64; n];
+    for (i, &a) in a.iter().enumerate().filter(|(_, a)| **a != 0) {
+        for (j, &b) in b.iter().enumerate() {
+            let term = a as u64 * b as u64 % q;
+            if i + j < n {
+                product[i + j] = (product[i + j] + term) % q;
+            } else {
+                // x^n = -1
+                product[i + j - n] = (product[i + j - n] + q - term) % q;
+            }
+        }
+    }
+    product.into_iter().map(|x| x as u32).collect()
+}
+
+/// Returns the contribution of the public inputs to the logup sum of the key AIR.
+///
+/// The coefficients of the public key consumed by the forward NTT are added back, and
+/// the zero message point read by the subtraction is emitted, as the sampler would. The
+/// coefficients are reduced modulo the M31 modulus, so the verifier rejects coefficients
+/// out of [0, q) beforehand.
+fn logup_sum(pk: &[u32], lookup_elements: &KeyLookupElements) -> SecureField {
+    let base = &lookup_elements.base;
+    let mut sum = SecureField::zero();
+    for (index, value) in pk.iter().enumerate() {
+        let denom: SecureField =
+            base.public_input
+                .combine(&[M31(PK_ID), M31(index as u32), M31(*value)]);
+        sum += denom.inverse();
+        let denom: SecureField = base.msg_point.combine(&[M31(index as u32), M31(0)]);
+        sum -= denom.inverse();
+    }
+    sum
+}
+
+/// Generates a STARK proof of possession of a well formed signing key for `instance.pk`.
+///
+/// # Errors
+///
+/// Returns `KeyProveError` if the instance is not a well formed key of a supported
+/// degree, see [`KeyInstance::validate`], or if any step in the proof generation fails.
+pub fn prove_key<MC: FalconMerkleChannel>(
+    instance: &KeyInstance,
+) -> Result<KeyProof<MC>, KeyProveError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    prove_key_with_config(instance, ProverConfig::default())
+}
+
+/// Generates a STARK proof of possession of a signing key with the given security
+/// parameters.
+///
+/// This is [`prove_key`] with a [`ProverConfig`]. The configuration is recorded in the
+/// proof.
+///
+/// # Errors
+///
+/// Returns `KeyProveError::UnsupportedConfig` if proofs cannot be generated with
+/// `config`, and the errors of [`prove_key`] otherwise.
+pub fn prove_key_with_config<MC: FalconMerkleChannel>(
+    instance: &KeyInstance,
+    config: ProverConfig,
+) -> Result<KeyProof<MC>, KeyProveError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    prove_key_with_backend::<SimdBackend, MC>(instance, config)
+}
+
+/// Generates a STARK proof of possession of a signing key on the backend `B`.
+///
+/// This is [`prove_key_with_config`] on another backend than the SIMD one, see
+/// [`prove_falcon_batch_with_backend`](crate::big_air::prove_falcon_batch_with_backend).
+/// The circuit is built for every call: a [`KeyProver`] reuses it across proofs.
+///
+/// # Errors
+///
+/// Returns the errors of [`prove_key_with_config`].
+pub fn prove_key_with_backend<B, MC>(
+    instance: &KeyInstance,
+    config: ProverConfig,
+) -> Result<KeyProof<MC>, KeyProveError>
+where
+    B: FalconBackend + BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    let params = CircuitParams::from_poly_size(instance.pk.len())
+        .ok_or(KeyProveError::UnsupportedDegree(instance.pk.len()))?;
+    KeyProver::<B, MC>::new(params, config)?.prove(instance)
+}
+
+/// Verifies a STARK proof generated by [`prove_key`] for the public key `pk`.
+///
+/// The verifier replays the prover's Fiat-Shamir transcript like
+/// [`crate::big_air::verify_falcon_batch`], with the public key as the only public input,
+/// and checks that the proof commits to the preprocessed trace of the key AIR.
+///
+/// # Errors
+///
+/// Returns `VerificationError` if the proof was generated for another degree or public
+/// key, a coefficient of the public key is not in [0, q), its claim does not match the
+/// circuit of that degree, its configuration is unsupported or weaker than `minimum`, it
+/// commits to another preprocessed trace than the one of the key AIR, the proof of work
+/// is invalid, the logup sums do not cancel out or the STARK proof does not verify.
+pub fn verify_key<MC: FalconMerkleChannel>(
+    proof: KeyProof<MC>,
+    pk: &[u32],
+    minimum: &ProverConfig,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    verify_key_with_optional_circuit_id(proof, pk, minimum, None)
+}
+
+/// Verifies a STARK proof generated by [`prove_key`] for the circuit `circuit_id`.
+///
+/// This is [`verify_key`] for a verifier that pinned the id of the circuit, e.g. from
+/// [`KeyProver::circuit_id`], instead of committing to the preprocessed trace to compute
+/// it.
+///
+/// # Errors
+///
+/// Returns `VerificationError` if the proof commits to another preprocessed trace, and
+/// the errors of [`verify_key`] otherwise.
+pub fn verify_key_with_circuit_id<MC: FalconMerkleChannel>(
+    proof: KeyProof<MC>,
+    pk: &[u32],
+    circuit_id: CircuitId<MC>,
+    minimum: &ProverConfig,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    verify_key_with_optional_circuit_id(proof, pk, minimum, Some(circuit_id))
+}
+
+/// Verifies a STARK proof of the key AIR, whose preprocessed trace has the root
+/// `circuit_id` if it is given, or the root of the preprocessed trace of the claim
+/// otherwise.
+fn verify_key_with_optional_circuit_id<MC: FalconMerkleChannel>(
+    proof: KeyProof<MC>,
+    pk: &[u32],
+    minimum: &ProverConfig,
+    circuit_id: Option<CircuitId<MC>>,
+) -> Result<(), VerificationError>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    let KeyProof {
+        params,
+        config,
+        pk: proof_pk,
+        claim,
+        interaction_pow,
+        interaction_claim,
+        stark_proof,
+    } = proof;
+
+    let expected = CircuitParams::from_poly_size(pk.len()).ok_or_else(|| {
+        VerificationError::InvalidStructure("unsupported polynomial degree".to_string())
+    })?;
+    if params != expected {
+        return Err(VerificationError::InvalidStructure(
+            "circuit parameters mismatch".to_string(),
+        ));
+    }
+    // The coefficients of the public key are added back to the logup sum as M31
+    // elements, where h and h + p are the same entry: only canonical ones are accepted
+    if pk.iter().any(|&value| value >= Q) {
+        return Err(VerificationError::InvalidStructure(
+            "public key coefficient out of range".to_string(),
+        ));
+    }
+    // The claim only holds trace sizes and bounds, which are all fixed by the parameters:
+    // a loosened bound must not be accepted
+    if claim != KeyClaim::new(expected) {
+        return Err(VerificationError::InvalidStructure(
+            "claim does not match the circuit parameters".to_string(),
+        ));
+    }
+    if proof_pk != pk {
+        return Err(VerificationError::InvalidStructure(
+            "public key mismatch".to_string(),
+        ));
+    }
+    check_config(&config, minimum)?;
+
+    // The preprocessed trace is fixed by the parameters and the configuration
+    let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
+    check_preprocessed_root::<MC>(
+        stark_proof.commitments[0],
+        preprocessed_columns,
+        &config,
+        circuit_id,
+    )?;
+
+    // Initialize Fiat-Shamir channel and commitment scheme
+    let channel = &mut MC::C::default();
+    config.mix_into(channel);
+    channel.mix_u32s(pk);
+    let commitment_scheme = &mut CommitmentSchemeVerifier::<MC>::new(config.pcs_config());
+
+    // The column log sizes only depend on the claim, so the components are built
+    // with dummy lookup elements to retrieve them.
+    let dummy_lookup_elements = KeyLookupElements::draw(&mut MC::C::default());
+    let log_sizes = KeyComponents::new(
+        &claim,
+        &dummy_lookup_elements,
+        &interaction_claim,
+        &preprocessed_columns_ids,
+    )
+    .column_log_sizes(preprocessed_log_sizes(&claim));
+
+    // Preprocessed columns
+    commitment_scheme.commit(stark_proof.commitments[0], &log_sizes[0], channel);
+
+    // Main trace
+    claim.mix_into(channel);
+    commitment_scheme.commit(
+        stark_proof.commitments[ORIGINAL_TRACE_IDX],
+        &log_sizes[ORIGINAL_TRACE_IDX],
+        channel,
+    );
+
+    // Proof of work and lookup relations
+    channel.mix_u64(interaction_pow);
+    if channel.trailing_zeros() < config.interaction_pow_bits {
+        return Err(VerificationError::ProofOfWork);
+    }
+    let lookup_elements = KeyLookupElements::draw(channel);
+
+    // Interaction trace
+    interaction_claim.mix_into(channel);
+    commitment_scheme.commit(
+        stark_proof.commitments[INTERACTION_TRACE_IDX],
+        &log_sizes[INTERACTION_TRACE_IDX],
+        channel,
+    );
+
+    if interaction_claim.claimed_sum() + logup_sum(pk, &lookup_elements) != SecureField::zero() {
+        return Err(VerificationError::InvalidStructure(
+            "invalid logup sum".to_string(),
+        ));
+    }
+
+    let components = KeyComponents::new(
+        &claim,
+        &lookup_elements,
+        &interaction_claim,
+        &preprocessed_columns_ids,
+    );
+    verify(
+        &components.components(),
+        channel,
+        commitment_scheme,
+        stark_proof,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{self, F, G, PK};
+    use stwo::{
+        core::{fields::m31::P, vcs::blake2_merkle::Blake2sMerkleChannel},
+        prover::backend::cpu::CpuBackend,
+    };
+
+    #[test]
+    fn test_prove_and_verify_key() {
+        let proof = prove_key::<Blake2sMerkleChannel>(&KeyInstance::new(PK, F, G)).unwrap();
+        verify_key(proof, PK, &ProverConfig::default()).unwrap();
+    }
+
+    #[test]
+    fn test_key_prover_reuses_its_circuit() {
+        let params = CircuitParams::from_poly_size(PK.len()).unwrap();
+        let config = ProverConfig::default();
+        let prover = KeyProver::<SimdBackend, Blake2sMerkleChannel>::new(params, config).unwrap();
+        let circuit_id = prover.circuit_id();
+        assert_eq!(
+            circuit_id,
+            prover::circuit_id::<Blake2sMerkleChannel>(params, &config)
+        );
+
+        let proof = prover.prove(&KeyInstance::new(PK, F, G)).unwrap();
+        assert_eq!(proof.circuit_id(), circuit_id);
+        verify_key_with_circuit_id(proof, PK, circuit_id, &config).unwrap();
+
+        // The CPU backend commits to the same circuit
+        let cpu_proof = prove_key_with_backend::<CpuBackend, Blake2sMerkleChannel>(
+            &KeyInstance::new(PK, F, G),
+            config,
+        )
+        .unwrap();
+        assert_eq!(cpu_proof.circuit_id(), circuit_id);
+        verify_key(cpu_proof, PK, &config).unwrap();
+    }
+
+    #[test]
+    fn test_verify_key_rejects_other_preprocessed_trace() {
+        let proof = prove_key::<Blake2sMerkleChannel>(&KeyInstance::new(PK, F, G)).unwrap();
+
+        // The root of the preprocessed trace of the Falcon-512 key AIR
+        let other =
+            prover::circuit_id::<Blake2sMerkleChannel>(CircuitParams::falcon_512(), &proof.config);
+        let mut tampered = proof.clone();
+        tampered.stark_proof.0.commitments[0] = other;
+        assert!(matches!(
+            verify_key(tampered, PK, &ProverConfig::default()),
+            Err(VerificationError::InvalidStructure(reason)) if reason == "preprocessed trace mismatch"
+        ));
+
+        // A verifier pinning another circuit rejects the honest proof
+        assert!(verify_key_with_circuit_id(proof, PK, other, &ProverConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_verify_key_rejects_non_canonical_pk() {
+        let proof = prove_key::<Blake2sMerkleChannel>(&KeyInstance::new(PK, F, G)).unwrap();
+
+        // A coefficient shifted by the M31 modulus, which adds back the same logup entry,
+        // also swapped in the proof
+        let mut pk = PK.to_vec();
+        pk[0] += P;
+        let mut tampered = proof;
+        tampered.pk = pk.clone();
+        assert!(matches!(
+            verify_key(tampered, &pk, &ProverConfig::default()),
+            Err(VerificationError::InvalidStructure(reason)) if reason == "public key coefficient out of range"
+        ));
+    }
+
+    #[test]
+    fn test_verify_key_rejects_other_pk() {
+        let proof = prove_key::<Blake2sMerkleChannel>(&KeyInstance::new(PK, F, G)).unwrap();
+        let mut pk = PK.to_vec();
+        pk[0] = (pk[0] + 1) % Q;
+        assert!(verify_key(proof, &pk, &ProverConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_key_over_the_norm_bound() {
+        let params = CircuitParams::from_poly_size(input::falcon_512::PK.len()).unwrap();
+        let instance = KeyInstance::new(
+            input::falcon_512::PK,
+            input::falcon_512::F,
+            input::falcon_512::G,
+        );
+        assert!(matches!(
+            instance.validate(params),
+            Err(KeyProveError::NormExceedsBound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_mismatched_key() {
+        let params = CircuitParams::from_poly_size(PK.len()).unwrap();
+        let mut g = G.to_vec();
+        g.swap(0, 1);
+        assert!(matches!(
+            KeyInstance::new(PK, F, &g).validate(params),
+            Err(KeyProveError::KeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_non_invertible_f() {
+        let params = CircuitParams::from_poly_size(PK.len()).unwrap();
+        let zero = vec![0; PK.len()];
+        assert!(matches!(
+            KeyInstance::new(PK, &zero, &zero).validate(params),
+            Err(KeyProveError::NotInvertible)
+        ));
+    }
+}
@@ -0,0 +1,406 @@
+//! # Key Norm Component
+//!
+//! This module implements the STARK proof component bounding the norm of the secret
+//! key (f, g). It is derived from the Euclidean norm of the signature verification, see
+//! [`euclidean_norm`](crate::polys::euclidean_norm), with two differences:
+//!
+//! - The coefficients are short, so their centered remainders are range checked against
+//!   [`KEY_COEFF_BOUND`] instead of q / 2. The squared norm then stays below
+//!   2n * KEY_COEFF_BOUND², far from the M31 modulus, and is compared exactly.
+//! - The key bound is small, so the norm is checked in one piece: `bound - norm` is range
+//!   checked in [0, bound], which fails for any norm above the bound.
+//!
+//! # Lookups
+//!
+//! - The first polynomial is s0 = -g, read from the subtraction like the signature s0
+//! - The second polynomial is f itself, which this component emits to the forward NTT:
+//!   the coefficients of f are only committed here, next to their norm
+//!
+//! # Trace Structure
+//!
+//! The component generates traces with the following columns:
+//! - s0, its borrow and its centered remainder
+//! - f, its borrow and its centered remainder
+//! - The cumulative sum of the squared remainders
+
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use stwo::{
+    core::{
+        ColumnVec,
+        channel::Channel,
+        fields::{m31::M31, qm31::SecureField},
+        poly::circle::CanonicCoset,
+        utils::bit_reverse_coset_to_circle_domain_order,
+    },
+    prover::{
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::{
+    FrameworkComponent, FrameworkEval, LogupTraceGenerator, ORIGINAL_TRACE_IDX, Relation,
+    RelationEntry,
+};
+
+use crate::{
+    big_air::{
+        public_inputs::Indices,
+        relation::{RCLookupElements, S0_POLY_ID, S1LookupElements, SubLookupElements},
+    },
+    polys::euclidean_norm::{NormColumn, NormFlags},
+    zq::Q,
+};
+
+/// The exclusive bound on the centered coefficients of f and g.
+///
+/// Any key within the norm bound has coefficients of absolute value at most 129.
+pub const KEY_COEFF_BOUND: u32 = 256;
+
+/// Claim parameters for the key norm circuit.
+///
+/// # Parameters
+///
+/// - `log_size`: The log base 2 of the trace size, one coefficient of each polynomial per row
+/// - `bound`: The inclusive bound on the squared norm of (f, g)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    /// The log base 2 of the trace size
+    pub log_size: u32,
+    /// The inclusive bound on the squared norm
+    pub bound: u32,
+}
+
+impl Claim {
+    /// Mixes the claim parameters into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.log_size as u64);
+        channel.mix_u64(self.bound as u64);
+    }
+
+    /// Generates the trace for the key norm component.
+    ///
+    /// # Parameters
+    ///
+    /// - `s0`: The coefficients of -g, in [0, q)
+    /// - `f`: The coefficients of f, in [0, q)
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple containing:
+    /// - `ColumnVec<CircleEvaluation<...>>`: The computation trace columns
+    /// - `Vec<M31>`: The centered remainders, for range checking against [`KEY_COEFF_BOUND`]
+    /// - `M31`: `bound - norm`, for range checking against `bound + 1`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a centered coefficient is not below [`KEY_COEFF_BOUND`] or if the squared
+    /// norm exceeds the bound.
+    pub fn gen_trace(
+        &self,
+        s0: &[u32],
+        f: &[u32],
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        Vec<M31>,
+        M31,
+    ) {
+        let n_rows = 1 << self.log_size;
+        assert_eq!(s0.len(), n_rows);
+        assert_eq!(f.len(), n_rows);
+        let borrow = |a: &u32| (*a > Q / 2) as u32;
+        let remainder = |a: &u32| if *a > Q / 2 { Q - *a } else { *a };
+        let remainders_s0 = s0.iter().map(remainder).collect::<Vec<_>>();
+        let remainders_f = f.iter().map(remainder).collect::<Vec<_>>();
+        assert!(
+            remainders_s0
+                .iter()
+                .chain(remainders_f.iter())
+                .all(|r| *r < KEY_COEFF_BOUND),
+            "key coefficients must be below {KEY_COEFF_BOUND}"
+        );
+        let cum_sum = remainders_s0
+            .iter()
+            .zip(remainders_f.iter())
+            .scan(0, |acc, (x, y)| {
+                *acc += x * x + y * y;
+                Some(*acc)
+            })
+            .collect::<Vec<_>>();
+        let norm = *cum_sum.last().unwrap();
+        assert!(
+            norm <= self.bound,
+            "squared norm {norm} exceeds {}",
+            self.bound
+        );
+        let range_checked = remainders_s0
+            .iter()
+            .chain(remainders_f.iter())
+            .map(|r| M31(*r))
+            .collect::<Vec<_>>();
+
+        // The cumulative sum is read at offset -1, so every column is permuted to the
+        // circle domain order
+        let domain = CanonicCoset::new(self.log_size).circle_domain();
+        (
+            [
+                s0.to_vec(),
+                s0.iter().map(borrow).collect(),
+                remainders_s0,
+                f.to_vec(),
+                f.iter().map(borrow).collect(),
+                remainders_f,
+                cum_sum,
+            ]
+            .into_iter()
+            .map(|col| {
+                let mut col = col
+                    .into_iter()
+                    .map(M31::from_u32_unchecked)
+                    .collect::<Vec<_>>();
+                bit_reverse_coset_to_circle_domain_order(&mut col);
+                CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
+                    domain,
+                    BaseColumn::from_iter(col),
+                )
+            })
+            .collect::<Vec<_>>(),
+            range_checked,
+            M31(self.bound - norm),
+        )
+    }
+}
+
+// Actual component that is used in the framework
+#[derive(Debug, Clone)]
+pub struct Eval {
+    /// The claim parameters
+    pub claim: Claim,
+    /// Lookup elements for range checking the centered coefficients
+    pub coeff_rc_lookup_elements: RCLookupElements,
+    /// Lookup elements for s0, from the subtraction
+    pub s0_lookup_elements: SubLookupElements,
+    /// Lookup elements for range checking `bound - norm`
+    pub key_bound_check_lookup_elements: RCLookupElements,
+    /// Lookup elements sending f to the forward NTT
+    pub f_lookup_elements: S1LookupElements,
+}
+
+impl FrameworkEval for Eval {
+    fn log_size(&self) -> u32 {
+        self.claim.log_size
+    }
+
+    fn max_constraint_log_degree_bound(&self) -> u32 {
+        self.claim.log_size + 1
+    }
+
+    fn evaluate<E: stwo_constraint_framework::EvalAtRow>(&self, mut eval: E) -> E {
+        // Those values were filled during the trace generation
+        let s0 = eval.next_trace_mask();
+        let borrow_s0 = eval.next_trace_mask();
+        let remainder_s0 = eval.next_trace_mask();
+        let f = eval.next_trace_mask();
+        let borrow_f = eval.next_trace_mask();
+        let remainder_f = eval.next_trace_mask();
+        let [cum_sum_prev, cum_sum_current] =
+            eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
+
+        for remainder in [&remainder_s0, &remainder_f] {
+            eval.add_to_relation(RelationEntry::new(
+                &self.coeff_rc_lookup_elements,
+                E::EF::one(),
+                &[remainder.clone()],
+            ));
+        }
+        // case borrow is 0: coeff - remainder = 0
+        // case borrow is 1: Q - coeff - remainder = 0
+        for (coeff, borrow, remainder) in [
+            (&s0, &borrow_s0, &remainder_s0),
+            (&f, &borrow_f, &remainder_f),
+        ] {
+            eval.add_constraint(
+                coeff.clone()
+                    + borrow.clone() * (E::F::from(M31(Q)) - coeff.clone() - coeff.clone())
+                    - remainder.clone(),
+            );
+            eval.add_constraint(borrow.clone() * (borrow.clone() - E::F::one()));
+        }
+
+        // The key fills the whole trace, whose first and last rows are fixed by the
+        // preprocessed flags
+        let poly_size = 1 << self.claim.log_size;
+        let [is_not_first, is_last] = [NormColumn::IsNotFirst, NormColumn::IsLast].map(|column| {
+            eval.get_preprocessed_column(
+                NormFlags::new(self.claim.log_size, poly_size, column).id(),
+            )
+        });
+        let squares = remainder_s0.clone() * remainder_s0 + remainder_f.clone() * remainder_f;
+        // for the first row, cum_sum_current = remainder^2
+        eval.add_constraint(
+            (E::F::one() - is_not_first.clone()) * (squares.clone() - cum_sum_current.clone()),
+        );
+        // for all the rows except the first one, cum_sum_prev + remainder^2 = cum_sum_current
+        eval.add_constraint(is_not_first * (cum_sum_prev + squares - cum_sum_current.clone()));
+
+        // on the last row, bound - norm must be in [0, bound]
+        eval.add_to_relation(RelationEntry::new(
+            &self.key_bound_check_lookup_elements,
+            E::EF::from(is_last),
+            &[E::F::from(M31(self.claim.bound)) - cum_sum_current],
+        ));
+
+        // s0 is read from the subtraction and f is sent to the forward NTT at the same index
+        let index = eval.get_preprocessed_column(
+            Indices::natural_circle_domain_order(self.claim.log_size).id(),
+        );
+        eval.add_to_relation(RelationEntry::new(
+            &self.s0_lookup_elements,
+            E::EF::one(),
+            &[
+                E::F::from(M31::from_u32_unchecked(S0_POLY_ID)),
+                E::F::zero(),
+                index.clone(),
+                s0,
+            ],
+        ));
+        eval.add_to_relation(RelationEntry::new(
+            &self.f_lookup_elements,
+            -E::EF::one(),
+            &[index, f],
+        ));
+
+        eval.finalize_logup();
+        eval
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionClaim {
+    /// The claimed sum for the interaction
+    pub claimed_sum: SecureField,
+}
+
+impl InteractionClaim {
+    /// Mixes the interaction claim into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_felts(&[self.claimed_sum]);
+    }
+
+    /// Generates the interaction trace for the key norm.
+    ///
+    /// # Parameters
+    ///
+    /// - `trace`: The trace columns from the key norm component
+    /// - `claim`: The claim of the component, fixing the bound
+    /// - `coeff_rc_lookup_elements`: The lookup elements for the centered coefficients
+    /// - `s0_lookup_elements`: The lookup elements of the subtraction output
+    /// - `key_bound_check_lookup_elements`: The lookup elements for `bound - norm`
+    /// - `f_lookup_elements`: The lookup elements sending f to the forward NTT
+    ///
+    /// # Returns
+    ///
+    /// Returns the interaction trace and the interaction claim.
+    pub fn gen_interaction_trace(
+        trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
+        claim: &Claim,
+        coeff_rc_lookup_elements: &RCLookupElements,
+        s0_lookup_elements: &SubLookupElements,
+        key_bound_check_lookup_elements: &RCLookupElements,
+        f_lookup_elements: &S1LookupElements,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
+        InteractionClaim,
+    ) {
+        let log_size = trace[0].domain.log_size();
+        let is_last = NormFlags::new(log_size, 1 << log_size, NormColumn::IsLast).gen_column_simd();
+        let index = Indices::natural_circle_domain_order(log_size).gen_column_simd();
+        let bound = PackedM31::broadcast(M31(claim.bound));
+
+        let mut logup_gen = LogupTraceGenerator::new(log_size);
+        // Range check the centered remainders of s0 and f
+        for column in [&trace[2], &trace[5]] {
+            let mut col_gen = logup_gen.new_col();
+            for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+                let denom: PackedQM31 = coeff_rc_lookup_elements.combine(&[column.data[vec_row]]);
+                col_gen.write_frac(vec_row, PackedQM31::one(), denom);
+            }
+            col_gen.finalize_col();
+        }
+        // Range check bound - norm on the last row
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+            let denom: PackedQM31 =
+                key_bound_check_lookup_elements.combine(&[bound - trace[6].data[vec_row]]);
+            col_gen.write_frac(vec_row, PackedQM31::from(is_last.data[vec_row]), denom);
+        }
+        col_gen.finalize_col();
+        // Read s0 from the subtraction
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+            let denom: PackedQM31 = s0_lookup_elements.combine(&[
+                PackedM31::broadcast(M31::from_u32_unchecked(S0_POLY_ID)),
+                PackedM31::zero(),
+                index.data[vec_row],
+                trace[0].data[vec_row],
+            ]);
+            col_gen.write_frac(vec_row, PackedQM31::one(), denom);
+        }
+        col_gen.finalize_col();
+        // Send f to the forward NTT
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+            let denom: PackedQM31 =
+                f_lookup_elements.combine(&[index.data[vec_row], trace[3].data[vec_row]]);
+            col_gen.write_frac(vec_row, -PackedQM31::one(), denom);
+        }
+        col_gen.finalize_col();
+        let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
+        (interaction_trace, InteractionClaim { claimed_sum })
+    }
+}
+
+/// Type alias for the key norm component.
+pub type Component = FrameworkComponent<Eval>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gen_trace_computes_the_exact_norm() {
+        // s0 = -g and f, with centered coefficients -1, 2, 0, ...
+        let mut s0 = vec![0; 16];
+        let mut f = vec![0; 16];
+        s0[0] = Q - 1;
+        f[0] = 2;
+        f[15] = Q - 3;
+        let claim = Claim {
+            log_size: 4,
+            bound: 14,
+        };
+        let (_, remainders, slack) = claim.gen_trace(&s0, &f);
+        assert_eq!(slack, M31(0));
+        assert_eq!(remainders[0], M31(1));
+        assert_eq!(remainders[16 + 15], M31(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "squared norm 14 exceeds 13")]
+    fn test_gen_trace_rejects_norm_above_bound() {
+        let mut f = vec![0; 16];
+        f[0] = 2;
+        f[1] = Q - 3;
+        f[2] = 1;
+        Claim {
+            log_size: 4,
+            bound: 13,
+        }
+        .gen_trace(&[0; 16], &f);
+    }
+}
@@ -0,0 +1,79 @@
+//! # Key AIR Proof Artifact
+//!
+//! A `KeyProof` carries everything a verifier needs to rebuild the statement "the
+//! prover knows a well formed signing key for this public key": the circuit
+//! parameters, the prover configuration, the public key, the claims, the interaction
+//! proof of work and the STARK proof itself, committed with the Merkle channel `MC`.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use stwo::core::{
+    channel::MerkleChannel, proof::StarkProof, vcs::blake2_merkle::Blake2sMerkleChannel,
+};
+
+use crate::{
+    CircuitParams,
+    big_air::{config::ProverConfig, prover::CircuitId},
+    key_air::{claim::KeyClaim, interaction_claim::KeyInteractionClaim},
+};
+
+/// A proof of possession of a well formed Falcon signing key.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "StarkProof<MC::H>: Serialize",
+    deserialize = "StarkProof<MC::H>: DeserializeOwned"
+))]
+pub struct KeyProof<MC: MerkleChannel = Blake2sMerkleChannel> {
+    pub params: CircuitParams,
+    pub config: ProverConfig,
+    /// The public key h, in [0, q)
+    pub pk: Vec<u32>,
+    pub claim: KeyClaim,
+    pub interaction_pow: u64,
+    pub interaction_claim: KeyInteractionClaim,
+    pub stark_proof: StarkProof<MC::H>,
+}
+
+impl<MC: MerkleChannel> KeyProof<MC> {
+    /// Returns the id of the circuit of the proof, the root of the preprocessed trace it
+    /// commits to.
+    pub fn circuit_id(&self) -> CircuitId<MC> {
+        self.stark_proof.commitments[0]
+    }
+}
+
+// Implemented by hand since the Merkle channels are neither `Clone` nor `Debug`
+impl<MC: MerkleChannel> Clone for KeyProof<MC>
+where
+    StarkProof<MC::H>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            params: self.params,
+            config: self.config,
+            pk: self.pk.clone(),
+            claim: self.claim.clone(),
+            interaction_pow: self.interaction_pow,
+            interaction_claim: self.interaction_claim.clone(),
+            stark_proof: self.stark_proof.clone(),
+        }
+    }
+}
+
+impl<MC: MerkleChannel> fmt::Debug for KeyProof<MC>
+where
+    StarkProof<MC::H>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyProof")
+            .field("params", &self.params)
+            .field("config", &self.config)
+            .field("pk", &self.pk)
+            .field("claim", &self.claim)
+            .field("interaction_pow", &self.interaction_pow)
+            .field("interaction_claim", &self.interaction_claim)
+            .field("stark_proof", &self.stark_proof)
+            .finish()
+    }
+}
@@ -0,0 +1,190 @@
+//! # Reusable Key Prover
+//!
+//! [`KeyProver`] proves possession of signing keys of one degree on any
+//! [`FalconBackend`], with the transcript of the Big AIR prover. The claim of the key AIR
+//! is fixed by the parameters, so the prover builds its circuit once: the twiddles, the
+//! preprocessed columns and the Merkle root they commit to.
+//!
+//! That root is the [`CircuitId`] of the key AIR. The verifier rejects proofs committing
+//! to another preprocessed trace: it builds the preprocessed trace to compute the id with
+//! [`circuit_id`], or takes an id it pinned, see
+//! [`verify_key_with_circuit_id`](super::verify_key_with_circuit_id).
+
+use std::marker::PhantomData;
+
+use num_traits::Zero;
+use stwo::{
+    core::{
+        channel::Channel, fields::qm31::SecureField, proof_of_work::GrindOps,
+        vcs::blake2_merkle::Blake2sMerkleChannel,
+    },
+    prover::{
+        backend::{BackendForChannel, simd::SimdBackend},
+        prove,
+    },
+};
+
+use crate::{
+    CircuitParams,
+    big_air::{
+        backend::FalconBackend,
+        config::ProverConfig,
+        proof::FalconMerkleChannel,
+        prover::{Circuit, CircuitId, preprocessed_root},
+    },
+    key_air::{
+        KeyInstance, KeyProveError, claim::KeyClaim, components::KeyComponents,
+        interaction_claim::KeyInteractionClaim, logup_sum, proof::KeyProof,
+        relation::KeyLookupElements,
+    },
+};
+
+/// Returns the id of the key AIR circuit of `params` proven with `config`, the root of
+/// its preprocessed trace.
+///
+/// This builds and commits to the preprocessed trace, like a verifier that did not pin
+/// the id of the circuit does to check the preprocessed root of a proof.
+pub fn circuit_id<MC>(params: CircuitParams, config: &ProverConfig) -> CircuitId<MC>
+where
+    MC: FalconMerkleChannel,
+    SimdBackend: BackendForChannel<MC>,
+{
+    let (preprocessed_columns, _) = KeyClaim::new(params).create_preprocessed_columns();
+    preprocessed_root::<MC>(preprocessed_columns, config)
+}
+
+/// A prover of possession of Falcon signing keys of one degree, reusing its preprocessed
+/// trace across proofs.
+pub struct KeyProver<B = SimdBackend, MC = Blake2sMerkleChannel>
+where
+    B: FalconBackend + BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    params: CircuitParams,
+    config: ProverConfig,
+    claim: KeyClaim,
+    circuit: Circuit<B>,
+    circuit_id: CircuitId<MC>,
+    _channel: PhantomData<MC>,
+}
+
+impl<B, MC> KeyProver<B, MC>
+where
+    B: FalconBackend + BackendForChannel<MC>,
+    MC: FalconMerkleChannel,
+{
+    /// Creates a prover of the degree of `params` with the security parameters of
+    /// `config`, and builds its circuit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyProveError::UnsupportedParams` if `params` are not the parameters of a
+    /// Falcon degree, and `KeyProveError::UnsupportedConfig` if proofs cannot be generated
+    /// with `config`.
+    pub fn new(params: CircuitParams, config: ProverConfig) -> Result<Self, KeyProveError> {
+        if CircuitParams::from_poly_log_size(params.poly_log_size) != Some(params) {
+            return Err(KeyProveError::UnsupportedParams(params));
+        }
+        if !config.is_supported() {
+            return Err(KeyProveError::UnsupportedConfig(config));
+        }
+        let claim = KeyClaim::new(params);
+        let (preprocessed_columns, preprocessed_columns_ids) = claim.create_preprocessed_columns();
+        let circuit = Circuit::from_preprocessed_columns(
+            preprocessed_columns,
+            preprocessed_columns_ids,
+            claim.max_log_size(),
+            &config,
+        );
+        let circuit_id = circuit.id(&config);
+        Ok(Self {
+            params,
+            config,
+            claim,
+            circuit,
+            circuit_id,
+            _channel: PhantomData,
+        })
+    }
+
+    /// Returns the circuit parameters of the prover.
+    pub fn params(&self) -> CircuitParams {
+        self.params
+    }
+
+    /// Returns the security parameters of the prover.
+    pub fn config(&self) -> ProverConfig {
+        self.config
+    }
+
+    /// Returns the id of the circuit of the prover, the root of its preprocessed trace,
+    /// for a verifier to pin.
+    pub fn circuit_id(&self) -> CircuitId<MC> {
+        self.circuit_id
+    }
+
+    /// Generates a STARK proof of possession of a well formed signing key for
+    /// `instance.pk`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyProveError` if the instance is not a well formed key of the degree of
+    /// the prover, see [`KeyInstance::validate`], or if any step in the proof generation
+    /// fails.
+    pub fn prove(&self, instance: &KeyInstance) -> Result<KeyProof<MC>, KeyProveError> {
+        instance.validate(self.params)?;
+        let config = self.config;
+        let claim = &self.claim;
+
+        // Initialize Fiat-Shamir channel and commit to the cached preprocessed columns
+        let channel = &mut MC::C::default();
+        config.mix_into(channel);
+        channel.mix_u32s(instance.pk);
+        let mut commitment_scheme = self.circuit.commitment_scheme::<MC>(&config, channel);
+        debug_assert_eq!(commitment_scheme.roots()[0], self.circuit_id);
+
+        // Generate and commit to main traces
+        let (trace, traces) = claim.gen_trace(&instance.f_mod_q(), instance.pk);
+        claim.mix_into(channel);
+        let mut tree_builder = commitment_scheme.tree_builder();
+        tree_builder.extend_evals(B::from_simd_columns(trace));
+        tree_builder.commit(channel);
+
+        // Generate proof of work and draw lookup relations
+        let interaction_pow = B::grind(channel, config.interaction_pow_bits);
+        channel.mix_u64(interaction_pow);
+        let lookup_elements = KeyLookupElements::draw(channel);
+
+        // Generate and commit to interaction traces
+        let (interaction_trace, interaction_claim) =
+            KeyInteractionClaim::gen_interaction_trace(claim, &lookup_elements, &traces);
+        interaction_claim.mix_into(channel);
+        let mut tree_builder = commitment_scheme.tree_builder();
+        tree_builder.extend_evals(B::from_simd_columns(interaction_trace));
+        tree_builder.commit(channel);
+
+        if interaction_claim.claimed_sum() + logup_sum(instance.pk, &lookup_elements)
+            != SecureField::zero()
+        {
+            return Err(KeyProveError::UnbalancedLookup);
+        }
+
+        let components = KeyComponents::new(
+            claim,
+            &lookup_elements,
+            &interaction_claim,
+            &self.circuit.preprocessed_columns_ids,
+        );
+        let stark_proof = prove::<B, MC>(&components.provers::<B>(), channel, commitment_scheme)?;
+
+        Ok(KeyProof {
+            params: self.params,
+            config,
+            pk: instance.pk.to_vec(),
+            claim: claim.clone(),
+            interaction_pow,
+            interaction_claim,
+            stark_proof,
+        })
+    }
+}
@@ -0,0 +1,41 @@
+//! # Key AIR Relations
+//!
+//! The key AIR reuses the relations of the Big AIR for the components they share, and
+//! draws three more for its own components: the checked NTT of f, read by the
+//! multiplication once the invertibility component has seen it, and the range checks of
+//! the key norm.
+
+use stwo::core::channel::Channel;
+
+use crate::big_air::relation::{LookupElements, NTTLookupElements, RCLookupElements};
+
+#[derive(Debug, Clone)]
+pub struct KeyLookupElements {
+    /// The relations shared with the Big AIR
+    pub base: LookupElements,
+    /// The NTT of f, from the invertibility component to the multiplication
+    pub checked_f_ntt: NTTLookupElements,
+    /// The centered coefficients of f and g
+    pub coeff_range_check: RCLookupElements,
+    /// The slack between the squared norm of the key and its bound
+    pub key_bound_check: RCLookupElements,
+}
+
+impl KeyLookupElements {
+    pub fn draw(channel: &mut impl Channel) -> Self {
+        Self {
+            base: LookupElements::draw(channel),
+            checked_f_ntt: NTTLookupElements::f_draw(channel),
+            coeff_range_check: RCLookupElements::draw(channel),
+            key_bound_check: RCLookupElements::draw(channel),
+        }
+    }
+
+    /// Returns the relations of the multiplication, which reads f once checked.
+    pub fn mul(&self) -> LookupElements {
+        LookupElements {
+            f_ntt: self.checked_f_ntt.clone(),
+            ..self.base.clone()
+        }
+    }
+}
@@ -12,29 +12,47 @@
 //! - **Modular Arithmetic**: Addition, multiplication, and subtraction modulo q
 //! - **Range Checking**: Ensures all values remain within the valid field range [0, q)
 //! - **NTT Operations**: Number Theoretic Transform for efficient polynomial operations
-//! - **STARK Proofs**: Zero-knowledge proofs of computational integrity
+//! - **Native Verification**: Plain-Rust reference of the verification arithmetic
+//! - **HashToPoint**: SHAKE256 and rejection sampling of the message point
+//! - **Encodings**: Decoding of the standard Falcon public key and signature formats
+//! - **STARK Proofs**: Succinct proofs of computational integrity
 
 pub mod big_air;
 pub mod debug;
+pub mod encoding;
+pub mod hash_to_point;
+pub mod key_air;
+pub mod native;
 pub mod ntts;
 pub mod polys;
 pub mod zq;
 
-/// Logarithm of the polynomial size used in NTT operations.
+use serde::{Deserialize, Serialize};
+use stwo::{core::channel::Channel, prover::backend::simd::m31::LOG_N_LANES};
+
+/// Logarithm of the largest polynomial size used in NTT operations.
 ///
-/// This constant determines the size of polynomials processed by the NTT:
+/// This constant determines the size of Falcon-1024 polynomials:
 /// - POLY_SIZE = 2^POLY_LOG_SIZE = 2^10 = 1024
-/// - Used for efficient polynomial multiplication and evaluation
+/// - The precomputed roots of unity cover every size up to 2^POLY_LOG_SIZE
+///
+/// The degree of a proof is a runtime parameter, see [`CircuitParams`].
 pub const POLY_LOG_SIZE: u32 = 10;
 
-/// The polynomial size used in NTT operations.
+/// The largest polynomial size used in NTT operations.
 ///
-/// This is the actual number of coefficients in each polynomial:
+/// This is the number of coefficients of Falcon-1024 polynomials:
 /// - POLY_SIZE = 1024 coefficients
 /// - Must be a power of 2 for efficient NTT computation
 /// - Compatible with Falcon signature scheme requirements
 pub const POLY_SIZE: usize = 1 << POLY_LOG_SIZE;
 
+/// Logarithm of the polynomial size of Falcon-512.
+pub const FALCON_512_LOG_SIZE: u32 = 9;
+
+/// Logarithm of the polynomial size of Falcon-1024.
+pub const FALCON_1024_LOG_SIZE: u32 = 10;
+
 /// Precomputed signature bounds for different polynomial sizes.
 ///
 /// These bounds are used to validate the Euclidean norm of signature polynomials
@@ -44,246 +62,756 @@ pub const SIGNATURE_BOUNDS: [u32; 10] = [
     101498, 208714, 428865, 892039, 1852696, 3842630, 7959734, 16468416, 34034726, 70265242,
 ];
 
-/// The signature bound for the current polynomial size.
+/// The signature bound for the largest polynomial size.
 ///
 /// This is the maximum allowed Euclidean norm for signature polynomials
 /// with POLY_SIZE coefficients. It's extracted from SIGNATURE_BOUNDS
 /// based on POLY_LOG_SIZE.
 pub const SIGNATURE_BOUND: u32 = SIGNATURE_BOUNDS[POLY_LOG_SIZE as usize - 1];
 
-/// Lower 14 bits of the signature bound.
+/// The parameters of the Falcon verification circuit.
 ///
-/// This represents the lower portion of the signature bound used for
-/// efficient range checking in the STARK proof system.
-pub const LOW_SIG_BOUND: u32 = SIGNATURE_BOUND & ((1 << 14) - 1);
-
-/// Upper bits of the signature bound (shifted right by 14).
+/// The degree of the polynomials is chosen at runtime: the number of NTT merges,
+/// the root tables and the default signature bound all follow from `poly_log_size`.
+/// The signature bound can be replaced by any public bound, see
+/// [`CircuitParams::with_signature_bound`], and is split in two limbs whose size
+/// follows from the bound.
 ///
-/// This represents the upper portion of the signature bound used for
-/// efficient range checking in the STARK proof system.
-pub const HIGH_SIG_BOUND: u32 = SIGNATURE_BOUND >> 14;
+/// # Supported Parameter Sets
+///
+/// - **Falcon-512**: `poly_log_size = 9`, signature bound `SIGNATURE_BOUNDS[8]`
+/// - **Falcon-1024**: `poly_log_size = 10`, signature bound `SIGNATURE_BOUNDS[9]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CircuitParams {
+    /// The log base 2 of the number of coefficients of the polynomials
+    pub poly_log_size: u32,
+    /// The modulus of the arithmetic
+    pub q: u32,
+    /// The bound on the squared norm of the signature
+    pub signature_bound: u32,
+}
+
+impl CircuitParams {
+    /// Returns the parameters for polynomials of 2^poly_log_size coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degree is neither the Falcon-512 nor the Falcon-1024 one.
+    pub fn new(poly_log_size: u32) -> Self {
+        Self::from_poly_log_size(poly_log_size).unwrap_or_else(|| {
+            panic!(
+                "unsupported polynomial size 2^{}, expected 2^{} or 2^{}",
+                poly_log_size, FALCON_512_LOG_SIZE, FALCON_1024_LOG_SIZE
+            )
+        })
+    }
+
+    /// Returns the parameters for polynomials of 2^poly_log_size coefficients,
+    /// or `None` if the degree is not supported.
+    pub fn from_poly_log_size(poly_log_size: u32) -> Option<Self> {
+        if poly_log_size != FALCON_512_LOG_SIZE && poly_log_size != FALCON_1024_LOG_SIZE {
+            return None;
+        }
+        Some(Self {
+            poly_log_size,
+            q: zq::Q,
+            signature_bound: SIGNATURE_BOUNDS[poly_log_size as usize - 1],
+        })
+    }
+
+    /// Returns the parameters for polynomials of `poly_size` coefficients,
+    /// or `None` if the degree is not supported.
+    pub fn from_poly_size(poly_size: usize) -> Option<Self> {
+        if !poly_size.is_power_of_two() {
+            return None;
+        }
+        Self::from_poly_log_size(poly_size.ilog2())
+    }
+
+    /// The Falcon-512 parameters.
+    pub fn falcon_512() -> Self {
+        Self::new(FALCON_512_LOG_SIZE)
+    }
+
+    /// The Falcon-1024 parameters.
+    pub fn falcon_1024() -> Self {
+        Self::new(FALCON_1024_LOG_SIZE)
+    }
+
+    /// Returns the number of coefficients of the polynomials.
+    pub fn poly_size(&self) -> usize {
+        1 << self.poly_log_size
+    }
+
+    /// Returns the parameters with the signature bound replaced by `signature_bound`,
+    /// e.g. the bound of another Falcon variant.
+    ///
+    /// The parameters are only supported if the bound is valid, see
+    /// [`CircuitParams::is_supported`].
+    pub fn with_signature_bound(self, signature_bound: u32) -> Self {
+        Self {
+            signature_bound,
+            ..self
+        }
+    }
+
+    /// Returns whether proofs can be generated with these parameters.
+    ///
+    /// The degree and the modulus must be the Falcon ones, and the signature bound must
+    /// be below 2^30, so that a norm and its slack to the bound cannot wrap around the
+    /// M31 modulus, with limbs large enough to be range checked.
+    pub fn is_supported(&self) -> bool {
+        Self::from_poly_log_size(self.poly_log_size).is_some_and(|params| params.q == self.q)
+            && self.signature_bound < 1 << 30
+            && self.high_sig_bound() >= 1 << LOG_N_LANES
+    }
+
+    /// Number of bits of the signature bound.
+    pub fn sig_bound_bits(&self) -> u32 {
+        u32::BITS - self.signature_bound.leading_zeros()
+    }
+
+    /// Number of bits of the low limbs of the norms and of their slacks to the
+    /// signature bound.
+    ///
+    /// The values below 2^[`sig_bound_bits`](CircuitParams::sig_bound_bits) are split in
+    /// two limbs of about the same size, so that both range checks stay small: 14 bits
+    /// for the Falcon-1024 bound.
+    pub fn sig_bound_limb_bits(&self) -> u32 {
+        self.sig_bound_bits().div_ceil(2)
+    }
+
+    /// Exclusive bound of the low limbs of the norms and of their slacks to the
+    /// signature bound.
+    pub fn low_sig_bound(&self) -> u32 {
+        1 << self.sig_bound_limb_bits()
+    }
+
+    /// Exclusive bound of the high limbs of the norms and of their slacks to the
+    /// signature bound.
+    ///
+    /// Together with [`CircuitParams::low_sig_bound`], it bounds both values by
+    /// 2^[`sig_bound_bits`](CircuitParams::sig_bound_bits), which is enough for the
+    /// circuit to compare the norms exactly with the bound.
+    pub fn high_sig_bound(&self) -> u32 {
+        1 << (self.sig_bound_bits() - self.sig_bound_limb_bits())
+    }
+
+    /// Mixes the parameters into the Fiat-Shamir channel.
+    pub fn mix_into(&self, channel: &mut impl Channel) {
+        channel.mix_u64(self.poly_log_size as u64);
+        channel.mix_u64(self.q as u64);
+        channel.mix_u64(self.signature_bound as u64);
+    }
+}
 
 /// Input data module containing test vectors and constants for the Falcon signature scheme.
 ///
 /// This module provides:
-/// - Test signature polynomial S1 for validation
-/// - Public key polynomial PK for verification
-/// - Message point polynomial for signature generation
-/// - All polynomials are defined over the field Z_q with 1024 coefficients
+/// - Test signature, both in its standard compressed encoding and as the polynomial S1
+/// - Public key polynomial PK for verification, and the secret polynomials F and G with
+///   PK = G / F mod q
+/// - The nonce and message of the test signatures, and their message point
+/// - The top-level polynomials are Falcon-1024 ones, defined over the field Z_q with
+///   1024 coefficients, and [`input::falcon_512`] holds a Falcon-512 instance
+///
+/// They are the first entries (`count = 0`) of the test vector files in `vectors/`,
+/// generated for this crate, and the polynomials are decoded from their encodings.
 pub mod input {
     use crate::POLY_SIZE;
 
+    /// The nonce r of the test signatures.
+    pub const NONCE: &[u8; 40] = &[
+        28, 46, 43, 184, 86, 157, 128, 108, 18, 81, 220, 201, 190, 227, 137, 18, 14, 186, 238, 163,
+        194, 216, 84, 90, 120, 118, 12, 90, 166, 88, 69, 184, 93, 228, 212, 186, 181, 185, 228, 82,
+    ];
+
+    /// The message of the test signatures.
+    pub const MESSAGE: &[u8] = b"falcon-air test vector";
+
+    /// A Falcon-1024 signature of [`MESSAGE`] with [`NONCE`], whose squared norm is well
+    /// below the Falcon-1024 bound.
     pub const TEST_S1: &[u32; POLY_SIZE] = &[
-        158, 161, 108, 11850, 12262, 103, 3, 76, 12034, 12157, 12060, 12165, 118, 12263, 12183, 46,
-        23, 12204, 12181, 61, 12111, 12073, 12209, 12225, 15, 135, 12062, 14, 12104, 115, 121, 447,
-        12182, 157, 12234, 12215, 11838, 12, 12049, 12174, 87, 12244, 272, 144, 163, 273, 136,
-        12234, 12260, 12181, 12223, 12120, 12143, 12201, 91, 44, 84, 9, 78, 12184, 276, 12267,
-        11874, 12046, 173, 12233, 55, 12165, 12200, 12139, 12078, 125, 6, 11915, 12182, 118, 48,
-        12225, 79, 278, 155, 12034, 49, 199, 12207, 12242, 12086, 12203, 12286, 12141, 11969,
-        12218, 12179, 189, 12244, 12143, 12117, 152, 12232, 1, 12213, 78, 106, 42, 152, 12211, 218,
-        12011, 26, 12170, 296, 40, 225, 11980, 22, 12010, 41, 12271, 18, 218, 86, 11948, 12267,
-        12054, 121, 53, 108, 165, 104, 12233, 12157, 12164, 131, 47, 12078, 186, 51, 230, 54, 60,
-        12102, 139, 13, 143, 250, 12116, 184, 65, 42, 11994, 190, 157, 12257, 206, 171, 11969,
-        12189, 12203, 49, 209, 62, 12181, 12282, 266, 12140, 194, 209, 12082, 15, 99, 12142, 242,
-        16, 15, 101, 12081, 12274, 180, 12097, 12108, 12198, 12190, 11862, 12124, 12284, 12022,
-        12227, 12159, 151, 138, 276, 12199, 7, 12227, 12178, 12153, 216, 109, 336, 12204, 15,
-        11951, 221, 11875, 12111, 320, 40, 11943, 12031, 12175, 12168, 31, 60, 12128, 291, 150,
-        336, 38, 75, 12234, 12182, 8, 12204, 12118, 11992, 12166, 50, 12252, 12154, 303, 12208,
-        12214, 11980, 2, 12093, 324, 12239, 12244, 94, 57, 12250, 12244, 12210, 196, 170, 11819,
-        183, 12172, 12176, 139, 177, 12281, 192, 11934, 12177, 52, 237, 343, 112, 213, 71, 11785,
-        179, 367, 12234, 12070, 97, 135, 12216, 12178, 12241, 12069, 147, 12199, 12220, 12268, 55,
-        11919, 127, 234, 72, 12165, 121, 57, 6, 12096, 12178, 250, 12098, 99, 253, 12061, 12273,
-        27, 12206, 21, 354, 56, 12044, 200, 12229, 85, 49, 91, 11923, 62, 11956, 77, 12178, 69, 41,
-        12270, 82, 12204, 12203, 12228, 64, 12068, 11983, 149, 11908, 12182, 12124, 12086, 12166,
-        263, 12007, 12084, 298, 17, 12140, 74, 12022, 109, 100, 12200, 128, 109, 128, 119, 12155,
-        12255, 12253, 68, 12160, 12286, 147, 0, 180, 168, 11982, 12222, 99, 12270, 248, 12231,
-        12212, 117, 251, 71, 84, 313, 52, 77, 62, 12245, 98, 178, 217, 243, 12228, 58, 287, 12136,
-        288, 110, 131, 11, 67, 12231, 12034, 12240, 12286, 12150, 12043, 45, 171, 12107, 12171,
-        12272, 10, 12281, 296, 142, 126, 223, 194, 40, 12265, 165, 338, 12282, 329, 118, 12092, 67,
-        12268, 12257, 259, 12073, 12028, 12240, 12255, 12207, 242, 12101, 171, 12011, 12104, 239,
-        12077, 12182, 211, 12271, 12266, 12270, 172, 45, 12114, 11744, 12099, 11, 12271, 77, 139,
-        111, 12105, 1, 8, 323, 59, 113, 12137, 118, 12202, 160, 12273, 12076, 12219, 122, 30, 12,
-        12272, 12174, 12110, 66, 292, 12154, 224, 34, 12249, 12283, 333, 12068, 12024, 12208, 37,
-        112, 75, 312, 12147, 12070, 366, 445, 177, 58, 12116, 63, 12023, 83, 12174, 12252, 139,
-        12061, 12218, 80, 12222, 25, 12197, 181, 12265, 12195, 12214, 12159, 196, 12158, 33, 40,
-        73, 12096, 12247, 12235, 12003, 18, 186, 32, 229, 12206, 12141, 12227, 12246, 36, 75, 77,
-        86, 12155, 12112, 174, 12187, 12228, 208, 12168, 12263, 290, 180, 80, 195, 12256, 123, 164,
-        12036, 12258, 407, 113, 81, 12169, 12140, 213, 12040, 174, 12162, 12201, 62, 11928, 12178,
-        12122, 32, 282, 31, 12199, 93, 245, 12243, 35, 130, 12263, 12231, 36, 12244, 285, 12113,
-        12196, 12206, 12107, 12251, 11986, 6, 200, 12207, 12241, 185, 12234, 11954, 498, 12271,
-        163, 242, 11632, 111, 8, 12182, 12189, 12041, 145, 125, 12268, 25, 11972, 196, 12193,
-        12214, 40, 271, 47, 326, 271, 118, 116, 82, 255, 84, 434, 12027, 105, 167, 152, 96, 12279,
-        29, 244, 12229, 12224, 32, 55, 12228, 12248, 12235, 12097, 12052, 171, 84, 1, 12169, 312,
-        12248, 12022, 12024, 158, 135, 1, 302, 120, 105, 13, 12285, 12154, 6, 115, 96, 69, 16, 24,
-        155, 172, 99, 80, 75, 334, 12253, 12264, 12253, 70, 353, 137, 57, 11918, 197, 12269, 47,
-        107, 12131, 104, 282, 53, 38, 12189, 62, 12161, 68, 12260, 12275, 12052, 4, 274, 4, 12240,
-        12238, 12023, 215, 12243, 12265, 12263, 12250, 12071, 70, 226, 182, 129, 12065, 109, 129,
-        12140, 208, 75, 174, 12207, 12194, 264, 132, 12199, 12253, 122, 149, 289, 120, 260, 12199,
-        171, 183, 11974, 57, 134, 253, 12105, 11945, 214, 12187, 123, 63, 12255, 400, 12021, 261,
-        12065, 12231, 12182, 78, 147, 12156, 12177, 12221, 206, 211, 12260, 157, 12201, 12132, 83,
-        376, 109, 11954, 11770, 12190, 34, 12178, 161, 110, 171, 118, 12266, 139, 11944, 11922,
-        138, 307, 12258, 41, 12202, 12246, 12236, 12205, 188, 28, 12125, 9, 199, 11951, 12072, 101,
-        12054, 12177, 12264, 12239, 12156, 243, 12204, 47, 12090, 173, 14, 50, 80, 12116, 11883,
-        328, 12193, 11963, 12261, 155, 12147, 12160, 12115, 55, 190, 12165, 12198, 232, 12258, 284,
-        11958, 1, 59, 12209, 12196, 262, 12070, 0, 242, 12246, 12218, 12248, 270, 11974, 12275,
-        145, 11867, 12003, 12162, 115, 12259, 12215, 69, 97, 12144, 12021, 211, 109, 11930, 12161,
-        12223, 69, 12080, 185, 63, 12123, 345, 12267, 12188, 11971, 127, 175, 194, 12155, 12271,
-        12241, 12234, 409, 12, 12052, 172, 304, 151, 159, 12209, 12144, 133, 12013, 12028, 0, 207,
-        12135, 390, 12121, 289, 159, 411, 12163, 12102, 12153, 51, 12206, 12222, 12277, 12284,
-        12211, 237, 12269, 154, 12252, 95, 12223, 12106, 12223, 12154, 12119, 105, 12117, 12158,
-        12066, 12144, 95, 12006, 12279, 112, 72, 11951, 76, 12151, 213, 227, 95, 12129, 12244,
-        12263, 319, 12281, 12159, 12094, 1, 66, 97, 12039, 9, 299, 32, 98, 12197, 12084, 69, 12030,
-        12156, 0, 12220, 3, 251, 116, 12149, 12047, 304, 226, 192, 12234, 11917, 12279, 155, 362,
-        12115, 12032, 17, 3, 12217, 12124, 11951, 122, 12273, 12205, 26, 341, 12211, 295, 102,
-        12287, 12236, 482, 79, 12127, 12230, 12163, 12245, 238, 12162, 12270, 12145, 205, 505, 55,
-        12241, 12287, 158, 11959, 15, 196, 295, 121, 12150, 12273, 106, 230, 244, 3, 6, 299, 181,
-        121, 48, 89, 12154, 182, 12151, 12287, 12225, 12202, 12269, 12145, 12265, 12030, 12051, 10,
-        186, 12127, 147, 57, 44, 11976, 49, 153, 239, 109, 12129, 12278, 152, 12044, 92, 11985,
-        12203, 12063, 100, 37, 12216, 208, 12233, 166, 12241, 12091,
+        12259, 12267, 12258, 1, 43, 37, 23, 9, 12280, 12281, 22, 12255, 23, 68, 12277, 12233,
+        12262, 12285, 1, 12245, 12256, 12240, 12273, 12237, 66, 12277, 12184, 12255, 69, 45, 12,
+        12251, 12247, 2, 56, 12287, 33, 18, 66, 45, 12283, 12246, 12245, 12280, 16, 12247, 12276,
+        32, 12251, 12283, 39, 12212, 5, 12273, 72, 12283, 12238, 11, 99, 12272, 12252, 12227,
+        12282, 65, 12284, 12248, 5, 11, 31, 1, 0, 28, 38, 12214, 36, 40, 17, 1, 93, 12270, 57,
+        12285, 33, 12264, 12282, 60, 39, 12279, 12264, 12281, 12288, 26, 12288, 24, 53, 12268, 29,
+        12253, 16, 12286, 3, 3, 83, 12273, 12279, 12217, 12260, 12262, 11, 12199, 9, 55, 34, 12254,
+        59, 30, 77, 67, 43, 12275, 12, 6, 35, 12275, 7, 12251, 1, 12266, 11, 12218, 12243, 12269,
+        12246, 12254, 12254, 12284, 12, 12285, 12264, 12233, 39, 12239, 87, 5, 5, 12263, 86, 33,
+        99, 12281, 12270, 66, 10, 12224, 12279, 12266, 12263, 12251, 12254, 12213, 7, 12263, 67,
+        12261, 12240, 7, 15, 12216, 9, 12256, 12, 12280, 1, 83, 48, 12284, 36, 12242, 12268, 12259,
+        14, 12287, 12261, 12280, 52, 12253, 12247, 12273, 12280, 12269, 60, 12255, 26, 9, 16,
+        12232, 91, 12256, 12282, 12263, 12251, 12225, 24, 12238, 18, 12230, 90, 30, 12234, 12274,
+        44, 12215, 12287, 12281, 12276, 71, 12276, 12241, 20, 12258, 12259, 12222, 24, 40, 0,
+        12223, 1, 29, 41, 12265, 12281, 12263, 4, 48, 21, 12288, 12277, 12218, 34, 12267, 12262,
+        39, 85, 20, 64, 39, 94, 0, 33, 61, 17, 12287, 3, 12257, 116, 35, 12273, 23, 9, 60, 6,
+        12246, 12258, 12203, 27, 76, 50, 14, 12231, 12228, 19, 69, 14, 12274, 12245, 29, 26, 12,
+        12246, 12284, 12287, 3, 12277, 54, 25, 12200, 12207, 12266, 6, 12243, 28, 12237, 12281,
+        12196, 42, 32, 12237, 12211, 12272, 12243, 52, 73, 12259, 12275, 15, 35, 67, 12263, 12246,
+        12265, 12275, 12250, 15, 12262, 12254, 55, 12231, 12251, 29, 12280, 12243, 12274, 12261,
+        11, 12213, 17, 12244, 38, 14, 12265, 31, 39, 12285, 12222, 12274, 12269, 52, 21, 0, 12243,
+        12223, 34, 29, 17, 12267, 3, 9, 129, 2, 21, 12266, 15, 12284, 46, 83, 12255, 47, 14, 12269,
+        12255, 14, 12258, 12262, 2, 12281, 49, 12265, 32, 29, 12255, 12285, 12234, 15, 12, 12242,
+        14, 12288, 36, 9, 21, 36, 12268, 12245, 12257, 12286, 12283, 12287, 22, 12276, 12269, 58,
+        50, 12275, 12252, 27, 0, 5, 12288, 12284, 12235, 16, 18, 37, 7, 12210, 14, 12235, 21, 55,
+        104, 12269, 10, 12284, 12248, 12273, 12198, 12282, 9, 7, 12276, 16, 12280, 12244, 12285,
+        12197, 12283, 12269, 55, 12283, 12269, 29, 68, 39, 71, 25, 54, 12280, 12285, 12252, 12256,
+        12272, 12255, 12267, 32, 21, 32, 19, 12256, 12257, 12271, 12228, 37, 27, 46, 12243, 45,
+        12240, 69, 7, 12281, 12235, 12212, 12288, 12288, 28, 36, 12259, 12241, 12240, 58, 12265, 5,
+        12276, 6, 41, 35, 12258, 27, 14, 60, 29, 25, 1, 12284, 12221, 19, 12246, 12276, 44, 4, 16,
+        55, 12238, 7, 12251, 50, 36, 12288, 2, 11, 0, 20, 12283, 5, 12265, 20, 12276, 55, 12248,
+        12282, 12266, 32, 5, 12229, 12238, 20, 12281, 44, 2, 65, 12259, 69, 12258, 12284, 12252,
+        99, 29, 12264, 3, 92, 12261, 34, 12155, 12282, 12247, 68, 12288, 37, 0, 12288, 37, 39,
+        12288, 12279, 7, 53, 52, 47, 12229, 22, 28, 30, 73, 2, 12244, 11, 12259, 21, 12232, 54,
+        12235, 25, 12221, 33, 12281, 12284, 34, 12276, 23, 12242, 12238, 0, 12276, 10, 45, 12263,
+        12219, 12276, 13, 29, 12230, 25, 12253, 95, 10, 15, 12277, 71, 12284, 70, 24, 12271, 12257,
+        12260, 12258, 12266, 12271, 2, 12264, 1, 17, 12284, 12279, 41, 12282, 29, 13, 12236, 12267,
+        50, 12244, 12280, 12286, 12272, 12244, 12264, 12219, 53, 6, 37, 12274, 56, 12284, 12277,
+        12255, 12235, 78, 56, 12278, 43, 13, 71, 0, 30, 13, 30, 12239, 12277, 5, 12250, 56, 12279,
+        10, 12250, 42, 12285, 7, 44, 12250, 31, 37, 12238, 12273, 65, 12249, 12253, 8, 60, 12281,
+        12253, 20, 12286, 1, 27, 12235, 89, 12260, 17, 12284, 34, 22, 12255, 38, 12283, 3, 12268,
+        6, 12276, 12256, 12266, 12259, 1, 12264, 12212, 12257, 12268, 14, 12262, 20, 13, 12285,
+        12285, 12277, 12284, 70, 12272, 12259, 12274, 12258, 35, 12211, 12183, 12237, 12239, 12268,
+        12251, 37, 12237, 5, 1, 26, 12283, 13, 28, 12197, 24, 20, 42, 7, 12224, 12259, 12247,
+        12219, 7, 12285, 12232, 12231, 12273, 8, 33, 36, 12281, 28, 12268, 36, 11, 12263, 12276,
+        34, 9, 39, 43, 22, 12195, 15, 12259, 47, 12284, 12262, 12278, 20, 12235, 12246, 32, 51, 33,
+        24, 12245, 38, 36, 12250, 12243, 18, 5, 66, 56, 33, 40, 12260, 22, 12258, 12265, 94, 12242,
+        0, 12221, 5, 12258, 59, 12234, 12274, 12257, 31, 12247, 20, 12257, 5, 12252, 14, 12270,
+        12271, 3, 28, 12267, 12243, 12198, 12218, 7, 16, 12287, 35, 27, 12259, 12263, 12272, 12259,
+        36, 31, 45, 12217, 55, 12245, 12262, 12247, 37, 6, 12201, 12265, 12272, 12247, 4, 23, 18,
+        12249, 12251, 73, 12286, 12282, 18, 12243, 46, 12256, 96, 12248, 13, 5, 27, 12216, 12248,
+        5, 8, 12282, 44, 12229, 12284, 18, 55, 12222, 0, 36, 12283, 7, 1, 12234, 38, 12252, 23, 22,
+        12260, 12241, 12258, 29, 12274, 12249, 17, 12181, 16, 18, 54, 12287, 12273, 12281, 54,
+        12273, 28, 33, 54, 12238, 12276, 21, 12287, 12274, 12286, 12266, 12287, 12266, 12269,
+        12227, 17, 12262, 12288, 12255, 7, 9, 66, 12222, 95, 12268, 86, 12215, 12286, 12200, 112,
+        3, 21, 3, 12253, 5, 22, 13, 12283, 12266, 3, 16, 12278, 12231, 18, 12272, 43, 12281, 12255,
+        12269, 25, 12255, 36, 26, 2, 12211, 12254, 27, 30, 12281, 41, 4, 6, 12287, 12253, 12262,
+        12257, 12260, 64, 1, 41, 19, 35, 12286, 61, 12220, 9, 12237, 8, 12281, 38, 12263, 1, 12235,
+        8, 12286, 26, 38, 68, 7, 12243, 12277, 18, 31, 33, 12219, 41, 12279, 0, 12178, 26, 12267,
+        19, 12277, 12285, 12264, 12284, 9, 12255, 13, 48, 12254, 12265, 12232, 12269, 12221, 4, 12,
+        12282, 12213, 54, 12207, 12193, 12201, 12271, 12242, 12243, 12248, 76, 12287, 50, 1, 12280,
+        12273, 12277, 12276, 10, 12249, 12265, 12260, 11, 12286, 49, 47, 12268, 12240, 0, 12263,
+        12284, 12238, 70, 12258,
     ];
+    /// The standard compressed encoding of the test signature: the header byte, [`NONCE`]
+    /// and the Golomb-Rice compressed [`TEST_S1`].
+    pub const SIGNATURE: &[u8] = &[
+        58, 28, 46, 43, 184, 86, 157, 128, 108, 18, 81, 220, 201, 190, 227, 137, 18, 14, 186, 238,
+        163, 194, 216, 84, 90, 120, 118, 12, 90, 166, 88, 69, 184, 93, 228, 212, 186, 181, 185,
+        228, 82, 158, 203, 103, 224, 50, 185, 44, 94, 19, 137, 196, 69, 180, 81, 122, 38, 51, 113,
+        155, 194, 64, 117, 154, 29, 142, 67, 105, 66, 198, 122, 116, 84, 89, 108, 51, 77, 170, 129,
+        78, 48, 82, 24, 149, 10, 91, 134, 213, 235, 49, 49, 13, 86, 54, 65, 166, 195, 73, 249, 176,
+        92, 133, 35, 13, 179, 133, 216, 242, 58, 93, 246, 30, 131, 133, 212, 193, 97, 113, 248, 12,
+        2, 57, 38, 229, 201, 37, 17, 24, 13, 119, 39, 57, 194, 72, 115, 56, 121, 228, 159, 21, 153,
+        196, 96, 99, 88, 24, 196, 215, 43, 29, 210, 68, 48, 112, 56, 29, 79, 33, 138, 228, 103,
+        115, 112, 190, 212, 38, 111, 34, 209, 206, 227, 212, 218, 28, 175, 29, 12, 131, 72, 241,
+        208, 125, 52, 7, 47, 11, 227, 235, 178, 154, 189, 30, 143, 11, 12, 194, 102, 119, 18, 125,
+        149, 94, 11, 5, 205, 85, 164, 54, 60, 70, 78, 133, 10, 224, 226, 178, 249, 173, 54, 143,
+        153, 7, 205, 80, 243, 155, 24, 60, 63, 147, 9, 208, 195, 49, 48, 26, 156, 195, 11, 36, 215,
+        229, 115, 208, 236, 22, 115, 19, 52, 210, 106, 178, 24, 156, 164, 243, 69, 26, 132, 196,
+        55, 53, 189, 14, 31, 53, 166, 224, 70, 54, 113, 45, 221, 106, 61, 183, 199, 203, 57, 88,
+        44, 70, 54, 143, 141, 216, 69, 51, 249, 238, 28, 98, 81, 0, 225, 64, 99, 178, 156, 198, 35,
+        53, 4, 152, 69, 112, 56, 206, 60, 139, 45, 155, 147, 213, 98, 148, 9, 61, 122, 1, 33, 158,
+        196, 112, 80, 61, 5, 210, 71, 144, 139, 194, 103, 144, 109, 94, 127, 173, 27, 166, 76, 161,
+        219, 173, 236, 78, 139, 14, 199, 235, 35, 177, 168, 102, 175, 11, 130, 129, 227, 38, 209,
+        158, 207, 75, 47, 6, 215, 71, 54, 152, 142, 236, 170, 65, 180, 231, 100, 117, 211, 74, 78,
+        123, 29, 15, 145, 208, 243, 90, 188, 198, 59, 79, 15, 205, 232, 230, 251, 173, 52, 119, 19,
+        174, 199, 231, 33, 124, 200, 142, 182, 77, 14, 204, 71, 228, 248, 78, 30, 63, 41, 52, 138,
+        192, 53, 220, 41, 20, 118, 35, 150, 129, 194, 96, 40, 20, 87, 47, 15, 194, 203, 170, 122,
+        41, 124, 59, 41, 162, 135, 103, 243, 112, 44, 68, 199, 49, 32, 142, 232, 176, 155, 120,
+        124, 51, 95, 14, 192, 201, 33, 49, 89, 38, 87, 89, 160, 193, 225, 176, 81, 108, 110, 82,
+        117, 50, 199, 105, 99, 112, 8, 46, 7, 11, 182, 136, 68, 164, 176, 126, 124, 59, 109, 21,
+        155, 218, 50, 144, 172, 46, 167, 33, 219, 195, 194, 96, 248, 216, 134, 39, 91, 132, 238,
+        97, 178, 147, 124, 54, 82, 59, 68, 147, 209, 227, 51, 108, 78, 19, 75, 161, 200, 232, 178,
+        210, 8, 172, 130, 39, 161, 208, 100, 183, 178, 88, 220, 187, 93, 45, 216, 209, 96, 248,
+        141, 183, 55, 3, 129, 142, 73, 51, 219, 13, 140, 235, 49, 5, 198, 193, 165, 50, 60, 252,
+        110, 29, 60, 142, 198, 96, 56, 94, 36, 79, 87, 141, 150, 65, 34, 19, 125, 156, 31, 77, 50,
+        146, 96, 96, 80, 184, 4, 83, 13, 5, 204, 69, 49, 179, 125, 78, 31, 47, 32, 130, 239, 54,
+        113, 76, 68, 178, 5, 65, 207, 81, 115, 248, 93, 45, 142, 59, 153, 129, 215, 51, 146, 44,
+        51, 15, 170, 162, 96, 100, 176, 12, 12, 150, 79, 129, 197, 65, 230, 179, 73, 126, 242, 45,
+        28, 143, 82, 96, 90, 216, 94, 122, 43, 185, 155, 109, 163, 60, 73, 14, 35, 11, 34, 198,
+        197, 245, 251, 56, 6, 54, 21, 45, 205, 113, 177, 176, 216, 238, 238, 51, 164, 175, 194,
+        161, 248, 202, 62, 22, 141, 24, 201, 104, 51, 185, 252, 190, 74, 5, 153, 128, 196, 112,
+        184, 169, 78, 30, 59, 13, 218, 229, 166, 90, 220, 78, 15, 35, 173, 204, 241, 166, 176, 105,
+        46, 62, 113, 133, 198, 104, 182, 212, 233, 198, 46, 87, 13, 163, 192, 35, 208, 216, 246,
+        203, 25, 5, 211, 206, 49, 80, 173, 60, 171, 9, 7, 150, 105, 227, 242, 93, 158, 66, 131,
+        168, 210, 66, 39, 152, 141, 36, 83, 7, 1, 141, 237, 171, 57, 216, 142, 22, 69, 22, 209, 73,
+        176, 208, 60, 172, 27, 27, 161, 203, 231, 160, 57, 158, 110, 131, 43, 14, 205, 197, 33,
+        184, 76, 38, 51, 11, 70, 200, 231, 177, 249, 249, 31, 59, 213, 180, 217, 101, 116, 210, 93,
+        164, 22, 3, 26, 195, 67, 99, 157, 200, 196, 82, 85, 7, 224, 231, 181, 92, 104, 62, 19, 115,
+        186, 200, 66, 36, 50, 76, 68, 115, 43, 36, 133, 230, 177, 178, 40, 76, 158, 87, 22, 239,
+        67, 243, 210, 252, 46, 111, 23, 20, 219, 106, 228, 19, 57, 12, 99, 89, 38, 146, 105, 245,
+        209, 40, 45, 10, 113, 33, 148, 103, 98, 217, 252, 197, 123, 95, 0, 226, 65, 115, 243, 189,
+        190, 63, 65, 31, 213, 69, 52, 16, 93, 44, 59, 39, 146, 129, 199, 50, 218, 238, 223, 30, 15,
+        16, 193, 72, 227, 121, 236, 214, 71, 61, 36, 143, 203, 121, 19, 125, 102, 111, 85, 37, 131,
+        118, 51, 25, 29, 84, 18, 47, 18, 212, 105, 169, 56, 60, 60, 75, 93, 46, 208, 216, 53, 48,
+        216, 44, 111, 147, 169, 130, 194, 48, 242, 205, 230, 22, 37, 55, 225, 192, 36, 152, 104,
+        60, 7, 111, 38, 210, 197, 226, 217, 221, 134, 126, 59, 143, 212, 68, 125, 145, 8, 148, 219,
+        5, 144, 196, 77, 178, 17, 201, 12, 219, 103, 141, 138, 224, 177, 248, 60, 190, 11, 47, 148,
+        223, 68, 115, 120, 29, 20, 30, 19, 66, 225, 215, 242, 181, 110, 86, 15, 179, 112, 129, 197,
+        96, 122, 72, 44, 90, 27, 134, 203, 192, 226, 24, 189, 212, 75, 35, 43, 196, 104, 178, 145,
+        157, 20, 146, 53, 2, 231, 104, 227, 113, 236, 68, 166, 9, 6, 193, 105, 51, 122, 12, 237, 2,
+        3, 41, 137, 200, 240, 115, 222, 44, 39, 105, 8, 196, 73, 179, 80, 29, 180, 35, 7, 26, 147,
+        81, 32, 250, 236, 100, 74, 63, 33, 227, 74, 113, 80, 15, 124, 107, 45, 19, 198, 97, 51, 56,
+        88, 78, 138, 27, 48, 209, 230, 55, 57, 78, 36, 18, 25, 135, 230, 77, 186, 94, 14, 198, 75,
+        95, 174, 212, 211, 48, 83, 40, 14, 39, 33, 140, 198, 194, 181, 25, 140, 236, 47, 7, 49,
+        151, 229, 118, 48, 12, 214, 23, 103, 70, 207, 192,
+    ];
+
+    /// The Falcon-1024 public key of the test signature.
     pub const PK: &[u32; POLY_SIZE] = &[
-        5247, 9979, 3373, 8523, 10235, 2234, 6595, 5160, 9115, 5767, 1978, 3350, 7239, 832, 5451,
-        6681, 11659, 1889, 9318, 11974, 5862, 4379, 3423, 9009, 10909, 10980, 10714, 7068, 3787,
-        4275, 90, 6946, 11567, 10325, 2543, 104, 1061, 9346, 8851, 8356, 11963, 5439, 9652, 7489,
-        2234, 4028, 5762, 2674, 12244, 2335, 1147, 1716, 12098, 11799, 12227, 11197, 639, 7058,
-        6508, 8970, 6636, 8466, 10330, 4034, 6968, 5194, 809, 56, 9690, 3977, 611, 3948, 6380,
-        4899, 7780, 2256, 6554, 6129, 10739, 59, 10147, 10703, 6129, 3455, 2533, 1669, 7104, 5057,
-        5453, 11565, 938, 6319, 8853, 8342, 7622, 8491, 5320, 5769, 8817, 5264, 4567, 11886, 11479,
-        8898, 4095, 6577, 3257, 8667, 5322, 1560, 461, 12220, 5078, 863, 574, 8440, 4891, 6552,
-        1810, 1853, 1226, 8095, 6038, 1799, 1401, 11798, 10900, 6127, 5130, 9603, 4340, 10935,
-        6063, 12022, 8994, 3245, 9952, 7109, 11214, 6761, 11870, 2591, 11388, 4438, 11134, 10931,
-        9254, 7181, 2351, 12153, 803, 11744, 500, 596, 3471, 5787, 2279, 3868, 11598, 5703, 11589,
-        5933, 1812, 6455, 4465, 10635, 6508, 1970, 599, 7823, 2214, 316, 352, 3361, 9286, 5305,
-        1938, 612, 8051, 4962, 10359, 9527, 3456, 10973, 9478, 8919, 3953, 10622, 11341, 11284,
-        11536, 10266, 11384, 8796, 5316, 8253, 4279, 325, 4130, 7858, 7728, 8121, 8270, 318, 10691,
-        6608, 11224, 11442, 665, 5979, 1547, 11140, 8845, 5477, 3865, 1363, 7876, 2589, 1396, 5864,
-        7033, 2539, 9196, 2731, 821, 2897, 9025, 8520, 11177, 7653, 1472, 2690, 7350, 7846, 5554,
-        7264, 2734, 490, 2436, 1278, 4121, 1435, 726, 3220, 8562, 2784, 5120, 8191, 2731, 3889,
-        12038, 892, 11556, 2395, 10644, 8839, 8490, 1320, 7611, 7475, 11730, 5707, 2464, 4070,
-        1281, 1680, 12121, 10599, 1955, 935, 3471, 4206, 5937, 7193, 4645, 294, 2207, 10670, 4028,
-        11476, 7630, 4534, 3123, 3243, 4170, 6164, 7608, 629, 2793, 2713, 639, 3479, 5555, 2174,
-        4607, 10406, 274, 8199, 9152, 6268, 5234, 11968, 1852, 1204, 12204, 77, 9438, 306, 12072,
-        2776, 10163, 2643, 9339, 9721, 5252, 7840, 7881, 5740, 12104, 3145, 9148, 10888, 624, 1230,
-        1301, 4900, 11456, 2489, 6506, 5254, 4806, 2679, 5630, 805, 1900, 8700, 10481, 6728, 3307,
-        857, 10933, 4406, 5291, 6175, 2165, 7734, 2415, 9416, 753, 7574, 3817, 7588, 11409, 9577,
-        9413, 8712, 1893, 1366, 2405, 616, 6623, 9333, 323, 11421, 954, 11446, 6925, 6964, 3851,
-        7115, 11876, 7081, 4280, 4697, 10444, 187, 8537, 4563, 3219, 2211, 10561, 5853, 5545,
-        10136, 7192, 1814, 8758, 1687, 618, 4484, 8348, 5361, 8957, 5149, 5799, 2901, 1111, 11114,
-        8353, 5380, 7990, 689, 8391, 6118, 9249, 5191, 1309, 11031, 10862, 10719, 6769, 7494, 9460,
-        2613, 4735, 6517, 11071, 7555, 1691, 2484, 5020, 1435, 9195, 4230, 6229, 401, 9340, 7086,
-        3484, 9420, 4757, 11049, 6354, 11898, 10294, 8132, 5272, 8632, 8610, 12268, 6281, 764,
-        11635, 4150, 6565, 10838, 2486, 8918, 4247, 3542, 2217, 3692, 4921, 5333, 9814, 8019, 1005,
-        5087, 7058, 7744, 954, 6677, 2865, 9270, 11314, 7515, 2456, 2861, 6962, 8822, 2739, 3284,
-        4482, 595, 10446, 9082, 612, 5454, 8248, 10201, 3803, 9371, 6635, 763, 6237, 10194, 383,
-        976, 5321, 4103, 9431, 965, 6998, 8376, 3697, 10231, 7040, 4741, 10553, 3055, 9813, 5123,
-        1814, 8909, 1764, 2302, 12106, 6144, 6117, 3866, 4672, 3959, 9515, 1980, 2900, 11855, 9953,
-        10990, 8927, 1246, 2599, 476, 9099, 11785, 4005, 619, 10826, 742, 3974, 11453, 6903, 3253,
-        9970, 7045, 11755, 1753, 12033, 9446, 10554, 3213, 6020, 9226, 27, 3030, 8352, 6087, 4193,
-        150, 11777, 5612, 4944, 6665, 1609, 3811, 1487, 5518, 2581, 12196, 7347, 11297, 5450, 6575,
-        3193, 3950, 9596, 9191, 7309, 6836, 8500, 10246, 6293, 7124, 4296, 8901, 4361, 3336, 863,
-        11243, 5602, 2341, 4787, 2911, 8381, 8521, 3761, 5664, 6557, 8641, 3825, 3965, 3321, 3644,
-        2620, 6084, 7395, 12110, 5081, 3963, 10242, 7858, 11940, 11601, 4966, 9852, 11312, 954,
-        11812, 573, 6410, 8775, 10125, 11396, 6827, 6595, 10091, 7383, 11218, 706, 7861, 10760,
-        7507, 2470, 11192, 7928, 10209, 10518, 8188, 1824, 9199, 9629, 3340, 5502, 858, 8373, 4271,
-        3004, 8047, 9132, 2183, 3676, 1447, 11447, 212, 10097, 3844, 3803, 2340, 4503, 11702,
-        10848, 6427, 1419, 180, 3193, 12184, 7917, 11792, 7460, 3590, 9976, 9069, 1034, 12093,
-        3091, 11148, 7132, 8158, 327, 7558, 5449, 9691, 1834, 1124, 10710, 3914, 7096, 4604, 56,
-        64, 4102, 3992, 10950, 4413, 8719, 7607, 7371, 6237, 8630, 7649, 6930, 550, 7604, 2621,
-        5910, 5748, 9809, 9210, 6810, 2937, 8013, 7762, 11616, 2674, 3293, 4408, 7050, 7168, 2197,
-        11685, 5713, 7151, 2016, 6747, 9495, 11381, 8684, 297, 5482, 7495, 9975, 3782, 6398, 12167,
-        11050, 4303, 7182, 6566, 6659, 1609, 12197, 2743, 1649, 9279, 5079, 4365, 11207, 8952,
-        4682, 1617, 401, 8838, 11689, 4463, 3018, 10819, 9085, 2859, 1645, 5232, 2687, 2383, 343,
-        297, 4070, 1678, 9062, 10228, 2114, 2768, 7599, 6609, 9955, 10327, 2248, 2170, 5494, 4722,
-        10649, 4127, 3485, 10772, 5166, 3295, 6415, 6226, 1677, 8047, 6492, 5546, 1622, 2077,
-        12223, 5911, 763, 2861, 4596, 8225, 11126, 8678, 12006, 6859, 4852, 4594, 7063, 6176, 5589,
-        11117, 3239, 9539, 11866, 7070, 5518, 5499, 3136, 5643, 5933, 7834, 9866, 7919, 8866, 65,
-        7713, 8837, 2001, 9366, 389, 8752, 9999, 3771, 8678, 857, 3632, 8990, 7308, 8397, 11795,
-        715, 4115, 6189, 100, 11760, 8629, 11783, 9091, 5433, 4483, 3150, 735, 12215, 5531, 1909,
-        271, 5679, 4589, 7996, 32, 9130, 10792, 4298, 10782, 2237, 5506, 537, 11448, 12037, 2559,
-        5117, 4360, 1878, 4510, 10297, 8893, 9774, 5948, 11546, 2050, 2143, 8574, 47, 2799, 2404,
-        1714, 2162, 3233, 11896, 6652, 9683, 1751, 8467, 11238, 8928, 11436, 866, 576, 9998, 2159,
-        12265, 8541, 11006, 592, 8382, 2093, 10512, 7093, 7769, 120, 3031, 1202, 12031, 12001,
-        3470, 3753, 8885, 7038, 6263, 489, 2033, 7862, 10854, 4993, 3710, 10760, 10791, 3116, 2679,
-        203, 7786, 4676, 8278, 7647, 2781, 10650, 484, 4109, 6497, 8633, 12128, 6079, 1946, 10054,
-        2669, 2374, 9835, 8461, 7976, 7083, 3983, 11972, 6373, 4261, 1130, 2306, 2624, 4421, 11190,
-        308, 8946, 7958, 4537, 3353, 12079, 6385, 11555, 9368, 4903, 7255, 9762, 9209, 9205, 10758,
-        1265, 10530, 8730, 11411, 10363, 9319, 8095, 12180, 6096, 3102, 8646, 7050, 8616, 11423,
-        98, 5464, 3568, 2657, 7517, 11358, 9820, 2332, 6939, 7508, 9391, 8603, 7314, 11160, 3581,
-        2298, 8446, 1790, 7649, 3126, 1238, 11471, 8508, 3226, 538, 9800, 6615, 11842, 1709, 8567,
-        9376, 396, 9842, 7604, 2302, 4641, 3981, 10380, 2345, 7214, 4138, 6094, 10265, 3396, 10833,
-        5202, 5540, 2872, 2282, 3630,
+        675, 6892, 10781, 11567, 2767, 823, 8037, 8292, 466, 10938, 2888, 11976, 3459, 1209, 9602,
+        9553, 3056, 11645, 5369, 1069, 1843, 5502, 12125, 4088, 5777, 10403, 10592, 5015, 7260,
+        6062, 11318, 205, 6627, 8252, 9847, 12110, 4453, 5969, 8513, 11353, 3302, 3393, 11523,
+        3116, 9384, 3609, 879, 8663, 5836, 7733, 9040, 8905, 3857, 3123, 11922, 9143, 1654, 11397,
+        6930, 8328, 8294, 5440, 5633, 721, 1842, 953, 7652, 3493, 1154, 7320, 6496, 7608, 521,
+        2926, 12166, 7198, 1993, 2930, 11008, 7801, 9723, 5462, 6303, 9851, 10098, 2071, 3555,
+        4648, 4977, 9094, 1049, 3033, 2883, 8647, 9816, 8538, 4690, 9557, 9970, 11680, 9813, 11640,
+        2867, 3029, 9619, 864, 172, 10398, 4615, 2032, 7557, 2935, 9503, 6383, 6425, 12096, 9548,
+        1178, 12040, 4329, 7976, 11043, 1314, 2990, 11895, 8359, 1959, 6752, 1036, 5509, 11445,
+        8683, 1835, 5516, 7046, 9025, 5978, 12025, 11677, 7213, 10826, 5106, 6025, 3969, 5703,
+        1930, 3471, 1487, 2784, 4557, 11177, 1797, 7323, 4872, 9116, 5371, 1997, 11335, 7577, 9549,
+        12264, 6427, 11424, 1252, 7472, 7364, 10948, 3033, 11262, 8192, 9412, 3938, 9105, 5440,
+        9182, 9876, 10104, 6968, 9868, 3792, 1157, 9599, 9728, 10265, 11045, 2727, 6718, 7363,
+        12247, 7374, 8226, 752, 4557, 8655, 1681, 4389, 7658, 5906, 4553, 6587, 4023, 8930, 6192,
+        4712, 3501, 9374, 1356, 7901, 4422, 3613, 6143, 11472, 4019, 9172, 10912, 8851, 7608, 1334,
+        9602, 4453, 9147, 7895, 2624, 921, 10250, 10207, 7094, 935, 12091, 1497, 596, 883, 1542,
+        272, 822, 8717, 5895, 4295, 11279, 8187, 1052, 16, 10031, 704, 7786, 3032, 3194, 9698,
+        7681, 7291, 11278, 4243, 6737, 11441, 7531, 9556, 5275, 2998, 8735, 1976, 11489, 8064,
+        2227, 3679, 1608, 9500, 4148, 1507, 4649, 10252, 6708, 1017, 8330, 6996, 289, 4894, 501,
+        4133, 2933, 10756, 353, 8822, 8545, 11555, 8495, 4208, 7042, 727, 7719, 8398, 11735, 8478,
+        4129, 7158, 7185, 1883, 10491, 1896, 10263, 1794, 8116, 5128, 5066, 5429, 2629, 7132, 6073,
+        11298, 11558, 9352, 11729, 7306, 1278, 941, 11553, 1277, 311, 11656, 9036, 6690, 961,
+        10785, 3588, 9336, 4818, 2036, 2084, 10054, 7286, 11110, 1525, 2127, 7232, 1826, 8119,
+        1735, 5325, 3262, 8463, 1204, 4946, 4090, 7298, 11218, 10745, 10730, 3149, 1397, 11376,
+        7623, 7365, 6702, 4620, 7926, 9201, 11099, 7380, 4728, 2956, 9691, 4592, 10802, 9760, 5517,
+        7313, 7405, 10847, 9691, 6924, 1081, 897, 8190, 3688, 6120, 10950, 2539, 4061, 11368, 6522,
+        10938, 188, 10527, 474, 576, 924, 8816, 5380, 4188, 766, 8983, 1662, 6132, 8102, 2126,
+        8056, 2839, 4, 3327, 2257, 8741, 4566, 8100, 8755, 6667, 11660, 6708, 11189, 11895, 5237,
+        618, 8935, 3167, 8230, 10068, 7917, 1225, 7680, 4892, 8609, 3038, 1756, 8704, 8318, 2595,
+        9601, 10184, 11403, 10424, 6205, 12146, 7493, 4564, 10160, 8437, 9174, 1558, 8798, 2695,
+        11475, 1160, 11504, 9482, 6618, 12142, 473, 6579, 306, 10381, 1897, 6233, 11765, 3016,
+        3065, 6541, 6456, 898, 3019, 10574, 6101, 10695, 6333, 9550, 12179, 10317, 5651, 192, 833,
+        5391, 8926, 6087, 11713, 5787, 9887, 4209, 4683, 8944, 2004, 8473, 4262, 5412, 7891, 5912,
+        5206, 6754, 3017, 11409, 10363, 1222, 2501, 11890, 2455, 7389, 8532, 10528, 7758, 7799,
+        1125, 2750, 1900, 9522, 11221, 3919, 11888, 3987, 8391, 11192, 10641, 1839, 1350, 8897,
+        1174, 3444, 11733, 6944, 9098, 11371, 7391, 6203, 4941, 10764, 3892, 5697, 1913, 4927,
+        10903, 7797, 5579, 4334, 4214, 1011, 6938, 4250, 2688, 5997, 7332, 10817, 5452, 10489,
+        7973, 8469, 740, 3528, 3069, 6475, 689, 9504, 5308, 2223, 10697, 3406, 386, 9032, 10153,
+        8993, 8261, 12016, 1053, 7709, 6818, 10727, 84, 9179, 1500, 9135, 9424, 7134, 2725, 4659,
+        11325, 7258, 5446, 459, 1032, 5318, 1243, 9, 10124, 1648, 7081, 8693, 9359, 547, 5424,
+        4501, 7531, 4578, 1717, 7222, 2345, 7046, 4473, 11172, 7345, 2898, 1429, 7578, 11335, 7217,
+        286, 10406, 3164, 11389, 4016, 587, 1552, 3112, 4340, 6981, 2584, 6261, 5221, 7155, 8453,
+        6204, 11739, 12281, 5322, 3161, 10953, 5048, 9882, 8467, 8365, 9521, 3202, 5105, 786, 9841,
+        7193, 7160, 6922, 7150, 8815, 4121, 9944, 3581, 5890, 4069, 11681, 5452, 3246, 6211, 2885,
+        526, 179, 11227, 10283, 3464, 4372, 5273, 1956, 9768, 3192, 10531, 4427, 6852, 3922, 2063,
+        11491, 7821, 6257, 197, 10232, 7375, 4474, 2081, 2689, 6733, 8277, 1111, 4493, 956, 2954,
+        11309, 6778, 11613, 934, 11668, 7937, 581, 11628, 10059, 9227, 7225, 1761, 431, 12005,
+        2075, 3499, 10708, 10158, 71, 5388, 10929, 8122, 3511, 307, 9434, 3777, 1979, 8465, 2790,
+        1072, 10197, 12071, 3784, 4388, 4239, 7562, 11520, 3209, 1108, 10734, 3938, 9367, 11705,
+        11737, 6960, 10743, 11299, 10393, 7219, 7594, 107, 9109, 457, 2608, 7595, 3839, 5055, 9776,
+        10202, 5996, 5585, 3980, 2350, 10468, 6890, 6409, 11302, 9472, 4799, 7656, 3699, 6754,
+        5350, 3583, 9217, 11269, 11304, 12215, 8037, 2499, 5781, 1956, 8748, 7147, 10595, 4724,
+        8318, 10349, 3753, 293, 2918, 2456, 1863, 138, 9239, 12131, 9992, 6862, 2160, 11709, 10387,
+        10838, 6570, 6557, 9817, 6817, 8184, 6407, 10155, 878, 10118, 5004, 11289, 3213, 4117,
+        5860, 2663, 4736, 6656, 1989, 3398, 10262, 1607, 3506, 7370, 4735, 3695, 9042, 6661, 12106,
+        3811, 6607, 8390, 6257, 2487, 1569, 4435, 2380, 3392, 4557, 9208, 429, 4117, 10909, 9947,
+        2693, 4349, 12144, 2405, 11204, 12171, 11178, 9270, 2557, 12225, 10774, 583, 8058, 10537,
+        2540, 5436, 4798, 10306, 2122, 6316, 6982, 3802, 1713, 8481, 5120, 42, 578, 1192, 11598,
+        6079, 7453, 7780, 4800, 9420, 5421, 1358, 4527, 3910, 2077, 3563, 6329, 4693, 2062, 3806,
+        5509, 1190, 9738, 3674, 8452, 6964, 2280, 3638, 8181, 2892, 8383, 11946, 1038, 10488,
+        10504, 7691, 11461, 10433, 2418, 10207, 3257, 10427, 41, 3897, 6034, 6519, 11011, 9778,
+        11594, 9136, 725, 5398, 1022, 6674, 3747, 2477, 1273, 10941, 4926, 1826, 9747, 4301, 12048,
+        1339, 5471, 6090, 11169, 10980, 9510, 9291, 10268, 8893, 5567, 6283, 3884, 2635, 2397,
+        3058, 10624, 8329, 494, 11206, 8428, 7065, 9416, 4230, 3206, 3540, 681, 11156, 7825, 9921,
+        6741, 5376, 11853, 4269, 1809, 11486, 11462, 11882, 10985, 4422, 7823, 3565, 9306, 3041,
+        11786, 103, 7986, 2906, 3487, 9341, 10071, 2162, 2517, 8927, 5789, 2360, 5464, 11086, 3914,
+        4871, 8072, 10510, 3830, 3561, 5714, 10475, 10579, 1052, 1250, 7221, 4242, 6103, 10226,
+        9739, 6002, 6248, 4743, 8084, 12279, 11706, 11508, 6883, 11427, 7625, 486, 2348, 636, 680,
+        10435, 10030, 1850, 10246, 11763, 3413, 9783, 2912, 11122, 8317, 11909, 9666, 5665, 5599,
+        4870, 7126, 10376, 4565, 9958, 5336, 3861, 34, 4452, 6182, 6203, 11977, 7641, 9345, 2880,
+        4649, 8262, 856,
     ];
 
+    /// The secret polynomial f of the Falcon-1024 test key, with [`PK`] = g / f mod q.
+    pub const F: &[i16; POLY_SIZE] = &[
+        -5, -1, 0, 5, 1, -1, 3, -2, -1, -2, 0, 2, 2, 1, 1, 1, 3, -1, -2, 3, 2, 3, 6, -1, -3, 0, 0,
+        2, 2, -2, -3, -1, 1, 1, 2, 2, 0, -5, -5, -3, 3, -1, 2, -4, 3, -4, 5, 1, 5, 2, -1, -5, -1,
+        1, 2, 4, -1, 1, 0, -1, -1, -6, -2, 1, -7, -1, -2, 0, 1, -6, -3, 5, 2, 6, -2, 1, -3, 3, 0,
+        1, 1, -1, -1, 3, -2, 0, -3, -2, 1, 1, 1, -1, -2, 0, -1, 0, -2, -1, -1, -3, -1, -4, 1, 1,
+        -4, -4, 3, -3, -3, 3, -1, -3, -2, 5, 3, 0, -3, 2, 4, 0, 3, -4, -1, 0, -3, 1, 2, -1, -1, -2,
+        -2, -3, -6, 2, -2, -4, 0, 1, -1, 3, 5, -2, -2, -1, -2, 2, -1, 4, -7, 2, 5, 3, -2, 1, 2, 0,
+        3, -4, 1, 5, 1, 0, -1, 1, -2, -4, 3, -4, 2, 4, -2, -2, 2, 6, 1, -2, 2, 2, 6, -1, -5, 1, 0,
+        4, 5, 5, -5, 1, 3, 2, -4, 0, 0, 4, -3, -3, 7, 5, 4, 3, 1, 3, -5, 0, -1, 2, 2, -1, 1, 0, 2,
+        2, -5, -3, 0, 2, -7, -4, 2, 0, 2, -5, 2, 1, 3, -5, -1, 5, 0, 2, 5, 4, -6, -3, 1, -1, 2, -4,
+        1, -1, 0, -1, -5, -3, -2, 0, -4, -2, 0, 0, 3, -3, -2, 6, 4, -3, 1, 2, -4, -5, 1, 0, -4, 0,
+        -1, 1, 7, -1, 2, 2, 1, 1, -1, 4, -1, 3, 1, -3, 6, 0, -4, -1, 1, 3, 3, -4, -4, -2, 0, -3,
+        -2, 2, -4, 1, -1, 3, -1, -2, 2, 0, 4, 0, -2, 2, 0, -1, -3, 4, -4, 2, -2, -4, 3, -3, -1, -5,
+        -1, 2, -2, -2, -4, -6, 3, -1, 0, 0, 0, -1, 1, 0, -2, 6, 2, 1, 2, -3, -1, 3, -3, -2, 1, -2,
+        3, -6, -2, -1, -1, -1, 2, 6, 0, -2, 4, 1, 0, 6, 2, -7, -5, 1, -1, -1, 4, -5, -4, 1, 1, -3,
+        2, 5, 1, 5, 3, 4, -3, 3, -1, 1, 4, -1, -3, -1, -5, -2, 1, 1, -5, 4, -1, 2, 1, -3, 3, 0, 1,
+        0, -1, 1, -1, 2, 0, 2, -3, -4, 0, 0, 1, 2, 1, -1, 0, 3, -2, 6, 6, 2, 0, -6, 5, 0, -9, 1,
+        -1, 0, -1, 4, 4, -5, 0, -2, -3, -4, 1, 1, 1, 5, -2, 2, -4, 1, 1, 4, -3, -1, -4, -2, -1, 1,
+        0, -2, 0, 2, 0, 1, 1, -2, -2, -4, 5, 1, -2, 2, 2, -2, -5, 0, -1, 5, 0, -2, 3, -1, 4, -1,
+        -4, -1, 6, -9, 3, 2, -1, 2, -3, 2, -1, 5, -3, 1, -4, 3, 0, 0, 1, -2, 0, 1, 1, -2, -1, -1,
+        2, 2, 6, 2, -2, -2, -2, 3, 6, 5, -2, -2, 4, -2, -1, 0, 5, 6, 6, 1, 2, -1, 3, -2, -5, -7,
+        -3, 6, -5, 0, 0, -1, -1, -3, -6, 0, -6, -4, -4, 0, 1, 2, 3, -3, 3, -1, 1, 1, 4, 2, -3, 1,
+        0, -1, 1, -2, 3, 2, 1, -3, 0, 3, 2, -6, -8, 2, 6, 3, 2, -2, -3, 5, 0, -5, -3, 0, 2, -2, 0,
+        -1, 0, 2, -2, 3, -2, -2, 2, -2, 1, -2, -1, 4, -2, -3, -5, -1, 0, 1, 1, 2, 3, 2, -5, 1, -1,
+        -3, 4, -3, 2, 5, 4, 1, 5, -1, -5, -5, 3, 6, -4, 5, -3, -3, 2, -2, 6, -1, -1, -3, -2, -1, 0,
+        -1, -1, 1, -3, 3, -3, -3, 2, 1, 2, 1, 0, -1, 2, 0, -4, 0, 0, 0, -1, 4, 4, -3, 1, -2, -2,
+        -3, -4, 3, 4, 0, 0, 0, 1, 0, 1, -2, 0, 3, 4, -4, -1, 0, -4, -7, 0, -5, 4, -1, -6, -1, -3,
+        -5, 4, -3, -3, 3, 0, -1, 0, -1, -1, 0, 0, 3, -1, -5, -5, 1, -5, -3, -3, -2, 0, 0, 2, -2,
+        -7, 4, 2, -3, 3, 1, -2, 0, 0, 1, 4, 6, -1, 1, 1, 6, 0, -4, 5, 6, 3, 1, -2, 1, 4, -2, -6,
+        -3, -2, 0, -1, 4, 0, -8, -6, -2, 2, -3, 0, -1, 1, -2, 6, 1, -2, 6, -6, -6, 2, 0, 0, 1, -1,
+        -3, -4, 5, 2, 2, 1, 2, 1, -1, 4, -2, -3, 2, -1, 4, 0, -1, -3, -5, 1, -1, 1, 0, 0, -2, 2,
+        -3, -3, 1, 4, 2, -2, -3, 5, -4, 2, -4, 1, 4, 0, -2, 1, 5, -4, 0, 4, 3, 1, -3, 3, 0, 0, 3,
+        5, -5, 3, 1, -3, -1, -1, -1, -2, 5, 5, 5, 6, -2, -6, -2, 1, -2, 3, 3, 1, -1, -1, -1, 3, 3,
+        0, 6, -2, -7, 2, 0, -4, -3, -3, 0, -2, -1, -2, 0, -4, -2, -3, -3, 5, 0, -3, 3, -3, 3, -2,
+        4, -3, -2, 1, 0, -4, 6, 0, 0, -5, 2, 2, 0, 2, 2, 1, -5, 0, 0, 0, 5, -2, -3, 1, -2, -1, -1,
+        -3, 4, 3, 1, 8, 4, 2, 2, -3, -3, 1, 4, -3, -2, 0, -2, -1, -2, 5, 2, 1, 0, 1, -1, -5, -3, 1,
+        1, -3, -1, 3, -2, 1, 1, 0, 7, -3, -2, -2, 3, 4, -3, 4, 3, 2, -1, 2, -2, 5, 1, 0, -1, -1, 3,
+        -2, 2, 0, -3, 0, 1, 2, 1, 2, 0, 1, 4, 4, 7, 3, 2, -2, 3, 5, -3, -6, 4, 4, 2, -1, -7, 3, -1,
+        6, 1, -1, -2, 2, 5, -6, -2, -3, -5, -2, -1, 2, -9, 2, -2, 2, -3, 2, 1, 0, 2, 3, 4, -4, -1,
+        1, -3, 3, -4, 3, 0, 2, -3, 0, 1, 2, -1, 1, 2, -5, -7, -4, 2, 2, 0, 4, 4, -2, 5,
+    ];
+
+    /// The secret polynomial g of the Falcon-1024 test key.
+    pub const G: &[i16; POLY_SIZE] = &[
+        -1, 1, -4, -2, -1, -6, -1, -4, 1, -1, -1, 0, 6, -3, 0, -3, -4, -2, 1, -1, 2, -3, 2, 0, 5,
+        4, -4, -1, -2, 0, 3, -3, 0, -3, 2, 3, 4, -4, -6, 1, 0, 1, 0, 3, 2, -5, 3, -4, 1, 0, -2, 1,
+        1, 3, 3, 6, 0, -3, -2, -3, -6, -2, 0, 2, -5, -2, -2, 0, 0, 2, -2, -4, 4, 2, -2, 5, 2, 3,
+        -1, 0, -6, -4, -1, 1, 1, 0, -2, -1, 2, -2, -4, -1, -4, -2, -5, 0, -2, 0, 5, 0, -1, 1, 0, 2,
+        3, 1, 1, -1, 3, 2, -2, 0, 4, -7, 0, 0, -3, -5, 2, 2, 0, 2, 3, 1, -3, -4, 0, -1, -7, 4, -2,
+        -1, 4, -4, 2, -1, -2, 2, -1, -1, -3, 1, 6, 0, 4, 2, 2, 0, 2, -3, 2, -2, 2, 2, 1, 6, 2, 0,
+        -2, -3, 0, 1, -1, 2, 1, 3, 3, -1, 2, -3, 0, 2, 0, -1, 0, -1, -4, 0, 0, 1, 0, -3, -1, 0, 0,
+        1, -1, 1, 5, 0, 0, 1, 2, -1, -2, 4, 3, -2, -4, 0, 3, -1, 4, -2, -2, -1, -5, 2, -2, -5, -7,
+        -1, 4, -4, -1, 1, -6, -5, 1, 2, 2, -1, 0, 2, 4, 3, 1, 0, -1, -1, -1, 2, 5, -4, -3, 0, -1,
+        -4, 2, 6, 3, 5, -1, -2, -2, -1, 2, -6, 2, -6, 0, 3, -3, -4, 0, 4, -2, -7, 0, -1, 5, 2, -8,
+        -5, -3, -2, -2, 0, -4, -1, 1, -5, -5, 1, 2, -6, 1, -2, -5, -1, 0, 3, -4, 0, -1, -1, 1, 0,
+        -1, -2, -1, 4, 0, 0, -3, 0, -1, -7, 3, 0, 3, -4, 0, 5, -5, -3, -1, 2, 0, 0, 4, -2, -3, -1,
+        5, 6, 1, -2, -1, -3, -4, -4, 0, 0, 0, -3, 2, 4, 1, -2, 2, -2, 0, 1, 0, -2, -1, 0, 4, -1, 2,
+        2, 1, 5, 2, 3, -2, 3, 3, -2, 1, -7, -5, -1, 0, 5, 2, 4, 0, 0, -3, 4, 1, 0, 1, 1, -2, 1, 4,
+        -3, 2, -2, -4, -1, 1, -4, -3, 4, 0, -2, 5, -4, 1, 1, -1, -6, -4, -6, 1, 7, -1, 1, 2, 4, -6,
+        8, 1, -5, -3, 0, 0, -1, 0, 0, 4, -3, 2, 1, 1, 0, 0, -3, 0, -1, -2, -3, 5, -1, 4, 2, -2, 0,
+        -2, 0, -8, -4, 3, 4, -1, 2, -2, 1, 0, 4, -1, -1, -1, -1, 2, -4, 0, 4, 0, 3, -4, -5, -2, -2,
+        -2, 3, -1, -3, 0, -2, -2, -5, 1, 2, 2, 1, -2, -3, -1, 4, 4, 1, -3, 1, 7, 5, 2, -9, -2, 2,
+        -1, -1, 2, 4, -3, -1, 0, 3, -4, -2, -1, -2, 5, 2, -6, 1, 1, 1, 1, -3, 0, -2, -1, -1, 2, -5,
+        -2, -1, -3, -1, -2, 3, -1, 0, 2, -1, 1, 1, -1, -2, -2, 0, -2, -5, -2, -3, 3, 0, -4, 0, 0,
+        -2, -3, 3, 0, -3, -3, 1, 6, 1, -2, 0, 1, -5, -4, 4, 2, 2, 3, -4, -3, 2, -2, 2, -1, -1, 3,
+        2, -1, -1, 1, 1, -4, -3, 5, -2, 1, -3, 0, -2, -5, 1, -1, 3, -3, -4, 1, -7, 4, 2, 0, -1, -4,
+        -1, -4, 3, -2, 2, 2, -1, -1, 1, 0, 0, -3, -5, 3, -2, -3, -3, 3, -1, -2, 3, -2, 2, 5, -2,
+        -1, 1, 3, 1, -2, 2, -3, -4, -1, -1, -4, 0, -1, 1, -1, 2, -1, -2, -2, 2, 2, 6, 1, -4, 1, -4,
+        -3, -4, 3, 1, 0, -2, -5, 1, -3, 2, 2, -3, 0, -2, 4, -2, 0, 1, 1, 0, -2, -4, -5, 0, 1, -2,
+        -2, -1, -2, -4, -2, 2, -5, 0, -3, -1, 0, 5, 2, 5, 1, -1, 0, 0, 6, 2, -2, 0, -3, -2, 4, 1,
+        1, 7, 2, -3, 4, 2, -5, 1, 0, -3, 0, -3, -2, 0, 0, -1, -2, -2, -6, -3, -1, 2, 2, -4, 0, -2,
+        0, 1, -1, -1, -1, -5, -1, -5, 3, 3, 1, -6, -6, 3, 0, 1, 3, 1, -2, -4, -3, 1, -6, -2, -1, 0,
+        -3, -2, -1, -8, 0, -1, 1, 1, 1, 0, 2, 1, -4, 1, -2, -2, 0, 2, 1, -3, 0, 3, 1, -3, -4, 3, 0,
+        3, 2, -1, -1, 2, -3, -4, 0, 1, 0, 1, 0, -1, -2, 2, 3, 0, 0, 3, -2, 0, 2, -3, 3, -1, -2, 0,
+        -3, -3, -1, 1, -2, -2, 4, 2, 1, -1, 2, -1, -3, -1, 3, 3, -2, 0, -2, 2, -1, 0, 2, 1, 2, -2,
+        1, 4, 1, 5, 4, 0, -3, -2, 3, -1, -2, 2, -3, 2, 3, -2, 1, -4, 2, -1, 4, 1, -3, 0, -1, -1,
+        -2, 3, -2, 1, 1, 3, 2, 3, 4, 1, -1, -2, -1, -2, 1, 0, -3, 2, 1, -1, 0, -3, -1, 3, 0, 0, 1,
+        -1, 0, 1, 2, -4, 3, -2, 1, 4, 2, 3, 2, -3, 1, -2, 4, 4, -4, 0, -4, 4, -1, 1, 4, 1, 2, -3,
+        0, 0, 1, -2, 2, -3, 1, 2, -1, -4, -5, -2, -3, 4, -2, -4, 3, 3, -3, -3, 3, 3, -1, 4, 0, 0,
+        -2, 3, 1, 4, -2, -1, 3, 2, -4, -3, 1, 2, -6, -2, -1, -5, -5, 7, 1, 4, -1, 1, 1, 3, 1, -2,
+        -2, 1, 0, 0, -2, -2, -2, -2, 2, -3, -1, -1, 0, 4, -4, -1, 0, -1, -2, 1, 2, -2, 0, 2, -2,
+        -3, -1, 1, -5, -2, -7, 3, 3, -1, -1, 1, -1, 0, 0, 1, 2, -1, -3, 1, 4, -1, 1, -2, -7, -1, 0,
+        2, 2, 0, 6, 4, 0, 2, 1, 6, -2, -2, 4, 1, -4, -5, 1, 3, 3, 0, 6, -1, -1, -3, -1, -2, -2, -3,
+        2, -3,
+    ];
+
+    /// HashToPoint([`NONCE`] || [`MESSAGE`]) for Falcon-1024.
     pub const MSG_POINT: &[u32; POLY_SIZE] = &[
-        1417, 3296, 4385, 7615, 12136, 4804, 4684, 1822, 7608, 6587, 2000, 2583, 11764, 6689,
-        10873, 11446, 9366, 1580, 9706, 10967, 11105, 5324, 4219, 10757, 9516, 8460, 3090, 7539,
-        4563, 8865, 10, 3473, 7945, 9334, 2194, 9167, 11424, 6470, 12093, 2584, 3754, 12081, 8630,
-        10209, 10040, 9722, 10869, 404, 12028, 7212, 11629, 11972, 6099, 748, 5830, 2520, 4053,
-        9956, 8943, 11686, 8430, 3611, 2707, 12041, 11007, 6573, 11285, 4080, 4621, 545, 3575,
-        2962, 6553, 3556, 5729, 7602, 5566, 6237, 8267, 304, 4654, 7845, 7415, 11787, 1883, 8418,
-        8166, 6694, 1533, 10229, 8064, 12237, 6713, 8860, 11387, 263, 5653, 3864, 5084, 3376, 5244,
-        1012, 7199, 6324, 9204, 8599, 747, 1985, 8325, 6345, 8200, 3541, 10407, 5851, 8359, 11752,
-        9341, 4140, 6979, 3251, 5863, 4862, 10480, 1579, 5606, 10924, 4750, 2355, 3268, 10770, 67,
-        4740, 2381, 5236, 10938, 10286, 11588, 11570, 6728, 2027, 1015, 1015, 11885, 8304, 12208,
-        7208, 10950, 5548, 4874, 1046, 5992, 9633, 9987, 8701, 9648, 4450, 201, 5334, 12126, 461,
-        2405, 9423, 3487, 2198, 5570, 5870, 7293, 6046, 2510, 6967, 9586, 3849, 2894, 9197, 12092,
-        3132, 6612, 12059, 9709, 500, 8826, 8401, 8886, 4905, 319, 10688, 7485, 4577, 2360, 3733,
-        6277, 911, 9561, 3624, 3100, 6241, 9728, 9689, 985, 1858, 1308, 1623, 2956, 6053, 1838,
-        12010, 6582, 1735, 6473, 575, 5111, 9122, 4553, 10633, 1769, 1027, 10138, 2963, 8605, 658,
-        3675, 6662, 11994, 1671, 10890, 2268, 1893, 4088, 8020, 1211, 2683, 1590, 8199, 10892,
-        8204, 2673, 10055, 11876, 140, 1472, 6560, 11214, 10635, 2822, 5026, 622, 1763, 2891, 6782,
-        5740, 8117, 4009, 1570, 11189, 7516, 8233, 271, 2256, 2432, 9093, 2212, 4660, 3872, 9444,
-        8179, 7071, 1502, 3738, 267, 10879, 7104, 4357, 8302, 5568, 7024, 1017, 6320, 11228, 7297,
-        4609, 8998, 5881, 1321, 7044, 6233, 329, 56, 5047, 2107, 10734, 5952, 10492, 918, 12040,
-        4617, 8114, 3248, 586, 1307, 12187, 10806, 10244, 5671, 1286, 10014, 7550, 3123, 11569,
-        7976, 7875, 3301, 8949, 10232, 3428, 196, 6399, 7591, 2328, 1406, 12253, 4544, 10900,
-        10583, 4333, 9145, 194, 7939, 7728, 2281, 739, 8450, 945, 9906, 5355, 2762, 206, 2424,
-        7054, 576, 1678, 2609, 6481, 11586, 7212, 4448, 10621, 115, 3117, 10017, 8340, 3245, 9355,
-        7881, 129, 5536, 3903, 9891, 8246, 7923, 4748, 11634, 6761, 3837, 3045, 1931, 11529, 8032,
-        8, 4607, 11157, 277, 6878, 6991, 2005, 2069, 1593, 11795, 5083, 9987, 2212, 3487, 9144,
-        8546, 10740, 4350, 5960, 7825, 11885, 11179, 12049, 10907, 5928, 11670, 3372, 9107, 11024,
-        4190, 2105, 1026, 11295, 11261, 6970, 8269, 702, 1865, 10318, 1974, 792, 3020, 5311, 4728,
-        7448, 7177, 430, 600, 7935, 6635, 4750, 7375, 2827, 5021, 8183, 7971, 6315, 6571, 6533,
-        3764, 1141, 6362, 7964, 2053, 11935, 176, 3493, 487, 4256, 3738, 6304, 2544, 8474, 1820,
-        2213, 3752, 2411, 5468, 9478, 6137, 11722, 7483, 10871, 6879, 9191, 3117, 205, 6956, 5130,
-        9, 2880, 6894, 10566, 8777, 5841, 6230, 3291, 1020, 48, 2022, 3685, 5819, 4288, 9263, 868,
-        6880, 7715, 2093, 5543, 8900, 11707, 2696, 5365, 6205, 5310, 7325, 7743, 5178, 6581, 11173,
-        3647, 9390, 2959, 6953, 9739, 8913, 12242, 1656, 36, 8670, 10427, 1554, 6877, 6627, 11536,
-        349, 11678, 491, 168, 8301, 5351, 3702, 5305, 11460, 11980, 3856, 4542, 3666, 916, 9396,
-        6283, 9214, 8396, 10796, 11705, 8769, 4111, 3064, 3994, 8194, 3528, 4247, 10516, 345, 898,
-        5439, 6909, 1265, 4892, 1656, 1306, 7467, 6196, 8553, 3757, 7776, 5700, 12219, 5853, 719,
-        7299, 4423, 9147, 10062, 11803, 9097, 160, 9739, 4737, 3892, 11492, 7717, 3250, 6575, 9834,
-        9843, 3026, 3379, 3480, 10816, 3003, 4014, 12237, 2888, 4687, 1258, 7036, 11916, 11388,
-        11955, 8897, 11521, 7098, 12073, 3051, 5038, 3616, 10395, 12273, 1908, 11712, 11558, 6225,
-        12274, 5525, 4964, 7562, 10158, 10835, 10086, 11003, 9140, 11094, 12211, 10537, 11090, 580,
-        494, 10561, 5970, 3233, 4059, 10387, 6478, 1734, 2719, 9969, 10040, 3125, 7518, 4464, 5652,
-        10426, 11479, 5855, 5356, 1056, 10190, 1779, 9270, 4691, 2981, 10587, 9161, 2463, 740,
-        3668, 3398, 1472, 8865, 8328, 6226, 5136, 5738, 9237, 10576, 11976, 6820, 318, 6028, 7619,
-        6113, 5801, 5767, 11142, 3426, 11416, 10093, 1474, 6451, 10839, 8535, 7479, 10287, 6719,
-        3865, 8555, 2693, 3150, 1052, 9129, 9037, 2764, 7435, 2252, 925, 7181, 6502, 4567, 4341,
-        8933, 7946, 5676, 9321, 10235, 1435, 12232, 9034, 7347, 3278, 3950, 8319, 1803, 1775,
-        11138, 4192, 3607, 7209, 10266, 8604, 9109, 12031, 3143, 1654, 5713, 7364, 7558, 6704,
-        1160, 8421, 5807, 1666, 1757, 3966, 4653, 7263, 5072, 9571, 3877, 1558, 6822, 8718, 3460,
-        11543, 935, 761, 5036, 6102, 6854, 3439, 9050, 557, 3167, 2056, 10866, 8198, 9271, 8261,
-        9237, 652, 5950, 5323, 7090, 11556, 1688, 11000, 4462, 4400, 4369, 7094, 11893, 2495, 197,
-        5020, 439, 867, 7831, 11914, 3284, 2996, 2678, 1545, 846, 11261, 10176, 95, 10425, 4012,
-        4318, 3683, 907, 4630, 9863, 11762, 12281, 10815, 10340, 1468, 5468, 3288, 4736, 5379,
-        7197, 3186, 11575, 1572, 8329, 455, 12045, 11056, 1329, 3, 6158, 4944, 11271, 418, 9516,
-        8310, 5861, 5385, 3320, 12075, 7977, 1269, 5438, 306, 4267, 7451, 4209, 1450, 7847, 8994,
-        6318, 1794, 5277, 8360, 3887, 8269, 7404, 4447, 7134, 6961, 9712, 7139, 2192, 10038, 5808,
-        2419, 9465, 11224, 11501, 941, 9891, 10430, 8265, 4490, 9043, 3316, 212, 10539, 10034, 57,
-        2010, 6544, 6380, 7788, 4156, 6013, 6349, 2855, 1960, 11049, 221, 6698, 9610, 10355, 11983,
-        6375, 10609, 4957, 9111, 3060, 11284, 5178, 3961, 6272, 10118, 10585, 6548, 5733, 2060,
-        3606, 1907, 6778, 1501, 428, 7902, 4136, 2795, 2828, 10950, 1862, 4768, 7182, 8799, 2429,
-        2806, 5440, 9114, 2122, 5170, 7297, 6697, 9923, 4878, 6359, 5350, 11726, 6217, 11701, 2348,
-        280, 7223, 721, 11219, 726, 10020, 7901, 9656, 5965, 9923, 769, 2324, 5970, 5495, 2367,
-        5429, 11256, 684, 11533, 3073, 9580, 12091, 10041, 4115, 11690, 1819, 1612, 11218, 11359,
-        10517, 9286, 9780, 3884, 4057, 1091, 2605, 4128, 1275, 472, 9733, 11114, 5854, 11704, 7335,
-        1705, 3015, 10240, 257, 11237, 3886, 9786, 11439, 8436, 6597, 3903, 2095, 3192, 3365, 1544,
-        6122, 477, 8558, 10232, 5728, 199, 741, 2257, 11092, 12018, 5264, 9175, 9126, 10026, 3575,
-        5009, 7750, 10056, 4555, 2546, 4290, 4696, 2729, 1080, 8592, 11855, 4018, 3578, 10448,
-        3590, 538, 8664, 11186, 5589, 10857, 4591, 11724, 9709, 11365, 7957, 8650, 2786, 7537, 127,
-        4038, 6871, 3918, 8300, 3298, 9483, 5987, 12127, 7279, 8021, 12123, 12011, 8915, 676, 7129,
-        11601, 1593, 10526, 9038, 3417, 10657, 4936, 5525,
+        8440, 11430, 6779, 9992, 5150, 11750, 7236, 10191, 2469, 9695, 1818, 6681, 7084, 8771,
+        7014, 5581, 6035, 10939, 4346, 4794, 5078, 1297, 4378, 658, 435, 12012, 1398, 4216, 4628,
+        6364, 8089, 2059, 10641, 11671, 7168, 5830, 7616, 6843, 5519, 9121, 4311, 3254, 5892, 4582,
+        6508, 8039, 4759, 4270, 10652, 4187, 7585, 1603, 2421, 9993, 7108, 5418, 3557, 9732, 2573,
+        685, 3495, 7221, 702, 7558, 12115, 8905, 6300, 2341, 6239, 401, 1962, 2587, 1946, 6906,
+        2222, 9917, 12033, 4662, 3445, 2571, 981, 4796, 10056, 11917, 2122, 8560, 10037, 9408,
+        4786, 1847, 6741, 10305, 9470, 3857, 7129, 5383, 5277, 4913, 4076, 11046, 2436, 2167, 9284,
+        6247, 10149, 4772, 9261, 5192, 5714, 6112, 5820, 9127, 9113, 5492, 9063, 10860, 7557,
+        10206, 3681, 5223, 1962, 6528, 8408, 1528, 9646, 1814, 3098, 4345, 877, 2005, 7889, 1790,
+        375, 8329, 9083, 7912, 4300, 6515, 10339, 10891, 8744, 5643, 6538, 10058, 9706, 1990, 4708,
+        6911, 1411, 4294, 2194, 3208, 1424, 1120, 6225, 8639, 4019, 9925, 1190, 2073, 5932, 8915,
+        11367, 442, 6888, 1887, 11098, 8243, 6439, 9772, 5621, 7126, 5557, 10293, 10699, 12228,
+        7159, 10416, 8393, 3260, 5484, 3251, 7806, 9219, 8474, 11039, 2101, 182, 2159, 4915, 5968,
+        10440, 988, 81, 1236, 722, 8663, 8821, 469, 11554, 2413, 763, 5237, 6954, 6082, 6914, 4437,
+        2984, 5234, 1787, 4707, 1526, 8736, 1049, 4359, 7210, 12178, 1741, 9191, 10850, 3286, 4791,
+        2588, 11145, 11889, 9820, 3, 11749, 11858, 1244, 8571, 10481, 11237, 5518, 4005, 2364,
+        7881, 1656, 11788, 10101, 663, 638, 11459, 6722, 10366, 4304, 197, 2004, 3234, 1870, 5397,
+        6800, 5189, 7197, 6141, 11193, 4001, 5859, 10828, 7716, 777, 6738, 9172, 10354, 7535,
+        10438, 3157, 9433, 8190, 11470, 10944, 5576, 4656, 3147, 11418, 4604, 7070, 12279, 8992,
+        9515, 9872, 6633, 3005, 3167, 6217, 3332, 9801, 4387, 128, 5749, 1863, 8122, 2012, 8638,
+        9925, 3773, 3418, 6784, 3303, 5132, 6829, 3328, 9587, 2439, 2414, 1903, 4395, 7018, 10827,
+        11720, 674, 8863, 9366, 9176, 10141, 4869, 7969, 311, 1069, 7560, 4843, 7593, 6344, 5777,
+        6798, 10655, 3444, 1965, 1406, 3764, 10387, 3935, 4870, 3085, 8889, 11144, 1702, 126, 9986,
+        120, 4258, 3817, 2063, 8194, 1498, 6044, 8027, 11650, 6613, 5564, 5216, 9549, 10, 9786,
+        1683, 11007, 7903, 7336, 5469, 6420, 7173, 6299, 8416, 1618, 1573, 8918, 11285, 8226, 3830,
+        6309, 8971, 6870, 1571, 2186, 11485, 3938, 8759, 10980, 9510, 8305, 6942, 5177, 4131, 1812,
+        9015, 11576, 4063, 8310, 4194, 9007, 7489, 7673, 892, 12066, 8929, 8229, 8981, 11447, 5267,
+        4137, 7126, 372, 8282, 4704, 9825, 1187, 1356, 538, 7551, 2257, 5876, 3082, 9904, 4375,
+        275, 7826, 3308, 12262, 4948, 1443, 8514, 10257, 10284, 8671, 1632, 835, 5675, 569, 7529,
+        9095, 11310, 11966, 11, 7629, 4461, 7089, 11761, 10174, 10285, 7332, 2216, 7478, 2861,
+        8529, 5530, 4026, 7480, 11647, 10003, 4763, 7663, 3808, 2700, 2460, 937, 10402, 599, 305,
+        861, 12248, 6660, 3210, 852, 1347, 10095, 6253, 10762, 1927, 11436, 6787, 2669, 10909,
+        3085, 8127, 10289, 4418, 1171, 5243, 2020, 5126, 1728, 11544, 5406, 7848, 1328, 506, 1798,
+        2746, 12080, 6316, 10480, 11478, 8576, 11091, 4631, 3611, 11615, 6426, 5783, 8509, 3204,
+        2154, 10724, 0, 9931, 10937, 1307, 3544, 9290, 1242, 4168, 7167, 9696, 7183, 5088, 162,
+        11179, 10451, 6684, 7447, 1382, 2480, 2685, 3964, 1047, 577, 11786, 5913, 8934, 7936,
+        10612, 4305, 181, 7263, 2235, 972, 11064, 3994, 9169, 1690, 8772, 7784, 6536, 10205, 9602,
+        5692, 7717, 4070, 5786, 10906, 2949, 7828, 2908, 6623, 7910, 10147, 2076, 7851, 4286, 6497,
+        3776, 7120, 12150, 7716, 6287, 9101, 2602, 2124, 415, 10590, 6948, 10903, 7071, 4407, 8638,
+        2851, 2831, 1948, 10467, 3306, 224, 4433, 4360, 3688, 4493, 10977, 1548, 3684, 4163, 7516,
+        2123, 4702, 5683, 12185, 4838, 12121, 8569, 6059, 11578, 6275, 575, 10071, 125, 6948, 2467,
+        11317, 7269, 9772, 414, 10967, 5248, 1627, 9719, 40, 12228, 7464, 9788, 9695, 7590, 9195,
+        8411, 5478, 4234, 4775, 9783, 5092, 635, 2249, 3330, 4406, 2338, 4771, 8993, 11151, 3222,
+        11021, 8915, 1870, 6309, 3410, 7161, 1980, 6019, 4784, 4470, 2816, 11455, 2580, 3842, 4645,
+        1639, 11120, 7445, 2526, 5514, 4929, 9532, 3705, 5423, 248, 12099, 6608, 7025, 7192, 11260,
+        9060, 10046, 980, 6937, 6350, 11443, 5387, 934, 5609, 11559, 3251, 3348, 11905, 9364, 7753,
+        5417, 4631, 6319, 5961, 2117, 6033, 7382, 4222, 10662, 8879, 4862, 8425, 1412, 2358, 6595,
+        1971, 7689, 1154, 5725, 313, 8990, 3257, 10192, 10277, 7400, 894, 8747, 8117, 7038, 11623,
+        7947, 5967, 10151, 9955, 11070, 10645, 8235, 5348, 62, 5387, 201, 1613, 3725, 10432, 4145,
+        7883, 10398, 10871, 9625, 6141, 9977, 11221, 7774, 11448, 5238, 5273, 4022, 4906, 12220,
+        7660, 3412, 5462, 6859, 1264, 1568, 3593, 8978, 4125, 4376, 4646, 2821, 3098, 6677, 7731,
+        3637, 9559, 8195, 2164, 11180, 1189, 1, 11128, 5687, 6903, 1964, 5036, 9358, 3757, 11252,
+        8093, 9355, 9779, 7799, 9826, 8411, 7120, 11940, 11859, 6216, 2210, 3091, 5831, 3195, 5635,
+        3046, 12139, 4775, 4968, 1784, 9786, 2959, 6298, 5442, 10380, 10076, 1087, 9029, 3089,
+        9566, 11833, 7879, 6507, 1148, 172, 10659, 2415, 1288, 7127, 2877, 2786, 8974, 8686, 12045,
+        11219, 7854, 7207, 9752, 3601, 5327, 8189, 2113, 9184, 6140, 5322, 3636, 10948, 4084, 4084,
+        10198, 1638, 5468, 7461, 11017, 4193, 2997, 2541, 7169, 9820, 8604, 6697, 1405, 730, 6291,
+        881, 4756, 3184, 4858, 866, 5631, 4057, 3429, 7996, 10190, 3061, 11061, 11124, 5293, 2656,
+        3731, 4499, 6094, 7773, 2553, 7188, 5773, 525, 11903, 6136, 4369, 5056, 11764, 7817, 8357,
+        11972, 9744, 4118, 6591, 11606, 11020, 3334, 3336, 11381, 7673, 11280, 6244, 287, 1386,
+        106, 2262, 5014, 11544, 7835, 3469, 3712, 437, 2445, 10406, 2548, 11792, 4946, 11984, 9578,
+        3078, 9682, 5082, 8825, 5617, 10874, 2554, 7847, 7817, 1141, 10512, 1225, 8942, 5654, 8598,
+        12007, 4700, 928, 1871, 1821, 9045, 4679, 427, 10301, 4194, 3068, 4329, 7793, 1420, 7131,
+        9383, 7158, 4980, 6044, 8082, 10009, 9393, 6670, 2537, 10162, 2496, 2263, 10266, 10322,
+        1932, 5143, 3703, 9677, 7326, 5716, 9564, 3058, 6736, 2842, 405, 3127, 6616, 8531, 7684,
+        4967, 2555, 446, 4490, 7876, 4599, 8675, 3982, 10388, 4634, 978, 9043, 9970, 354, 5469,
+        6731, 11841, 10434, 4210, 9202, 1885, 4765, 6151, 2123, 7472, 3214, 5635, 5916, 4586, 2726,
+        5372, 633, 167, 2602, 9627, 5517, 7808, 1854, 6147, 7037, 6901, 8229, 4064, 9882, 11758,
+        3210, 7291, 7273, 10378, 1526, 9018, 5266, 11544, 8266, 3227, 8733, 9977, 8049, 1597, 2915,
+        9114, 8095, 9,
     ];
+
+    /// A Falcon-512 verification instance.
+    ///
+    /// The key pair was generated from a fixed seed following the Falcon key
+    /// generation (Gaussian f, g and NTRUSolve), and `MSG_POINT` is the
+    /// HashToPoint of [`NONCE`] and [`MESSAGE`]. `TEST_S1` is a signature of it
+    /// whose squared norm is well below the Falcon-512 bound.
+    pub mod falcon_512 {
+        use crate::FALCON_512_LOG_SIZE;
+
+        pub use super::{MESSAGE, NONCE};
+
+        pub const TEST_S1: &[u32; 1 << FALCON_512_LOG_SIZE] = &[
+            12279, 31, 51, 37, 12257, 32, 12278, 12282, 12211, 82, 62, 28, 12253, 12265, 12279, 20,
+            44, 7, 57, 3, 79, 12271, 12, 12272, 36, 32, 12263, 12259, 32, 12274, 9, 43, 12268, 12,
+            39, 36, 12229, 12279, 16, 30, 12237, 58, 47, 12270, 28, 25, 12239, 36, 12284, 15, 49,
+            12255, 0, 12283, 29, 12231, 12, 13, 0, 22, 5, 12227, 12273, 12270, 12224, 12284, 4, 87,
+            12276, 12244, 12271, 46, 12273, 83, 0, 12280, 15, 34, 12222, 12224, 41, 12241, 12273,
+            8, 12252, 43, 28, 35, 57, 12244, 12285, 9, 33, 1, 12287, 12276, 12276, 12271, 12268,
+            12259, 3, 40, 12282, 12249, 22, 32, 19, 12217, 38, 12288, 12192, 12267, 12271, 33,
+            12253, 29, 23, 12, 12270, 12247, 26, 12285, 12223, 12280, 91, 22, 12281, 64, 12229,
+            12225, 12251, 0, 40, 12230, 12218, 26, 12281, 12274, 12207, 12245, 5, 12286, 23, 12250,
+            19, 12256, 12251, 66, 12279, 12265, 59, 45, 63, 74, 115, 67, 9, 32, 20, 12, 12271,
+            12279, 12267, 36, 12278, 12288, 16, 6, 12256, 12234, 6, 12284, 12278, 22, 71, 40,
+            12277, 12255, 21, 18, 25, 12222, 12269, 51, 10, 50, 12239, 14, 21, 28, 12256, 12281,
+            30, 12260, 12282, 12276, 12275, 8, 12256, 68, 12282, 12286, 12279, 12273, 12232, 12262,
+            12286, 12240, 12277, 24, 18, 28, 2, 12285, 12242, 12260, 36, 19, 12265, 6, 11, 26,
+            12275, 12284, 12287, 12257, 12257, 17, 12233, 12220, 12281, 12271, 40, 12253, 16, 2,
+            12274, 12270, 9, 38, 6, 42, 37, 12243, 12280, 12258, 12267, 54, 12283, 19, 56, 12281,
+            12226, 41, 69, 12282, 12204, 8, 54, 12274, 39, 9, 50, 2, 0, 5, 12251, 34, 8, 44, 19,
+            12202, 32, 18, 62, 33, 12273, 12264, 12258, 12246, 12288, 12274, 38, 45, 19, 36, 50,
+            12262, 12263, 12249, 12227, 58, 12262, 3, 12252, 12240, 19, 12262, 12251, 12248, 15,
+            12281, 11, 75, 2, 12274, 14, 12271, 17, 12267, 12222, 39, 12186, 12204, 12280, 76,
+            12246, 27, 12279, 12284, 7, 3, 12284, 12267, 59, 12259, 12194, 53, 12273, 12259, 3, 27,
+            12287, 17, 31, 12284, 12278, 55, 12271, 12274, 35, 58, 75, 11, 12274, 13, 42, 12242,
+            12273, 12271, 12280, 63, 12281, 36, 27, 28, 16, 22, 12247, 12278, 37, 11, 12283, 10,
+            12241, 10, 12, 32, 12237, 12268, 28, 78, 12218, 28, 17, 2, 12241, 12280, 12285, 25,
+            12274, 1, 37, 12216, 11, 12260, 25, 20, 60, 12, 12280, 37, 12222, 5, 29, 12276, 79,
+            12236, 19, 18, 12267, 2, 36, 0, 29, 49, 4, 12273, 67, 14, 12255, 12286, 12241, 12275,
+            12203, 12217, 12277, 12248, 12256, 12256, 54, 12250, 12282, 59, 8, 12266, 12265, 12250,
+            0, 1, 12253, 12274, 1, 12274, 37, 12235, 12254, 7, 12230, 12252, 12260, 12, 1, 16,
+            12246, 21, 12271, 12236, 57, 12248, 27, 34, 12266, 2, 12261, 12270, 66, 36, 66, 45, 79,
+            16, 12269, 48, 62, 27, 12257, 12278, 12264, 12256, 50, 12257, 12244, 118, 44, 12263,
+            12258, 12223, 26, 12255, 12279, 12284, 32, 82, 37, 12229, 12281, 12236, 14, 6, 12258,
+            49, 17, 12247, 12247, 12280, 42, 12286, 24, 12214, 12287, 15, 12281, 12282, 12259,
+            12288, 29, 12244, 12243, 12225, 12277, 12270,
+        ];
+
+        /// The standard compressed encoding of the Falcon-512 test signature.
+        pub const SIGNATURE: &[u8] = &[
+            57, 28, 46, 43, 184, 86, 157, 128, 108, 18, 81, 220, 201, 190, 227, 137, 18, 14, 186,
+            238, 163, 194, 216, 84, 90, 120, 118, 12, 90, 166, 88, 69, 184, 93, 228, 212, 186, 181,
+            185, 228, 82, 138, 143, 204, 228, 186, 9, 6, 47, 15, 206, 169, 79, 163, 154, 76, 198,
+            42, 41, 44, 131, 206, 96, 116, 252, 148, 51, 35, 36, 144, 102, 179, 210, 12, 124, 38,
+            87, 149, 134, 73, 228, 155, 204, 84, 66, 61, 180, 157, 75, 242, 113, 200, 206, 202, 73,
+            133, 135, 204, 116, 80, 12, 52, 119, 117, 12, 134, 192, 34, 208, 93, 246, 67, 39, 193,
+            194, 193, 42, 248, 221, 110, 74, 93, 144, 169, 192, 49, 48, 249, 23, 15, 131, 41, 216,
+            100, 33, 26, 89, 92, 114, 71, 57, 214, 225, 33, 50, 24, 14, 11, 27, 141, 201, 101, 115,
+            208, 57, 70, 31, 81, 22, 144, 68, 249, 18, 108, 15, 135, 45, 146, 144, 233, 35, 177,
+            120, 102, 79, 85, 26, 194, 112, 177, 53, 184, 182, 34, 129, 188, 224, 105, 160, 18,
+            141, 223, 30, 53, 136, 199, 244, 181, 144, 92, 28, 95, 79, 19, 208, 233, 168, 88, 172,
+            196, 238, 91, 63, 165, 92, 232, 112, 153, 4, 82, 25, 146, 197, 101, 164, 152, 188, 12,
+            66, 13, 161, 219, 193, 176, 184, 184, 181, 30, 81, 140, 209, 69, 98, 81, 158, 30, 82,
+            103, 10, 153, 108, 161, 209, 88, 230, 135, 17, 30, 206, 225, 241, 184, 232, 70, 134,
+            137, 135, 193, 226, 178, 27, 156, 222, 15, 99, 140, 140, 68, 163, 144, 44, 38, 191, 59,
+            36, 137, 230, 32, 208, 184, 214, 59, 11, 130, 208, 104, 34, 59, 142, 46, 35, 37, 40,
+            210, 68, 32, 88, 252, 156, 38, 77, 6, 149, 73, 117, 216, 156, 254, 90, 109, 134, 137,
+            206, 49, 27, 249, 77, 23, 15, 213, 132, 77, 177, 242, 120, 76, 202, 5, 0, 130, 233,
+            164, 80, 137, 100, 79, 175, 32, 137, 79, 164, 57, 12, 206, 127, 87, 129, 199, 201, 165,
+            177, 57, 36, 203, 55, 154, 212, 111, 167, 89, 184, 30, 151, 99, 19, 205, 233, 181, 48,
+            252, 68, 46, 151, 2, 199, 195, 178, 81, 28, 183, 14, 79, 231, 234, 226, 105, 154, 184,
+            222, 43, 11, 7, 129, 225, 114, 211, 188, 247, 126, 107, 144, 207, 64, 227, 120, 40,
+            140, 127, 11, 139, 155, 228, 177, 242, 57, 213, 46, 23, 143, 134, 202, 181, 249, 12,
+            150, 38, 127, 136, 146, 70, 227, 145, 8, 182, 171, 23, 37, 133, 225, 161, 91, 8, 84,
+            50, 65, 180, 202, 199, 41, 220, 120, 228, 70, 5, 176, 196, 225, 35, 56, 248, 12, 151,
+            147, 11, 206, 198, 98, 147, 200, 102, 38, 75, 195, 130, 199, 113, 180, 253, 172, 78,
+            37, 150, 129, 73, 32, 17, 217, 140, 19, 33, 67, 135, 104, 176, 123, 12, 119, 91, 145,
+            140, 212, 232, 116, 51, 109, 62, 30, 119, 8, 203, 230, 52, 240, 8, 14, 147, 31, 1, 199,
+            201, 118, 218, 56, 62, 239, 75, 157, 134, 64, 98, 26, 184, 174, 75, 107, 57, 212, 198,
+            228, 89, 120, 22, 115, 39, 66, 146, 80, 165, 180, 248, 134, 82, 97, 62, 141, 232, 49,
+            121, 157, 12, 203, 65, 173, 187, 75, 51, 89, 254, 20, 107, 69, 138, 194, 200, 42, 82,
+            93, 230, 35, 107, 14, 131, 103, 230, 49, 29, 86, 171, 19, 42, 193, 198, 57, 120, 40,
+            126, 35, 15, 158, 192, 199, 117, 186, 238, 6, 51, 39,
+        ];
+
+        pub const PK: &[u32; 1 << FALCON_512_LOG_SIZE] = &[
+            5236, 182, 9391, 6223, 7173, 173, 7323, 7388, 9861, 9039, 1035, 5852, 8912, 2123, 8685,
+            11065, 8031, 1951, 3002, 10132, 5539, 1138, 6663, 3075, 666, 11418, 6094, 10005, 11393,
+            12040, 10591, 2107, 5777, 4097, 100, 396, 9925, 1825, 976, 1107, 4839, 10784, 6811,
+            11450, 9125, 9698, 9273, 7571, 12273, 5828, 1545, 343, 8268, 9614, 1857, 8439, 3603,
+            1861, 9776, 9772, 8352, 3844, 5507, 5054, 1399, 6526, 11336, 6690, 3975, 7888, 8918,
+            5297, 1478, 3233, 10869, 10279, 136, 807, 6889, 2925, 7673, 10825, 766, 885, 11199,
+            11827, 5908, 11677, 9859, 538, 10206, 1490, 44, 12018, 1306, 10776, 10285, 10453, 2885,
+            5038, 6322, 11456, 11160, 11545, 2220, 6833, 11179, 2925, 10031, 8415, 6211, 4560,
+            1545, 11622, 9694, 4522, 1235, 7793, 5356, 9974, 12218, 10284, 1625, 4740, 8414, 6741,
+            6047, 6022, 595, 4966, 2809, 2037, 6798, 10686, 6128, 7918, 160, 4054, 46, 11881, 2185,
+            8176, 2339, 1168, 4076, 7928, 10920, 10745, 1118, 226, 11161, 3709, 2612, 2584, 2552,
+            3221, 3355, 526, 10228, 10133, 866, 8008, 10798, 4859, 1647, 1761, 4979, 1445, 12164,
+            9351, 1963, 98, 5875, 355, 7940, 6467, 6348, 8020, 159, 1491, 4847, 3440, 7532, 9293,
+            7349, 7281, 11195, 6982, 9691, 9817, 12035, 5507, 5583, 7289, 1890, 3896, 10939, 2435,
+            11130, 6401, 9115, 11418, 424, 7605, 9528, 8438, 8450, 1475, 4868, 11798, 5213, 7821,
+            2579, 10322, 9096, 3121, 9961, 1415, 6446, 9291, 5157, 388, 3855, 7697, 3329, 3961,
+            8548, 2800, 7222, 1693, 5864, 100, 4995, 3848, 2389, 6465, 7166, 2176, 9151, 2612,
+            11078, 10903, 8701, 6124, 4173, 9015, 3579, 2981, 11333, 1842, 7542, 4128, 5756, 7649,
+            7320, 10374, 8266, 1403, 11204, 11658, 10630, 3435, 9715, 6408, 5390, 1737, 7826, 187,
+            10718, 7405, 258, 7754, 3578, 1753, 1783, 7108, 11957, 11341, 4809, 5736, 9970, 8205,
+            11877, 3156, 183, 5970, 10841, 9684, 2744, 3179, 7491, 10498, 1761, 5298, 4071, 229,
+            2870, 6140, 4797, 5747, 1018, 5287, 515, 11487, 2036, 2423, 859, 2475, 2104, 1904,
+            9783, 9846, 11233, 1528, 7282, 1630, 6311, 1529, 3781, 8091, 2894, 10725, 6200, 7320,
+            8208, 7682, 755, 9360, 7179, 10029, 6678, 5844, 11389, 8902, 3309, 8833, 2784, 2503,
+            4834, 1011, 9148, 4250, 3189, 7072, 6250, 4779, 6601, 11812, 11558, 7199, 1014, 5633,
+            4184, 4803, 4921, 7795, 1422, 3963, 3464, 9821, 10144, 9797, 11599, 273, 5525, 3817,
+            1142, 11762, 9685, 2673, 4871, 7018, 1131, 10093, 10826, 7650, 4264, 10671, 4952, 683,
+            10974, 2202, 3900, 10976, 1025, 7376, 4178, 3545, 5858, 12113, 3142, 2587, 11670, 9592,
+            5324, 3554, 10768, 5998, 7236, 11279, 1080, 11900, 7540, 12287, 847, 3318, 889, 8682,
+            7220, 7094, 11700, 10468, 3429, 9944, 4672, 8527, 3336, 7286, 12192, 11231, 2487, 5894,
+            6522, 7823, 9192, 11973, 8065, 959, 11664, 3256, 8831, 8996, 7036, 6299, 2841, 3479,
+            11191, 2479, 8781, 6751, 8968, 1952, 12043, 4618, 1180, 8845, 6517, 3592, 3560, 4409,
+            7103, 7107, 4520, 11653, 11128, 10878, 10611, 4925, 5516, 627, 2826, 4613, 4082, 1271,
+            9466, 9807, 8430, 2014, 3397, 2904, 11202, 1242, 3678, 7145, 732, 392, 5243, 7891,
+            4823, 2546, 11101, 428, 11292, 6309, 1465, 9674, 6534, 2466, 2293, 10373, 2056, 10821,
+            8354, 6659, 6133, 2466, 10904, 11522, 3543, 1116, 255, 154, 5435, 10161, 10022, 9517,
+            11580, 5920, 7425, 8621, 2896, 10860,
+        ];
+
+        /// The secret polynomial f of the Falcon-512 test key, with [`PK`] = g / f mod q.
+        ///
+        /// The squared norm of (f, g) exceeds the bound of the Falcon key generation, which
+        /// would have rejected this key.
+        pub const F: &[i16; 1 << FALCON_512_LOG_SIZE] = &[
+            -2, 0, 0, -2, -7, -2, 9, 4, 5, 5, -4, 1, -2, -6, -3, 4, -6, -1, -2, 3, -1, -7, 4, -2,
+            1, 3, -5, -5, 0, -5, -6, 2, -4, -3, -1, 5, -2, 4, 6, 2, -2, -7, -6, 5, 4, 1, -7, -2, 3,
+            -1, -2, 1, -6, 4, -6, 10, -4, 0, -1, -1, -5, 3, 1, 4, 3, -5, 5, -5, -1, 5, 4, -5, 5, 2,
+            5, -2, 1, -6, 3, -3, -1, -1, -1, 5, 5, 4, 7, 0, -2, 1, -4, 2, -2, 7, 4, -6, 5, -2, 0,
+            -4, 3, -2, -1, 2, -3, 0, -2, 10, 0, 4, -3, -3, 0, -6, 2, -8, 5, -3, 1, 2, 5, -15, -3,
+            7, 1, 3, 2, -1, 1, 4, 1, -3, -4, 0, -1, -4, 8, 1, 0, 4, -4, -7, -2, -4, 5, 1, -3, -3,
+            3, 6, -14, 7, 5, -4, -2, 11, 4, 2, 2, 4, 3, -1, 2, -3, -2, -1, -1, -8, -1, 9, -5, 0, 1,
+            0, 2, -2, 7, 0, -2, -5, 4, -2, -4, 8, 4, -3, -6, -1, 3, 0, -4, 0, -4, 1, 6, -3, -2, -5,
+            1, 0, -5, -2, 4, 2, -5, 2, 1, -1, 2, 0, -3, 1, 3, 1, -2, 5, 8, 3, -6, -2, 1, 1, 8, -2,
+            -8, -6, 4, -6, -2, -1, 3, 1, -5, 4, -3, -9, -1, 0, 8, 1, 5, -1, 0, -2, -3, 1, 5, -8, 5,
+            0, 0, 5, -5, 0, 1, 3, -1, -5, 3, 6, 2, -3, 1, -1, 4, 2, -5, -3, 6, -2, -3, 1, 4, -13,
+            3, -6, 5, 6, -1, 2, -5, -4, 2, 1, 4, 4, 2, 0, 2, -1, 2, 1, -1, -2, 3, -6, 2, -4, 4, -1,
+            4, 0, -10, 0, -5, -1, -1, -1, -3, 2, -9, -7, 6, -2, -2, -3, 6, 10, -5, 4, -9, -2, 0,
+            -4, 4, 1, 1, -8, -7, -11, 4, -3, 0, -2, 1, -2, -5, -1, -1, 2, 3, -8, 2, -3, -9, 0, -1,
+            -2, 3, 1, 4, -4, -5, -2, 7, 4, 1, -3, 2, 4, 0, 1, -9, -5, 6, 0, -3, -3, -7, 3, 4, 11,
+            7, 5, 8, -3, 2, 4, 3, 8, -3, 0, 0, -5, 8, 0, 1, 4, -8, 1, -4, 4, 5, -1, -5, 3, 2, 2, 5,
+            -4, -1, -3, -5, -1, -2, 2, 6, -2, -5, 7, -10, -7, -1, -4, -1, -2, 7, 2, -2, 4, 7, 2, 9,
+            -1, -3, -4, -4, -5, 2, 1, 5, 4, -2, 2, -2, 0, -3, -7, 6, 1, -6, -2, 3, 1, -5, -1, -1,
+            5, 5, 2, -2, -6, -1, -1, 4, 3, -5, 5, 3, -6, 0, -4, -2, 1, -4, 3, 7, -3, -9, 8, -5, 1,
+            5, 0, 0, 0, 2, -3, -4, -6, -2, 3, 7, 1, -6, -2, 0, -1, -7, 0, 3, 7, -4, -6, 0, 4, 3, 2,
+            -8, -6, -2, 5, -3, 8, -3, -4, 3, 3, 0, 8, -2, -5,
+        ];
+
+        /// The secret polynomial g of the Falcon-512 test key.
+        pub const G: &[i16; 1 << FALCON_512_LOG_SIZE] = &[
+            -4, -4, -1, -2, 8, 1, -1, 0, -2, 4, 0, 2, -15, 1, 1, -9, 7, -3, -9, -1, 8, 0, 3, 8, -2,
+            -1, -4, -6, 5, -2, -3, 0, 1, 4, 0, -4, 5, 1, -1, 3, 3, 1, 2, 3, 3, 2, -1, 4, -1, 1, -1,
+            -9, -1, 5, -4, 4, 0, -4, 2, 11, 4, 4, 3, 1, 0, 1, 3, -5, -7, 0, -6, -1, -3, 5, -3, 4,
+            1, 3, -1, 1, 2, -7, -5, -5, -3, 1, -3, -5, -4, 1, -5, 5, 2, 4, -3, -8, 8, 4, -2, -3, 3,
+            2, -9, 0, -4, -3, -4, -3, -4, 0, 9, 0, -3, -4, -4, -6, 6, -2, 9, 3, 4, -2, 1, 0, -3, 1,
+            6, -3, 3, 1, 2, -2, 0, 3, 10, -3, 4, -4, 6, -5, -4, 5, 4, 2, 3, -4, -1, 3, -3, -1, -1,
+            4, -7, -2, -5, 1, -1, -2, 2, 5, 6, -3, -6, 1, 0, -8, 1, -6, 3, -1, 2, 1, 4, 2, -6, 6,
+            3, 6, 7, 0, 1, -3, 7, -5, -6, 8, -6, -7, 0, 1, 1, -7, 5, 0, 2, 7, -1, 7, 4, 3, 2, -2,
+            0, 0, 1, 11, -3, -2, -2, -1, 3, 0, 0, -5, 1, 2, 12, 1, 1, -1, 1, -3, -4, -3, 6, -4, -5,
+            4, -6, 0, 2, 1, 0, 2, 0, -2, -1, -1, -7, -5, -3, -3, 0, -1, 11, -9, -1, 1, -5, 3, 2, 0,
+            -4, 3, 4, -2, 1, -10, 1, 2, 1, 0, -3, -2, 0, -3, 2, 4, -2, -8, 0, 2, -5, -3, 4, 0, -4,
+            3, -1, -2, -5, 9, 0, -6, 3, 6, 2, 8, 0, -3, -8, -1, -8, -3, 3, 4, 1, 1, 3, 0, 0, 3, -4,
+            3, -8, -3, 1, 4, 3, 5, 2, -8, 2, -9, 1, 1, 1, 4, -4, 0, -1, 3, -7, 0, -6, 2, 4, -9, -7,
+            -6, 3, 2, -5, -3, -2, 8, 3, 4, -5, -4, 5, -3, 3, -3, 5, 1, 2, -5, -8, 2, -2, 5, 0, 1,
+            -1, -2, -9, 3, -2, 3, -10, 6, 2, 2, -3, 3, 6, 5, 3, 1, -1, -3, 2, -2, -5, -2, 3, 2, 3,
+            0, -5, -5, 0, 6, 0, 5, -2, 9, 3, 0, 5, 11, -3, 3, -4, 2, 0, -9, 7, 2, -4, 1, 1, -2, -2,
+            -1, -9, 6, -2, -6, 0, -1, -1, -1, 2, -8, 1, 5, 5, -3, 1, -1, -1, -5, 1, -1, 5, -1, -6,
+            0, -6, -3, 0, 3, -6, -9, 3, 3, 2, 0, 1, -3, 0, -6, 3, 7, -6, 1, -2, -9, -3, 1, -1, -9,
+            1, -8, -5, -1, 0, 1, 0, 5, 3, -1, 3, -7, -2, -1, -2, 1, 1, 8, 3, -1, 1, 1, -5, 5, -2,
+            1, 5, -3, -3, -3, -6, -6, 4, 3, -3, 2, 1, -4, -3, -9, 0, 6, 3, -3, 1, -1, 0, 6, -3, -6,
+            -2, -2, -2, -1, 6, -8, 4, 5,
+        ];
+
+        pub const MSG_POINT: &[u32; 1 << FALCON_512_LOG_SIZE] = &[
+            8440, 11430, 6779, 9992, 5150, 11750, 7236, 10191, 2469, 9695, 1818, 6681, 7084, 8771,
+            7014, 5581, 6035, 10939, 4346, 4794, 5078, 1297, 4378, 658, 435, 12012, 1398, 4216,
+            4628, 6364, 8089, 2059, 10641, 11671, 7168, 5830, 7616, 6843, 5519, 9121, 4311, 3254,
+            5892, 4582, 6508, 8039, 4759, 4270, 10652, 4187, 7585, 1603, 2421, 9993, 7108, 5418,
+            3557, 9732, 2573, 685, 3495, 7221, 702, 7558, 12115, 8905, 6300, 2341, 6239, 401, 1962,
+            2587, 1946, 6906, 2222, 9917, 12033, 4662, 3445, 2571, 981, 4796, 10056, 11917, 2122,
+            8560, 10037, 9408, 4786, 1847, 6741, 10305, 9470, 3857, 7129, 5383, 5277, 4913, 4076,
+            11046, 2436, 2167, 9284, 6247, 10149, 4772, 9261, 5192, 5714, 6112, 5820, 9127, 9113,
+            5492, 9063, 10860, 7557, 10206, 3681, 5223, 1962, 6528, 8408, 1528, 9646, 1814, 3098,
+            4345, 877, 2005, 7889, 1790, 375, 8329, 9083, 7912, 4300, 6515, 10339, 10891, 8744,
+            5643, 6538, 10058, 9706, 1990, 4708, 6911, 1411, 4294, 2194, 3208, 1424, 1120, 6225,
+            8639, 4019, 9925, 1190, 2073, 5932, 8915, 11367, 442, 6888, 1887, 11098, 8243, 6439,
+            9772, 5621, 7126, 5557, 10293, 10699, 12228, 7159, 10416, 8393, 3260, 5484, 3251, 7806,
+            9219, 8474, 11039, 2101, 182, 2159, 4915, 5968, 10440, 988, 81, 1236, 722, 8663, 8821,
+            469, 11554, 2413, 763, 5237, 6954, 6082, 6914, 4437, 2984, 5234, 1787, 4707, 1526,
+            8736, 1049, 4359, 7210, 12178, 1741, 9191, 10850, 3286, 4791, 2588, 11145, 11889, 9820,
+            3, 11749, 11858, 1244, 8571, 10481, 11237, 5518, 4005, 2364, 7881, 1656, 11788, 10101,
+            663, 638, 11459, 6722, 10366, 4304, 197, 2004, 3234, 1870, 5397, 6800, 5189, 7197,
+            6141, 11193, 4001, 5859, 10828, 7716, 777, 6738, 9172, 10354, 7535, 10438, 3157, 9433,
+            8190, 11470, 10944, 5576, 4656, 3147, 11418, 4604, 7070, 12279, 8992, 9515, 9872, 6633,
+            3005, 3167, 6217, 3332, 9801, 4387, 128, 5749, 1863, 8122, 2012, 8638, 9925, 3773,
+            3418, 6784, 3303, 5132, 6829, 3328, 9587, 2439, 2414, 1903, 4395, 7018, 10827, 11720,
+            674, 8863, 9366, 9176, 10141, 4869, 7969, 311, 1069, 7560, 4843, 7593, 6344, 5777,
+            6798, 10655, 3444, 1965, 1406, 3764, 10387, 3935, 4870, 3085, 8889, 11144, 1702, 126,
+            9986, 120, 4258, 3817, 2063, 8194, 1498, 6044, 8027, 11650, 6613, 5564, 5216, 9549, 10,
+            9786, 1683, 11007, 7903, 7336, 5469, 6420, 7173, 6299, 8416, 1618, 1573, 8918, 11285,
+            8226, 3830, 6309, 8971, 6870, 1571, 2186, 11485, 3938, 8759, 10980, 9510, 8305, 6942,
+            5177, 4131, 1812, 9015, 11576, 4063, 8310, 4194, 9007, 7489, 7673, 892, 12066, 8929,
+            8229, 8981, 11447, 5267, 4137, 7126, 372, 8282, 4704, 9825, 1187, 1356, 538, 7551,
+            2257, 5876, 3082, 9904, 4375, 275, 7826, 3308, 12262, 4948, 1443, 8514, 10257, 10284,
+            8671, 1632, 835, 5675, 569, 7529, 9095, 11310, 11966, 11, 7629, 4461, 7089, 11761,
+            10174, 10285, 7332, 2216, 7478, 2861, 8529, 5530, 4026, 7480, 11647, 10003, 4763, 7663,
+            3808, 2700, 2460, 937, 10402, 599, 305, 861, 12248, 6660, 3210, 852, 1347, 10095, 6253,
+            10762, 1927, 11436, 6787, 2669, 10909, 3085, 8127, 10289, 4418, 1171, 5243, 2020, 5126,
+            1728, 11544, 5406, 7848, 1328, 506, 1798, 2746, 12080, 6316, 10480, 11478, 8576, 11091,
+            4631, 3611, 11615, 6426, 5783, 8509, 3204, 2154, 10724, 0, 9931, 10937, 1307, 3544,
+            9290, 1242, 4168, 7167,
+        ];
+    }
 }
@@ -0,0 +1,428 @@
+//! # Native Verification
+//!
+//! This module implements the arithmetic of the Falcon signature verification in plain
+//! Rust, without any trace: the NTT and its inverse, the multiplication and the
+//! subtraction in Z_q[x]/(x^n + 1), the centering of the coefficients and the squared
+//! norm of a signature.
+//!
+//! It gives the values the trace generators must output, so that each of them is tested
+//! against it, and lets callers check a signature before paying for a proof.
+//!
+//! # Verification
+//!
+//! For the signature polynomial s1 of the message point c under the public key h,
+//! [`verify`] computes s0 = c - s1 * h and accepts the signature if the squared norm of
+//! (s0, s1), with coefficients centered in [-q/2, q/2], is at most the signature bound
+//! of the degree.
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    POLY_SIZE, SIGNATURE_BOUNDS,
+    ntts::{I2, ROOTS},
+    zq::{Q, inverses::INVERSES_MOD_Q},
+};
+
+/// The squared norm of an accepted signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Norm(pub u64);
+
+/// Reasons for which a signature is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Reject {
+    #[error("unsupported polynomial size {0}")]
+    UnsupportedDegree(usize),
+    #[error("{poly} has {found} coefficients, expected {expected}")]
+    DegreeMismatch {
+        poly: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("coefficient {index} of {poly} is {value}, expected a value in [0, q)")]
+    CoefficientOutOfRange {
+        poly: &'static str,
+        index: usize,
+        value: u32,
+    },
+    #[error("squared norm {norm} exceeds the bound {bound}")]
+    NormExceedsBound { norm: u64, bound: u32 },
+}
+
+/// Computes the NTT of `poly` natively, in the order the forward NTT circuit outputs it.
+///
+/// This is the recursive transform the butterfly and the merges implement: the NTTs of
+/// the even and odd coefficients are merged with the roots of unity of the size of `poly`.
+///
+/// # Panics
+///
+/// Panics if the size of `poly` is not a power of two of at most 1024 coefficients.
+pub fn ntt(poly: &[u32]) -> Vec<u32> {
+    let n = poly.len();
+    assert!(n.is_power_of_two() && n <= 1 << ROOTS.len());
+    if n == 1 {
+        return poly.to_vec();
+    }
+    let q = Q as u64;
+    let even = ntt(&poly.iter().step_by(2).copied().collect::<Vec<_>>());
+    let odd = ntt(&poly.iter().skip(1).step_by(2).copied().collect::<Vec<_>>());
+    let roots = ROOTS[n.ilog2() as usize - 1];
+    let mut poly_ntt = Vec::with_capacity(n);
+    for (i, (f0, f1)) in even.into_iter().zip(odd).enumerate() {
+        let f1_times_root = f1 as u64 * roots[2 * i] as u64 % q;
+        poly_ntt.push(((f0 as u64 + f1_times_root) % q) as u32);
+        poly_ntt.push(((f0 as u64 + q - f1_times_root) % q) as u32);
+    }
+    poly_ntt
+}
+
+/// Computes the coefficients of the polynomial whose NTT is `poly_ntt` natively, the
+/// inverse of [`ntt`].
+///
+/// # Panics
+///
+/// Panics if the size of `poly_ntt` is not a power of two of at most 1024 coefficients.
+pub fn intt(poly_ntt: &[u32]) -> Vec<u32> {
+    let n = poly_ntt.len();
+    assert!(n.is_power_of_two() && n <= 1 << ROOTS.len());
+    if n == 1 {
+        return poly_ntt.to_vec();
+    }
+    let q = Q as u64;
+    let roots = ROOTS[n.ilog2() as usize - 1];
+    let (even_ntt, odd_ntt): (Vec<_>, Vec<_>) = poly_ntt
+        .chunks_exact(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let (f0, f1) = (pair[0] as u64, pair[1] as u64);
+            let inv_root = INVERSES_MOD_Q[roots[2 * i] as usize] as u64;
+            let even = I2 as u64 * (f0 + f1) % q;
+            let odd = I2 as u64 * (f0 + q - f1) % q * inv_root % q;
+            (even as u32, odd as u32)
+        })
+        .unzip();
+    intt(&even_ntt)
+        .into_iter()
+        .interleave(intt(&odd_ntt))
+        .collect()
+}
+
+/// Multiplies `a` and `b` in Z_q[x]/(x^n + 1), through their NTTs like the circuit does.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` do not have the same size, a power of two of at most 1024
+/// coefficients.
+pub fn poly_mul_mod(a: &[u32], b: &[u32]) -> Vec<u32> {
+    assert_eq!(a.len(), b.len(), "a and b must have the same length");
+    let q = Q as u64;
+    let product_ntt = ntt(a)
+        .into_iter()
+        .zip(ntt(b))
+        .map(|(a, b)| (a as u64 * b as u64 % q) as u32)
+        .collect_vec();
+    intt(&product_ntt)
+}
+
+/// Subtracts `b` from `a` coefficient by coefficient, modulo q.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` do not have the same size.
+pub fn poly_sub_mod(a: &[u32], b: &[u32]) -> Vec<u32> {
+    assert_eq!(a.len(), b.len(), "a and b must have the same length");
+    a.iter().zip(b).map(|(a, b)| (a + Q - b) % Q).collect()
+}
+
+/// Returns the representative of `x` in [-q/2, q/2], for `x` in [0, q).
+pub fn center(x: u32) -> i32 {
+    if x > Q / 2 {
+        x as i32 - Q as i32
+    } else {
+        x as i32
+    }
+}
+
+/// Returns the squared Euclidean norm of (s0, s1), with centered coefficients.
+pub fn squared_norm(s0: &[u32], s1: &[u32]) -> u64 {
+    s0.iter()
+        .chain(s1)
+        .map(|&x| u64::from(center(x).unsigned_abs()).pow(2))
+        .sum()
+}
+
+/// Verifies the signature polynomial `s1` of the message point `msg_point` under the
+/// public key `pk`, against the signature bound of their degree.
+///
+/// # Errors
+///
+/// Returns `Reject` if the degree is not a power of two between 2 and 1024, the
+/// polynomials do not have the same degree or coefficients in [0, q), or the squared
+/// norm of the signature exceeds the bound.
+pub fn verify(s1: &[u32], pk: &[u32], msg_point: &[u32]) -> Result<Norm, Reject> {
+    let n = pk.len();
+    check_degree(n)?;
+    verify_with_bound(s1, pk, msg_point, SIGNATURE_BOUNDS[n.ilog2() as usize - 1])
+}
+
+/// Verifies the signature polynomial `s1` of the message point `msg_point` under the
+/// public key `pk`, against the signature bound `bound`.
+///
+/// # Errors
+///
+/// Returns `Reject` for the same reasons as [`verify`].
+pub fn verify_with_bound(
+    s1: &[u32],
+    pk: &[u32],
+    msg_point: &[u32],
+    bound: u32,
+) -> Result<Norm, Reject> {
+    let n = pk.len();
+    check_degree(n)?;
+    let polys = [("s1", s1), ("pk", pk), ("msg_point", msg_point)];
+    for (poly, coefficients) in polys {
+        if coefficients.len() != n {
+            return Err(Reject::DegreeMismatch {
+                poly,
+                expected: n,
+                found: coefficients.len(),
+            });
+        }
+        if let Some((index, &value)) = coefficients.iter().find_position(|&&value| value >= Q) {
+            return Err(Reject::CoefficientOutOfRange { poly, index, value });
+        }
+    }
+
+    let s0 = poly_sub_mod(msg_point, &poly_mul_mod(s1, pk));
+    let norm = squared_norm(&s0, s1);
+    if norm > bound as u64 {
+        return Err(Reject::NormExceedsBound { norm, bound });
+    }
+    Ok(Norm(norm))
+}
+
+/// Checks that `n` is the degree of a signature bound.
+fn check_degree(n: usize) -> Result<(), Reject> {
+    if !n.is_power_of_two() || !(2..=POLY_SIZE).contains(&n) {
+        return Err(Reject::UnsupportedDegree(n));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::chain;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::input::{MSG_POINT, PK, TEST_S1, falcon_512};
+    use crate::ntts::{intt as intt_circuit, ntt as ntt_circuit};
+    use crate::polys::{euclidean_norm, mul, sub};
+    use crate::{CircuitParams, POLY_LOG_SIZE};
+
+    /// Multiplies `a` and `b` in Z_q[x]/(x^n + 1) with a schoolbook multiplication.
+    fn schoolbook_mul_mod(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let n = a.len();
+        let q = Q as u64;
+        let mut product = vec![0u64; n];
+        for (i, &a) in a.iter().enumerate() {
+            for (j, &b) in b.iter().enumerate() {
+                let term = a as u64 * b as u64 % q;
+                if i + j < n {
+                    product[i + j] += term;
+                } else {
+                    // x^n = -1
+                    product[i + j - n] += q - term;
+                }
+            }
+        }
+        product.into_iter().map(|c| (c % q) as u32).collect()
+    }
+
+    #[test]
+    fn test_poly_mul_mod_is_negacyclic() {
+        assert_eq!(poly_mul_mod(TEST_S1, PK), schoolbook_mul_mod(TEST_S1, PK));
+
+        // x * x^(n - 1) = x^n = -1
+        let mut x = vec![0; 16];
+        x[1] = 1;
+        let mut x_pow = vec![0; 16];
+        x_pow[15] = 1;
+        let mut minus_one = vec![0; 16];
+        minus_one[0] = Q - 1;
+        assert_eq!(poly_mul_mod(&x, &x_pow), minus_one);
+    }
+
+    /// Pairs of random polynomials of the same size, for every size with a signature bound.
+    fn poly_pairs() -> impl Strategy<Value = (Vec<u32>, Vec<u32>)> {
+        (1..=POLY_LOG_SIZE).prop_flat_map(|log_size| {
+            let poly = || prop::collection::vec(0..Q, 1 << log_size);
+            (poly(), poly())
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_intt_inverts_ntt((a, _) in poly_pairs()) {
+            prop_assert_eq!(intt(&ntt(&a)), a.clone());
+            prop_assert_eq!(ntt(&intt(&a)), a);
+        }
+
+        #[test]
+        fn prop_ntt_product_is_the_negacyclic_convolution((a, b) in poly_pairs()) {
+            prop_assert_eq!(poly_mul_mod(&a, &b), schoolbook_mul_mod(&a, &b));
+        }
+
+        #[test]
+        fn prop_ntt_is_linear((a, b) in poly_pairs(), k in 0..Q) {
+            let combination = a.iter().zip(&b).map(|(a, b)| (a + k * b) % Q).collect_vec();
+            let expected = ntt(&a)
+                .into_iter()
+                .zip(ntt(&b))
+                .map(|(a, b)| (a + k * b) % Q)
+                .collect_vec();
+            prop_assert_eq!(ntt(&combination), expected);
+        }
+    }
+
+    #[test]
+    fn test_center() {
+        assert_eq!(center(0), 0);
+        assert_eq!(center(Q / 2), (Q / 2) as i32);
+        assert_eq!(center(Q / 2 + 1), -((Q / 2) as i32));
+        assert_eq!(center(Q - 1), -1);
+    }
+
+    #[test]
+    fn test_verify_test_vectors() {
+        assert_eq!(verify(TEST_S1, PK, MSG_POINT), Ok(Norm(3277089)));
+        assert_eq!(
+            verify(falcon_512::TEST_S1, falcon_512::PK, falcon_512::MSG_POINT),
+            Ok(Norm(1525943))
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_invalid_inputs() {
+        assert_eq!(
+            verify_with_bound(TEST_S1, PK, MSG_POINT, 3277088),
+            Err(Reject::NormExceedsBound {
+                norm: 3277089,
+                bound: 3277088
+            })
+        );
+        assert_eq!(
+            verify(&TEST_S1[..512], PK, MSG_POINT),
+            Err(Reject::DegreeMismatch {
+                poly: "s1",
+                expected: 1024,
+                found: 512
+            })
+        );
+        let mut pk = PK.to_vec();
+        pk[3] = Q;
+        assert_eq!(
+            verify(TEST_S1, &pk, MSG_POINT),
+            Err(Reject::CoefficientOutOfRange {
+                poly: "pk",
+                index: 3,
+                value: Q
+            })
+        );
+        assert_eq!(
+            verify(&[0; 3], &[0; 3], &[0; 3]),
+            Err(Reject::UnsupportedDegree(3))
+        );
+    }
+
+    /// Runs the trace generators of the signature verification on one instance, like
+    /// `BigClaim::gen_trace` chains them, and checks every output against this module.
+    fn assert_trace_generators_match(s1: &[u32], pk: &[u32], msg_point: &[u32]) {
+        let poly_size = pk.len();
+        let log_size = poly_size.ilog2();
+
+        // Forward NTTs of s1 and pk
+        let forward = |poly: &[u32]| {
+            let butterfly = ntt_circuit::butterfly::Claim {
+                log_size: log_size - 1,
+            };
+            let mut outputs = butterfly.gen_trace(poly, poly_size).2;
+            for stage in 1..log_size as usize {
+                let merge = ntt_circuit::Claim {
+                    log_size: log_size - 1,
+                };
+                outputs = merge.gen_trace(&outputs, stage).2;
+            }
+            outputs.concat()
+        };
+        let s1_ntt = forward(s1);
+        let pk_ntt = forward(pk);
+        assert_eq!(s1_ntt, ntt(s1));
+        assert_eq!(pk_ntt, ntt(pk));
+
+        // Pointwise multiplication
+        let (_, mul_remainders) = mul::Claim { log_size }.gen_trace(&s1_ntt, &pk_ntt);
+        let product_ntt = mul_remainders.into_iter().map(|r| r.0).collect_vec();
+        let expected_product_ntt = ntt(s1)
+            .into_iter()
+            .zip(ntt(pk))
+            .map(|(a, b)| a * b % Q)
+            .collect_vec();
+        assert_eq!(product_ntt, expected_product_ntt);
+
+        // Inverse NTT of the product
+        let mut outputs = vec![product_ntt];
+        for _ in 1..log_size {
+            let split = intt_circuit::Claim {
+                log_size: log_size - 1,
+            };
+            outputs = split.gen_trace(&outputs).2;
+        }
+        let ibutterfly = intt_circuit::ibutterfly::Claim {
+            log_size: log_size - 1,
+        };
+        let (_, _, product) = ibutterfly.gen_trace(&outputs, poly_size);
+        assert_eq!(product, poly_mul_mod(s1, pk));
+
+        // s0 = c - s1 * h
+        let (_, sub_remainders) = sub::Claim { log_size }.gen_trace(msg_point, &product);
+        let s0 = sub_remainders.into_iter().map(|r| r.0).collect_vec();
+        assert_eq!(s0, poly_sub_mod(msg_point, &poly_mul_mod(s1, pk)));
+
+        // Squared norm, split with its slack to the bound on the last row, after the
+        // cumulative sums of the previous rows
+        let Norm(norm) = verify(s1, pk, msg_point).unwrap();
+        let params = CircuitParams::new(log_size);
+        let (bound, limb_bits) = (params.signature_bound, params.sig_bound_limb_bits());
+        let claim = euclidean_norm::Claim {
+            log_size,
+            bound,
+            limb_bits,
+        };
+        let (_, remainders, low_limbs, high_limbs) = claim.gen_trace(&s0, s1, poly_size);
+        let expected_remainders = chain!(&s0, s1)
+            .map(|&x| center(x).unsigned_abs())
+            .collect_vec();
+        assert_eq!(
+            remainders.iter().map(|r| r.0).collect_vec(),
+            expected_remainders
+        );
+        let limbs = low_limbs
+            .iter()
+            .zip(&high_limbs)
+            .map(|(low, high)| (low.0 + (high.0 << limb_bits)) as u64)
+            .collect_vec();
+        assert_eq!(limbs.len(), 2 * poly_size);
+        assert_eq!(limbs[2 * poly_size - 2..], [norm, bound as u64 - norm]);
+    }
+
+    #[test]
+    fn test_trace_generators_match_falcon_1024() {
+        assert_trace_generators_match(TEST_S1, PK, MSG_POINT);
+    }
+
+    #[test]
+    fn test_trace_generators_match_falcon_512() {
+        assert_trace_generators_match(falcon_512::TEST_S1, falcon_512::PK, falcon_512::MSG_POINT);
+    }
+}
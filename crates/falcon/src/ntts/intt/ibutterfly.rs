@@ -19,7 +19,8 @@
 //! The INTT is the inverse transform that converts from evaluation form to coefficient form,
 //! which is the inverse of the NTT (Number Theoretic Transform).
 
-use num_traits::One;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
@@ -29,7 +30,12 @@ use stwo::{
         utils::bit_reverse,
     },
     prover::{
-        backend::simd::{SimdBackend, column::BaseColumn, m31::LOG_N_LANES, qm31::PackedQM31},
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
         poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
@@ -38,15 +44,18 @@ use stwo_constraint_framework::{
 };
 
 use crate::{
-    POLY_SIZE,
-    big_air::relation::{
-        IButterflyLookupElements, INTTLookupElements, LookupElements, RCLookupElements,
+    big_air::{
+        public_inputs::Indices,
+        relation::{
+            IButterflyLookupElements, INTTLookupElements, LookupElements, PRODUCT_POLY_ID,
+            RCLookupElements,
+        },
     },
     ntts::{I2, SQ1},
     zq::{Q, add::AddMod, inverses::INVERSES_MOD_Q, mul::MulMod, sub::SubMod},
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     pub log_size: u32,
@@ -70,6 +79,12 @@ impl Claim {
     /// 3. Performs final butterfly operations using SQ1 inverse
     /// 4. Produces polynomial in coefficient form
     ///
+    /// # Parameters
+    ///
+    /// - `polys`: The pairs of coefficients output by the last INTT split, one polynomial
+    ///   after the other
+    /// - `poly_size`: The number of coefficients of each output polynomial
+    ///
     /// # Returns
     ///
     /// Returns a tuple containing:
@@ -79,12 +94,13 @@ impl Claim {
     pub fn gen_trace(
         &self,
         polys: &[Vec<u32>],
+        poly_size: usize,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         Vec<Vec<M31>>,
         Vec<u32>,
     ) {
-        let mut butterflied_poly = Vec::with_capacity(POLY_SIZE);
+        let mut butterflied_poly = Vec::with_capacity(1 << (self.log_size + 1));
         let mut f_ntt_0_col = vec![];
         let mut f_ntt_1_col = vec![];
         let mut f_ntt_0_plus_f_ntt_1_quotient_col = vec![];
@@ -182,7 +198,7 @@ impl Claim {
         ];
 
         let domain = CanonicCoset::new(self.log_size).circle_domain();
-        bit_reverse(&mut butterflied_poly);
+        butterflied_poly.chunks_mut(poly_size).for_each(bit_reverse);
         (
             [
                 f_ntt_0_col,
@@ -229,6 +245,8 @@ pub struct Eval {
     pub intt_output_lookup_elements: INTTLookupElements,
     /// Lookup elements for butterfly operations
     pub ibutterfly_output_lookup_elements: IButterflyLookupElements,
+    /// The number of coefficients of each output polynomial
+    pub poly_size: usize,
 }
 
 impl FrameworkEval for Eval {
@@ -259,15 +277,28 @@ impl FrameworkEval for Eval {
 
         // Add input coefficients to INTT output lookup relation for verification
         // This ensures the input values are properly connected to the INTT computation
+        let row = eval.get_preprocessed_column(Indices::natural(self.claim.log_size).id());
+        let poly_id = E::F::from(M31::from_u32_unchecked(PRODUCT_POLY_ID));
+        let input_index = row.clone() + row;
         eval.add_to_relation(RelationEntry::new(
             &self.intt_output_lookup_elements,
             E::EF::one(),
-            &[f_ntt_0.clone()],
+            &[
+                poly_id.clone(),
+                E::F::one(),
+                input_index.clone(),
+                f_ntt_0.clone(),
+            ],
         ));
         eval.add_to_relation(RelationEntry::new(
             &self.intt_output_lookup_elements,
             E::EF::one(),
-            &[f_ntt_1.clone()],
+            &[
+                poly_id.clone(),
+                E::F::one(),
+                input_index + E::F::one(),
+                f_ntt_1.clone(),
+            ],
         ));
 
         // Step 1: Add the two coefficients with modular arithmetic
@@ -324,15 +355,31 @@ impl FrameworkEval for Eval {
 
         // Add butterfly output values to lookup relation for verification
         // These relations ensure the butterfly outputs are properly connected to the INTT computation
+        // The outputs are the coefficients bitrev(r) and bitrev(r) + poly_size / 2 of the
+        // product, once each polynomial is bit-reversed
+        let output_index = eval.get_preprocessed_column(
+            Indices::bit_reversed(self.claim.log_size, self.poly_size.ilog2()).id(),
+        );
+        let half = E::F::from(M31::from_u32_unchecked(self.poly_size as u32 / 2));
         eval.add_to_relation(RelationEntry::new(
             &self.ibutterfly_output_lookup_elements,
             -E::EF::one(),
-            &[i2_times_f_ntt_0_plus_f_ntt_1_remainder],
+            &[
+                poly_id.clone(),
+                E::F::zero(),
+                output_index.clone(),
+                i2_times_f_ntt_0_plus_f_ntt_1_remainder,
+            ],
         ));
         eval.add_to_relation(RelationEntry::new(
             &self.ibutterfly_output_lookup_elements,
             -E::EF::one(),
-            &[i2_times_inv_mod_q_sqr1_times_f_ntt_0_minus_f_ntt_1_remainder],
+            &[
+                poly_id,
+                E::F::zero(),
+                output_index + half,
+                i2_times_inv_mod_q_sqr1_times_f_ntt_0_minus_f_ntt_1_remainder,
+            ],
         ));
 
         eval.finalize_logup();
@@ -349,7 +396,7 @@ impl FrameworkEval for Eval {
 ///
 /// The interaction trace verifies that all remainders from modular operations
 /// (multiplication, addition, subtraction) are properly bounded within the field.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction between NTT and range checking
     pub claimed_sum: SecureField,
@@ -378,6 +425,7 @@ impl InteractionClaim {
     /// - `trace`: The main NTT computation trace columns
     /// - `rc_lookup_elements`: The lookup elements for range checking
     /// - `ntt_lookup_elements`: The lookup elements for NTT operations
+    /// - `poly_size`: The number of coefficients of each output polynomial
     ///
     /// # Returns
     ///
@@ -385,6 +433,7 @@ impl InteractionClaim {
     pub fn gen_interaction_trace(
         trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
         lookup_elements: &LookupElements,
+        poly_size: usize,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         InteractionClaim,
@@ -396,14 +445,22 @@ impl InteractionClaim {
         // Phase 1: Interaction trace for the initial butterfly phase
         // Check remainder values from columns 3, 5, 7 of each 8-column group
         // These columns contain the remainder values from modular arithmetic operations
-        for col in [0, 1] {
+        let rows = Indices::natural(log_size).gen_column_simd();
+        let poly_id = PackedM31::broadcast(M31::from_u32_unchecked(PRODUCT_POLY_ID));
+        for (col, offset) in [(0, PackedM31::zero()), (1, PackedM31::one())] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 // Each butterfly operation uses 8 columns, so we access the remainder columns
                 let result_packed = trace[col].data[vec_row];
 
                 // Create the denominator using the lookup elements for range checking
-                let denom: PackedQM31 = lookup_elements.intt.combine(&[result_packed]);
+                let row = rows.data[vec_row];
+                let denom: PackedQM31 = lookup_elements.intt.combine(&[
+                    poly_id,
+                    PackedM31::one(),
+                    row + row + offset,
+                    result_packed,
+                ]);
                 col_gen.write_frac(vec_row, PackedQM31::one(), denom);
             }
             col_gen.finalize_col();
@@ -422,14 +479,21 @@ impl InteractionClaim {
             col_gen.finalize_col();
         }
 
-        for col in [5, 9] {
+        let output_indices = Indices::bit_reversed(log_size, poly_size.ilog2()).gen_column_simd();
+        let half = PackedM31::broadcast(M31::from_u32_unchecked(poly_size as u32 / 2));
+        for (col, offset) in [(5, PackedM31::zero()), (9, half)] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 // Each butterfly operation uses 8 columns, so we access the remainder columns
                 let result_packed = trace[col].data[vec_row];
 
                 // Create the denominator using the lookup elements for range checking
-                let denom: PackedQM31 = lookup_elements.ibutterfly.combine(&[result_packed]);
+                let denom: PackedQM31 = lookup_elements.ibutterfly.combine(&[
+                    poly_id,
+                    PackedM31::zero(),
+                    output_indices.data[vec_row] + offset,
+                    result_packed,
+                ]);
                 col_gen.write_frac(vec_row, -PackedQM31::one(), denom);
             }
             col_gen.finalize_col();
@@ -19,41 +19,54 @@
 //! into smaller subproblems for recursive computation.
 
 use itertools::Itertools;
-use num_traits::One;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
         channel::Channel,
         fields::{
+            FieldExpOps,
             m31::M31,
             qm31::{SECURE_EXTENSION_DEGREE, SecureField},
         },
         pcs::TreeVec,
         poly::circle::CanonicCoset,
-        utils::bit_reverse_coset_to_circle_domain_order,
     },
     prover::{
-        backend::simd::{SimdBackend, column::BaseColumn, m31::LOG_N_LANES, qm31::PackedQM31},
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
         poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
 use stwo_constraint_framework::{
-    FrameworkComponent, FrameworkEval, LogupTraceGenerator, ORIGINAL_TRACE_IDX, Relation,
-    RelationEntry,
+    FrameworkComponent, FrameworkEval, LogupTraceGenerator, Relation, RelationEntry,
 };
 
 use crate::{
-    big_air::relation::{
-        INTTInputLookupElements, INTTLookupElements, InvRootsLookupElements, RCLookupElements,
+    big_air::{
+        public_inputs::Indices,
+        relation::{
+            INTTInputLookupElements, INTTLookupElements, InvRootsLookupElements, PRODUCT_POLY_ID,
+            RCLookupElements,
+        },
+    },
+    ntts::{
+        I2, ROOTS,
+        intt::split::Split,
+        layout::{ButterflyLayout, LayoutColumn},
     },
-    ntts::{I2, ROOTS, intt::split::Split},
     zq::{Q, add::AddMod, inverses::INVERSES_MOD_Q, mul::MulMod, sub::SubMod},
 };
 
 pub mod ibutterfly;
 pub mod split;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     pub log_size: u32,
@@ -63,7 +76,7 @@ impl Claim {
     /// Returns the log sizes for the traces.
     ///
     /// Returns a tree structure containing the log sizes for:
-    /// - `preprocessed_trace`: Empty (no preprocessing needed)
+    /// - `preprocessed_trace`: Empty (the layout columns are shared, see `ButterflyLayout`)
     /// - `trace`: Main INTT computation trace
     /// - `interaction_trace`: Range checking interaction trace
     pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
@@ -106,12 +119,10 @@ impl Claim {
     ) {
         let mut output_polys = vec![];
 
-        let mut trace = vec![vec![]; 16];
+        // The root indices j and the filled rows are preprocessed, see `ButterflyLayout`
+        let mut trace = vec![vec![]; 13];
         let mut remainders = vec![];
         let mut js = vec![];
-        let mut row = 0;
-        trace[0] = vec![0; 1 << self.log_size];
-        trace[1] = vec![0; 1 << self.log_size];
 
         for poly in input_polys.iter() {
             let mut f0_ntt = vec![];
@@ -129,32 +140,26 @@ impl Claim {
                 // Note: We use regular roots here but will inverse later
                 let root = INVERSES_MOD_Q[ROOTS[poly.len().ilog2() as usize - 1][j] as usize];
                 js.push(j as u32);
-                if j == 0 {
-                    trace[0][row] = 1;
-                }
-                trace[1][row] = 1;
-                row += 1;
-                trace[2].push(j as u32);
-                trace[3].push(root);
+                trace[0].push(root);
 
-                trace[4].push(*f_even);
-                trace[5].push(*f_odd);
+                trace[1].push(*f_even);
+                trace[2].push(*f_odd);
 
                 // Step 1: Add even and odd coefficients
                 // f_even[i] + f_odd[i]
                 let f_even_plus_f_odd_quotient = (*f_even + *f_odd) / Q;
                 let f_even_plus_f_odd_remainder = (*f_even + *f_odd) % Q;
 
-                trace[6].push(f_even_plus_f_odd_quotient);
-                trace[7].push(f_even_plus_f_odd_remainder);
+                trace[3].push(f_even_plus_f_odd_quotient);
+                trace[4].push(f_even_plus_f_odd_remainder);
 
                 // Step 2: Apply scaling factor I2 to the sum
                 // I2 * (f_even[i] + f_odd[i]) where I2 = inv(2) mod q
                 let i2_times_f_even_plus_f_odd_quotient = (I2 * f_even_plus_f_odd_remainder) / Q;
                 let i2_times_f_even_plus_f_odd_remainder = (I2 * f_even_plus_f_odd_remainder) % Q;
 
-                trace[8].push(i2_times_f_even_plus_f_odd_quotient);
-                trace[9].push(i2_times_f_even_plus_f_odd_remainder);
+                trace[5].push(i2_times_f_even_plus_f_odd_quotient);
+                trace[6].push(i2_times_f_even_plus_f_odd_remainder);
 
                 // Step 3: Subtract odd from even coefficients
                 // f_even[i] - f_odd[i] (with borrow handling for modular subtraction)
@@ -162,16 +167,16 @@ impl Claim {
                 let f_even_minus_f_odd_remainder =
                     (*f_even + f_even_minus_f_odd_borrow * Q - *f_odd) % Q;
 
-                trace[10].push(f_even_minus_f_odd_borrow);
-                trace[11].push(f_even_minus_f_odd_remainder);
+                trace[7].push(f_even_minus_f_odd_borrow);
+                trace[8].push(f_even_minus_f_odd_remainder);
 
                 // Step 4: Apply scaling factor I2 to the difference
                 // I2 * (f_even[i] - f_odd[i])
                 let i2_times_f_even_minus_f_odd_quotient = (I2 * f_even_minus_f_odd_remainder) / Q;
                 let i2_times_f_even_minus_f_odd_remainder = (I2 * f_even_minus_f_odd_remainder) % Q;
 
-                trace[12].push(i2_times_f_even_minus_f_odd_quotient);
-                trace[13].push(i2_times_f_even_minus_f_odd_remainder);
+                trace[9].push(i2_times_f_even_minus_f_odd_quotient);
+                trace[10].push(i2_times_f_even_minus_f_odd_remainder);
 
                 // Step 5: Multiply by inverse root of unity
                 // I2 * (f_even[i] - f_odd[i]) * inv_root[i] where inv_root[i] = 1/root[i]
@@ -180,8 +185,8 @@ impl Claim {
                 let i2_times_f_even_minus_f_odd_times_root_inv_remainder =
                     (i2_times_f_even_minus_f_odd_remainder * root) % Q;
 
-                trace[14].push(i2_times_f_even_minus_f_odd_times_root_inv_quotient);
-                trace[15].push(i2_times_f_even_minus_f_odd_times_root_inv_remainder);
+                trace[11].push(i2_times_f_even_minus_f_odd_times_root_inv_quotient);
+                trace[12].push(i2_times_f_even_minus_f_odd_times_root_inv_remainder);
 
                 // Store the results for the next recursive level
                 f0_ntt.push(i2_times_f_even_plus_f_odd_remainder);
@@ -198,11 +203,11 @@ impl Claim {
             .map(|col| col.into_iter().map(M31).collect_vec())
             .collect_vec();
 
-        remainders.extend(trace[7].clone());
-        remainders.extend(trace[9].clone());
-        remainders.extend(trace[11].clone());
-        remainders.extend(trace[13].clone());
-        remainders.extend(trace[15].clone());
+        remainders.extend(trace[4].clone());
+        remainders.extend(trace[6].clone());
+        remainders.extend(trace[8].clone());
+        remainders.extend(trace[10].clone());
+        remainders.extend(trace[12].clone());
 
         // Convert the trace values to circle evaluations for the proof system
         let domain = CanonicCoset::new(self.log_size).circle_domain();
@@ -210,8 +215,7 @@ impl Claim {
         (
             trace
                 .into_iter()
-                .map(|mut col| {
-                    bit_reverse_coset_to_circle_domain_order(&mut col);
+                .map(|col| {
                     CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
                         domain,
                         BaseColumn::from_iter(col),
@@ -241,7 +245,7 @@ pub struct Eval {
     pub input_lookup_elements: INTTInputLookupElements,
     /// Lookup elements for INTT output
     pub intt_lookup_elements: INTTLookupElements,
-    /// Stage of the INTT computation
+    /// The number of coefficients of each input polynomial
     pub poly_size: usize,
     /// Lookup elements for inverse roots of unity
     pub inv_roots_lookup_elements: InvRootsLookupElements,
@@ -264,19 +268,13 @@ impl FrameworkEval for Eval {
     /// the computation trace generated by `gen_trace`. It ensures that all
     /// modular arithmetic operations are correctly verified through range checking.
     fn evaluate<E: stwo_constraint_framework::EvalAtRow>(&self, mut eval: E) -> E {
-        // Extract the filled mask that indicates which positions contain valid data
-        let is_first_coeff = eval.next_trace_mask();
-        eval.add_constraint(is_first_coeff.clone() * (is_first_coeff.clone() - E::F::one()));
-
-        let is_filled = eval.next_trace_mask();
-        eval.add_constraint(is_filled.clone() * (is_filled.clone() - E::F::one()));
-
-        let [j_prev, j] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
-        eval.add_constraint(is_first_coeff.clone() * j.clone());
-        eval.add_constraint(
-            (is_first_coeff.clone() - E::F::one())
-                * (j.clone() - j_prev - E::F::one() - E::F::one()),
-        );
+        // The filled rows and the root index of each row are fixed by the layout, in chunks
+        // of poly_size / 2 rows
+        let [is_filled, j] = [LayoutColumn::IsFilled, LayoutColumn::RootIndex].map(|column| {
+            eval.get_preprocessed_column(
+                ButterflyLayout::new(self.claim.log_size, self.poly_size / 2, column).id(),
+            )
+        });
 
         let inv = eval.next_trace_mask();
         // Extract even and odd coefficients from the trace
@@ -289,16 +287,36 @@ impl FrameworkEval for Eval {
             &[j.clone(), inv.clone()],
         ));
         // Add input coefficients to lookup relation for verification
-        // This ensures the input values are properly connected to the INTT computation
+        // Row r splits the coefficients 2r and 2r + 1 of the polynomial p of `poly_size`
+        // coefficients into coefficient j / 2 of the polynomials 2p and 2p + 1, of indices
+        // 2r - j / 2 and 2r - j / 2 + poly_size / 2
+        let row = eval.get_preprocessed_column(Indices::natural(self.claim.log_size).id());
+        let poly_id = E::F::from(M31::from_u32_unchecked(PRODUCT_POLY_ID));
+        let input_stage = E::F::from(M31::from_u32_unchecked(self.poly_size.ilog2()));
+        let output_stage = input_stage.clone() - E::F::one();
+        let input_index = row.clone() + row;
+        let f0_index = input_index.clone() - j.clone() * E::F::from(M31(2).inverse());
+        let f1_index =
+            f0_index.clone() + E::F::from(M31::from_u32_unchecked(self.poly_size as u32 / 2));
         eval.add_to_relation(RelationEntry::new(
             &self.input_lookup_elements,
             E::EF::from(is_filled.clone()),
-            &[f_even.clone()],
+            &[
+                poly_id.clone(),
+                input_stage.clone(),
+                input_index.clone(),
+                f_even.clone(),
+            ],
         ));
         eval.add_to_relation(RelationEntry::new(
             &self.input_lookup_elements,
             E::EF::from(is_filled.clone()),
-            &[f_odd.clone()],
+            &[
+                poly_id.clone(),
+                input_stage,
+                input_index + E::F::one(),
+                f_odd.clone(),
+            ],
         ));
 
         // Step 1: Add even and odd coefficients with modular arithmetic
@@ -364,13 +382,13 @@ impl FrameworkEval for Eval {
         eval.add_to_relation(RelationEntry::new(
             &self.intt_lookup_elements,
             -E::EF::from(is_filled.clone()),
-            &[f0],
+            &[poly_id.clone(), output_stage.clone(), f0_index, f0],
         ));
 
         eval.add_to_relation(RelationEntry::new(
             &self.intt_lookup_elements,
             -E::EF::from(is_filled.clone()),
-            &[f1],
+            &[poly_id, output_stage, f1_index, f1],
         ));
 
         eval.finalize_logup();
@@ -384,7 +402,7 @@ impl FrameworkEval for Eval {
 /// with the range checking component through the lookup protocol. The interaction
 /// ensures that all modular arithmetic operations produce results within the
 /// expected range.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction between INTT and range checking
     pub claimed_sum: SecureField,
@@ -408,7 +426,11 @@ impl InteractionClaim {
     /// # Parameters
     ///
     /// - `trace`: The main INTT computation trace columns
-    /// - `lookup_elements`: The lookup elements for range checking
+    /// - `rc_lookup_elements`: The lookup elements for range checking
+    /// - `intt_input_lookup_elements`: The lookup elements of the split polynomials
+    /// - `intt_lookup_elements`: The lookup elements of the INTT output
+    /// - `inv_roots_lookup_elements`: The lookup elements for inverse roots of unity
+    /// - `poly_size`: The number of coefficients of each input polynomial
     ///
     /// # Returns
     ///
@@ -419,35 +441,48 @@ impl InteractionClaim {
         intt_input_lookup_elements: &INTTInputLookupElements,
         intt_lookup_elements: &INTTLookupElements,
         inv_roots_lookup_elements: &InvRootsLookupElements,
+        poly_size: usize,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         InteractionClaim,
     ) {
         let log_size = trace[0].domain.log_size();
-        let is_filled = trace[1].clone();
+        let [is_filled, js] = [LayoutColumn::IsFilled, LayoutColumn::RootIndex]
+            .map(|column| ButterflyLayout::new(log_size, poly_size / 2, column).gen_column_simd());
         let mut logup_gen = LogupTraceGenerator::new(log_size);
 
         let mut col_gen = logup_gen.new_col();
         for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-            let j = trace[2].data[vec_row]; // must have all lanes populated
-            let inv_root = trace[3].data[vec_row]; // must have all lanes populated
+            let j = js.data[vec_row];
+            let inv_root = trace[0].data[vec_row]; // must have all lanes populated
             let denom: PackedQM31 = inv_roots_lookup_elements.combine(&[j, inv_root]);
             col_gen.write_frac(vec_row, PackedQM31::from(is_filled.data[vec_row]), denom);
         }
         col_gen.finalize_col();
 
         // input linking
-        for col_offset in [4, 5] {
+        // The inputs and outputs are keyed by polynomial, stage and index
+        let rows = Indices::natural(log_size).gen_column_simd();
+        let poly_id = PackedM31::broadcast(M31::from_u32_unchecked(PRODUCT_POLY_ID));
+        let input_stage = PackedM31::broadcast(M31::from_u32_unchecked(poly_size.ilog2()));
+        let output_stage = input_stage - PackedM31::one();
+        for (col_offset, offset) in [(1, PackedM31::zero()), (2, PackedM31::one())] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 let v = trace[col_offset].data[vec_row]; // must have all lanes populated
-                let denom: PackedQM31 = intt_input_lookup_elements.combine(&[v]);
+                let row = rows.data[vec_row];
+                let denom: PackedQM31 = intt_input_lookup_elements.combine(&[
+                    poly_id,
+                    input_stage,
+                    row + row + offset,
+                    v,
+                ]);
                 col_gen.write_frac(vec_row, PackedQM31::from(is_filled.data[vec_row]), denom);
             }
             col_gen.finalize_col();
         }
         // range check
-        for col_offset in [7, 9, 11, 13, 15] {
+        for col_offset in [4, 6, 8, 10, 12] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 let v = trace[col_offset].data[vec_row]; // must have all lanes populated
@@ -459,11 +494,16 @@ impl InteractionClaim {
 
         // output linking
 
-        for col_offset in [9, 15] {
+        let inv2 = PackedM31::broadcast(M31(2).inverse());
+        let half = PackedM31::broadcast(M31::from_u32_unchecked(poly_size as u32 / 2));
+        for (col_offset, offset) in [(6, PackedM31::zero()), (12, half)] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 let v = trace[col_offset].data[vec_row]; // must have all lanes populated
-                let denom: PackedQM31 = intt_lookup_elements.combine(&[v]);
+                let row = rows.data[vec_row];
+                let index = row + row - js.data[vec_row] * inv2 + offset;
+                let denom: PackedQM31 =
+                    intt_lookup_elements.combine(&[poly_id, output_stage, index, v]);
                 col_gen.write_frac(vec_row, -PackedQM31::from(is_filled.data[vec_row]), denom);
             }
             col_gen.finalize_col();
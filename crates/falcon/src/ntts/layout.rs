@@ -0,0 +1,110 @@
+//! # Butterfly Layout
+//!
+//! The NTT merges and the INTT splits lay their butterflies out in chunks of rows: a merge
+//! of two polynomials of `h` coefficients takes `h` rows, a split of a polynomial of `2h`
+//! coefficients takes `h` rows, and row `r` of a chunk uses the root of unity of index `2r`.
+//!
+//! This layout only depends on the size of the trace and of the chunks, so it is fixed by
+//! preprocessed columns rather than by the witness: the prover cannot choose which rows
+//! count toward the lookups nor where the root index sequences restart.
+
+use stwo::{
+    core::{
+        fields::m31::{BaseField, M31},
+        poly::circle::CanonicCoset,
+    },
+    prover::{
+        backend::simd::{SimdBackend, column::BaseColumn},
+        poly::{BitReversedOrder, circle::CircleEvaluation},
+    },
+};
+use stwo_constraint_framework::preprocessed_columns::PreProcessedColumnId;
+
+/// The preprocessed columns describing the butterfly of each row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutColumn {
+    /// 1 on every row of a complete chunk
+    IsFilled,
+    /// `2r` on row `r` of a chunk, the index of the root of unity of the butterfly
+    RootIndex,
+}
+
+/// Preprocessed column of the butterfly layout of a trace of `2^log_size` rows, in chunks
+/// of `chunk_size` rows.
+#[derive(Debug, Clone)]
+pub struct ButterflyLayout {
+    pub log_size: u32,
+    pub chunk_size: usize,
+    pub column: LayoutColumn,
+}
+
+impl ButterflyLayout {
+    pub fn new(log_size: u32, chunk_size: usize, column: LayoutColumn) -> Self {
+        Self {
+            log_size,
+            chunk_size,
+            column,
+        }
+    }
+
+    /// Returns all the layout columns of a trace of `2^log_size` rows.
+    pub fn all(log_size: u32, chunk_size: usize) -> Vec<Self> {
+        [LayoutColumn::IsFilled, LayoutColumn::RootIndex]
+            .into_iter()
+            .map(|column| Self::new(log_size, chunk_size, column))
+            .collect()
+    }
+
+    /// Returns the value of the column on `row`.
+    pub fn value(&self, row: usize) -> u32 {
+        let n_rows = 1 << self.log_size;
+        let n_chunk_rows = n_rows / self.chunk_size * self.chunk_size;
+        match self.column {
+            LayoutColumn::IsFilled => (row < n_chunk_rows) as u32,
+            LayoutColumn::RootIndex => 2 * (row % self.chunk_size) as u32,
+        }
+    }
+
+    /// Generates the preprocessed column.
+    ///
+    /// The merge and split traces are never read at an offset, so the column is in natural
+    /// order.
+    pub fn gen_column_simd(&self) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+        CircleEvaluation::new(
+            CanonicCoset::new(self.log_size).circle_domain(),
+            BaseColumn::from_iter((0..1 << self.log_size).map(|row| M31(self.value(row)))),
+        )
+    }
+
+    /// Returns the unique identifier for this preprocessed column.
+    pub fn id(&self) -> PreProcessedColumnId {
+        let name = match self.column {
+            LayoutColumn::IsFilled => "is_filled",
+            LayoutColumn::RootIndex => "root_index",
+        };
+        PreProcessedColumnId {
+            id: format!(
+                "butterfly_layout_{}_{}_{}",
+                name, self.log_size, self.chunk_size
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_index_restarts_every_chunk() {
+        let layout = ButterflyLayout::new(4, 4, LayoutColumn::RootIndex);
+        let values = (0..16).map(|row| layout.value(row)).collect::<Vec<_>>();
+        assert_eq!(values, [0, 2, 4, 6].repeat(4));
+    }
+
+    #[test]
+    fn test_is_filled_covers_complete_chunks() {
+        let layout = ButterflyLayout::new(4, 16, LayoutColumn::IsFilled);
+        assert!((0..16).all(|row| layout.value(row) == 1));
+    }
+}
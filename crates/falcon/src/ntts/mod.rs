@@ -24,13 +24,17 @@
 //!
 //! ## Modules
 //!
-//! - [`ntt`]: Forward NTT implementation with butterfly and merge phases
-//! - [`intt`]: Inverse NTT implementation with split and butterfly phases  
-//! - [`roots`]: Precomputed roots of unity for all polynomial sizes
+//! - [`ntt`](mod@ntt): Forward NTT implementation with butterfly and merge phases
+//! - [`intt`](mod@intt): Inverse NTT implementation with split and butterfly phases  
+//! - [`layout`]: Preprocessed row layout of the merges and splits
+//! - [`roots`]: Roots of unity for all polynomial sizes
+//!
+//! [`ntt()`] and [`intt()`] compute the same transforms natively, e.g. to give the public
+//! key in NTT form. They are re-exported from [`native`](crate::native).
 //!
 //! ## Key Constants
 //!
-//! - `ROOTS`: Precomputed roots of unity for sizes 2, 4, 8, ..., 1024
+//! - `ROOTS`: Roots of unity for sizes 2, 4, 8, ..., 1024, generated at compile time
 //! - `SQ1`: Square root of 1 in the field Z_q
 //! - `I2`: Modular inverse of 2 (6145) used as scaling factor for INTT
 //!
@@ -38,7 +42,7 @@
 //!
 //! - Field size: q = 12289 (12 * 1024 + 1)
 //! - Polynomial sizes: 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024
-//! - Primitive root: g = 7 (generator of Z_q*)
+//! - Generator: g = 7, a primitive 2048-th root of unity
 //!
 //! # Usage
 //!
@@ -48,15 +52,18 @@
 
 use crate::{ntts::roots::*, zq::inverses::INVERSES_MOD_Q};
 
+pub use crate::native::{intt, ntt};
+
 pub mod intt;
+pub mod layout;
 pub mod ntt;
 pub mod roots;
 
-/// Precomputed roots of unity for all polynomial sizes used in NTT operations.
+/// Roots of unity for all polynomial sizes used in NTT operations.
 ///
 /// This array contains roots of unity for polynomial sizes 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024.
 /// Each subarray contains the roots needed for one level of the NTT computation.
-/// The roots are computed as powers of the primitive root g = 7 in the field Z_q.
+/// The roots are computed as powers of [`GENERATOR`] in the field Z_q.
 pub const ROOTS: [&[u32]; 10] = [
     ROOTS_2, ROOTS_4, ROOTS_8, ROOTS_16, ROOTS_32, ROOTS_64, ROOTS_128, ROOTS_256, ROOTS_512,
     ROOTS_1024,
@@ -70,3 +77,103 @@ pub const SQ1: u32 = ROOTS_2[0];
 /// This constant (value: 6145) is used as a scaling factor in the inverse NTT (INTT)
 /// to normalize the polynomial coefficients after the transform.
 pub const I2: u32 = INVERSES_MOD_Q[2];
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        big_air::FalconInstance,
+        debug::assert_transform_constraints,
+        input::{MESSAGE, PK, SIGNATURE, falcon_512},
+        native,
+        zq::{Q, pow_mod},
+    };
+
+    #[test]
+    fn test_intt_inverts_ntt() {
+        assert_eq!(intt(&ntt(PK)), PK);
+        let one = [1, 0, 0, 0];
+        assert_eq!(ntt(&one), vec![1; 4]);
+    }
+
+    #[test]
+    fn test_ntt_known_answers() {
+        assert_eq!(SQ1, 1479);
+        assert_eq!(SQ1 * SQ1 % Q, Q - 1);
+        assert_eq!(I2, 6145);
+        assert_eq!(2 * I2 % Q, 1);
+        assert_eq!(ntt(&[3, 5]), [(3 + 5 * SQ1) % Q, (3 + Q - 5 * SQ1 % Q) % Q]);
+
+        // The NTT of x holds the points the polynomials are evaluated at: the n distinct
+        // n-th roots of -1, which catches a wrong entry in the root tables
+        for log_size in 1..=ROOTS.len() {
+            let n = 1 << log_size;
+            let mut x = vec![0; n];
+            x[1] = 1;
+            let points = ntt(&x);
+            assert!(
+                points
+                    .iter()
+                    .all(|&point| pow_mod(point, n as u32, Q) == Q - 1)
+            );
+            assert_eq!(points.iter().unique().count(), n);
+        }
+    }
+
+    /// Pairs of random polynomials of the degree of a supported circuit, with the test
+    /// signature verification of that degree.
+    fn circuit_poly_pairs() -> impl Strategy<Value = (FalconInstance<'static>, Vec<u32>, Vec<u32>)>
+    {
+        prop_oneof![
+            Just(FalconInstance::new(SIGNATURE, PK, MESSAGE)),
+            Just(FalconInstance::new(
+                falcon_512::SIGNATURE,
+                falcon_512::PK,
+                falcon_512::MESSAGE
+            )),
+        ]
+        .prop_flat_map(|instance| {
+            let poly = || prop::collection::vec(0..Q, instance.pk.len());
+            (Just(instance), poly(), poly())
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(4))]
+
+        #[test]
+        fn prop_circuit_intt_inverts_ntt((instance, a, _) in circuit_poly_pairs()) {
+            let mut one = vec![0; a.len()];
+            one[0] = 1;
+            prop_assert_eq!(assert_transform_constraints(instance, &a, &one), a);
+        }
+
+        #[test]
+        fn prop_circuit_product_matches_native((instance, a, b) in circuit_poly_pairs()) {
+            prop_assert_eq!(
+                assert_transform_constraints(instance, &a, &b),
+                native::poly_mul_mod(&a, &b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_ntt_matches_the_circuit() {
+        let poly_size = PK.len();
+        let log_size = poly_size.ilog2();
+        let butterfly = ntt::butterfly::Claim {
+            log_size: log_size - 1,
+        };
+        let (_, _, mut outputs) = butterfly.gen_trace(PK, poly_size);
+        for stage in 1..log_size as usize {
+            let merge = ntt::Claim {
+                log_size: log_size - 1,
+            };
+            outputs = merge.gen_trace(&outputs, stage).2;
+        }
+        assert_eq!(outputs.concat(), ntt(PK));
+    }
+}
@@ -18,7 +18,8 @@
 //! This is the first phase of the NTT that converts from coefficient form to
 //! intermediate form ready for recursive merging.
 
-use num_traits::One;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
@@ -28,7 +29,12 @@ use stwo::{
         utils::bit_reverse,
     },
     prover::{
-        backend::simd::{SimdBackend, column::BaseColumn, m31::LOG_N_LANES, qm31::PackedQM31},
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
         poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
@@ -37,13 +43,17 @@ use stwo_constraint_framework::{
 };
 
 use crate::{
-    POLY_SIZE,
-    big_air::relation::{ButterflyLookupElements, RCLookupElements},
+    big_air::{
+        public_inputs::{Indices, PK_ID},
+        relation::{
+            ButterflyLookupElements, PublicInputLookupElements, RCLookupElements, S1LookupElements,
+        },
+    },
     ntts::SQ1,
     zq::{Q, add::AddMod, mul::MulMod, sub::SubMod},
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     pub log_size: u32,
@@ -67,6 +77,11 @@ impl Claim {
     /// 3. Performs butterfly operations using SQ1 (square root of 1)
     /// 4. Produces intermediate results ready for recursive merging
     ///
+    /// # Parameters
+    ///
+    /// - `poly`: The input polynomials of `poly_size` coefficients, one after the other
+    /// - `poly_size`: The number of coefficients of each polynomial
+    ///
     /// # Returns
     ///
     /// Returns a tuple containing:
@@ -75,19 +90,22 @@ impl Claim {
     #[allow(clippy::type_complexity)]
     pub fn gen_trace(
         &self,
-        poly: &[u32; POLY_SIZE],
+        poly: &[u32],
+        poly_size: usize,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         Vec<Vec<M31>>,
         Vec<Vec<u32>>,
     ) {
-        // Initialize the input polynomial with values [1, 2, ..., POLY_SIZE]
+        // Copy the input polynomials, whose total size matches the claim (2 coefficients per row)
+        assert_eq!(poly.len(), 1 << (self.log_size + 1));
+        assert_eq!(poly.len() % poly_size, 0);
         let mut poly = poly.to_vec();
 
         // Apply bit-reversal permutation to prepare for in-place NTT computation
-        // This ensures the polynomial is in the correct order for the butterfly operations
-        bit_reverse(&mut poly);
-        let mut butterflied_poly = Vec::with_capacity(POLY_SIZE / 2);
+        // This ensures each polynomial is in the correct order for the butterfly operations
+        poly.chunks_mut(poly_size).for_each(bit_reverse);
+        let mut butterflied_poly = Vec::with_capacity(poly.len() / 2);
         let mut f0_col = vec![];
         let mut f1_col = vec![];
         let mut f1_times_sq1_quotient_col = vec![];
@@ -200,10 +218,18 @@ impl Claim {
 pub struct Eval {
     /// The claim parameters defining the NTT computation
     pub claim: Claim,
+    /// The number of coefficients of each input polynomial
+    pub poly_size: usize,
     /// Lookup elements for range checking modular arithmetic operations
     pub rc_lookup_elements: RCLookupElements,
+    /// The identifier of the transformed polynomial in the output lookups
+    pub poly_id: u32,
     /// Lookup elements for NTT operations
     pub butterfly_output_lookup_elements: ButterflyLookupElements,
+    /// Lookup elements binding the input to the public key, if the input is public
+    pub public_input_lookup_elements: Option<PublicInputLookupElements>,
+    /// Lookup elements binding the input to the decompressed signature, if the input is s1
+    pub s1_lookup_elements: Option<S1LookupElements>,
 }
 
 impl FrameworkEval for Eval {
@@ -253,6 +279,8 @@ impl FrameworkEval for Eval {
 
         // Step 1: Evaluate multiplication f1 * SQ1 with modular arithmetic decomposition
         // This computes f1 * SQ1 = quotient * Q + remainder and verifies the decomposition
+        let f0_input = f0.clone();
+        let f1_input = f1.clone();
         MulMod::new(
             f1,
             sq1.clone(),
@@ -282,18 +310,72 @@ impl FrameworkEval for Eval {
         .evaluate(&self.rc_lookup_elements, &mut eval);
 
         // Add butterfly output values to lookup relation for verification
-        // These relations ensure the butterfly outputs are properly connected to the NTT computation
+        // Row r outputs the polynomial r of 2 coefficients, of indices 2r and 2r + 1 after one
+        // NTT layer, so that the merges cannot read them at other positions
+        let row = eval.get_preprocessed_column(Indices::natural(self.claim.log_size).id());
+        let poly_id = E::F::from(M31::from_u32_unchecked(self.poly_id));
+        let stage = E::F::one();
+        let output_index = row.clone() + row;
         eval.add_to_relation(RelationEntry::new(
             &self.butterfly_output_lookup_elements,
             -E::EF::one(),
-            &[f0_plus_f1_times_sq1_remainder],
+            &[
+                poly_id.clone(),
+                stage.clone(),
+                output_index.clone(),
+                f0_plus_f1_times_sq1_remainder,
+            ],
         ));
         eval.add_to_relation(RelationEntry::new(
             &self.butterfly_output_lookup_elements,
             -E::EF::one(),
-            &[f0_minus_f1_times_sq1_remainder],
+            &[
+                poly_id,
+                stage,
+                output_index + E::F::one(),
+                f0_minus_f1_times_sq1_remainder,
+            ],
         ));
 
+        // Bind the input coefficients to the public key
+        // After the bit-reversal permutation, row r of a polynomial holds the coefficients
+        // of index bitrev(r) and bitrev(r) + poly_size / 2
+        if let Some(public_input_lookup_elements) = &self.public_input_lookup_elements {
+            let index = eval.get_preprocessed_column(
+                Indices::bit_reversed(self.claim.log_size, self.poly_size.ilog2()).id(),
+            );
+            let half = E::F::from(M31::from_u32_unchecked(self.poly_size as u32 / 2));
+            let pk_id = E::F::from(M31::from_u32_unchecked(PK_ID));
+            eval.add_to_relation(RelationEntry::new(
+                public_input_lookup_elements,
+                -E::EF::one(),
+                &[pk_id.clone(), index.clone(), f0_input.clone()],
+            ));
+            eval.add_to_relation(RelationEntry::new(
+                public_input_lookup_elements,
+                -E::EF::one(),
+                &[pk_id, index + half, f1_input.clone()],
+            ));
+        }
+
+        // Bind the input coefficients to the ones decompressed from the signature
+        if let Some(s1_lookup_elements) = &self.s1_lookup_elements {
+            let index = eval.get_preprocessed_column(
+                Indices::bit_reversed(self.claim.log_size, self.poly_size.ilog2()).id(),
+            );
+            let half = E::F::from(M31::from_u32_unchecked(self.poly_size as u32 / 2));
+            eval.add_to_relation(RelationEntry::new(
+                s1_lookup_elements,
+                E::EF::one(),
+                &[index.clone(), f0_input],
+            ));
+            eval.add_to_relation(RelationEntry::new(
+                s1_lookup_elements,
+                E::EF::one(),
+                &[index + half, f1_input],
+            ));
+        }
+
         eval.finalize_logup();
         eval
     }
@@ -308,7 +390,7 @@ impl FrameworkEval for Eval {
 ///
 /// The interaction trace verifies that all remainders from modular operations
 /// (multiplication, addition, subtraction) are properly bounded within the field.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction between NTT and range checking
     pub claimed_sum: SecureField,
@@ -337,6 +419,12 @@ impl InteractionClaim {
     /// - `trace`: The main NTT computation trace columns
     /// - `rc_lookup_elements`: The lookup elements for range checking
     /// - `ntt_lookup_elements`: The lookup elements for NTT operations
+    /// - `poly_id`: The identifier of the transformed polynomial in the output lookups
+    /// - `public_input_lookup_elements`: The lookup elements binding the input to the public
+    ///   key, if the input is public
+    /// - `s1_lookup_elements`: The lookup elements binding the input to the decompressed
+    ///   signature, if the input is s1
+    /// - `poly_size`: The number of coefficients of each input polynomial
     ///
     /// # Returns
     ///
@@ -345,6 +433,10 @@ impl InteractionClaim {
         trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
         rc_lookup_elements: &RCLookupElements,
         butterfly_output_lookup_elements: &ButterflyLookupElements,
+        poly_id: u32,
+        public_input_lookup_elements: Option<&PublicInputLookupElements>,
+        s1_lookup_elements: Option<&S1LookupElements>,
+        poly_size: usize,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         InteractionClaim,
@@ -370,18 +462,62 @@ impl InteractionClaim {
             col_gen.finalize_col();
         }
 
-        for col in [5, 7] {
+        // Butterfly outputs, keyed by polynomial, stage and index (2 * row and 2 * row + 1)
+        let rows = Indices::natural(log_size).gen_column_simd();
+        let poly_id = PackedM31::broadcast(M31::from_u32_unchecked(poly_id));
+        let stage = PackedM31::one();
+        for (col, offset) in [(5, PackedM31::zero()), (7, PackedM31::one())] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 // Each butterfly operation uses 8 columns, so we access the remainder columns
                 let result_packed = trace[col].data[vec_row];
+                let row = rows.data[vec_row];
 
                 // Create the denominator using the lookup elements for range checking
-                let denom: PackedQM31 = butterfly_output_lookup_elements.combine(&[result_packed]);
+                let denom: PackedQM31 = butterfly_output_lookup_elements.combine(&[
+                    poly_id,
+                    stage,
+                    row + row + offset,
+                    result_packed,
+                ]);
                 col_gen.write_frac(vec_row, -PackedQM31::one(), denom);
             }
             col_gen.finalize_col();
         }
+
+        // Public key lookups for the input coefficients (columns 0 and 1)
+        if let Some(public_input_lookup_elements) = public_input_lookup_elements {
+            let index = Indices::bit_reversed(log_size, poly_size.ilog2()).gen_column_simd();
+            let pk_id = PackedM31::broadcast(M31::from_u32_unchecked(PK_ID));
+            let half = PackedM31::broadcast(M31::from_u32_unchecked(poly_size as u32 / 2));
+            for (col, offset) in [(0, PackedM31::zero()), (1, half)] {
+                let mut col_gen = logup_gen.new_col();
+                for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+                    let denom: PackedQM31 = public_input_lookup_elements.combine(&[
+                        pk_id,
+                        index.data[vec_row] + offset,
+                        trace[col].data[vec_row],
+                    ]);
+                    col_gen.write_frac(vec_row, -PackedQM31::one(), denom);
+                }
+                col_gen.finalize_col();
+            }
+        }
+
+        // Decompressed signature lookups for the input coefficients (columns 0 and 1)
+        if let Some(s1_lookup_elements) = s1_lookup_elements {
+            let index = Indices::bit_reversed(log_size, poly_size.ilog2()).gen_column_simd();
+            let half = PackedM31::broadcast(M31::from_u32_unchecked(poly_size as u32 / 2));
+            for (col, offset) in [(0, PackedM31::zero()), (1, half)] {
+                let mut col_gen = logup_gen.new_col();
+                for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+                    let denom: PackedQM31 = s1_lookup_elements
+                        .combine(&[index.data[vec_row] + offset, trace[col].data[vec_row]]);
+                    col_gen.write_frac(vec_row, PackedQM31::one(), denom);
+                }
+                col_gen.finalize_col();
+            }
+        }
         let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
 
         (interaction_trace, InteractionClaim { claimed_sum })
@@ -395,6 +531,7 @@ impl InteractionClaim {
 ///
 /// The NTT component performs polynomial evaluation from coefficient form
 /// to evaluation form using modular arithmetic operations with range checking.
-/// It supports polynomial sizes up to 1024 coefficients and uses the field Z_q
+/// It supports polynomial sizes up to 1024 coefficients, possibly several polynomials
+/// stacked in the same trace, and uses the field Z_q
 /// where q = 12289.
 pub type Component = FrameworkComponent<Eval>;
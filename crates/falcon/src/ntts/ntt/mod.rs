@@ -20,41 +20,51 @@
 
 use itertools::Itertools;
 
-use num_traits::One;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
         channel::Channel,
         fields::{
+            FieldExpOps,
             m31::M31,
             qm31::{SECURE_EXTENSION_DEGREE, SecureField},
         },
         pcs::TreeVec,
         poly::circle::CanonicCoset,
-        utils::bit_reverse_coset_to_circle_domain_order,
     },
     prover::{
-        backend::simd::{SimdBackend, column::BaseColumn, m31::LOG_N_LANES, qm31::PackedQM31},
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
         poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
 use stwo_constraint_framework::{
-    FrameworkComponent, FrameworkEval, LogupTraceGenerator, ORIGINAL_TRACE_IDX, Relation,
-    RelationEntry,
+    FrameworkComponent, FrameworkEval, LogupTraceGenerator, Relation, RelationEntry,
 };
 
 use crate::{
-    big_air::relation::{
-        InputLookupElements, NTTLookupElements, RCLookupElements, RootsLookupElements,
+    big_air::{
+        public_inputs::Indices,
+        relation::{InputLookupElements, NTTLookupElements, RCLookupElements, RootsLookupElements},
+    },
+    ntts::{
+        ROOTS,
+        layout::{ButterflyLayout, LayoutColumn},
+        ntt::merge::Merge,
     },
-    ntts::{ROOTS, ntt::merge::Merge},
     zq::{Q, add::AddMod, mul::MulMod, sub::SubMod},
 };
 
 pub mod butterfly;
 pub mod merge;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     pub log_size: u32,
@@ -64,7 +74,7 @@ impl Claim {
     /// Returns the log sizes for the traces.
     ///
     /// Returns a tree structure containing the log sizes for:
-    /// - `preprocessed_trace`: Empty (no preprocessing needed)
+    /// - `preprocessed_trace`: Empty (the layout columns are shared, see `ButterflyLayout`)
     /// - `trace`: Main NTT computation trace
     /// - `interaction_trace`: Range checking interaction trace
     pub fn log_sizes(&self) -> TreeVec<Vec<u32>> {
@@ -111,7 +121,7 @@ impl Claim {
         // Prepare data structures for the merging phase
 
         let mut output_polys = Vec::with_capacity(input_polys.len() / 2);
-        let mut trace = vec![vec![]; 12];
+        let mut trace = vec![vec![]; 9];
         let mut remainders = vec![];
         let mut js = vec![];
         // Phase 2: Recursive Merging Operations
@@ -127,10 +137,8 @@ impl Claim {
         //
         // The roots of unity (w[2 * i]) are precomputed and stored in ROOTS[i][2 * j]
         // Each level doubles the polynomial size until we reach the final evaluation form
-        trace[0] = vec![0; 1 << self.log_size];
-        trace[1] = vec![0; 1 << self.log_size];
-        trace[0][0] = 1;
-        let mut row = 0;
+        //
+        // The root indices j and the filled rows are preprocessed, see `ButterflyLayout`
         for coeffs in input_polys.chunks_exact(2) {
             let left = &coeffs[0]; // f0_ntt polynomial
             let right = &coeffs[1]; // f1_ntt polynomial
@@ -143,32 +151,26 @@ impl Claim {
                 let j = 2 * j;
                 let root = ROOTS[stage][j];
                 js.push(j as u32);
-                trace[1][row] = 1;
-                if j == 0 {
-                    trace[0][row] = 1;
-                }
-                row += 1;
-                trace[2].push(j as u32);
-                trace[3].push(root);
+                trace[0].push(root);
 
-                trace[4].push(*coeff_left);
-                trace[5].push(*coeff_right);
+                trace[1].push(*coeff_left);
+                trace[2].push(*coeff_right);
 
                 // Step 1: Multiply f1_ntt coefficient by root of unity
                 // f1_ntt[i] * w[2 * i] = quotient * Q + remainder
                 let root_times_f1_quotient = (*coeff_right * root) / Q;
                 let root_times_f1_remainder = (*coeff_right * root) % Q;
 
-                trace[6].push(root_times_f1_quotient);
-                trace[7].push(root_times_f1_remainder);
+                trace[3].push(root_times_f1_quotient);
+                trace[4].push(root_times_f1_remainder);
 
                 // Step 2: Add f0_ntt coefficient to the multiplied result
                 // f0_ntt[i] + f1_ntt[i] * w[2 * i] = quotient * Q + remainder
                 let f0_plus_root_times_f1_quotient = (*coeff_left + root_times_f1_remainder) / Q;
                 let f0_plus_root_times_f1_remainder = (*coeff_left + root_times_f1_remainder) % Q;
 
-                trace[8].push(f0_plus_root_times_f1_quotient);
-                trace[9].push(f0_plus_root_times_f1_remainder);
+                trace[5].push(f0_plus_root_times_f1_quotient);
+                trace[6].push(f0_plus_root_times_f1_remainder);
 
                 // Step 3: Subtract the multiplied result from f0_ntt coefficient
                 // f0_ntt[i] - f1_ntt[i] * w[2 * i] = quotient * Q + remainder (with borrow handling)
@@ -176,8 +178,8 @@ impl Claim {
                 let f0_minus_root_times_f1_remainder =
                     (*coeff_left + f0_minus_root_times_f1_borrow * Q - root_times_f1_remainder) % Q;
 
-                trace[10].push(f0_minus_root_times_f1_borrow);
-                trace[11].push(f0_minus_root_times_f1_remainder);
+                trace[7].push(f0_minus_root_times_f1_borrow);
+                trace[8].push(f0_minus_root_times_f1_remainder);
 
                 // Store the results for the next recursive level
                 merged_poly.push(f0_plus_root_times_f1_remainder);
@@ -192,9 +194,9 @@ impl Claim {
             .map(|col| col.into_iter().map(M31).collect_vec())
             .collect_vec();
 
-        remainders.extend(trace[7].clone());
-        remainders.extend(trace[9].clone());
-        remainders.extend(trace[11].clone());
+        remainders.extend(trace[4].clone());
+        remainders.extend(trace[6].clone());
+        remainders.extend(trace[8].clone());
 
         // Convert the trace values to circle evaluations for the proof system
         let domain = CanonicCoset::new(self.log_size).circle_domain();
@@ -202,8 +204,7 @@ impl Claim {
         (
             trace
                 .into_iter()
-                .map(|mut col| {
-                    bit_reverse_coset_to_circle_domain_order(&mut col);
+                .map(|col| {
                     CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
                         domain,
                         BaseColumn::from_iter(col),
@@ -230,6 +231,7 @@ impl Claim {
 pub struct Eval {
     /// The claim parameters defining the NTT computation
     pub claim: Claim,
+    /// The number of coefficients of each input polynomial
     pub poly_size: usize,
     /// Lookup elements for range checking modular arithmetic operations
     pub rc_lookup_elements: RCLookupElements,
@@ -263,18 +265,12 @@ impl FrameworkEval for Eval {
     /// 2. Evaluates recursive merging operations using roots of unity
     /// 3. Verifies all modular arithmetic operations through range checking
     fn evaluate<E: stwo_constraint_framework::EvalAtRow>(&self, mut eval: E) -> E {
-        // Extract the filled mask that indicates which positions contain valid data
-        let is_first_coeff = eval.next_trace_mask();
-        eval.add_constraint(is_first_coeff.clone() * (is_first_coeff.clone() - E::F::one()));
-
-        let is_filled = eval.next_trace_mask();
-        eval.add_constraint(is_filled.clone() * (is_filled.clone() - E::F::one()));
-
-        let [j_prev, j] = eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
-        eval.add_constraint(is_first_coeff.clone() * j.clone());
-        eval.add_constraint(
-            (is_first_coeff - E::F::one()) * (j.clone() - j_prev - E::F::one() - E::F::one()),
-        );
+        // The filled rows and the root index of each row are fixed by the layout
+        let [is_filled, j] = [LayoutColumn::IsFilled, LayoutColumn::RootIndex].map(|column| {
+            eval.get_preprocessed_column(
+                ButterflyLayout::new(self.claim.log_size, self.poly_size, column).id(),
+            )
+        });
 
         let root = eval.next_trace_mask();
         // Extract the left coefficient from the trace
@@ -288,17 +284,37 @@ impl FrameworkEval for Eval {
         ));
 
         // Add input coefficients to lookup relation for verification
-        // This ensures the input values are properly connected to the NTT computation
+        // Row r merges coefficient j / 2 of the polynomials 2p and 2p + 1 of `poly_size`
+        // coefficients, of indices 2r - j / 2 and 2r - j / 2 + poly_size, into the coefficients
+        // 2r and 2r + 1 of the merged polynomial p
+        let row = eval.get_preprocessed_column(Indices::natural(self.claim.log_size).id());
+        let poly_id = E::F::from(M31::from_u32_unchecked(self.ntt_lookup_elements.poly_id()));
+        let input_stage = E::F::from(M31::from_u32_unchecked(self.poly_size.ilog2()));
+        let output_stage = input_stage.clone() + E::F::one();
+        let output_index = row.clone() + row;
+        let left_index = output_index.clone() - j.clone() * E::F::from(M31(2).inverse());
+        let right_index =
+            left_index.clone() + E::F::from(M31::from_u32_unchecked(self.poly_size as u32));
         eval.add_to_relation(RelationEntry::new(
             &self.input_lookup_elements,
             E::EF::from(is_filled.clone()),
-            &[coeff_left.clone()],
+            &[
+                poly_id.clone(),
+                input_stage.clone(),
+                left_index,
+                coeff_left.clone(),
+            ],
         ));
 
         eval.add_to_relation(RelationEntry::new(
             &self.input_lookup_elements,
             E::EF::from(is_filled.clone()),
-            &[coeff_right.clone()],
+            &[
+                poly_id.clone(),
+                input_stage,
+                right_index,
+                coeff_right.clone(),
+            ],
         ));
 
         // Step 1: Multiply f1_ntt coefficient by root of unity with modular arithmetic
@@ -341,12 +357,22 @@ impl FrameworkEval for Eval {
         eval.add_to_relation(RelationEntry::new(
             &self.ntt_lookup_elements,
             -E::EF::from(is_filled.clone()),
-            &[merged_poly[0].clone()],
+            &[
+                poly_id.clone(),
+                output_stage.clone(),
+                output_index.clone(),
+                merged_poly[0].clone(),
+            ],
         ));
         eval.add_to_relation(RelationEntry::new(
             &self.ntt_lookup_elements,
             -E::EF::from(is_filled.clone()),
-            &[merged_poly[1].clone()],
+            &[
+                poly_id,
+                output_stage,
+                output_index + E::F::one(),
+                merged_poly[1].clone(),
+            ],
         ));
 
         eval.finalize_logup();
@@ -363,7 +389,7 @@ impl FrameworkEval for Eval {
 ///
 /// The interaction trace verifies that all remainders from modular operations
 /// (multiplication, addition, subtraction) are properly bounded within the field.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction between NTT and range checking
     pub claimed_sum: SecureField,
@@ -392,6 +418,9 @@ impl InteractionClaim {
     /// - `trace`: The main NTT computation trace columns
     /// - `rc_lookup_elements`: The lookup elements for range checking
     /// - `ntt_lookup_elements`: The lookup elements for NTT operations
+    /// - `input_lookup_elements`: The lookup elements of the merged polynomials
+    /// - `roots_lookup_elements`: The lookup elements for roots of unity
+    /// - `poly_size`: The number of coefficients of each input polynomial
     ///
     /// # Returns
     ///
@@ -402,34 +431,46 @@ impl InteractionClaim {
         ntt_lookup_elements: &NTTLookupElements,
         input_lookup_elements: &InputLookupElements,
         roots_lookup_elements: &RootsLookupElements,
+        poly_size: usize,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         InteractionClaim,
     ) {
         let log_size = trace[0].domain.log_size();
         let mut logup_gen = LogupTraceGenerator::new(log_size);
-        let is_filled = trace[1].clone();
+        let [is_filled, js] = [LayoutColumn::IsFilled, LayoutColumn::RootIndex]
+            .map(|column| ButterflyLayout::new(log_size, poly_size, column).gen_column_simd());
 
         let mut col_gen = logup_gen.new_col();
         for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-            let j = trace[2].data[vec_row]; // must have all lanes populated
-            let root = trace[3].data[vec_row]; // must have all lanes populated
+            let j = js.data[vec_row];
+            let root = trace[0].data[vec_row]; // must have all lanes populated
             let denom: PackedQM31 = roots_lookup_elements.combine(&[j, root]);
             col_gen.write_frac(vec_row, PackedQM31::from(is_filled.data[vec_row]), denom);
         }
         col_gen.finalize_col();
 
-        for col_offset in [4, 5] {
+        // The inputs and outputs are keyed by polynomial, stage and index
+        let rows = Indices::natural(log_size).gen_column_simd();
+        let poly_id = PackedM31::broadcast(M31::from_u32_unchecked(ntt_lookup_elements.poly_id()));
+        let input_stage = PackedM31::broadcast(M31::from_u32_unchecked(poly_size.ilog2()));
+        let output_stage = input_stage + PackedM31::one();
+        let inv2 = PackedM31::broadcast(M31(2).inverse());
+        let size = PackedM31::broadcast(M31::from_u32_unchecked(poly_size as u32));
+        for (col_offset, offset) in [(1, PackedM31::zero()), (2, size)] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 let v = trace[col_offset].data[vec_row]; // must have all lanes populated
-                let denom: PackedQM31 = input_lookup_elements.combine(&[v]);
+                let row = rows.data[vec_row];
+                let index = row + row - js.data[vec_row] * inv2 + offset;
+                let denom: PackedQM31 =
+                    input_lookup_elements.combine(&[poly_id, input_stage, index, v]);
                 col_gen.write_frac(vec_row, PackedQM31::from(is_filled.data[vec_row]), denom);
             }
             col_gen.finalize_col();
         }
 
-        for col_offset in [7, 9, 11] {
+        for col_offset in [4, 6, 8] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 let v = trace[col_offset].data[vec_row]; // must have all lanes populated
@@ -439,11 +480,13 @@ impl InteractionClaim {
             col_gen.finalize_col();
         }
 
-        for col_offset in [9, 11] {
+        for (col_offset, offset) in [(6, PackedM31::zero()), (8, PackedM31::one())] {
             let mut col_gen = logup_gen.new_col();
             for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
                 let v = trace[col_offset].data[vec_row]; // must have all lanes populated
-                let denom: PackedQM31 = ntt_lookup_elements.combine(&[v]);
+                let row = rows.data[vec_row];
+                let denom: PackedQM31 =
+                    ntt_lookup_elements.combine(&[poly_id, output_stage, row + row + offset, v]);
                 col_gen.write_frac(vec_row, -PackedQM31::from(is_filled.data[vec_row]), denom);
             }
             col_gen.finalize_col();
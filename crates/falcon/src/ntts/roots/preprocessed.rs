@@ -13,6 +13,7 @@
 
 use itertools::Itertools;
 use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
@@ -86,7 +87,7 @@ impl Roots {
 }
 
 // This is a helper function for the prover to generate the trace for the range_check component
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     pub log_size: u32,
@@ -152,7 +153,7 @@ impl FrameworkEval for Eval {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction
     pub claimed_sum: QM31,
@@ -22,9 +22,20 @@
 //!    - Accumulate squared remainders in a cumulative sum
 //!    - The result is a regular integer sum (not modular)
 //!
-//! 3. **Range Checking**: Verify that the final Euclidean norm is within predefined bounds
-//!    - Check that the norm is below the signature bound threshold
-//!    - Ensure the signature meets the security requirements
+//! 3. **Bound Comparison**: Verify that every cumulative sum is at most the bound B
+//!    - Split the cumulative sum and the slack B - sum of every row in a low limb of k
+//!      bits and a high limb, where k is half the bit length of B, rounded up
+//!    - Constrain the limbs of the sum to recompose the cumulative sum, and the limbs of
+//!      the slack to recompose B minus that sum
+//!    - Range check the low limbs in [0, 2^k) and the high limbs in [0, 2^(bits(B) - k)):
+//!      the sum and the slack are then integers below 2^bits(B) whose sum is B modulo
+//!      the M31 modulus, so their sum is B and the cumulative sum is at most B
+//!
+//! Checking the last row alone is not enough: the cumulative sum is computed modulo the
+//! M31 modulus p, so a signature of squared norm k * p + r with r at most B would pass.
+//! With every row checked, each cumulative sum is at most B < 2^30, and the next one adds
+//! two squared remainders of at most (q / 2)^2 each, so it cannot wrap around p: by
+//! induction, the last cumulative sum is the squared norm over the integers.
 //!
 //! # Trace Structure
 //!
@@ -32,7 +43,7 @@
 //! - Borrow indicators for coefficient normalization
 //! - Normalized remainders for both polynomials
 //! - Cumulative sums of squared remainders
-//! - Final Euclidean norm values
+//! - Limbs of the cumulative sums and of their slacks to the bound
 //!
 //! # Usage
 //!
@@ -42,32 +53,115 @@
 //! security parameters of the signature scheme.
 
 use itertools::chain;
-use num_traits::One;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
         channel::Channel,
-        fields::{m31::M31, qm31::SecureField},
+        fields::{
+            m31::{BaseField, M31},
+            qm31::SecureField,
+        },
         pcs::TreeVec,
         poly::circle::CanonicCoset,
         utils::bit_reverse_coset_to_circle_domain_order,
     },
     prover::{
-        backend::simd::{SimdBackend, column::BaseColumn, m31::LOG_N_LANES, qm31::PackedQM31},
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
         poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
 use stwo_constraint_framework::{
     FrameworkComponent, FrameworkEval, LogupTraceGenerator, ORIGINAL_TRACE_IDX, Relation,
-    RelationEntry,
+    RelationEntry, preprocessed_columns::PreProcessedColumnId,
 };
 
 use crate::{
-    POLY_SIZE,
-    big_air::relation::{LookupElements, RCLookupElements, SubLookupElements},
+    big_air::{
+        public_inputs::Indices,
+        relation::{
+            LookupElements, RCLookupElements, S0_POLY_ID, S1LookupElements, SubLookupElements,
+        },
+    },
     zq::Q,
 };
 
+/// The preprocessed columns describing the signature coefficient of each row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormColumn {
+    /// 1 on every row of a signature but the first one
+    IsNotFirst,
+    /// 1 on the last row of every signature
+    IsLast,
+}
+
+/// Preprocessed column of the row flags of a trace of `2^log_size` rows holding one
+/// signature of `poly_size` coefficients after the other.
+#[derive(Debug, Clone)]
+pub struct NormFlags {
+    pub log_size: u32,
+    pub poly_size: usize,
+    pub column: NormColumn,
+}
+
+impl NormFlags {
+    pub fn new(log_size: u32, poly_size: usize, column: NormColumn) -> Self {
+        Self {
+            log_size,
+            poly_size,
+            column,
+        }
+    }
+
+    /// Returns all the row flag columns of the trace.
+    pub fn all(log_size: u32, poly_size: usize) -> Vec<Self> {
+        [NormColumn::IsNotFirst, NormColumn::IsLast]
+            .into_iter()
+            .map(|column| Self::new(log_size, poly_size, column))
+            .collect()
+    }
+
+    /// Generates the preprocessed column.
+    ///
+    /// The column is permuted like the trace, whose cumulative sum is read at offset -1.
+    pub fn gen_column_simd(&self) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+        let mut values = (0..1 << self.log_size)
+            .map(|row: usize| {
+                let position = row % self.poly_size;
+                M31(match self.column {
+                    NormColumn::IsNotFirst => (position != 0) as u32,
+                    NormColumn::IsLast => (position == self.poly_size - 1) as u32,
+                })
+            })
+            .collect::<Vec<_>>();
+        bit_reverse_coset_to_circle_domain_order(&mut values);
+        CircleEvaluation::new(
+            CanonicCoset::new(self.log_size).circle_domain(),
+            BaseColumn::from_iter(values),
+        )
+    }
+
+    /// Returns the unique identifier for this preprocessed column.
+    pub fn id(&self) -> PreProcessedColumnId {
+        let name = match self.column {
+            NormColumn::IsNotFirst => "is_not_first",
+            NormColumn::IsLast => "is_last",
+        };
+        PreProcessedColumnId {
+            id: format!(
+                "euclidean_norm_{}_{}_{}",
+                name, self.log_size, self.poly_size
+            ),
+        }
+    }
+}
+
 /// Claim parameters for the Euclidean norm circuit.
 ///
 /// This struct defines the parameters needed to generate and verify Euclidean norm proofs.
@@ -78,10 +172,18 @@ use crate::{
 ///
 /// - `log_size`: The log base 2 of the trace size (e.g., 10 for 1024 coefficients)
 ///   This determines the number of polynomial coefficients and the size of the computation trace.
-#[derive(Debug, Clone)]
+/// - `bound`: The inclusive bound on the squared norm of every signature
+/// - `limb_bits`: The number of bits of the low limbs of the norms and slacks, which
+///   follows from the bound (see
+///   [`CircuitParams::sig_bound_limb_bits`](crate::CircuitParams::sig_bound_limb_bits))
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     pub log_size: u32,
+    /// The inclusive bound on the squared norm
+    pub bound: u32,
+    /// The number of bits of the low limbs of the norms and slacks
+    pub limb_bits: u32,
 }
 
 impl Claim {
@@ -96,6 +198,8 @@ impl Claim {
     /// Mixes the claim parameters into the Fiat-Shamir channel.
     pub fn mix_into(&self, channel: &mut impl Channel) {
         channel.mix_u64(self.log_size as u64);
+        channel.mix_u64(self.bound as u64);
+        channel.mix_u64(self.limb_bits as u64);
     }
 
     /// Generates the trace for the Euclidean norm component.
@@ -110,29 +214,44 @@ impl Claim {
     /// 1. Normalizes coefficients from [0, q) to [-q/2, q/2]
     /// 2. Computes squared remainders for both polynomials
     /// 3. Accumulates the squared sums
-    /// 4. Checks that the final norm is within predefined bounds
+    /// 4. Splits every cumulative sum and its slack to the bound in limbs
     ///
     /// # Parameters
     ///
     /// - `s0`: First polynomial signature with coefficients in [0, q)
     /// - `s1`: Second polynomial signature with coefficients in [0, q)
+    /// - `poly_size`: The number of coefficients of each signature, the inputs holding
+    ///   one signature after the other
     ///
     /// # Returns
     ///
     /// Returns a tuple containing:
     /// - `ColumnVec<CircleEvaluation<...>>`: The computation trace columns
     /// - `Vec<M31>`: Remainder values for range checking
-    /// - `(u32, u32)`: Final Euclidean norm values for both polynomials
+    /// - `Vec<M31>`: The low limbs of the cumulative sum and of the slack of each row, in
+    ///   row order, for range checking against 2^limb_bits
+    /// - `Vec<M31>`: Their high limbs, for range checking against the rest of the bound
+    ///
+    /// # Panics
+    ///
+    /// Panics if the squared norm of a signature exceeds the bound.
     #[allow(clippy::type_complexity)]
     pub fn gen_trace(
         &self,
-        s0: &[u32; POLY_SIZE],
-        s1: &[u32; POLY_SIZE],
+        s0: &[u32],
+        s1: &[u32],
+        poly_size: usize,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         Vec<M31>,
-        (u32, u32),
+        Vec<M31>,
+        Vec<M31>,
     ) {
+        // One coefficient of each polynomial per row
+        let n_rows = 1 << self.log_size;
+        assert_eq!(s0.len(), n_rows);
+        assert_eq!(s1.len(), n_rows);
+        assert_eq!(n_rows % poly_size, 0);
         let mut borrows_s0 = s0
             .iter()
             .map(|a| M31((*a > Q / 2) as u32))
@@ -149,23 +268,22 @@ impl Claim {
             .iter()
             .map(|a| if *a > Q / 2 { Q - *a } else { *a })
             .collect::<Vec<_>>();
-        let mut cum_sum = Vec::with_capacity(POLY_SIZE);
-        remainders_s0
-            .iter()
-            .zip(remainders_s1.iter())
-            .fold(0, |acc, (x, y)| {
+        // The cumulative sum restarts at the first row of every signature
+        let mut cum_sum = Vec::with_capacity(n_rows);
+        for (chunk_s0, chunk_s1) in remainders_s0
+            .chunks(poly_size)
+            .zip(remainders_s1.chunks(poly_size))
+        {
+            chunk_s0.iter().zip(chunk_s1.iter()).fold(0, |acc, (x, y)| {
                 cum_sum.push(acc + x * x + y * y);
                 assert!(acc + x * x + y * y < (1 << 31) - 1);
                 acc + x * x + y * y
             });
+        }
         let remainders = chain!(remainders_s0.clone(), remainders_s1.clone())
             .map(M31)
             .collect::<Vec<_>>();
-        let mut is_not_first = vec![M31(1); POLY_SIZE];
-        is_not_first[0] = M31(0);
         let domain = CanonicCoset::new(self.log_size).circle_domain();
-        let mut is_last = vec![M31(0); POLY_SIZE];
-        is_last[POLY_SIZE - 1] = M31(1);
         let mut s0 = s0
             .iter()
             .map(|a| M31::from_u32_unchecked(*a))
@@ -194,14 +312,37 @@ impl Claim {
             .map(|a| M31::from_u32_unchecked(*a))
             .collect::<Vec<_>>();
 
-        let last_cum_sum = cum_sum.last().unwrap().0;
+        // The cumulative sums only grow, so they are all at most the bound if the norm of
+        // each signature, on its last row, is
+        for last in (poly_size - 1..n_rows).step_by(poly_size) {
+            let norm = cum_sum[last].0;
+            assert!(
+                norm <= self.bound,
+                "squared norm {norm} exceeds {}",
+                self.bound
+            );
+        }
 
-        let mut cum_sum_low = vec![M31(0); POLY_SIZE];
-        cum_sum_low[POLY_SIZE - 1] = M31(last_cum_sum & ((1 << 14) - 1));
-        let mut cum_sum_high = vec![M31(0); POLY_SIZE];
-        cum_sum_high[POLY_SIZE - 1] = M31(last_cum_sum >> 14);
+        // Every cumulative sum and its slack to the bound are split in limbs
+        let low_mask = (1 << self.limb_bits) - 1;
+        let mut limbs = vec![vec![M31(0); n_rows]; 4];
+        let mut low_limbs = Vec::with_capacity(2 * n_rows);
+        let mut high_limbs = Vec::with_capacity(2 * n_rows);
+        for (row, sum) in cum_sum.iter().enumerate() {
+            for (i, value) in [sum.0, self.bound - sum.0].into_iter().enumerate() {
+                let (low, high) = (M31(value & low_mask), M31(value >> self.limb_bits));
+                limbs[2 * i][row] = low;
+                limbs[2 * i + 1][row] = high;
+                low_limbs.push(low);
+                high_limbs.push(high);
+            }
+        }
 
+        // The limbs follow the cumulative sum, which is read at offset -1
         bit_reverse_coset_to_circle_domain_order(&mut cum_sum);
+        for column in limbs.iter_mut() {
+            bit_reverse_coset_to_circle_domain_order(column);
+        }
         (
             [
                 s0,
@@ -211,12 +352,9 @@ impl Claim {
                 borrows_s1,
                 bitrev_remainders_s1,
                 cum_sum.clone(),
-                is_not_first,
-                is_last,
-                cum_sum_low,
-                cum_sum_high,
             ]
             .into_iter()
+            .chain(limbs)
             .map(|col| {
                 CircleEvaluation::<SimdBackend, _, BitReversedOrder>::new(
                     domain,
@@ -225,7 +363,8 @@ impl Claim {
             })
             .collect::<Vec<_>>(),
             remainders,
-            (last_cum_sum & ((1 << 14) - 1), last_cum_sum >> 14),
+            low_limbs,
+            high_limbs,
         )
     }
 }
@@ -235,12 +374,20 @@ impl Claim {
 pub struct Eval {
     /// The claim parameters
     pub claim: Claim,
+    /// The number of coefficients of each signature
+    pub poly_size: usize,
     /// Lookup elements for range checking
     pub half_rc_lookup_elements: RCLookupElements,
     /// Lookup elements for input
     pub s0_lookup_elements: SubLookupElements,
+    /// Lookup elements for range checking the low limbs of the cumulative sum and of its
+    /// slack
     pub low_sig_bound_check_lookup_elements: RCLookupElements,
+    /// Lookup elements for range checking the high limbs of the cumulative sum and of its
+    /// slack
     pub high_sig_bound_check_lookup_elements: RCLookupElements,
+    /// Lookup elements binding s1 to the decompressed signature
+    pub s1_lookup_elements: S1LookupElements,
 }
 
 impl FrameworkEval for Eval {
@@ -262,10 +409,8 @@ impl FrameworkEval for Eval {
         let remainder_s1 = eval.next_trace_mask();
         let [cum_sum_prev, cum_sum_current] =
             eval.next_interaction_mask(ORIGINAL_TRACE_IDX, [-1, 0]);
-        let is_not_first = eval.next_trace_mask();
-        let is_last = eval.next_trace_mask();
-        let low_cum_sum = eval.next_trace_mask();
-        let high_cum_sum = eval.next_trace_mask();
+        let [low_norm, high_norm, low_slack, high_slack] =
+            std::array::from_fn(|_| eval.next_trace_mask());
 
         eval.add_to_relation(RelationEntry::new(
             &self.half_rc_lookup_elements,
@@ -287,7 +432,10 @@ impl FrameworkEval for Eval {
             s1.clone() + borrow_s1.clone() * (E::F::from(M31(Q)) - s1.clone() - s1.clone())
                 - remainder_s1.clone(),
         );
-        eval.add_constraint(is_not_first.clone() * (is_not_first.clone() - E::F::one()));
+        // The first row of every signature is fixed by the preprocessed flags
+        let is_not_first = eval.get_preprocessed_column(
+            NormFlags::new(self.claim.log_size, self.poly_size, NormColumn::IsNotFirst).id(),
+        );
         eval.add_constraint(borrow_s0.clone() * (borrow_s0.clone() - E::F::one()));
         eval.add_constraint(borrow_s1.clone() * (borrow_s1.clone() - E::F::one()));
         //for the first row, cum_sum_current = remainder^2
@@ -306,22 +454,52 @@ impl FrameworkEval for Eval {
                     - cum_sum_current.clone()),
         );
 
+        // s0 and s1 are read at the same index
+        let index = eval.get_preprocessed_column(
+            Indices::natural_circle_domain_order(self.claim.log_size).id(),
+        );
         eval.add_to_relation(RelationEntry::new(
             &self.s0_lookup_elements,
             E::EF::one(),
-            &[s0.clone()],
+            &[
+                E::F::from(M31::from_u32_unchecked(S0_POLY_ID)),
+                E::F::zero(),
+                index.clone(),
+                s0.clone(),
+            ],
         ));
 
-        // if it's the last row check if the signature is in the range
-        eval.add_to_relation(RelationEntry::new(
-            &self.low_sig_bound_check_lookup_elements,
-            E::EF::from(is_last.clone()),
-            &[low_cum_sum],
-        ));
+        // On every row, cum_sum + slack = bound with both split in range checked limbs,
+        // so that no cumulative sum wraps around the modulus and the norm is at most the
+        // bound
+        let limb_shift = E::F::from(M31(1 << self.claim.limb_bits));
+        eval.add_constraint(
+            low_norm.clone() + high_norm.clone() * limb_shift.clone() - cum_sum_current.clone(),
+        );
+        eval.add_constraint(
+            low_slack.clone() + high_slack.clone() * limb_shift + cum_sum_current
+                - E::F::from(M31(self.claim.bound)),
+        );
+        for low in [low_norm, low_slack] {
+            eval.add_to_relation(RelationEntry::new(
+                &self.low_sig_bound_check_lookup_elements,
+                E::EF::one(),
+                &[low],
+            ));
+        }
+        for high in [high_norm, high_slack] {
+            eval.add_to_relation(RelationEntry::new(
+                &self.high_sig_bound_check_lookup_elements,
+                E::EF::one(),
+                &[high],
+            ));
+        }
+
+        // s1 is the signature decompressed from the public input
         eval.add_to_relation(RelationEntry::new(
-            &self.high_sig_bound_check_lookup_elements,
-            E::EF::from(is_last.clone()),
-            &[high_cum_sum],
+            &self.s1_lookup_elements,
+            E::EF::one(),
+            &[index, s1],
         ));
 
         eval.finalize_logup();
@@ -329,7 +507,7 @@ impl FrameworkEval for Eval {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction
     pub claimed_sum: SecureField,
@@ -362,7 +540,7 @@ impl InteractionClaim {
         InteractionClaim,
     ) {
         let log_size = trace[0].domain.log_size();
-        let is_last = trace[8].clone();
+        let index = Indices::natural_circle_domain_order(log_size).gen_column_simd();
 
         let mut logup_gen = LogupTraceGenerator::new(log_size);
         // Range check s0
@@ -399,29 +577,37 @@ impl InteractionClaim {
         let mut col_gen = logup_gen.new_col();
         for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
             let result_packed = trace[0].data[vec_row];
-            let denom: PackedQM31 = lookup_elements.sub.combine(&[result_packed]);
+            let denom: PackedQM31 = lookup_elements.sub.combine(&[
+                PackedM31::broadcast(M31::from_u32_unchecked(S0_POLY_ID)),
+                PackedM31::zero(),
+                index.data[vec_row],
+                result_packed,
+            ]);
             let numerator = PackedQM31::one();
             col_gen.write_frac(vec_row, numerator, denom);
         }
         col_gen.finalize_col();
-        let mut col_gen = logup_gen.new_col();
-        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-            let result_packed = trace[9].data[vec_row];
-            let denom: PackedQM31 = lookup_elements
-                .low_sig_bound_check
-                .combine(&[result_packed]);
-            let numerator = PackedQM31::from(is_last.data[vec_row]);
-            col_gen.write_frac(vec_row, numerator, denom);
+        // Range check the limbs of the cumulative sum and of the slack on every row, in the
+        // order of the evaluation
+        for (column, range_check) in [
+            (7, &lookup_elements.low_sig_bound_check),
+            (9, &lookup_elements.low_sig_bound_check),
+            (8, &lookup_elements.high_sig_bound_check),
+            (10, &lookup_elements.high_sig_bound_check),
+        ] {
+            let mut col_gen = logup_gen.new_col();
+            for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+                let denom: PackedQM31 = range_check.combine(&[trace[column].data[vec_row]]);
+                col_gen.write_frac(vec_row, PackedQM31::one(), denom);
+            }
+            col_gen.finalize_col();
         }
-        col_gen.finalize_col();
         let mut col_gen = logup_gen.new_col();
         for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-            let result_packed = trace[10].data[vec_row];
             let denom: PackedQM31 = lookup_elements
-                .high_sig_bound_check
-                .combine(&[result_packed]);
-            let numerator = PackedQM31::from(is_last.data[vec_row]);
-            col_gen.write_frac(vec_row, numerator, denom);
+                .s1
+                .combine(&[index.data[vec_row], trace[3].data[vec_row]]);
+            col_gen.write_frac(vec_row, PackedQM31::one(), denom);
         }
         col_gen.finalize_col();
         let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
@@ -431,3 +617,185 @@ impl InteractionClaim {
 
 /// Type alias for the modular multiplication component.
 pub type Component = FrameworkComponent<Eval>;
+
+#[cfg(test)]
+mod tests {
+    use stwo::{
+        core::{channel::Blake2sChannel, fields::m31::P},
+        prover::backend::Column,
+    };
+    use stwo_constraint_framework::TraceLocationAllocator;
+
+    use super::*;
+    use crate::debug::row_evaluator::evaluate_rows;
+
+    /// Returns s0 and s1 with centered coefficients -1 and 2, 0, ..., -3, of squared
+    /// norm 14.
+    fn short_polys() -> (Vec<u32>, Vec<u32>) {
+        let mut s0 = vec![0; 16];
+        let mut s1 = vec![0; 16];
+        s0[0] = Q - 1;
+        s1[0] = 2;
+        s1[15] = Q - 3;
+        (s0, s1)
+    }
+
+    #[test]
+    fn test_gen_trace_splits_every_cumulative_sum_and_its_slack() {
+        let (s0, s1) = short_polys();
+        // On the first row, 5 = 5 + 0 * 2^3 and 20 - 5 = 7 + 1 * 2^3, and on the last one
+        // 14 = 6 + 1 * 2^3 and 20 - 14 = 6 + 0 * 2^3
+        let claim = Claim {
+            log_size: 4,
+            bound: 20,
+            limb_bits: 3,
+        };
+        let (_, _, low_limbs, high_limbs) = claim.gen_trace(&s0, &s1, 16);
+        assert_eq!(low_limbs.len(), 32);
+        assert_eq!(low_limbs[..2], [M31(5), M31(7)]);
+        assert_eq!(high_limbs[..2], [M31(0), M31(1)]);
+        assert_eq!(low_limbs[30..], [M31(6), M31(6)]);
+        assert_eq!(high_limbs[30..], [M31(1), M31(0)]);
+
+        // A norm at the bound has a zero slack
+        let claim = Claim { bound: 14, ..claim };
+        let (_, _, low_limbs, high_limbs) = claim.gen_trace(&s0, &s1, 16);
+        assert_eq!(low_limbs[30..], [M31(6), M31(0)]);
+        assert_eq!(high_limbs[30..], [M31(1), M31(0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "squared norm 14 exceeds 13")]
+    fn test_gen_trace_rejects_norm_above_bound() {
+        let (s0, s1) = short_polys();
+        Claim {
+            log_size: 4,
+            bound: 13,
+            limb_bits: 2,
+        }
+        .gen_trace(&s0, &s1, 16);
+    }
+
+    /// Returns the trace of a single signature of `2^claim.log_size` coefficients, in
+    /// natural order, with the cumulative sums computed modulo the M31 modulus and split
+    /// in limbs like the honest ones.
+    fn wrapped_trace(claim: &Claim, s0: &[u32], s1: &[u32]) -> Vec<Vec<M31>> {
+        let low_mask = (1 << claim.limb_bits) - 1;
+        let mut columns = vec![vec![]; 11];
+        let mut cum_sum = M31(0);
+        for (&a, &b) in s0.iter().zip(s1) {
+            let [(s_a, borrow_a, rem_a), (s_b, borrow_b, rem_b)] =
+                [a, b].map(|x| (M31(x), M31((x > Q / 2) as u32), M31(x.min(Q - x))));
+            cum_sum += rem_a * rem_a + rem_b * rem_b;
+            let slack = M31(claim.bound) - cum_sum;
+            let row = [
+                s_a,
+                borrow_a,
+                rem_a,
+                s_b,
+                borrow_b,
+                rem_b,
+                cum_sum,
+                M31(cum_sum.0 & low_mask),
+                M31(cum_sum.0 >> claim.limb_bits),
+                M31(slack.0 & low_mask),
+                M31(slack.0 >> claim.limb_bits),
+            ];
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+        for column in columns.iter_mut() {
+            bit_reverse_coset_to_circle_domain_order(column);
+        }
+        columns
+    }
+
+    #[test]
+    fn test_norm_wrapping_around_the_modulus_is_rejected() {
+        // 28 rows of 2 * (q / 2)^2, then 5792^2 + 85^2: a squared norm of p + 58, which
+        // is 58 modulo p, within the bound, but far above it over the integers
+        let log_size = 5;
+        let mut s0 = vec![0; 32];
+        let mut s1 = vec![0; 32];
+        s0[..28].fill(Q / 2);
+        s1[..28].fill(Q / 2);
+        s0[28] = 5792;
+        s1[28] = 85;
+        let norm: u64 = chain!(&s0, &s1).map(|&x| (x as u64).pow(2)).sum();
+        assert_eq!(norm, P as u64 + 58);
+
+        // 100 has 7 bits: the low limbs are in [0, 2^4) and the high ones in [0, 2^3)
+        let claim = Claim {
+            log_size,
+            bound: 100,
+            limb_bits: 4,
+        };
+        let lookup_elements = LookupElements::draw(&mut Blake2sChannel::default());
+        let is_not_first = NormFlags::new(log_size, 32, NormColumn::IsNotFirst);
+        let indices = Indices::natural_circle_domain_order(log_size);
+        let preprocessed_columns = [
+            (is_not_first.gen_column_simd(), is_not_first.id()),
+            (indices.gen_column_simd(), indices.id()),
+        ]
+        .map(|(column, id)| (column.values.to_cpu(), id));
+        let component = Component::new(
+            &mut TraceLocationAllocator::new_with_preproccessed_columns(
+                &preprocessed_columns
+                    .iter()
+                    .map(|(_, id)| id.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            Eval {
+                claim: claim.clone(),
+                poly_size: 32,
+                half_rc_lookup_elements: lookup_elements.half_range_check.clone(),
+                s0_lookup_elements: lookup_elements.sub.clone(),
+                low_sig_bound_check_lookup_elements: lookup_elements.low_sig_bound_check.clone(),
+                high_sig_bound_check_lookup_elements: lookup_elements.high_sig_bound_check.clone(),
+                s1_lookup_elements: lookup_elements.s1.clone(),
+            },
+            SecureField::zero(),
+        );
+        let main = wrapped_trace(&claim, &s0, &s1);
+        let trace = TreeVec::new(vec![
+            preprocessed_columns
+                .iter()
+                .map(|(column, _)| column)
+                .collect(),
+            main.iter().collect(),
+        ]);
+
+        // Every constraint holds, as the limbs recompose the cumulative sums modulo p
+        let (rows, n_violated_rows) = evaluate_rows(&component, &trace);
+        assert_eq!(n_violated_rows, 0);
+
+        // but the range checks only accept the limbs of the rows whose cumulative sum is
+        // at most the bound: the last 4 rows, after the wrap around. The range check
+        // components only yield the values in range, so the logup sum cannot vanish for
+        // the other rows, where the last-row check alone accepted the trace
+        let is_in_range = |elements: &RCLookupElements, range: u32, denominator| {
+            (0..range)
+                .any(|value| elements.combine::<M31, SecureField>(&[M31(value)]) == denominator)
+        };
+        let n_rows_in_range = rows
+            .iter()
+            .filter(|fractions| {
+                fractions[3..5].iter().all(|fraction| {
+                    is_in_range(
+                        &lookup_elements.low_sig_bound_check,
+                        1 << 4,
+                        fraction.denominator,
+                    )
+                }) && fractions[5..7].iter().all(|fraction| {
+                    is_in_range(
+                        &lookup_elements.high_sig_bound_check,
+                        1 << 3,
+                        fraction.denominator,
+                    )
+                })
+            })
+            .count();
+        assert_eq!(n_rows_in_range, 4);
+    }
+}
@@ -46,6 +46,7 @@
 //! polynomial evaluation multiplication and NTT computations.
 
 use num_traits::One;
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
@@ -55,7 +56,12 @@ use stwo::{
         poly::circle::CanonicCoset,
     },
     prover::{
-        backend::simd::{SimdBackend, column::BaseColumn, m31::LOG_N_LANES, qm31::PackedQM31},
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
         poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
@@ -64,7 +70,13 @@ use stwo_constraint_framework::{
 };
 
 use crate::{
-    big_air::relation::{LookupElements, MulLookupElements, NTTLookupElements, RCLookupElements},
+    big_air::{
+        public_inputs::{Indices, PK_NTT_ID},
+        relation::{
+            F_POLY_ID, G_POLY_ID, LookupElements, MulLookupElements, NTTLookupElements,
+            PRODUCT_POLY_ID, PublicInputLookupElements, RCLookupElements,
+        },
+    },
     zq::{Q, mul::MulMod},
 };
 
@@ -78,7 +90,7 @@ use crate::{
 ///
 /// - `log_size`: The log base 2 of the trace size (e.g., 10 for 1024 operations)
 ///   This determines the number of multiplication operations and the size of the computation trace.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     ///
@@ -229,8 +241,13 @@ pub struct Eval {
     pub f_ntt_lookup_elements: NTTLookupElements,
     /// Lookup elements for g_ntt
     pub g_ntt_lookup_elements: NTTLookupElements,
+    /// Lookup elements binding g_ntt to the public key in NTT form, if it is public, in
+    /// which case there is no G-NTT to read it from
+    pub pk_ntt_lookup_elements: Option<PublicInputLookupElements>,
     /// Lookup elements for multiplication
     pub mul_lookup_elements: MulLookupElements,
+    /// The log size of the polynomials, which is the number of layers of their NTTs
+    pub poly_log_size: u32,
 }
 
 impl FrameworkEval for Eval {
@@ -250,28 +267,58 @@ impl FrameworkEval for Eval {
         let r = eval.next_trace_mask();
         MulMod::new(a.clone(), b.clone(), q, r.clone())
             .evaluate(&self.rc_lookup_elements, &mut eval);
+        // Row i multiplies the coefficients i of the NTTs of f and g, which went through
+        // all the NTT layers, into the coefficient i of the product
+        let index = eval.get_preprocessed_column(Indices::natural(self.claim.log_size).id());
+        let stage = E::F::from(M31::from_u32_unchecked(self.poly_log_size));
         eval.add_to_relation(RelationEntry::new(
             &self.f_ntt_lookup_elements,
             E::EF::one(),
-            &[a],
-        ));
-        eval.add_to_relation(RelationEntry::new(
-            &self.g_ntt_lookup_elements,
-            E::EF::one(),
-            &[b],
+            &[
+                E::F::from(M31::from_u32_unchecked(F_POLY_ID)),
+                stage.clone(),
+                index.clone(),
+                a,
+            ],
         ));
+        if let Some(pk_ntt_lookup_elements) = &self.pk_ntt_lookup_elements {
+            eval.add_to_relation(RelationEntry::new(
+                pk_ntt_lookup_elements,
+                -E::EF::one(),
+                &[
+                    E::F::from(M31::from_u32_unchecked(PK_NTT_ID)),
+                    index.clone(),
+                    b,
+                ],
+            ));
+        } else {
+            eval.add_to_relation(RelationEntry::new(
+                &self.g_ntt_lookup_elements,
+                E::EF::one(),
+                &[
+                    E::F::from(M31::from_u32_unchecked(G_POLY_ID)),
+                    stage.clone(),
+                    index.clone(),
+                    b,
+                ],
+            ));
+        }
         eval.add_to_relation(RelationEntry::new(
             &self.mul_lookup_elements,
             -E::EF::one(),
-            &[r],
-            // &[E::F::zero()],
+            &[
+                E::F::from(M31::from_u32_unchecked(PRODUCT_POLY_ID)),
+                stage,
+                index,
+                r,
+            ],
         ));
         eval.finalize_logup();
         eval
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction
     pub claimed_sum: SecureField,
@@ -292,6 +339,9 @@ impl InteractionClaim {
     ///
     /// - `trace`: The trace columns from the multiplication component
     /// - `lookup_elements`: The lookup elements for range checking
+    /// - `poly_log_size`: The log size of the polynomials, the stage of their NTTs
+    /// - `pk_ntt_lookup_elements`: The lookup elements binding the operand b to the public
+    ///   key in NTT form, if it is public
     ///
     /// # Returns
     ///
@@ -299,12 +349,19 @@ impl InteractionClaim {
     pub fn gen_interaction_trace(
         trace: &[CircleEvaluation<SimdBackend, M31, BitReversedOrder>],
         lookup_elements: &LookupElements,
+        poly_log_size: u32,
+        pk_ntt_lookup_elements: Option<&PublicInputLookupElements>,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         InteractionClaim,
     ) {
         let log_size = trace[0].domain.log_size();
         let mut logup_gen = LogupTraceGenerator::new(log_size);
+        let indices = Indices::natural(log_size).gen_column_simd();
+        let stage = PackedM31::broadcast(M31::from_u32_unchecked(poly_log_size));
+        let f_poly_id = PackedM31::broadcast(M31::from_u32_unchecked(F_POLY_ID));
+        let g_poly_id = PackedM31::broadcast(M31::from_u32_unchecked(G_POLY_ID));
+        let product_poly_id = PackedM31::broadcast(M31::from_u32_unchecked(PRODUCT_POLY_ID));
         // Range check for remainder values
         let mut col_gen = logup_gen.new_col();
         for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
@@ -328,7 +385,12 @@ impl InteractionClaim {
             let result_packed = trace[0].data[vec_row];
 
             // Create the denominator using the lookup elements
-            let denom: PackedQM31 = lookup_elements.f_ntt.combine(&[result_packed]);
+            let denom: PackedQM31 = lookup_elements.f_ntt.combine(&[
+                f_poly_id,
+                stage,
+                indices.data[vec_row],
+                result_packed,
+            ]);
 
             // The numerator is 1 (we're consuming the value created by the NTT)
             let numerator = PackedQM31::one();
@@ -337,14 +399,31 @@ impl InteractionClaim {
         }
         col_gen.finalize_col();
 
-        // G-NTT lookup for operand b
+        // G-NTT lookup for operand b, or public key lookup if it is given in NTT form
+        let pk_ntt_id = PackedM31::broadcast(M31::from_u32_unchecked(PK_NTT_ID));
         let mut col_gen = logup_gen.new_col();
         for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
             // Get the operand b value from the trace (column 1)
             let result_packed = trace[1].data[vec_row];
 
+            if let Some(pk_ntt_lookup_elements) = pk_ntt_lookup_elements {
+                let denom: PackedQM31 = pk_ntt_lookup_elements.combine(&[
+                    pk_ntt_id,
+                    indices.data[vec_row],
+                    result_packed,
+                ]);
+                // The numerator is -1 (the verifier adds the public key back)
+                col_gen.write_frac(vec_row, -PackedQM31::one(), denom);
+                continue;
+            }
+
             // Create the denominator using the lookup elements
-            let denom: PackedQM31 = lookup_elements.g_ntt.combine(&[result_packed]);
+            let denom: PackedQM31 = lookup_elements.g_ntt.combine(&[
+                g_poly_id,
+                stage,
+                indices.data[vec_row],
+                result_packed,
+            ]);
 
             // The numerator is 1 (we're consuming the value created by the NTT)
 
@@ -361,7 +440,12 @@ impl InteractionClaim {
             let result_packed = trace[3].data[vec_row];
 
             // Create the denominator using the lookup elements
-            let denom: PackedQM31 = lookup_elements.mul.combine(&[result_packed]);
+            let denom: PackedQM31 = lookup_elements.mul.combine(&[
+                product_poly_id,
+                stage,
+                indices.data[vec_row],
+                result_packed,
+            ]);
 
             // The numerator is -1 (we're producing a value)
             let numerator = -PackedQM31::one();
@@ -45,7 +45,8 @@
 //! subtraction is required, such as in the Falcon signature scheme for
 //! coefficient-wise polynomial operations.
 
-use num_traits::One;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
@@ -55,7 +56,12 @@ use stwo::{
         poly::circle::CanonicCoset,
     },
     prover::{
-        backend::simd::{SimdBackend, column::BaseColumn, m31::LOG_N_LANES, qm31::PackedQM31},
+        backend::simd::{
+            SimdBackend,
+            column::BaseColumn,
+            m31::{LOG_N_LANES, PackedM31},
+            qm31::PackedQM31,
+        },
         poly::{BitReversedOrder, circle::CircleEvaluation},
     },
 };
@@ -64,8 +70,12 @@ use stwo_constraint_framework::{
 };
 
 use crate::{
-    big_air::relation::{
-        IButterflyLookupElements, LookupElements, RCLookupElements, SubLookupElements,
+    big_air::{
+        public_inputs::Indices,
+        relation::{
+            IButterflyLookupElements, LookupElements, MsgPointLookupElements, PRODUCT_POLY_ID,
+            RCLookupElements, S0_POLY_ID, SubLookupElements,
+        },
     },
     zq::{Q, sub::SubMod},
 };
@@ -74,7 +84,7 @@ use crate::{
 ///
 /// Contains the logarithmic size of the trace, which determines the number of
 /// subtraction operations that can be proven.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size (determines number of operations: 2^log_size)
     pub log_size: u32,
@@ -172,6 +182,8 @@ pub struct Eval {
     pub ibutterfly_lookup_elements: IButterflyLookupElements,
     /// Lookup elements for subtraction operations
     pub sub_lookup_elements: SubLookupElements,
+    /// Lookup elements for the message point sampled by HashToPoint
+    pub msg_point_lookup_elements: MsgPointLookupElements,
 }
 
 impl FrameworkEval for Eval {
@@ -190,25 +202,44 @@ impl FrameworkEval for Eval {
         let borrow = eval.next_trace_mask();
         let remainder = eval.next_trace_mask();
 
-        SubMod::new(a, b.clone(), borrow, remainder.clone())
+        SubMod::new(a.clone(), b.clone(), borrow, remainder.clone())
             .evaluate(&self.rc_lookup_elements, &mut eval);
 
+        // Row i subtracts the coefficient i of the product, back in coefficient form, from
+        // the coefficient i of the message point
+        let index = eval.get_preprocessed_column(Indices::natural(self.claim.log_size).id());
         eval.add_to_relation(RelationEntry::new(
             &self.sub_lookup_elements,
             -E::EF::one(),
-            &[remainder],
+            &[
+                E::F::from(M31::from_u32_unchecked(S0_POLY_ID)),
+                E::F::zero(),
+                index.clone(),
+                remainder,
+            ],
         ));
         eval.add_to_relation(RelationEntry::new(
             &self.ibutterfly_lookup_elements,
             E::EF::one(),
-            &[b],
+            &[
+                E::F::from(M31::from_u32_unchecked(PRODUCT_POLY_ID)),
+                E::F::zero(),
+                index.clone(),
+                b,
+            ],
+        ));
+        // The first operand is the message point sampled by HashToPoint
+        eval.add_to_relation(RelationEntry::new(
+            &self.msg_point_lookup_elements,
+            E::EF::one(),
+            &[index, a],
         ));
         eval.finalize_logup();
         eval
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction
     pub claimed_sum: SecureField,
@@ -233,6 +264,7 @@ impl InteractionClaim {
     ) {
         let log_size = trace[0].domain.log_size();
         let mut logup_gen = LogupTraceGenerator::new(log_size);
+        let index = Indices::natural(log_size).gen_column_simd();
 
         // Range check for remainder values
         let mut col_gen = logup_gen.new_col();
@@ -257,7 +289,12 @@ impl InteractionClaim {
             let result_packed = trace[3].data[vec_row];
 
             // Create the denominator using the lookup elements
-            let denom: PackedQM31 = lookup_elements.sub.combine(&[result_packed]);
+            let denom: PackedQM31 = lookup_elements.sub.combine(&[
+                PackedM31::broadcast(M31::from_u32_unchecked(S0_POLY_ID)),
+                PackedM31::zero(),
+                index.data[vec_row],
+                result_packed,
+            ]);
 
             // The numerator is -1 (we're producing a value)
             let numerator = -PackedQM31::one();
@@ -272,7 +309,12 @@ impl InteractionClaim {
             let result_packed = trace[1].data[vec_row];
 
             // Create the denominator using the lookup elements
-            let denom: PackedQM31 = lookup_elements.ibutterfly.combine(&[result_packed]);
+            let denom: PackedQM31 = lookup_elements.ibutterfly.combine(&[
+                PackedM31::broadcast(M31::from_u32_unchecked(PRODUCT_POLY_ID)),
+                PackedM31::zero(),
+                index.data[vec_row],
+                result_packed,
+            ]);
 
             // The numerator is 1 (we're consuming a value)
             let numerator = PackedQM31::one();
@@ -281,6 +323,18 @@ impl InteractionClaim {
         }
         col_gen.finalize_col();
 
+        // Message point lookup for operand a
+        let mut col_gen = logup_gen.new_col();
+        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+            let denom: PackedQM31 = lookup_elements
+                .msg_point
+                .combine(&[index.data[vec_row], trace[0].data[vec_row]]);
+
+            // The numerator is 1 (we're consuming a coefficient sampled by HashToPoint)
+            col_gen.write_frac(vec_row, PackedQM31::one(), denom);
+        }
+        col_gen.finalize_col();
+
         let (interaction_trace, claimed_sum) = logup_gen.finalize_last();
         (interaction_trace, InteractionClaim { claimed_sum })
     }
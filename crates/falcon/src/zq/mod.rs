@@ -19,6 +19,20 @@ pub mod sub;
 /// the Falcon signature scheme requirements.
 pub const Q: u32 = 12 * 1024 + 1;
 
+/// Returns `base^exponent` modulo `q`, by square and multiply.
+pub const fn pow_mod(base: u32, exponent: u32, q: u32) -> u32 {
+    let q = q as u64;
+    let (mut result, mut base, mut exponent) = (1 % q, base as u64 % q, exponent);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % q;
+        }
+        base = base * base % q;
+        exponent >>= 1;
+    }
+    result as u32
+}
+
 pub enum Operation<E: stwo_constraint_framework::EvalAtRow> {
     Add(add::AddMod<E>),
     Sub(sub::SubMod<E>),
@@ -40,6 +40,7 @@
 //! It's integrated into the larger Big AIR proof system for comprehensive validation.
 
 use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
 use stwo::{
     core::{
         ColumnVec,
@@ -62,51 +63,54 @@ use stwo_constraint_framework::{
 
 use crate::big_air::relation::RCLookupElements;
 
-/// Range check component for values in the field Z_q.
+/// Range check component for values in [0, bound).
 ///
 /// This struct provides utility functions for range checking operations
-/// in the field Z_q where Q is the field size. It handles preprocessed
-/// column generation and sizing for efficient range validation.
-///
-/// # Type Parameters
-///
-/// - `Q`: The field size (e.g., 12289 for the main field)
+/// against a bound chosen at runtime (e.g. Q = 12289 for the main field, or
+/// the limbs of the signature bound of the selected Falcon degree). It handles
+/// preprocessed column generation and sizing for efficient range validation.
 ///
 /// # Mathematical Properties
 ///
-/// - Ensures all values are in the range [0, Q)
+/// - Ensures all values are in the range [0, bound)
 /// - Uses lookup tables for efficient verification
-/// - Supports arbitrary field sizes through const generics
 #[derive(Debug, Clone)]
-pub struct RangeCheck<const Q: u32>;
+pub struct RangeCheck {
+    /// The exclusive upper bound of the checked values
+    pub bound: u32,
+}
+
+impl RangeCheck {
+    pub fn new(bound: u32) -> Self {
+        Self { bound }
+    }
 
-impl<const Q: u32> RangeCheck<Q> {
     /// Returns the log size needed for the range check column.
     ///
-    /// The size is log₂(Q) + 1 to accommodate all values in [0, Q).
-    pub fn log_size() -> u32 {
-        Q.next_power_of_two().ilog2()
+    /// The size is the smallest power of 2 accommodating all values in [0, bound).
+    pub fn log_size(&self) -> u32 {
+        self.bound.next_power_of_two().ilog2()
     }
 
     /// Generates the preprocessed column for range checking.
     ///
-    /// The column contains all values from 0 to Q-1, followed by zeros
+    /// The column contains all values from 0 to bound-1, followed by zeros
     /// to fill the remaining space up to the next power of 2.
-    pub fn gen_column_simd() -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
+    pub fn gen_column_simd(&self) -> CircleEvaluation<SimdBackend, BaseField, BitReversedOrder> {
         CircleEvaluation::new(
-            CanonicCoset::new(Self::log_size()).circle_domain(),
+            CanonicCoset::new(self.log_size()).circle_domain(),
             BaseColumn::from_iter(
-                (0..Q)
+                (0..self.bound)
                     .map(M31)
-                    .chain((Q..Q.next_power_of_two()).map(|_| M31::zero())),
+                    .chain((self.bound..self.bound.next_power_of_two()).map(|_| M31::zero())),
             ),
         )
     }
 
     /// Returns the unique identifier for this preprocessed column.
-    pub fn id() -> PreProcessedColumnId {
+    pub fn id(&self) -> PreProcessedColumnId {
         PreProcessedColumnId {
-            id: format!("range_check_{}", Q),
+            id: format!("range_check_{}", self.bound),
         }
     }
 }
@@ -121,10 +125,13 @@ impl<const Q: u32> RangeCheck<Q> {
 ///
 /// - `log_size`: The log base 2 of the trace size, determining the number
 ///   of rows in the execution trace (2^log_size rows)
-#[derive(Debug, Clone)]
+/// - `bound`: The exclusive upper bound of the checked values
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claim {
     /// The log base 2 of the trace size
     pub log_size: u32,
+    /// The exclusive upper bound of the checked values
+    pub bound: u32,
 }
 
 impl Claim {
@@ -139,6 +146,15 @@ impl Claim {
     /// - `channel`: The Fiat-Shamir channel for mixing claim parameters
     pub fn mix_into(&self, channel: &mut impl Channel) {
         channel.mix_u64(self.log_size as u64);
+        channel.mix_u64(self.bound as u64);
+    }
+
+    /// Returns the claim of a range check against `bound`.
+    pub fn new(bound: u32) -> Self {
+        Self {
+            log_size: RangeCheck::new(bound).log_size(),
+            bound,
+        }
     }
 
     /// Generates the trace for the range_check component.
@@ -187,23 +203,19 @@ impl Claim {
 /// STARK proof framework. It combines the claim parameters with lookup
 /// elements to provide comprehensive range checking functionality.
 ///
-/// # Type Parameters
-///
-/// - `Q`: The field size for range checking (e.g., 12289)
-///
 /// # Fields
 ///
-/// - `claim`: The claim parameters defining the trace size and properties
+/// - `claim`: The claim parameters defining the trace size and the bound
 /// - `lookup_elements`: The lookup elements for establishing range check relations
 #[derive(Debug, Clone)]
-pub struct Eval<const Q: u32> {
+pub struct Eval {
     /// The claim parameters
     pub claim: Claim,
     /// Lookup elements for range checking
     pub lookup_elements: RCLookupElements,
 }
 
-impl<const Q: u32> FrameworkEval for Eval<Q> {
+impl FrameworkEval for Eval {
     fn log_size(&self) -> u32 {
         self.claim.log_size
     }
@@ -214,7 +226,7 @@ impl<const Q: u32> FrameworkEval for Eval<Q> {
 
     fn evaluate<E: EvalAtRow>(&self, mut eval: E) -> E {
         let multiplicity = eval.next_trace_mask();
-        let range_check_col = eval.get_preprocessed_column(RangeCheck::<Q>::id());
+        let range_check_col = eval.get_preprocessed_column(RangeCheck::new(self.claim.bound).id());
 
         // Add the trace column to the lookup relation with coefficient -1
         // This ensures that the sum of all lookups equals zero
@@ -240,7 +252,7 @@ impl<const Q: u32> FrameworkEval for Eval<Q> {
 ///
 /// - `claimed_sum`: The claimed sum of all lookup relations, which should
 ///   equal zero for a valid range checking proof
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteractionClaim {
     /// The claimed sum for the interaction
     pub claimed_sum: QM31,
@@ -270,9 +282,11 @@ impl InteractionClaim {
     ///
     /// - `trace`: The trace column from the range check component containing multiplicities
     /// - `lookup_elements`: The lookup elements for establishing range check relations
-    pub fn gen_interaction_trace<const Q: u32>(
+    /// - `bound`: The exclusive upper bound of the checked values
+    pub fn gen_interaction_trace(
         trace: &CircleEvaluation<SimdBackend, M31, BitReversedOrder>,
         lookup_elements: &RCLookupElements,
+        bound: u32,
     ) -> (
         ColumnVec<CircleEvaluation<SimdBackend, M31, BitReversedOrder>>,
         Self,
@@ -285,7 +299,7 @@ impl InteractionClaim {
         let mut col_gen = logup_gen.new_col();
 
         // Generate the preprocessed range check column for lookup validation
-        let range_check_col = RangeCheck::<Q>::gen_column_simd();
+        let range_check_col = RangeCheck::new(bound).gen_column_simd();
 
         // Process each row in the trace to establish lookup relations
         for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
@@ -321,8 +335,4 @@ impl InteractionClaim {
 /// This provides a convenient way to reference the range check component
 /// in the STARK proof framework. It combines the evaluation logic with
 /// the framework's component system for integration into larger proofs.
-///
-/// # Type Parameters
-///
-/// - `Q`: The field size for range checking (e.g., 12289)
-pub type Component<const Q: u32> = FrameworkComponent<Eval<Q>>;
+pub type Component = FrameworkComponent<Eval>;